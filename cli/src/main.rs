@@ -1,17 +1,45 @@
+use bridge_buddy_core::actors::card_analyser::Feedback;
+use bridge_buddy_core::actors::duplicate_match::DuplicateMatch;
 use bridge_buddy_core::actors::game_client::GameClient;
 use bridge_buddy_core::actors::table::Table;
-use bridge_buddy_core::engine::hand_evaluation::ForumDPlus2015Evaluator;
+use bridge_buddy_core::engine::bidding_engine::bidding_system::BiddingSystem;
+use bridge_buddy_core::engine::bidding_engine::forum_d_plus_2015_bidding_engine::{
+    ForumDPlus2015BiddingEngine, KeycardResponses,
+};
+use bridge_buddy_core::engine::bidding_engine::system_bidding_engine::SystemBiddingEngine;
+use bridge_buddy_core::engine::bidding_engine::SelectBid;
+use bridge_buddy_core::engine::card_play_engine::declarer_plan::DeclarerPlan;
+use bridge_buddy_core::engine::card_play_engine::mock_card_play_engine::MockCardPlayEngine;
+use bridge_buddy_core::engine::card_play_engine::opening_leads::{LeadAgreement, OpeningLeads};
+use bridge_buddy_core::engine::card_play_engine::signals::{Carding, Discards, Signals};
+use bridge_buddy_core::engine::distribution_odds::DistributionOdds;
+use bridge_buddy_core::engine::engine_state::HandDescription;
+use bridge_buddy_core::engine::hand_evaluation::{EvaluationMethod, ForumDPlus2015Evaluator, EVALUATION_METHODS};
 use bridge_buddy_core::engine::mock_bridge_engine::MockBridgeEngine;
+use bridge_buddy_core::engine::subjective_game_view::SubjectiveGamePhaseStateView;
+use bridge_buddy_core::error::BBError;
+use bridge_buddy_core::game::game_phase_states::{BiddingState, GamePhaseState, NextToPlay};
+use bridge_buddy_core::interactive::cli_bid_selector::CliBidSelector;
+use bridge_buddy_core::interactive::cli_move_selector::CliMoveSelector;
+use bridge_buddy_core::interchange::{lin, pbn};
 
 use bridge_buddy_core::primitives::card::suit::SUIT_ARRAY;
+use bridge_buddy_core::primitives::card::Rank;
+use bridge_buddy_core::primitives::card::Suit::{Clubs, Hearts, Spades};
 use bridge_buddy_core::primitives::deal::seat::SEAT_ARRAY;
 use bridge_buddy_core::primitives::deal::Hand;
-use bridge_buddy_core::primitives::deal::Seat::{East, North, South, West};
-use bridge_buddy_core::primitives::Deal;
-use bridge_buddy_dds::DoubleDummySolver;
+use bridge_buddy_core::primitives::deal::Seat::{self, East, North, South, West};
+use bridge_buddy_core::primitives::game_event::{BidEvent, DiscloseHandEvent};
+use bridge_buddy_core::primitives::hand_info::explanation::Explanation;
+use bridge_buddy_core::primitives::{Card, Contract, Deal};
+use bridge_buddy_dds::{
+    DoubleDummyCardAnalyser, DoubleDummyClaimVerifier, DoubleDummySolver, MonteCarloCardPlayEngine, PostMortem,
+    SuitCombination,
+};
 use clap::{Parser, Subcommand};
 use std::io::stdin;
 use std::process::exit;
+use std::rc::Rc;
 use std::str::FromStr;
 use std::time::SystemTime;
 
@@ -26,10 +54,16 @@ struct Args {
 
 #[derive(Subcommand)]
 enum Command {
-    /// Evaluate a bridge hand according to Forum D Plus 2015
+    /// Evaluate a bridge hand according to Forum D Plus 2015 or other methods
     Evaluate {
         /// Hand to evaluate (if not given, it will be queried interactively)
         hand: Option<String>,
+        /// Method to evaluate the hand with, forum-d, milton, bergen, zar, knr or banzai (may be repeated)
+        #[arg(long = "method", default_value = "forum-d")]
+        methods: Vec<EvaluationMethod>,
+        /// Evaluate the hand with all methods side by side
+        #[arg(long)]
+        compare: bool,
     },
     Dds {
         north: Option<String>,
@@ -37,7 +71,154 @@ enum Command {
         south: Option<String>,
         west: Option<String>,
     },
-    Play,
+    Play {
+        /// Let the double dummy solver rule on claims instead of the opponents
+        #[arg(long)]
+        verify_claims: bool,
+        /// Rule on irregularities like insufficient bids or leads out of turn instead of rejecting them
+        #[arg(long)]
+        director: bool,
+        /// Play Mini-bridge for beginners: no auction, the side with more high card points declares
+        #[arg(long)]
+        mini_bridge: bool,
+        /// File describing the bidding system the bots should use (defaults to Forum D Plus 2015)
+        #[arg(long)]
+        system: Option<String>,
+        /// Responses to keycard Blackwood the Forum D Plus 2015 bots use, 1430 or 3014
+        #[arg(long, default_value = "1430")]
+        keycards: KeycardResponses,
+        /// Which card the bots lead from a suit headed by an honor, 4th or 3rd5th
+        #[arg(long, default_value = "4th")]
+        leads: LeadAgreement,
+        /// Whether partner signals high or low to encourage and show an even count, standard or upside-down
+        #[arg(long, default_value = "standard")]
+        carding: Carding,
+        /// What partner's first discard shows, attitude or odd-even
+        #[arg(long, default_value = "attitude")]
+        discards: Discards,
+        /// Whether the opponents signal high or low to encourage and show an even count, standard or upside-down
+        #[arg(long, default_value = "standard")]
+        their_carding: Carding,
+        /// What the opponents' first discard shows, attitude or odd-even
+        #[arg(long, default_value = "attitude")]
+        their_discards: Discards,
+        /// Let the bots play the cards by solving this many random deals double dummy (slow, but much stronger)
+        #[arg(long)]
+        samples: Option<usize>,
+        /// With --samples, let the bots choose their opening leads from the samples as well instead of by the book
+        #[arg(long)]
+        sampled_leads: bool,
+        /// Tell whether your cards cost a trick double dummy, after every-card or at the end-of-hand
+        #[arg(long)]
+        feedback: Option<Feedback>,
+        /// Review the play double dummy once the game has ended
+        #[arg(long)]
+        post_mortem: bool,
+    },
+    /// Let two teams of bots play the same boards in duplicate fashion and compare them in IMPs
+    Duplicate {
+        /// Number of boards to play
+        #[arg(long, default_value = "16")]
+        boards: usize,
+        /// Seed of the first board, the following boards use the next seeds
+        #[arg(long, default_value = "1")]
+        seed: u64,
+        /// File describing the bidding system of team A (defaults to Forum D Plus 2015)
+        #[arg(long)]
+        a_system: Option<String>,
+        /// File describing the bidding system of team B (defaults to Forum D Plus 2015)
+        #[arg(long)]
+        b_system: Option<String>,
+        /// Let team A play the cards by solving this many random deals double dummy
+        #[arg(long)]
+        a_samples: Option<usize>,
+        /// Let team B play the cards by solving this many random deals double dummy
+        #[arg(long)]
+        b_samples: Option<usize>,
+        /// Which card team A leads from a suit headed by an honor, 4th or 3rd5th
+        #[arg(long, default_value = "4th")]
+        a_leads: LeadAgreement,
+        /// Which card team B leads from a suit headed by an honor, 4th or 3rd5th
+        #[arg(long, default_value = "4th")]
+        b_leads: LeadAgreement,
+        /// Number of team A's biggest losses to list for review
+        #[arg(long, default_value = "5")]
+        losses: usize,
+    },
+    /// Review a played game from a PBN or LIN file double dummy, trick by trick and card by card
+    PostMortem {
+        /// File with the game, read as LIN if it ends in .lin and as PBN otherwise
+        file: String,
+    },
+    /// Bid boards as South with the bots, then compare the contract with the system's and the double dummy best
+    BidPractice {
+        /// Number of boards to bid
+        #[arg(long, default_value = "1")]
+        boards: usize,
+        /// What your hand should look like, e.g. "Hand is balanced. Hand has 15 to 17 hcp."
+        #[arg(long)]
+        my_hand: Option<String>,
+        /// What partner's hand should look like, in the same words as --my-hand
+        #[arg(long)]
+        partners_hand: Option<String>,
+        /// File describing the bidding system the bots should use (defaults to Forum D Plus 2015)
+        #[arg(long)]
+        system: Option<String>,
+        /// Responses to keycard Blackwood the Forum D Plus 2015 bots use, 1430 or 3014
+        #[arg(long, default_value = "1430")]
+        keycards: KeycardResponses,
+    },
+    /// Find the best way to play a single suit for each number of tricks, e.g. "AQ32" opposite "654"
+    SuitCombination {
+        /// Dummy's cards in the suit, "-" for a void
+        north: String,
+        /// Declarer's cards in the suit, "-" for a void
+        south: String,
+        /// Cards West is known to hold, e.g. "K" after an opening bid
+        #[arg(long, default_value = "")]
+        west_holds: String,
+        /// Cards East is known to hold
+        #[arg(long, default_value = "")]
+        east_holds: String,
+    },
+    /// Count declarer's winners and losers and list the sources of extra tricks, e.g. "S:AK2,H:AQ2,..."
+    Plan {
+        /// Declarer's hand
+        declarer: String,
+        /// Dummy's hand
+        dummy: String,
+        /// The contract with declarer's seat, e.g. "S3NT" or "N4S"
+        contract: String,
+    },
+    /// Exact odds of how missing cards split and of the suit lengths of a hidden hand
+    Odds {
+        #[command(subcommand)]
+        query: OddsQuery,
+    },
+}
+
+#[derive(Subcommand)]
+enum OddsQuery {
+    /// How the missing cards of a suit split between the two opponents, e.g. "odds split 5"
+    Split {
+        /// Number of cards missing in the suit
+        missing: u8,
+        /// Number of particular cards among the missing ones, e.g. 1 for the queen, to show how often they are short
+        #[arg(long, default_value = "0")]
+        particular: u8,
+    },
+    /// Suit lengths of a hidden hand, e.g. partner's, given the cards seen elsewhere
+    Hand {
+        /// Cards seen outside the hand, e.g. your own, like "SA SK H2"
+        #[arg(long, default_value = "")]
+        seen: String,
+        /// Cards known to be in the hand
+        #[arg(long, default_value = "")]
+        holds: String,
+        /// Vacant places in the other hidden hands, 26 if only your own hand is seen
+        #[arg(long, default_value = "26")]
+        other_places: u8,
+    },
 }
 
 fn main() {
@@ -91,13 +272,54 @@ fn main() {
             println!("DDS calculated: \n{}", result);
             println!("Calculation took {:?}", time.elapsed().unwrap())
         }
-        Command::Play => {
+        Command::Play {
+            verify_claims,
+            director,
+            mini_bridge,
+            system,
+            keycards,
+            leads,
+            carding,
+            discards,
+            their_carding,
+            their_discards,
+            samples,
+            sampled_leads,
+            feedback,
+            post_mortem,
+        } => {
             let mut table = Table::empty();
+            table.set_director_mode(director);
+            table.set_mini_bridge(mini_bridge);
 
-            let north_player = GameClient::new_with_engine(North);
-            let south_player = GameClient::new_interactive(South);
-            let east_player = GameClient::new_with_engine(East);
-            let west_player = GameClient::new_with_move_selector(West, MockBridgeEngine::new(West));
+            if verify_claims {
+                table.set_claim_verifier(DoubleDummyClaimVerifier::new());
+            }
+
+            let system = system.map(|path| read_system(&path));
+            let signals = |seat: Seat| match seat.same_axis(&South) {
+                true => Signals::new(carding, discards),
+                false => Signals::new(their_carding, their_discards),
+            };
+            let bot = |seat| {
+                create_bot(
+                    seat,
+                    system.as_ref(),
+                    keycards,
+                    leads,
+                    signals(seat),
+                    samples,
+                    sampled_leads,
+                )
+            };
+            let north_player = GameClient::new_with_move_selector(North, bot(North));
+            if feedback.is_some() {
+                table.set_card_analyser(DoubleDummyCardAnalyser::new(), South);
+            }
+            let user = CliMoveSelector::with_feedback(South, feedback.unwrap_or_default()).with_advisor(bot(South));
+            let south_player = GameClient::new_with_move_selector(South, user);
+            let east_player = GameClient::new_with_move_selector(East, bot(East));
+            let west_player = GameClient::new_with_move_selector(West, bot(West));
 
             table.seat_player(north_player, North).unwrap();
             table.seat_player(south_player, South).unwrap();
@@ -106,8 +328,216 @@ fn main() {
 
             table.new_game().unwrap();
             table.run_game().unwrap();
+
+            if post_mortem {
+                match PostMortem::from_history(&table.history()) {
+                    Ok(post_mortem) => print!("{}", post_mortem),
+                    Err(_) => println!("The board was passed out, there is no play to review."),
+                }
+            }
+        }
+        Command::Duplicate {
+            boards,
+            seed,
+            a_system,
+            b_system,
+            a_samples,
+            b_samples,
+            a_leads,
+            b_leads,
+            losses,
+        } => {
+            let a_system = a_system.map(|path| read_system(&path));
+            let b_system = b_system.map(|path| read_system(&path));
+            let keycards = KeycardResponses::default();
+            let team_a = |seat| {
+                create_bot(
+                    seat,
+                    a_system.as_ref(),
+                    keycards,
+                    a_leads,
+                    Signals::default(),
+                    a_samples,
+                    false,
+                )
+            };
+            let team_b = |seat| {
+                create_bot(
+                    seat,
+                    b_system.as_ref(),
+                    keycards,
+                    b_leads,
+                    Signals::default(),
+                    b_samples,
+                    false,
+                )
+            };
+
+            let report = DuplicateMatch::from_seeds(seed, boards)
+                .play(team_a, team_b)
+                .unwrap_or_else(|err| {
+                    println!("could not play the match: {}", err);
+                    exit(1);
+                });
+            print!("{}", report);
+            for board in report.biggest_losses(losses) {
+                println!();
+                println!("Seed {}, team A lost {} IMPs:", board.seed, -board.imps);
+                println!("{}", board.deal);
+                println!("Team A North-South: {}", board.results[0]);
+                println!("Team B North-South: {}", board.results[1]);
+            }
+        }
+        Command::PostMortem { file } => {
+            let record = std::fs::read_to_string(&file).unwrap_or_else(|err| {
+                println!("could not read {}: {}", file, err);
+                exit(1);
+            });
+            let history = match file.to_lowercase().ends_with(".lin") {
+                true => lin::read_game(&record),
+                false => pbn::read_game(&record),
+            };
+            let post_mortem = history.and_then(|history| PostMortem::from_history(&history));
+            match post_mortem {
+                Ok(post_mortem) => print!("{}", post_mortem),
+                Err(err) => {
+                    println!("could not review {}: {}", file, err);
+                    exit(1);
+                }
+            }
+        }
+        Command::BidPractice {
+            boards,
+            my_hand,
+            partners_hand,
+            system,
+            keycards,
+        } => {
+            let engine: Rc<dyn SelectBid> = match system {
+                Some(path) => Rc::new(SystemBiddingEngine::new(read_system(&path))),
+                None => Rc::new(ForumDPlus2015BiddingEngine::with_keycard_responses(keycards)),
+            };
+            let constraints = [(South, my_hand), (North, partners_hand)]
+                .into_iter()
+                .filter_map(|(seat, text)| Some((seat, read_hand_description(&text?))))
+                .collect::<Vec<_>>();
+            let user = CliBidSelector::new(South).with_advisor(engine.clone());
+            let mut dds = DoubleDummySolver::default();
+
+            for _ in 0..boards {
+                let deal = deal_board(&constraints);
+                let practice = bid_board(&deal, |seat| match seat {
+                    South => &user,
+                    _ => engine.as_ref(),
+                });
+                let recommended = bid_board(&deal, |_| engine.as_ref());
+
+                println!("{}", deal);
+                let result = dds.solve(deal);
+                let vulnerability = deal.board.vulnerability();
+                let print_auction = |name: &str, state: &BiddingState| {
+                    println!("{}: {}", name, state.bid_manager.bid_line());
+                    match state.implied_contract() {
+                        Some(contract) => println!(
+                            "  {}, {} double dummy",
+                            contract,
+                            result.score_for(contract, South, vulnerability)
+                        ),
+                        None => println!("  passed out"),
+                    }
+                };
+                print_auction("Your auction", &practice);
+                print_auction("The system's auction", &recommended);
+                match result.best_contract_for(South, vulnerability) {
+                    Some((contract, score)) => println!("Best contract double dummy: {}, {}", contract, score),
+                    None => println!("Best contract double dummy: none, defend"),
+                }
+            }
+        }
+        Command::SuitCombination {
+            north,
+            south,
+            west_holds,
+            east_holds,
+        } => {
+            let combination = SuitCombination::from_holdings(&north, &south).and_then(|combination| {
+                [(West, &west_holds), (East, &east_holds)]
+                    .into_iter()
+                    .flat_map(|(seat, ranks)| ranks.chars().map(move |char| (seat, char)))
+                    .try_fold(combination, |combination, (seat, char)| {
+                        Ok(combination.with_known_card(seat, Rank::from_char(char)?))
+                    })
+            });
+            match combination {
+                Ok(combination) => {
+                    println!("{}", combination);
+                    for play in combination.table() {
+                        print!("{}", play);
+                    }
+                }
+                Err(err) => {
+                    println!("invalid suit combination: {}", err);
+                    exit(1);
+                }
+            }
         }
-        Command::Evaluate { hand } => {
+        Command::Plan {
+            declarer,
+            dummy,
+            contract,
+        } => {
+            let plan = Hand::from_str(&declarer).and_then(|declarer| {
+                let dummy = Hand::from_str(&dummy)?;
+                let contract = Contract::from_str(&contract)?;
+                Ok(DeclarerPlan::new(&declarer, &dummy, contract))
+            });
+            match plan {
+                Ok(plan) => print!("{}", plan),
+                Err(err) => {
+                    println!("invalid hands or contract: {}", err);
+                    exit(1);
+                }
+            }
+        }
+        Command::Odds { query } => match query {
+            OddsQuery::Split { missing, particular } => match DistributionOdds::opponents(Clubs, missing) {
+                Ok(odds) => {
+                    println!("{} cards missing:", missing);
+                    for ((longer, shorter), probability) in odds.splits(Clubs) {
+                        println!("{}-{}: {:6.2}%", longer, shorter, probability * 100.0);
+                    }
+                    for length in (1..=missing).filter(|&length| particular > 0 && length >= particular) {
+                        println!(
+                            "{} particular card(s) in a holding of {} in either hand: {:6.2}%",
+                            particular,
+                            length,
+                            2.0 * odds.holding(Clubs, length, particular) * 100.0
+                        );
+                    }
+                }
+                Err(_) => {
+                    println!("at most 13 cards of a suit can be missing");
+                    exit(1);
+                }
+            },
+            OddsQuery::Hand {
+                seen,
+                holds,
+                other_places,
+            } => {
+                let odds = read_cards(&holds).and_then(|holds| {
+                    read_cards(&seen).and_then(|seen| DistributionOdds::for_hand(&holds, &seen, other_places))
+                });
+                match odds {
+                    Ok(odds) => print_hand_odds(&odds),
+                    Err(err) => {
+                        println!("invalid cards or places: {}", err);
+                        exit(1);
+                    }
+                }
+            }
+        },
+        Command::Evaluate { hand, methods, compare } => {
             let hand_result = match hand {
                 None => {
                     let mut hand = String::new();
@@ -131,23 +561,13 @@ fn main() {
                 Ok(hand) => {
                     println!("{}", hand);
                     println!("hand_type: {}", hand.hand_type());
-                    println!("high-card points: {}", ForumDPlus2015Evaluator::hcp(&hand));
-                    println!(
-                        "length points: {}",
-                        ForumDPlus2015Evaluator::length_points(&hand, None, &[])
-                    );
-                    println!("adjustments:");
-                    println!(
-                        "  aces and tens: {}",
-                        ForumDPlus2015Evaluator::adjustment_aces_and_tens(&hand)
-                    );
-                    println!(
-                        "  unguarded honors: {}",
-                        ForumDPlus2015Evaluator::adjustment_unguarded_honors(&hand)
-                    );
-                    println!("suit qualities:");
-                    for suit in SUIT_ARRAY.into_iter().rev() {
-                        println!("{}: {}", suit, ForumDPlus2015Evaluator::suit_quality(&hand, suit));
+                    let methods = match compare {
+                        true => EVALUATION_METHODS.to_vec(),
+                        false => methods,
+                    };
+                    match methods[..] {
+                        [method] => print_evaluation(&hand, method),
+                        _ => print_comparison(&hand, &methods),
                     }
                 }
                 Err(err) => {
@@ -158,3 +578,151 @@ fn main() {
         }
     }
 }
+
+fn read_cards(cards: &str) -> Result<Vec<Card>, BBError> {
+    cards.split_whitespace().map(Card::from_str).collect()
+}
+
+fn print_hand_odds(odds: &DistributionOdds) {
+    for &suit in SUIT_ARRAY.iter().rev() {
+        let lengths = (0..=13)
+            .map(|length| (length, odds.suit_length(suit, length)))
+            .filter(|&(_, probability)| probability > 0.0)
+            .map(|(length, probability)| format!("{}: {:.2}%", length, probability * 100.0))
+            .collect::<Vec<_>>();
+        println!("{} {}", suit, lengths.join(", "));
+    }
+    let major = odds.pattern(|lengths| lengths[Hearts as usize] >= 4 || lengths[Spades as usize] >= 4);
+    let balanced = odds.pattern(|lengths| {
+        lengths.iter().all(|&length| length >= 2) && lengths.iter().filter(|&&length| length == 2).count() <= 1
+    });
+    println!("Four or more cards in a major: {:.2}%", major * 100.0);
+    println!("Balanced: {:.2}%", balanced * 100.0);
+}
+
+fn print_evaluation(hand: &Hand<13>, method: EvaluationMethod) {
+    let evaluator = method.evaluator();
+    println!("{}: {}", evaluator.name(), evaluator.evaluate(hand));
+    for (part, value) in evaluator.breakdown(hand) {
+        println!("  {}: {}", part, value);
+    }
+    if method == EvaluationMethod::ForumDPlus2015 {
+        println!("suit qualities:");
+        for suit in SUIT_ARRAY.into_iter().rev() {
+            println!("{}: {}", suit, ForumDPlus2015Evaluator::suit_quality(hand, suit));
+        }
+    }
+}
+
+fn print_comparison(hand: &Hand<13>, methods: &[EvaluationMethod]) {
+    println!("{:<20}{:>8}{:>10}{:>10}", "method", "value", "opening", "margin");
+    for method in methods {
+        let evaluator = method.evaluator();
+        let value = evaluator.evaluate(hand);
+        let opening = evaluator.opening_value();
+        println!(
+            "{:<20}{:>8.2}{:>10.2}{:>+10.2}",
+            evaluator.name(),
+            value,
+            opening,
+            value - opening
+        );
+    }
+}
+
+fn create_bot(
+    seat: Seat,
+    system: Option<&BiddingSystem>,
+    keycards: KeycardResponses,
+    leads: LeadAgreement,
+    signals: Signals,
+    samples: Option<usize>,
+    sampled_leads: bool,
+) -> MockBridgeEngine {
+    let mut bot = match system {
+        Some(system) => MockBridgeEngine::with_bidding_engine(seat, SystemBiddingEngine::new(system.clone())),
+        None => {
+            MockBridgeEngine::with_bidding_engine(seat, ForumDPlus2015BiddingEngine::with_keycard_responses(keycards))
+        }
+    };
+    match samples {
+        Some(samples) => {
+            let card_play_engine = MonteCarloCardPlayEngine::new(seat)
+                .with_samples(samples)
+                .with_signals(signals);
+            let card_play_engine = match sampled_leads {
+                true => card_play_engine,
+                false => card_play_engine.with_opening_leads(OpeningLeads::new(leads)),
+            };
+            let card_play_engine = match system {
+                Some(system) => card_play_engine.with_interpreter(SystemBiddingEngine::new(system.clone())),
+                None => {
+                    card_play_engine.with_interpreter(ForumDPlus2015BiddingEngine::with_keycard_responses(keycards))
+                }
+            };
+            bot.set_card_play_engine(card_play_engine);
+        }
+        None => bot.set_card_play_engine(MockCardPlayEngine::with_lead_agreement(seat, leads).with_signals(signals)),
+    }
+    bot
+}
+
+fn read_system(path: &str) -> BiddingSystem {
+    let system = std::fs::read_to_string(path).unwrap_or_else(|err| {
+        println!("could not read {}: {}", path, err);
+        exit(1);
+    });
+    BiddingSystem::from_str(&system).unwrap_or_else(|err| {
+        println!("invalid bidding system: {}", err);
+        exit(1);
+    })
+}
+
+fn read_hand_description(text: &str) -> HandDescription {
+    let explanation = Explanation::from_str(text).unwrap_or_else(|err| {
+        println!("invalid hand description: {}", err);
+        exit(1);
+    });
+    let mut description = HandDescription::unknown();
+    description.narrow(&explanation);
+    description
+}
+
+fn deal_board(constraints: &[(Seat, HandDescription)]) -> Deal<13> {
+    const MAX_ATTEMPTS: usize = 1_000_000;
+    (0..MAX_ATTEMPTS)
+        .map(|_| Deal::random())
+        .find(|deal: &Deal<13>| {
+            constraints
+                .iter()
+                .all(|(seat, description)| description.admits(deal.hand_of(*seat)))
+        })
+        .unwrap_or_else(|| {
+            println!("could not deal a board that fits the hand descriptions");
+            exit(1);
+        })
+}
+
+fn bid_board<'a>(deal: &Deal<13>, bidder: impl Fn(Seat) -> &'a dyn SelectBid) -> BiddingState {
+    let mut state = BiddingState::new(deal.board, false, false);
+    for seat in SEAT_ARRAY {
+        let hand = *deal.hand_of(seat);
+        state
+            .process_disclose_hand_event(DiscloseHandEvent { seat, hand })
+            .unwrap();
+    }
+    while !state.bidding_has_ended() {
+        let player = state.next_to_play();
+        let selector = bidder(player);
+        let bid = selector.select_bid(SubjectiveGamePhaseStateView::new_bidding(&state, player));
+        let explanation = selector.alert(state.bid_manager.bids(), bid);
+        let bid_event = BidEvent {
+            player,
+            bid,
+            alert: explanation.is_some(),
+            explanation,
+        };
+        state.process_make_bid_event(bid_event).unwrap();
+    }
+    state
+}
@@ -0,0 +1,32 @@
+use crate::primitives::deal::Seat;
+use crate::primitives::{Card, Contract, Deal};
+use strum::{Display, EnumString};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardAnalysis {
+    pub player: Seat,
+    pub card: Card,
+    pub trick_number: usize,
+    pub tricks_lost: usize,
+    pub best_cards: Vec<Card>,
+}
+
+pub trait AnalyseCard<const N: usize = 13> {
+    fn analyse_card(
+        &mut self,
+        deal: &Deal<N>,
+        contract: Contract,
+        played_cards: &[Card],
+        player: Seat,
+        card: Card,
+    ) -> Option<CardAnalysis>;
+}
+
+#[derive(Debug, Default, Display, EnumString, Clone, Copy, PartialEq, Eq)]
+pub enum Feedback {
+    #[default]
+    #[strum(serialize = "every-card")]
+    EveryCard,
+    #[strum(serialize = "end-of-hand")]
+    EndOfHand,
+}
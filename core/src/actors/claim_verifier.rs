@@ -0,0 +1,7 @@
+use crate::game::game_phase_states::CardPlayState;
+use crate::primitives::game_event::ClaimEvent;
+use crate::primitives::Deal;
+
+pub trait VerifyClaim<const N: usize = 13> {
+    fn verify_claim(&mut self, deal: &Deal<N>, state: &CardPlayState<N>, claim: ClaimEvent) -> bool;
+}
@@ -0,0 +1,231 @@
+use crate::actors::game_client::GameClient;
+use crate::actors::table::Table;
+use crate::engine::SelectMove;
+use crate::error::BBError;
+use crate::game::scoring::{ScoreCalculator, ScorePoints};
+use crate::primitives::deal::seat::SEAT_ARRAY;
+use crate::primitives::deal::Seat;
+use crate::primitives::game_event::GameEvent;
+use crate::primitives::game_result::GameResult;
+use crate::primitives::Deal;
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+// each board is played at two tables, and each team sits North-South at one of them
+pub struct DuplicateMatch {
+    deals: Vec<(u64, Deal<13>)>,
+}
+
+impl DuplicateMatch {
+    pub fn from_seeds(first_seed: u64, boards: usize) -> Self {
+        let deals = (first_seed..first_seed + boards as u64)
+            .map(|seed| (seed, Deal::from_u64_seed(seed)))
+            .collect_vec();
+        DuplicateMatch { deals }
+    }
+
+    pub fn play<A: SelectMove, B: SelectMove>(
+        &self,
+        team_a: impl Fn(Seat) -> A,
+        team_b: impl Fn(Seat) -> B,
+    ) -> Result<MatchReport, BBError> {
+        let mut boards = Vec::new();
+        for &(seed, deal) in &self.deals {
+            let open_room = Self::play_table(deal, |seat| match seat.same_axis(&Seat::North) {
+                true => GameClient::new_with_move_selector(seat, team_a(seat)),
+                false => GameClient::new_with_move_selector(seat, team_b(seat)),
+            })?;
+            let closed_room = Self::play_table(deal, |seat| match seat.same_axis(&Seat::North) {
+                true => GameClient::new_with_move_selector(seat, team_b(seat)),
+                false => GameClient::new_with_move_selector(seat, team_a(seat)),
+            })?;
+            boards.push(BoardComparison::new(seed, deal, [open_room, closed_room]));
+        }
+        Ok(MatchReport { boards })
+    }
+
+    fn play_table<'a>(deal: Deal<13>, player: impl Fn(Seat) -> GameClient<'a>) -> Result<GameResult, BBError> {
+        let mut table = Table::empty();
+        for seat in SEAT_ARRAY {
+            table.seat_player(player(seat), seat)?;
+        }
+        table.new_game_from_deal(deal)?;
+        table.run_game()?;
+        table
+            .history()
+            .iter()
+            .find_map(|event| match event {
+                GameEvent::GameEnded(event) => Some(event.result),
+                _ => None,
+            })
+            .ok_or(BBError::GameStuck)
+    }
+}
+
+// team A sits North-South at the first table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardComparison {
+    pub seed: u64,
+    pub deal: Deal<13>,
+    pub results: [GameResult; 2],
+    pub scores: [ScorePoints; 2],
+    pub imps: isize,
+}
+
+impl BoardComparison {
+    pub fn new(seed: u64, deal: Deal<13>, results: [GameResult; 2]) -> Self {
+        let vulnerability = deal.vulnerable();
+        let scores = [
+            ScoreCalculator::score_result(results[0], vulnerability),
+            ScoreCalculator::NO_SCORE - ScoreCalculator::score_result(results[1], vulnerability),
+        ];
+        BoardComparison {
+            seed,
+            deal,
+            results,
+            scores,
+            imps: ScoreCalculator::imps(scores[0] + scores[1]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchReport {
+    pub boards: Vec<BoardComparison>,
+}
+
+impl MatchReport {
+    pub fn total_imps(&self) -> isize {
+        self.boards.iter().map(|board| board.imps).sum()
+    }
+
+    pub fn imps_per_board(&self) -> f64 {
+        match self.boards.len() {
+            0 => 0.0,
+            n => self.total_imps() as f64 / n as f64,
+        }
+    }
+
+    // 95%, estimated from the spread of the swings on single boards
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        let n = self.boards.len() as f64;
+        let total = self.total_imps() as f64;
+        if self.boards.len() < 2 {
+            return (total, total);
+        }
+        let mean = self.imps_per_board();
+        let variance = self
+            .boards
+            .iter()
+            .map(|board| (board.imps as f64 - mean).powi(2))
+            .sum::<f64>()
+            / (n - 1.0);
+        let margin = 1.96 * (variance * n).sqrt();
+        (total - margin, total + margin)
+    }
+
+    pub fn biggest_losses(&self, count: usize) -> Vec<&BoardComparison> {
+        self.boards
+            .iter()
+            .filter(|board| board.imps < 0)
+            .sorted_by_key(|board| board.imps)
+            .take(count)
+            .collect_vec()
+    }
+}
+
+impl Display for MatchReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for board in &self.boards {
+            writeln!(
+                f,
+                "Seed {:>6}: {:<16} {:>6} | {:<16} {:>6} | {:>+4} IMPs",
+                board.seed,
+                board.results[0].to_string(),
+                board.scores[0].to_string(),
+                board.results[1].to_string(),
+                board.scores[1].to_string(),
+                board.imps
+            )?;
+        }
+        let (low, high) = self.confidence_interval();
+        writeln!(
+            f,
+            "Team A: {:+} IMPs over {} boards ({:+.2} per board), 95% confidence interval {:+.1} to {:+.1}",
+            self.total_imps(),
+            self.boards.len(),
+            self.imps_per_board(),
+            low,
+            high
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BoardComparison, DuplicateMatch, MatchReport};
+    use crate::engine::bidding_engine::forum_d_plus_2015_bidding_engine::ForumDPlus2015BiddingEngine;
+    use crate::engine::mock_bridge_engine::MockBridgeEngine;
+    use crate::game::scoring::ScoreCalculator;
+    use crate::primitives::deal::Board;
+    use crate::primitives::game_result::GameResult;
+    use crate::primitives::{Contract, Deal};
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    fn board(seed: u64, open_room: (&str, usize), closed_room: (&str, usize)) -> BoardComparison {
+        let result =
+            |(contract, tricks)| GameResult::calculate_game_result(Contract::from_str(contract).unwrap(), tricks);
+        // board 1 is not vulnerable
+        let deal = Deal {
+            board: Board::from_number(1),
+            ..Deal::from_u64_seed(seed)
+        };
+        BoardComparison::new(seed, deal, [result(open_room), result(closed_room)])
+    }
+
+    #[test]
+    fn same_teams_tie() {
+        let bot = |seat| MockBridgeEngine::with_bidding_engine(seat, ForumDPlus2015BiddingEngine::new());
+        let report = DuplicateMatch::from_seeds(1, 2).play(bot, bot).unwrap();
+
+        assert_eq!(report.boards.len(), 2);
+        assert_eq!(report.total_imps(), 0);
+        assert_eq!(report.confidence_interval(), (0.0, 0.0));
+        assert!(report.biggest_losses(5).is_empty());
+    }
+
+    #[test_case("N4S", 10, "N4S", 10, 0; "Push")]
+    #[test_case("N4S", 10, "N2S", 10, 6; "Game against part score")]
+    #[test_case("N2S", 10, "E3H", 8, 3; "Part score against a set")]
+    #[test_case("E4H", 10, "E4H", 9, -10; "Gift to the opponents")]
+    fn imps(open_room: &str, open_tricks: usize, closed_room: &str, closed_tricks: usize, expected: isize) {
+        let board = board(1, (open_room, open_tricks), (closed_room, closed_tricks));
+        assert_eq!(board.imps, expected);
+        assert_eq!(board.imps, ScoreCalculator::imps(board.scores[0] + board.scores[1]));
+    }
+
+    #[test]
+    fn statistics() {
+        let report = MatchReport {
+            boards: vec![
+                board(1, ("N4S", 10), ("N2S", 10)),
+                board(1, ("N4S", 10), ("N4S", 10)),
+                board(1, ("E4H", 10), ("E4H", 9)),
+                board(1, ("N4S", 9), ("N4S", 10)),
+            ],
+        };
+
+        assert_eq!(report.total_imps(), -14);
+        assert_eq!(report.imps_per_board(), -3.5);
+        let (low, high) = report.confidence_interval();
+        assert!(low < -14.0 && high > -14.0);
+        assert!((high + low + 28.0).abs() < 1e-9);
+        let losses = report.biggest_losses(5);
+        assert_eq!(
+            losses.iter().map(|board| board.imps).collect::<Vec<_>>(),
+            vec![-10, -10]
+        );
+        assert_eq!(report.biggest_losses(1).len(), 1);
+    }
+}
@@ -1,4 +1,6 @@
+use crate::actors::card_analyser::CardAnalysis;
 use crate::engine::mock_bridge_engine::MockBridgeEngine;
+use crate::engine::reason::Reason;
 use crate::engine::subjective_game_view::SubjectiveGameStateView;
 use crate::engine::{Move, SelectMove};
 use crate::error::BBError;
@@ -6,17 +8,20 @@ use crate::game::GameState;
 use crate::interactive::cli_move_selector::CliMoveSelector;
 use crate::primitives::deal::Seat;
 
-use crate::primitives::game_event::{BidEvent, CardEvent, GameEvent};
+use crate::primitives::game_event::{
+    BidEvent, CardEvent, ClaimEvent, ClaimResponseEvent, ConcedeEvent, GameEvent, IrregularityResponseEvent,
+    LeadPenaltyEvent, UndoRequestEvent, UndoResponseEvent,
+};
 use crate::primitives::player_event::PlayerEvent;
 
-pub struct GameClient<'a> {
+pub struct GameClient<'a, const N: usize = 13> {
     seat: Seat,
-    game: Option<GameState>,
-    move_selector: Box<dyn SelectMove + 'a>,
+    game: Option<GameState<N>>,
+    move_selector: Box<dyn SelectMove<N> + 'a>,
 }
 
-impl<'a> GameClient<'a> {
-    pub fn process_game_event(&mut self, event: GameEvent) -> Result<(), BBError> {
+impl<'a, const N: usize> GameClient<'a, N> {
+    pub fn process_game_event(&mut self, event: GameEvent<N>) -> Result<(), BBError> {
         match event {
             GameEvent::NewGame(new_game_event) => {
                 self.game = Some(GameState::from_new_game_event(new_game_event));
@@ -25,14 +30,38 @@ impl<'a> GameClient<'a> {
             _ => match &mut self.game {
                 None => Err(BBError::GameHasNotStarted)?,
                 Some(game) => {
+                    let visible_event = Self::hide_partners_alert(event, self.seat);
                     self.move_selector
-                        .process_game_event(event, SubjectiveGameStateView::new(game, self.seat))?;
+                        .process_game_event(visible_event, SubjectiveGameStateView::new(game, self.seat))?;
                     game.process_game_event(event)
                 }
             },
         }
     }
 
+    fn hide_partners_alert(event: GameEvent<N>, seat: Seat) -> GameEvent<N> {
+        match event {
+            GameEvent::Bid(bid_event) if bid_event.player == seat.partner() => GameEvent::Bid(BidEvent {
+                alert: false,
+                explanation: None,
+                ..bid_event
+            }),
+            _ => event,
+        }
+    }
+
+    pub fn process_card_analysis(&mut self, analysis: CardAnalysis) {
+        self.move_selector.process_card_analysis(analysis);
+    }
+
+    pub fn explain_move(&self, event: PlayerEvent) -> Option<Reason> {
+        self.move_selector.explain_move(event)
+    }
+
+    pub fn process_move_reason(&mut self, event: PlayerEvent, reason: Reason) {
+        self.move_selector.process_move_reason(event, reason);
+    }
+
     pub fn get_move(&self) -> Result<PlayerEvent, BBError> {
         match &self.game {
             None => Err(BBError::GameHasNotStarted),
@@ -40,7 +69,7 @@ impl<'a> GameClient<'a> {
         }
     }
 
-    fn get_move_for_game(&self, game: &GameState) -> Result<PlayerEvent, BBError> {
+    fn get_move_for_game(&self, game: &GameState<N>) -> Result<PlayerEvent, BBError> {
         match game.next_to_play() {
             Some(next_player)
                 if next_player == self.seat || Some(next_player) == self.dummy() && self.can_play_for_dummy() =>
@@ -48,7 +77,12 @@ impl<'a> GameClient<'a> {
                 let chosen_move = self
                     .move_selector
                     .select_move(SubjectiveGameStateView::new(game, self.seat))?;
-                Ok(Self::wrap_move_in_event(chosen_move, next_player))
+                // when playing for dummy, a takeback is still requested by declarer
+                let player = match chosen_move {
+                    Move::UndoRequest => self.seat,
+                    _ => next_player,
+                };
+                Ok(Self::wrap_move_in_event(chosen_move, player))
             }
             Some(next_player) => Err(BBError::CannotPlayFor(next_player)),
             None => Err(BBError::OutOfTurn(None)),
@@ -57,28 +91,32 @@ impl<'a> GameClient<'a> {
 
     fn wrap_move_in_event(chosen_move: Move, player: Seat) -> PlayerEvent {
         match chosen_move {
-            Move::Bid(bid) => PlayerEvent::Bid(BidEvent { player, bid }),
+            Move::Bid(bid) => PlayerEvent::Bid(BidEvent {
+                player,
+                bid,
+                alert: false,
+                explanation: None,
+            }),
+            Move::AlertedBid(bid, explanation) => PlayerEvent::Bid(BidEvent {
+                player,
+                bid,
+                alert: true,
+                explanation,
+            }),
             Move::Card(card) => PlayerEvent::Card(CardEvent { player, card }),
+            Move::Claim(tricks) => PlayerEvent::Claim(ClaimEvent { player, tricks }),
+            Move::Concede => PlayerEvent::Concede(ConcedeEvent { player }),
+            Move::ClaimResponse(accept) => PlayerEvent::ClaimResponse(ClaimResponseEvent { player, accept }),
+            Move::UndoRequest => PlayerEvent::UndoRequest(UndoRequestEvent { player }),
+            Move::UndoResponse(accept) => PlayerEvent::UndoResponse(UndoResponseEvent { player, accept }),
+            Move::IrregularityResponse(accept) => {
+                PlayerEvent::IrregularityResponse(IrregularityResponseEvent { player, accept })
+            }
+            Move::LeadPenalty(penalty) => PlayerEvent::LeadPenalty(LeadPenaltyEvent { player, penalty }),
         }
     }
 
-    pub fn new_with_engine(seat: Seat) -> Self {
-        GameClient {
-            seat,
-            game: None,
-            move_selector: Box::new(MockBridgeEngine::new(seat)),
-        }
-    }
-
-    pub fn new_interactive(seat: Seat) -> Self {
-        GameClient {
-            seat,
-            game: None,
-            move_selector: Box::new(CliMoveSelector::new(seat)),
-        }
-    }
-
-    pub fn new_with_move_selector<T: SelectMove + 'a>(seat: Seat, selector: T) -> Self {
+    pub fn new_with_move_selector<T: SelectMove<N> + 'a>(seat: Seat, selector: T) -> Self {
         GameClient {
             seat,
             game: None,
@@ -105,16 +143,35 @@ impl<'a> GameClient<'a> {
     }
 }
 
+impl GameClient<'_> {
+    pub fn new_with_engine(seat: Seat) -> Self {
+        GameClient {
+            seat,
+            game: None,
+            move_selector: Box::new(MockBridgeEngine::new(seat)),
+        }
+    }
+
+    pub fn new_interactive(seat: Seat) -> Self {
+        GameClient {
+            seat,
+            game: None,
+            move_selector: Box::new(CliMoveSelector::new(seat)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::actors::game_client::GameClient;
     use crate::primitives::bid::{Bid, ContractBid};
     use crate::primitives::contract::Contract;
-    use crate::primitives::deal::Board;
+    use crate::primitives::deal::{Board, Seat};
     use crate::primitives::game_event::GameEvent::{BiddingEnded, DiscloseHand};
     use crate::primitives::game_event::{
         BidEvent, BiddingEndedEvent, CardEvent, DiscloseHandEvent, GameEvent, NewGameEvent,
     };
+    use crate::primitives::hand_info::explanation::Explanation;
     use crate::primitives::player_event::PlayerEvent;
     use crate::primitives::{Card, Hand};
     use std::str::FromStr;
@@ -131,7 +188,11 @@ mod test {
 
         let mut player = GameClient::new_with_engine(seat);
 
-        let ng_event = NewGameEvent { board };
+        let ng_event = NewGameEvent {
+            board,
+            director: false,
+            mini_bridge: false,
+        };
         let event = GameEvent::NewGame(ng_event);
 
         player.process_game_event(event).unwrap();
@@ -145,6 +206,8 @@ mod test {
         let expected_event = PlayerEvent::Bid(BidEvent {
             player: seat,
             bid: Bid::Contract(ContractBid::from_str("1C").unwrap()),
+            alert: false,
+            explanation: None,
         });
 
         assert_eq!(player_event, expected_event);
@@ -160,7 +223,11 @@ mod test {
 
         let mut player = GameClient::new_with_engine(seat);
 
-        let ng_event = NewGameEvent { board };
+        let ng_event = NewGameEvent {
+            board,
+            director: false,
+            mini_bridge: false,
+        };
         let event = GameEvent::NewGame(ng_event);
 
         player.process_game_event(event).unwrap();
@@ -178,6 +245,8 @@ mod test {
             let event = GameEvent::Bid(BidEvent {
                 player: player_pos,
                 bid,
+                alert: false,
+                explanation: None,
             });
             player.process_game_event(event).unwrap();
             player_pos = player_pos + 1;
@@ -190,11 +259,35 @@ mod test {
 
         let player_event = player.get_move().unwrap();
 
+        // top of the sequence in the longest suit
         let expected_event = PlayerEvent::Card(CardEvent {
             player: seat,
-            card: Card::from_str("CJ").unwrap(),
+            card: Card::from_str("CA").unwrap(),
         });
 
         assert_eq!(player_event, expected_event);
     }
+
+    #[test]
+    fn partners_alert_is_hidden() {
+        let explanation = Explanation::from_str("Hand has 15 to 17 hcp.").unwrap();
+        let event: GameEvent = GameEvent::Bid(BidEvent {
+            player: Seat::North,
+            bid: Bid::from_str("1NT").unwrap(),
+            alert: true,
+            explanation: Some(explanation),
+        });
+
+        assert_eq!(
+            GameClient::hide_partners_alert(event, Seat::South),
+            GameEvent::Bid(BidEvent {
+                player: Seat::North,
+                bid: Bid::from_str("1NT").unwrap(),
+                alert: false,
+                explanation: None,
+            })
+        );
+        assert_eq!(GameClient::hide_partners_alert(event, Seat::East), event);
+        assert_eq!(GameClient::hide_partners_alert(event, Seat::North), event);
+    }
 }
@@ -1,33 +1,51 @@
+use crate::actors::card_analyser::{AnalyseCard, CardAnalysis};
+use crate::actors::claim_verifier::VerifyClaim;
 use crate::error::BBError;
 use crate::game::game_phase_states::GamePhaseState;
+use crate::game::mini_bridge::MiniBridge;
 use crate::game::scoring::ScoreCalculator;
 use crate::game::GameState;
 use crate::primitives::deal::seat::SEAT_ARRAY;
 use crate::primitives::deal::Seat;
 use crate::primitives::game_event::{
-    BiddingEndedEvent, DiscloseHandEvent, DummyUncoveredEvent, GameEndedEvent, GameEvent, NewGameEvent,
+    BiddingEndedEvent, CardEvent, ClaimEvent, ClaimRulingEvent, DiscloseHandEvent, DummyUncoveredEvent, GameEndedEvent,
+    GameEvent, NewGameEvent, UndoEvent, UndoRequestEvent,
 };
 use crate::primitives::game_result::GameResult;
 use crate::primitives::player_event::PlayerEvent;
 use crate::primitives::{Contract, Deal};
 use itertools::Itertools;
 
-pub struct GameManager {
-    deal: Deal<13>,
-    game: Option<GameState>,
-    history: Vec<GameEvent>,
+pub struct GameManager<const N: usize = 13> {
+    deal: Deal<N>,
+    game: Option<GameState<N>>,
+    history: Vec<GameEvent<N>>,
+    director: bool,
+    mini_bridge: bool,
 }
 
-impl GameManager {
-    pub fn new_from_deal(deal: Deal<13>) -> Self {
+impl<const N: usize> GameManager<N> {
+    pub fn new_from_deal(deal: Deal<N>) -> Self {
         GameManager {
             deal,
             game: None,
             history: Vec::new(),
+            director: false,
+            mini_bridge: false,
         }
     }
 
-    pub fn history(&self) -> Vec<GameEvent> {
+    // has to be set before the game starts
+    pub fn set_director_mode(&mut self, director: bool) {
+        self.director = director;
+    }
+
+    // has to be set before the game starts
+    pub fn set_mini_bridge(&mut self, mini_bridge: bool) {
+        self.mini_bridge = mini_bridge;
+    }
+
+    pub fn history(&self) -> Vec<GameEvent<N>> {
         self.history.iter().copied().collect_vec()
     }
 
@@ -38,20 +56,42 @@ impl GameManager {
         }
     }
 
+    pub fn dummy(&self) -> Option<Seat> {
+        self.game.as_ref()?.declarer().map(|declarer| declarer.partner())
+    }
+
     pub fn new() -> Self {
         let deal = Deal::random();
         Self::new_from_deal(deal)
     }
 
+    pub fn pending_claim(&self) -> Option<ClaimEvent> {
+        match &self.game {
+            Some(GameState::CardPlay(state)) => state.pending_claim(),
+            _ => None,
+        }
+    }
+
+    pub fn pending_undo_request(&self) -> Option<UndoRequestEvent> {
+        self.game.as_ref()?.pending_undo_request()
+    }
+
     pub fn start_game(&mut self) -> Result<(), BBError> {
         match self.game {
             Some(_) => Err(BBError::GameAlreadyStarted),
             None => {
-                let new_game_event = NewGameEvent { board: self.deal.board };
+                let new_game_event = NewGameEvent {
+                    board: self.deal.board,
+                    director: self.director,
+                    mini_bridge: self.mini_bridge,
+                };
                 let game_event = GameEvent::NewGame(new_game_event);
                 self.add_event_to_history(game_event);
                 self.game = Some(GameState::from_new_game_event(new_game_event));
                 self.disclose_hands();
+                if self.mini_bridge {
+                    self.end_bidding(MiniBridge::contract(&self.deal));
+                }
                 Ok(())
             }
         }
@@ -61,7 +101,10 @@ impl GameManager {
         match &mut self.game {
             None => Err(BBError::GameHasNotStarted)?,
             Some(game) => {
-                let game_event = GameEvent::from(event);
+                let game_event = match game.irregularity(event) {
+                    Some(irregularity) => GameEvent::Irregularity(irregularity),
+                    None => GameEvent::from(event),
+                };
                 game.process_game_event(game_event)?;
                 self.add_event_to_history(game_event);
             }
@@ -72,6 +115,10 @@ impl GameManager {
     }
 
     fn react_to_new_game_state(&mut self) {
+        if let Some(request) = self.game.as_ref().unwrap().approved_undo_request() {
+            self.undo(request);
+        }
+
         match &mut self.game.as_mut().unwrap() {
             GameState::Bidding(state) => {
                 if state.bidding_has_ended() {
@@ -100,6 +147,17 @@ impl GameManager {
         }
     }
 
+    fn undo(&mut self, request: UndoRequestEvent) {
+        let game = self.game.as_mut().unwrap();
+        let undo_event = UndoEvent {
+            player: request.player,
+            moves: game.moves_to_undo(request.player).unwrap(),
+        };
+        let game_event = GameEvent::Undo(undo_event);
+        self.add_event_to_history(game_event);
+        self.game.as_mut().unwrap().process_game_event(game_event).unwrap();
+    }
+
     fn end_bidding(&mut self, contract: Contract) {
         let bidding_ended_event = BiddingEndedEvent {
             final_contract: contract,
@@ -134,18 +192,57 @@ impl GameManager {
         let game_ended_event = GameEndedEvent {
             deal: self.deal,
             result,
-            score: ScoreCalculator::score_result(result, self.deal.vulnerable()),
+            score: match self.mini_bridge {
+                true => ScoreCalculator::score_mini_bridge_result(result),
+                false => ScoreCalculator::score_result(result, self.deal.vulnerable()),
+            },
         };
         let event = GameEvent::GameEnded(game_ended_event);
         self.add_event_to_history(event);
         self.game.as_mut().unwrap().process_game_event(event).unwrap();
     }
 
-    fn add_event_to_history(&mut self, event: GameEvent) {
+    fn add_event_to_history(&mut self, event: GameEvent<N>) {
         self.history.push(event);
     }
+
+    pub fn verify_pending_claim(&mut self, verifier: &mut dyn VerifyClaim<N>) -> Result<(), BBError> {
+        let ruling_event = match &self.game {
+            Some(GameState::CardPlay(state)) => match state.pending_claim() {
+                Some(claim) => ClaimRulingEvent {
+                    claim,
+                    valid: verifier.verify_claim(&self.deal, state, claim),
+                },
+                None => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+
+        let game_event = GameEvent::ClaimRuling(ruling_event);
+        self.game.as_mut().unwrap().process_game_event(game_event)?;
+        self.add_event_to_history(game_event);
+
+        self.react_to_new_game_state();
+        Ok(())
+    }
+
+    pub fn analyse_card(&self, analyser: &mut dyn AnalyseCard<N>, card_event: CardEvent) -> Option<CardAnalysis> {
+        let (contract, trick_manager) = match self.game.as_ref()? {
+            GameState::OpeningLead(state) => (state.contract, &state.trick_manager),
+            GameState::CardPlay(state) => (state.contract, &state.trick_manager),
+            _ => return None,
+        };
+        analyser.analyse_card(
+            &self.deal,
+            contract,
+            trick_manager.played_cards(),
+            card_event.player,
+            card_event.card,
+        )
+    }
 }
-impl Default for GameManager {
+
+impl<const N: usize> Default for GameManager<N> {
     fn default() -> Self {
         Self::new()
     }
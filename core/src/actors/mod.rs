@@ -1,3 +1,7 @@
+pub mod card_analyser;
+pub mod claim_verifier;
+pub mod duplicate_match;
 pub mod game_client;
 pub mod game_manager;
+pub mod spectator;
 pub mod table;
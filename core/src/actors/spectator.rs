@@ -0,0 +1,133 @@
+use crate::engine::observer_game_view::{Observer, ObserverGameStateView};
+use crate::error::BBError;
+use crate::game::GameState;
+use crate::primitives::game_event::GameEvent;
+use std::collections::VecDeque;
+
+// a public spectator never receives the hidden hands; with a delay, events are only shown
+// once `delay` further events have happened, and everything is revealed at the end
+pub struct Spectator<const N: usize = 13> {
+    observer: Observer,
+    delay: usize,
+    game: Option<GameState<N>>,
+    delayed_events: VecDeque<GameEvent<N>>,
+}
+
+impl<const N: usize> Spectator<N> {
+    pub fn new(observer: Observer) -> Self {
+        Self::delayed(observer, 0)
+    }
+
+    pub fn delayed(observer: Observer, delay: usize) -> Self {
+        Spectator {
+            observer,
+            delay,
+            game: None,
+            delayed_events: VecDeque::new(),
+        }
+    }
+
+    pub fn observer(&self) -> Observer {
+        self.observer
+    }
+
+    pub fn process_game_event(&mut self, event: GameEvent<N>) -> Result<(), BBError> {
+        if self.observer == Observer::Public && matches!(event, GameEvent::DiscloseHand(_)) {
+            return Ok(());
+        }
+        self.delayed_events.push_back(event);
+
+        let game_has_ended = matches!(event, GameEvent::GameEnded(_));
+        while self.delayed_events.len() > self.delay || game_has_ended && !self.delayed_events.is_empty() {
+            let event = self.delayed_events.pop_front().unwrap();
+            self.show_event(event)?;
+        }
+        Ok(())
+    }
+
+    fn show_event(&mut self, event: GameEvent<N>) -> Result<(), BBError> {
+        match (event, &mut self.game) {
+            (GameEvent::NewGame(new_game_event), _) => {
+                self.game = Some(GameState::from_new_game_event(new_game_event));
+                Ok(())
+            }
+            (_, None) => Err(BBError::GameHasNotStarted),
+            (_, Some(game)) => game.process_game_event(event),
+        }
+    }
+
+    pub fn view(&self) -> Option<ObserverGameStateView<'_, N>> {
+        self.game
+            .as_ref()
+            .map(|game| ObserverGameStateView::new(game, self.observer))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::actors::spectator::Spectator;
+    use crate::engine::observer_game_view::Observer;
+    use crate::primitives::bid::Bid;
+    use crate::primitives::deal::seat::SEAT_ARRAY;
+    use crate::primitives::deal::Seat;
+    use crate::primitives::game_event::{BidEvent, DiscloseHandEvent, GameEvent, NewGameEvent};
+    use crate::primitives::Deal;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    fn start_game(spectator: &mut Spectator, deal: &Deal<13>) {
+        let new_game_event = NewGameEvent {
+            board: deal.board,
+            director: false,
+            mini_bridge: false,
+        };
+        spectator
+            .process_game_event(GameEvent::NewGame(new_game_event))
+            .unwrap();
+        for seat in SEAT_ARRAY {
+            let disclose_hand_event = DiscloseHandEvent {
+                seat,
+                hand: *deal.hand_of(seat),
+            };
+            spectator
+                .process_game_event(GameEvent::DiscloseHand(disclose_hand_event))
+                .unwrap();
+        }
+    }
+
+    #[test_case(Observer::Kibitzer, true; "Kibitzer")]
+    #[test_case(Observer::Public, false; "Public")]
+    fn hidden_hands(observer: Observer, visible: bool) {
+        let deal = Deal::from_u64_seed(9000);
+        let mut spectator = Spectator::new(observer);
+        start_game(&mut spectator, &deal);
+
+        let view = spectator.view().unwrap();
+        for seat in SEAT_ARRAY {
+            assert_eq!(view.hand_of(seat).is_some(), visible);
+        }
+    }
+
+    #[test]
+    fn delayed_spectator() {
+        let deal: Deal<13> = Deal::from_u64_seed(9000);
+        let mut spectator = Spectator::delayed(Observer::Public, 2);
+        start_game(&mut spectator, &deal);
+
+        for (index, bid) in ["p", "1NT", "p", "2C"].into_iter().enumerate() {
+            let bid_event = BidEvent {
+                player: Seat::West + index,
+                bid: Bid::from_str(bid).unwrap(),
+                alert: false,
+                explanation: None,
+            };
+            spectator.process_game_event(GameEvent::Bid(bid_event)).unwrap();
+        }
+
+        let view = spectator.view().unwrap();
+        assert_eq!(
+            view.bids(),
+            [Bid::from_str("p").unwrap(), Bid::from_str("1NT").unwrap()]
+        );
+    }
+}
@@ -1,27 +1,59 @@
+use crate::actors::card_analyser::AnalyseCard;
+use crate::actors::claim_verifier::VerifyClaim;
 use crate::actors::game_client::GameClient;
 use crate::actors::game_manager::GameManager;
+use crate::actors::spectator::Spectator;
+use crate::engine::reason::Reason;
 use crate::error::BBError;
 use crate::game::scoring::ScorePoints;
 use crate::primitives::deal::Seat;
 use crate::primitives::game_event::GameEvent;
+use crate::primitives::player_event::PlayerEvent;
 use crate::primitives::Deal;
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 
-pub struct Table<'a> {
-    game_manager: Option<GameManager>,
-    seats: BTreeMap<Seat, Box<GameClient<'a>>>,
+pub struct Table<'a, const N: usize = 13> {
+    game_manager: Option<GameManager<N>>,
+    seats: BTreeMap<Seat, Box<GameClient<'a, N>>>,
+    claim_verifier: Option<Box<dyn VerifyClaim<N> + 'a>>,
+    card_analyser: Option<(Box<dyn AnalyseCard<N> + 'a>, Seat)>,
+    spectators: Vec<Spectator<N>>,
+    director: bool,
+    mini_bridge: bool,
 }
 
-impl<'a> Table<'a> {
+impl<'a, const N: usize> Table<'a, N> {
     pub fn empty() -> Self {
         Table {
             game_manager: None,
             seats: BTreeMap::new(),
+            claim_verifier: None,
+            card_analyser: None,
+            spectators: Vec::new(),
+            director: false,
+            mini_bridge: false,
         }
     }
 
-    pub fn seat_player(&mut self, player: GameClient<'a>, seat: Seat) -> Result<(), BBError> {
+    pub fn set_director_mode(&mut self, director: bool) {
+        self.director = director;
+    }
+
+    pub fn set_mini_bridge(&mut self, mini_bridge: bool) {
+        self.mini_bridge = mini_bridge;
+    }
+
+    pub fn set_claim_verifier<T: VerifyClaim<N> + 'a>(&mut self, verifier: T) {
+        self.claim_verifier = Some(Box::new(verifier));
+    }
+
+    // including dummy's cards when the player is declarer
+    pub fn set_card_analyser<T: AnalyseCard<N> + 'a>(&mut self, analyser: T, seat: Seat) {
+        self.card_analyser = Some((Box::new(analyser), seat));
+    }
+
+    pub fn seat_player(&mut self, player: GameClient<'a, N>, seat: Seat) -> Result<(), BBError> {
         if let Entry::Vacant(e) = self.seats.entry(seat) {
             e.insert(Box::new(player));
             Ok(())
@@ -30,17 +62,35 @@ impl<'a> Table<'a> {
         }
     }
 
+    pub fn add_spectator(&mut self, spectator: Spectator<N>) {
+        self.spectators.push(spectator);
+    }
+
+    pub fn spectators(&self) -> &[Spectator<N>] {
+        &self.spectators
+    }
+
     pub fn new_game(&mut self) -> Result<(), BBError> {
         let deal = Deal::random();
         self.new_game_from_deal(deal)
     }
 
-    pub fn new_game_from_deal(&mut self, deal: Deal<13>) -> Result<(), BBError> {
-        self.game_manager = Some(GameManager::new_from_deal(deal));
+    pub fn new_game_from_deal(&mut self, deal: Deal<N>) -> Result<(), BBError> {
+        let mut manager = GameManager::new_from_deal(deal);
+        manager.set_director_mode(self.director);
+        manager.set_mini_bridge(self.mini_bridge);
+        self.game_manager = Some(manager);
         Ok(())
     }
 
-    fn broadcast_event(&mut self, event: GameEvent) {
+    pub fn history(&self) -> Vec<GameEvent<N>> {
+        self.game_manager.as_ref().map(GameManager::history).unwrap_or_default()
+    }
+
+    fn broadcast_event(&mut self, event: GameEvent<N>) {
+        for spectator in self.spectators.iter_mut() {
+            spectator.process_game_event(event).unwrap();
+        }
         match event {
             GameEvent::DiscloseHand(dh_event) => {
                 let seat = dh_event.seat;
@@ -62,8 +112,6 @@ impl<'a> Table<'a> {
 
             let mut i = 0;
 
-            let mut dummy = None;
-
             loop {
                 let history = self.game_manager.as_ref().unwrap().history();
 
@@ -75,29 +123,53 @@ impl<'a> Table<'a> {
                     if let GameEvent::GameEnded(ge_event) = event {
                         return Ok(ge_event.score);
                     }
-
-                    if let GameEvent::BiddingEnded(be_event) = event {
-                        dummy = Some(be_event.final_contract.declarer.partner());
-                    }
                 }
 
                 let next_player = self.game_manager.as_ref().unwrap().next_to_play().unwrap();
+                // dummy is looked up every time, as an undo might take the game back into the auction
+                let dummy = self.game_manager.as_ref().unwrap().dummy();
                 // println!("Next Player: {:?}", next_player);
 
-                let player_event = if Some(next_player) == dummy {
-                    self.seats.get(&dummy.unwrap().partner()).unwrap().get_move().unwrap()
-                } else {
-                    self.seats.get(&next_player).unwrap().get_move().unwrap()
+                let chooser = match Some(next_player) == dummy {
+                    true => dummy.unwrap().partner(),
+                    false => next_player,
                 };
+                let player_event = self.seats.get(&chooser).unwrap().get_move().unwrap();
+
+                if let Some(reason) = self.seats.get(&chooser).unwrap().explain_move(player_event) {
+                    // the details might give away the rest of the player's hand
+                    let reason = Reason {
+                        details: Vec::new(),
+                        ..reason
+                    };
+                    for (_, player) in self.seats.iter_mut().filter(|(&seat, _)| seat != chooser) {
+                        player.process_move_reason(player_event, reason.clone());
+                    }
+                }
 
                 // println!("Player made move: {:?}", player_event);
 
+                if let (PlayerEvent::Card(card_event), Some((analyser, seat))) =
+                    (player_event, self.card_analyser.as_mut())
+                {
+                    if *seat == chooser {
+                        let manager = self.game_manager.as_ref().unwrap();
+                        if let Some(analysis) = manager.analyse_card(analyser.as_mut(), card_event) {
+                            self.seats.get_mut(&chooser).unwrap().process_card_analysis(analysis);
+                        }
+                    }
+                }
+
                 self.game_manager
                     .as_mut()
                     .unwrap()
                     .process_player_event(player_event)
                     .unwrap();
 
+                if let Some(verifier) = self.claim_verifier.as_deref_mut() {
+                    self.game_manager.as_mut().unwrap().verify_pending_claim(verifier)?;
+                }
+
                 i += 1;
 
                 if i > 1000 {
@@ -112,9 +184,227 @@ impl<'a> Table<'a> {
 
 #[cfg(test)]
 mod test {
+    use crate::actors::card_analyser::{AnalyseCard, CardAnalysis};
+    use crate::actors::claim_verifier::VerifyClaim;
     use crate::actors::game_client::GameClient;
+    use crate::actors::spectator::Spectator;
     use crate::actors::table::Table;
+    use crate::engine::bidding_engine::SelectBid;
+    use crate::engine::card_play_engine::SelectCard;
+    use crate::engine::mock_bridge_engine::MockBridgeEngine;
+    use crate::engine::observer_game_view::Observer;
+    use crate::engine::reason::Reason;
+    use crate::engine::subjective_game_view::{SubjectiveGamePhaseStateView, SubjectiveGameStateView};
+    use crate::engine::{Move, SelectMove};
+    use crate::error::BBError;
+    use crate::game::game_phase_states::{BiddingState, CardPlayState, OpeningLeadState};
+    use crate::primitives::bid::{AuxiliaryBid, Bid};
+    use crate::primitives::deal::Seat;
     use crate::primitives::deal::Seat::*;
+    use crate::primitives::game_event::{ClaimEvent, GameEvent, NewGameEvent};
+    use crate::primitives::player_event::PlayerEvent;
+    use crate::primitives::{Card, Contract, Deal};
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    struct FixedRuling(bool);
+
+    impl VerifyClaim for FixedRuling {
+        fn verify_claim(&mut self, _deal: &Deal<13>, _state: &CardPlayState, _claim: ClaimEvent) -> bool {
+            self.0
+        }
+    }
+
+    struct Counter(Rc<Cell<usize>>);
+
+    impl AnalyseCard for Counter {
+        fn analyse_card(
+            &mut self,
+            _deal: &Deal<13>,
+            _contract: Contract,
+            played_cards: &[Card],
+            player: Seat,
+            card: Card,
+        ) -> Option<CardAnalysis> {
+            self.0.set(self.0.get() + 1);
+            Some(CardAnalysis {
+                player,
+                card,
+                trick_number: played_cards.len() / 4 + 1,
+                tricks_lost: 0,
+                best_cards: vec![card],
+            })
+        }
+    }
+
+    struct Regretful {
+        engine: MockBridgeEngine,
+        regretted: Cell<bool>,
+    }
+
+    impl Regretful {
+        fn new(seat: Seat) -> Self {
+            Regretful {
+                engine: MockBridgeEngine::new(seat),
+                regretted: Cell::new(false),
+            }
+        }
+    }
+
+    impl SelectBid for Regretful {
+        fn select_bid(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> Bid {
+            self.engine.select_bid(state)
+        }
+    }
+
+    impl SelectCard for Regretful {
+        fn select_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card {
+            self.engine.select_card(state)
+        }
+
+        fn select_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Card {
+            self.engine.select_opening_lead(state)
+        }
+    }
+
+    impl SelectMove for Regretful {
+        fn select_move(&self, game: SubjectiveGameStateView) -> Result<Move, BBError> {
+            if let SubjectiveGameStateView::CardPlay(state) = &game {
+                if state.pending_undo_request().is_none() && state.played_cards().len() > 8 && !self.regretted.get() {
+                    self.regretted.set(true);
+                    return Ok(Move::UndoRequest);
+                }
+            }
+            self.engine.select_move(game)
+        }
+
+        fn process_game_event(&mut self, event: GameEvent, game_state: SubjectiveGameStateView) -> Result<(), BBError> {
+            self.engine.process_game_event(event, game_state)
+        }
+    }
+
+    struct Listener {
+        engine: MockBridgeEngine,
+        explained: Rc<RefCell<Vec<PlayerEvent>>>,
+    }
+
+    impl SelectBid for Listener {
+        fn select_bid(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> Bid {
+            self.engine.select_bid(state)
+        }
+    }
+
+    impl SelectCard for Listener {
+        fn select_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card {
+            self.engine.select_card(state)
+        }
+
+        fn select_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Card {
+            self.engine.select_opening_lead(state)
+        }
+    }
+
+    impl SelectMove for Listener {
+        fn process_game_event(&mut self, event: GameEvent, game_state: SubjectiveGameStateView) -> Result<(), BBError> {
+            self.engine.process_game_event(event, game_state)
+        }
+
+        fn process_move_reason(&mut self, event: PlayerEvent, reason: Reason) {
+            assert!(reason.details.is_empty());
+            self.explained.borrow_mut().push(event);
+        }
+    }
+
+    // opens 1NT and plays its first legal card, with hands of any size
+    struct FirstLegal;
+
+    impl<const N: usize> SelectBid<N> for FirstLegal {
+        fn select_bid(&self, state: SubjectiveGamePhaseStateView<BiddingState<N>>) -> Bid {
+            match state.bids().is_empty() {
+                true => Bid::from_str("1NT").unwrap(),
+                false => Bid::Auxiliary(AuxiliaryBid::Pass),
+            }
+        }
+    }
+
+    impl<const N: usize> SelectCard<N> for FirstLegal {
+        fn select_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState<N>>) -> Card {
+            state.legal_cards(state.next_to_play())[0]
+        }
+
+        fn select_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState<N>>) -> Card {
+            state.legal_cards()[0]
+        }
+    }
+
+    impl<const N: usize> SelectMove<N> for FirstLegal {
+        fn process_game_event(
+            &mut self,
+            _event: GameEvent<N>,
+            _game: SubjectiveGameStateView<N>,
+        ) -> Result<(), BBError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn run_game_with_move_reasons() {
+        let mut table = Table::empty();
+
+        for seat in [North, East, West] {
+            table.seat_player(GameClient::new_with_engine(seat), seat).unwrap();
+        }
+        let explained = Rc::new(RefCell::new(Vec::new()));
+        let listener = Listener {
+            engine: MockBridgeEngine::new(South),
+            explained: explained.clone(),
+        };
+        table
+            .seat_player(GameClient::new_with_move_selector(South, listener), South)
+            .unwrap();
+
+        table.new_game_from_deal(Deal::from_u64_seed(9000)).unwrap();
+        table.run_game().unwrap();
+
+        let manager = table.game_manager.as_ref().unwrap();
+        let dummy = manager.dummy();
+        let chooser = |player: Seat| match Some(player) == dummy {
+            true => player.partner(),
+            false => player,
+        };
+        let bots_moves = manager
+            .history()
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::Bid(bid_event) if bid_event.player != South => Some(PlayerEvent::Bid(*bid_event)),
+                GameEvent::Card(card_event) if chooser(card_event.player) != South => {
+                    Some(PlayerEvent::Card(*card_event))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(!bots_moves.is_empty());
+        assert_eq!(*explained.borrow(), bots_moves);
+    }
+
+    #[test]
+    fn run_game_with_undo() {
+        let mut table = Table::empty();
+
+        for seat in [North, East, South, West] {
+            table
+                .seat_player(GameClient::new_with_move_selector(seat, Regretful::new(seat)), seat)
+                .unwrap();
+        }
+
+        table.new_game_from_deal(Deal::from_u64_seed(9000)).unwrap();
+        table.run_game().unwrap();
+
+        let history = table.game_manager.as_ref().unwrap().history();
+        assert!(history.iter().any(|event| matches!(event, GameEvent::Undo(_))));
+    }
 
     #[test]
     fn run_game() {
@@ -133,4 +423,132 @@ mod test {
         table.new_game().unwrap();
         table.run_game().unwrap();
     }
+
+    #[test]
+    fn run_game_in_director_mode() {
+        let mut table = Table::empty();
+        table.set_director_mode(true);
+
+        for seat in [North, East, South, West] {
+            table.seat_player(GameClient::new_with_engine(seat), seat).unwrap();
+        }
+
+        table.new_game_from_deal(Deal::from_u64_seed(9000)).unwrap();
+        table.run_game().unwrap();
+
+        let history = table.game_manager.as_ref().unwrap().history();
+        assert!(matches!(
+            history[0],
+            GameEvent::NewGame(NewGameEvent { director: true, .. })
+        ));
+        assert!(matches!(history.last(), Some(GameEvent::GameEnded(_))));
+    }
+
+    #[test]
+    fn run_mini_bridge_game() {
+        let mut table = Table::empty();
+        table.set_mini_bridge(true);
+
+        for seat in [North, East, South, West] {
+            table.seat_player(GameClient::new_with_engine(seat), seat).unwrap();
+        }
+
+        table.new_game_from_deal(Deal::from_u64_seed(9000)).unwrap();
+        table.run_game().unwrap();
+
+        let history = table.game_manager.as_ref().unwrap().history();
+        assert!(matches!(
+            history[0],
+            GameEvent::NewGame(NewGameEvent { mini_bridge: true, .. })
+        ));
+        assert!(matches!(history[5], GameEvent::BiddingEnded(_)));
+        assert!(!history.iter().any(|event| matches!(event, GameEvent::Bid(_))));
+        assert!(matches!(history.last(), Some(GameEvent::GameEnded(_))));
+    }
+
+    #[test]
+    fn run_game_with_three_cards_per_hand() {
+        let mut table = Table::<3>::empty();
+        table.add_spectator(Spectator::new(Observer::Kibitzer));
+
+        for seat in [North, East, South, West] {
+            table
+                .seat_player(GameClient::new_with_move_selector(seat, FirstLegal), seat)
+                .unwrap();
+        }
+
+        table.new_game_from_deal(Deal::from_u64_seed(9000)).unwrap();
+        table.run_game().unwrap();
+
+        let kibitzer = table.spectators()[0].view().unwrap();
+        assert_eq!(kibitzer.played_tricks().len(), 3);
+        assert_eq!(kibitzer.hand_of(North).unwrap().cards().count(), 3);
+        assert!(matches!(table.history().last(), Some(GameEvent::GameEnded(_))));
+    }
+
+    #[test]
+    fn run_game_with_spectators() {
+        let mut table = Table::empty();
+        table.add_spectator(Spectator::new(Observer::Kibitzer));
+        table.add_spectator(Spectator::delayed(Observer::Public, 5));
+
+        for seat in [North, East, South, West] {
+            table.seat_player(GameClient::new_with_engine(seat), seat).unwrap();
+        }
+
+        table.new_game_from_deal(Deal::from_u64_seed(9000)).unwrap();
+        table.run_game().unwrap();
+
+        let kibitzer = table.spectators()[0].view().unwrap();
+        let public = table.spectators()[1].view().unwrap();
+        assert_eq!(kibitzer.bids(), public.bids());
+        assert_eq!(kibitzer.played_tricks().len(), public.played_tricks().len());
+        for seat in [North, East, South, West] {
+            assert!(kibitzer.hand_of(seat).is_some());
+            assert_eq!(public.hand_of(seat).is_some(), public.dummy() == Some(seat));
+        }
+    }
+
+    #[test_case(true; "Valid")]
+    #[test_case(false; "Invalid")]
+    fn run_game_with_claim_verifier(ruling: bool) {
+        let mut table = Table::empty();
+
+        for seat in [North, East, South, West] {
+            table.seat_player(GameClient::new_with_engine(seat), seat).unwrap();
+        }
+        table.set_claim_verifier(FixedRuling(ruling));
+
+        table.new_game().unwrap();
+        table.run_game().unwrap();
+    }
+
+    #[test]
+    fn run_game_with_card_analyser() {
+        let mut table = Table::empty();
+
+        for seat in [North, East, South, West] {
+            table.seat_player(GameClient::new_with_engine(seat), seat).unwrap();
+        }
+        let count = Rc::new(Cell::new(0));
+        table.set_card_analyser(Counter(count.clone()), South);
+
+        table.new_game_from_deal(Deal::from_u64_seed(9000)).unwrap();
+        table.run_game().unwrap();
+
+        let manager = table.game_manager.as_ref().unwrap();
+        let dummy = manager.dummy();
+        let souths_cards = manager
+            .history()
+            .iter()
+            .filter(|event| match event {
+                GameEvent::Card(card_event) => match Some(card_event.player) == dummy {
+                    true => card_event.player.partner() == South,
+                    false => card_event.player == South,
+                },
+                _ => false,
+            })
+            .count();
+        assert_eq!(count.get(), souths_cards);
+    }
 }
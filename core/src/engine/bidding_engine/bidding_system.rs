@@ -0,0 +1,200 @@
+use crate::error::BBError;
+use crate::primitives::bid::{AuxiliaryBid, Bid};
+use crate::primitives::hand_info::explanation::Explanation;
+use std::str::FromStr;
+
+// one rule per line: `auction => call[!] [@priority] [: what the call shows]`, '#' starts a comment.
+// The auction is matched after any initial passes, `-` stands for an opening and `*` for any call.
+// Among rules that fit, the highest priority wins, the first one in the file among equals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BiddingSystem {
+    name: String,
+    rules: Vec<BidRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BidRule {
+    pub auction: Vec<CallPattern>,
+    pub bid: Bid,
+    pub alert: bool,
+    pub priority: i32,
+    pub shows: Explanation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallPattern {
+    Any,
+    Call(Bid),
+}
+
+impl BiddingSystem {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn rules(&self) -> &[BidRule] {
+        &self.rules
+    }
+
+    pub fn rules_for(&self, auction: &[Bid]) -> Vec<&BidRule> {
+        let mut rules = self
+            .rules
+            .iter()
+            .filter(|rule| rule.matches(auction))
+            .collect::<Vec<_>>();
+        rules.sort_by_key(|rule| -rule.priority);
+        rules
+    }
+}
+
+impl BidRule {
+    pub fn matches(&self, auction: &[Bid]) -> bool {
+        let calls = auction
+            .iter()
+            .skip_while(|&&bid| bid == Bid::Auxiliary(AuxiliaryBid::Pass))
+            .collect::<Vec<_>>();
+        calls.len() == self.auction.len()
+            && calls.iter().zip(&self.auction).all(|(&&bid, pattern)| match pattern {
+                CallPattern::Any => true,
+                CallPattern::Call(call) => *call == bid,
+            })
+    }
+}
+
+impl FromStr for BiddingSystem {
+    type Err = BBError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut name = String::new();
+        let mut rules = Vec::new();
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.strip_prefix("system ") {
+                Some(system) => name = system.trim().to_string(),
+                None => rules.push(BidRule::from_str(line)?),
+            }
+        }
+        Ok(BiddingSystem { name, rules })
+    }
+}
+
+impl FromStr for BidRule {
+    type Err = BBError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (auction, rest) = s
+            .split_once("=>")
+            .ok_or(BBError::ParseError(s.into(), "rule has no '=>'"))?;
+        let (call, shows) = match rest.split_once(':') {
+            Some((call, shows)) => (call, Explanation::from_str(shows)?),
+            None => (rest, Explanation::default()),
+        };
+
+        let auction = auction
+            .split_whitespace()
+            .filter(|&token| token != "-")
+            .map(|token| match token {
+                "*" => Ok(CallPattern::Any),
+                call => Bid::from_str(call).map(CallPattern::Call),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut tokens = call.split_whitespace();
+        let call = tokens.next().ok_or(BBError::ParseError(s.into(), "rule has no call"))?;
+        let (bid, alert) = match call.strip_suffix('!') {
+            Some(call) => (Bid::from_str(call)?, true),
+            None => (Bid::from_str(call)?, false),
+        };
+        let priority = match tokens.next() {
+            Some(priority) => priority
+                .strip_prefix('@')
+                .and_then(|priority| priority.parse().ok())
+                .ok_or(BBError::ParseError(priority.into(), "invalid priority"))?,
+            None => 0,
+        };
+        if tokens.next().is_some() {
+            return Err(BBError::ParseError(s.into(), "unexpected text after the priority"));
+        }
+
+        Ok(BidRule {
+            auction,
+            bid,
+            alert,
+            priority,
+            shows,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    fn bids(auction: &[&str]) -> Vec<Bid> {
+        auction.iter().map(|bid| Bid::from_str(bid).unwrap()).collect()
+    }
+
+    #[test]
+    fn parse_rule() {
+        let rule = BidRule::from_str("1NT P => 2C! @10 : Hand has 8 to 37 hcp.").unwrap();
+        assert_eq!(
+            rule.auction,
+            [
+                CallPattern::Call(Bid::from_str("1NT").unwrap()),
+                CallPattern::Call(Bid::from_str("P").unwrap())
+            ]
+        );
+        assert_eq!(rule.bid, Bid::from_str("2C").unwrap());
+        assert!(rule.alert);
+        assert_eq!(rule.priority, 10);
+        assert_eq!(rule.shows.hcp, Some((8, 37)));
+    }
+
+    #[test_case("1NT P 2C!"; "No arrow")]
+    #[test_case("1NT P =>"; "No call")]
+    #[test_case("1NT P => 2C 10"; "Priority without @")]
+    #[test_case("1NT P => 2C @10 Stayman"; "Free text")]
+    #[test_case("1NT P => 2C : Stayman"; "Free text explanation")]
+    #[test_case("1NT P => 8C"; "Unknown call")]
+    fn invalid_rule(rule: &str) {
+        assert!(BidRule::from_str(rule).is_err());
+    }
+
+    #[test_case("- => 1NT", &[], true; "Opening")]
+    #[test_case("- => 1NT", &["P", "P"], true; "Opening in third seat")]
+    #[test_case("- => 1NT", &["1C"], false; "Not an opening")]
+    #[test_case("1NT * => 2C", &["P", "1NT", "X"], true; "Any call")]
+    #[test_case("1NT P => 2C", &["1NT", "X"], false; "Different call")]
+    #[test_case("1NT P => 2C", &["1NT", "P", "2C", "P"], false; "Longer auction")]
+    fn rule_matches(rule: &str, auction: &[&str], expected: bool) {
+        let rule = BidRule::from_str(rule).unwrap();
+        assert_eq!(rule.matches(&bids(auction)), expected);
+    }
+
+    #[test]
+    fn rules_by_priority() {
+        let system = BiddingSystem::from_str(
+            "# Test
+system Test
+1NT P => P
+1NT P => 2D! @20 : Hand has 5 to 13 cards in ♥.
+1NT P => 3NT @10
+
+1NT P => 2H! @20 : Hand has 5 to 13 cards in ♠.",
+        )
+        .unwrap();
+        assert_eq!(system.name(), "Test");
+        assert_eq!(system.rules().len(), 4);
+
+        let calls = system
+            .rules_for(&bids(&["1NT", "P"]))
+            .iter()
+            .map(|rule| rule.bid)
+            .collect::<Vec<_>>();
+        assert_eq!(calls, bids(&["2D", "2H", "3NT", "P"]));
+    }
+}
@@ -0,0 +1,493 @@
+use super::{cheapest, cheapest_contract, contract, game_in, jump, pass, ForumDPlus2015BiddingEngine};
+use crate::engine::hand_evaluation::ForumDPlus2015Evaluator;
+use crate::engine::subjective_game_view::SubjectiveSeat;
+use crate::primitives::bid::{AuxiliaryBid, Bid, ContractBid};
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::contract::{Level, Strain};
+use crate::primitives::deal::hand::HandType;
+use crate::primitives::hand_info::suit_quality::SuitQuality;
+use crate::primitives::{Hand, Suit};
+use itertools::Itertools;
+
+type Call = (SubjectiveSeat, Bid);
+
+const DOUBLE: Bid = Bid::Auxiliary(AuxiliaryBid::Double);
+const REDOUBLE: Bid = Bid::Auxiliary(AuxiliaryBid::Redouble);
+
+// over their opening: five-card overcalls, 1NT with 15-18 and a stopper, takeout doubles, cue-bid raises;
+// over their interference: negative doubles, cue-bid raises and penalty doubles of 1NT
+impl ForumDPlus2015BiddingEngine {
+    pub(super) fn competitive(hand: &Hand<13>, auction: &[Bid]) -> Bid {
+        let calls = Self::calls_with_callers(auction);
+        let Some(last) = auction.iter().rev().find_map(|bid| match bid {
+            Bid::Contract(bid) => Some(*bid),
+            _ => None,
+        }) else {
+            return pass();
+        };
+
+        match calls.first() {
+            Some((SubjectiveSeat::LeftHandOpponent | SubjectiveSeat::RightHandOpponent, Bid::Contract(opening))) => {
+                Self::defending(hand, auction, &calls, *opening, last)
+            }
+            Some((_, Bid::Contract(opening))) => Self::contested(hand, auction, &calls, *opening, last),
+            _ => pass(),
+        }
+    }
+
+    fn calls_with_callers(auction: &[Bid]) -> Vec<Call> {
+        auction
+            .iter()
+            .enumerate()
+            .filter(|(_, &bid)| bid != pass())
+            .map(|(index, &bid)| {
+                let caller = match (auction.len() - index) % 4 {
+                    0 => SubjectiveSeat::Myself,
+                    1 => SubjectiveSeat::RightHandOpponent,
+                    2 => SubjectiveSeat::Partner,
+                    _ => SubjectiveSeat::LeftHandOpponent,
+                };
+                (caller, bid)
+            })
+            .collect()
+    }
+
+    fn their_suits(calls: &[Call]) -> Vec<Suit> {
+        calls
+            .iter()
+            .filter(|(caller, _)| is_opponent(*caller))
+            .filter_map(|(_, bid)| match bid {
+                Bid::Contract(ContractBid {
+                    strain: Strain::Trump(suit),
+                    ..
+                }) => Some(*suit),
+                _ => None,
+            })
+            .unique()
+            .collect()
+    }
+
+    fn defending(hand: &Hand<13>, auction: &[Bid], calls: &[Call], opening: ContractBid, last: ContractBid) -> Bid {
+        let their_suits = Self::their_suits(calls);
+        let our_calls = calls.iter().filter(|(caller, _)| !is_opponent(*caller)).collect_vec();
+
+        match our_calls[..] {
+            [] => Self::overcall(hand, auction, &their_suits, last),
+            [(SubjectiveSeat::Partner, partners_call)] => {
+                Self::advance(hand, auction, *partners_call, &their_suits, opening, last)
+            }
+            _ => pass(),
+        }
+    }
+
+    fn overcall(hand: &Hand<13>, auction: &[Bid], their_suits: &[Suit], last: ContractBid) -> Bid {
+        if last.level > Level::Two {
+            return pass();
+        }
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        // in the pass-out seat, we borrow a king from partner
+        let balancing = auction.ends_with(&[pass(), pass()]);
+        let borrowed = if balancing { 3.0 } else { 0.0 };
+
+        let balanced = matches!(hand.hand_type(), HandType::Balanced(_));
+        let stops_their_suits = their_suits
+            .iter()
+            .all(|&suit| ForumDPlus2015Evaluator::stops(suit, hand, true));
+        let over_no_trump = last.strain == Strain::NoTrump;
+        if balanced
+            && stops_their_suits
+            && !over_no_trump
+            && last.level == Level::One
+            && (15.0..=18.0).contains(&(hcp + borrowed))
+        {
+            return cheapest(Strain::NoTrump, last);
+        }
+        if hcp >= 18.0 {
+            return DOUBLE;
+        }
+
+        let overcall_suit = SUIT_ARRAY
+            .into_iter()
+            .filter(|suit| !their_suits.contains(suit))
+            .filter(|&suit| hand.length_in(suit) >= 5)
+            .filter(|&suit| ForumDPlus2015Evaluator::suit_quality(hand, suit) >= SuitQuality::Acceptable)
+            .max_by_key(|&suit| (hand.length_in(suit), suit));
+        if let Some(suit) = overcall_suit {
+            let bid = cheapest_contract(Strain::Trump(suit), last);
+            let good_suit =
+                hand.length_in(suit) >= 6 || ForumDPlus2015Evaluator::suit_quality(hand, suit) >= SuitQuality::Good;
+            let sound = match bid.level {
+                Level::One => hcp + borrowed >= 8.0,
+                Level::Two => hcp + borrowed >= 11.0 && good_suit,
+                _ => false,
+            };
+            if sound && hcp <= 16.0 {
+                return Bid::Contract(bid);
+            }
+        }
+
+        let short_in_their_suits = their_suits.iter().all(|&suit| hand.length_in(suit) <= 2);
+        let support_for_unbid_suits = SUIT_ARRAY
+            .into_iter()
+            .filter(|suit| !their_suits.contains(suit))
+            .all(|suit| hand.length_in(suit) >= 3);
+        if hcp + borrowed >= 12.0 && short_in_their_suits && support_for_unbid_suits && !over_no_trump {
+            return DOUBLE;
+        }
+        // doubling no trump is for penalties
+        if hcp + borrowed >= 15.0 && over_no_trump {
+            return DOUBLE;
+        }
+        pass()
+    }
+
+    fn advance(
+        hand: &Hand<13>,
+        auction: &[Bid],
+        partners_call: Bid,
+        their_suits: &[Suit],
+        opening: ContractBid,
+        last: ContractBid,
+    ) -> Bid {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        let rho_passed = auction.last() == Some(&pass());
+        let stops_their_suits = their_suits
+            .iter()
+            .all(|&suit| ForumDPlus2015Evaluator::stops(suit, hand, true));
+        let unbid_suit = SUIT_ARRAY
+            .into_iter()
+            .filter(|suit| !their_suits.contains(suit))
+            .max_by_key(|&suit| (hand.length_in(suit), suit.is_major(), suit));
+
+        match partners_call {
+            Bid::Auxiliary(AuxiliaryBid::Double) if !rho_passed => match unbid_suit {
+                Some(suit) if hand.length_in(suit) >= 4 && hcp >= 6.0 => cheapest(Strain::Trump(suit), last),
+                _ => pass(),
+            },
+            Bid::Auxiliary(AuxiliaryBid::Double) => {
+                // converting partner's takeout double into a penalty double
+                if let Strain::Trump(suit) = last.strain {
+                    if hand.length_in(suit) >= 5
+                        && ForumDPlus2015Evaluator::suit_quality(hand, suit) >= SuitQuality::Good
+                    {
+                        return pass();
+                    }
+                }
+                let Some(suit) = unbid_suit else { return pass() };
+                let no_major = hand.length_in(suit) < 4 || suit.is_minor();
+                match hcp {
+                    x if x >= 13.0 && stops_their_suits && no_major => contract(Level::Three, Strain::NoTrump),
+                    x if x >= 13.0 && suit.is_major() => game_in(suit),
+                    x if (6.0..=9.0).contains(&x) && stops_their_suits && no_major => cheapest(Strain::NoTrump, last),
+                    x if x >= 9.0 => jump(Strain::Trump(suit), last),
+                    _ => cheapest(Strain::Trump(suit), last),
+                }
+            }
+            Bid::Contract(ContractBid {
+                strain: Strain::NoTrump,
+                ..
+            }) if rho_passed => {
+                let major = [Suit::Spades, Suit::Hearts]
+                    .into_iter()
+                    .find(|&suit| hand.length_in(suit) >= 5 && !their_suits.contains(&suit));
+                match (hcp, major) {
+                    (x, Some(major)) if x >= 10.0 && hand.length_in(major) >= 6 => game_in(major),
+                    (x, _) if x >= 10.0 => contract(Level::Three, Strain::NoTrump),
+                    (x, _) if x >= 8.0 => contract(Level::Two, Strain::NoTrump),
+                    (_, Some(major)) => cheapest(Strain::Trump(major), last),
+                    _ => pass(),
+                }
+            }
+            Bid::Contract(ContractBid {
+                strain: Strain::Trump(partners_suit),
+                ..
+            }) => {
+                if hand.length_in(partners_suit) >= 3 {
+                    let support_points = Self::fit_points(hand, partners_suit, 5);
+                    return match (support_points, opening.strain) {
+                        (x, _) if x >= 13.0 && partners_suit.is_major() => game_in(partners_suit),
+                        // cue-bid raise, showing at least a limit raise
+                        (x, Strain::Trump(their_suit)) if x >= 10.0 => cheapest(Strain::Trump(their_suit), last),
+                        (x, _) if x >= 6.0 => cheapest(Strain::Trump(partners_suit), last),
+                        _ => pass(),
+                    };
+                }
+                let own_suit = SUIT_ARRAY
+                    .into_iter()
+                    .filter(|suit| !their_suits.contains(suit) && *suit != partners_suit)
+                    .filter(|&suit| hand.length_in(suit) >= 5)
+                    .find(|&suit| ForumDPlus2015Evaluator::suit_quality(hand, suit) >= SuitQuality::Good);
+                if let (Some(suit), true) = (own_suit, hcp >= 8.0) {
+                    let bid = cheapest_contract(Strain::Trump(suit), last);
+                    if bid.level <= Level::Two {
+                        return Bid::Contract(bid);
+                    }
+                }
+                match hcp {
+                    x if x >= 15.0 && stops_their_suits => contract(Level::Three, Strain::NoTrump),
+                    x if x >= 12.0 && stops_their_suits => jump(Strain::NoTrump, last),
+                    x if x >= 8.0 && stops_their_suits => cheapest(Strain::NoTrump, last),
+                    _ => pass(),
+                }
+            }
+            _ => pass(),
+        }
+    }
+
+    fn contested(hand: &Hand<13>, auction: &[Bid], calls: &[Call], opening: ContractBid, last: ContractBid) -> Bid {
+        let their_suits = Self::their_suits(calls);
+        let our_calls = calls
+            .iter()
+            .filter(|(caller, _)| !is_opponent(*caller))
+            .map(|(_, bid)| *bid)
+            .collect_vec();
+        let callers = calls
+            .iter()
+            .filter(|(caller, _)| !is_opponent(*caller))
+            .map(|(caller, _)| *caller)
+            .collect_vec();
+        let their_last_call = calls
+            .iter()
+            .rev()
+            .find(|(caller, _)| is_opponent(*caller))
+            .map(|(_, bid)| *bid);
+
+        match (&callers[..], &our_calls[..], their_last_call) {
+            ([SubjectiveSeat::Partner], _, Some(their_call)) => {
+                Self::respond_to_interference(hand, opening, their_call, last)
+            }
+            ([SubjectiveSeat::Myself, SubjectiveSeat::Partner], [_, DOUBLE], _) => {
+                Self::answer_negative_double(hand, auction, opening, &their_suits, last)
+            }
+            (
+                [SubjectiveSeat::Myself, SubjectiveSeat::Partner],
+                [_, Bid::Contract(ContractBid {
+                    strain: Strain::Trump(cue_suit),
+                    ..
+                })],
+                _,
+            ) if their_suits.contains(cue_suit) => match opening.strain {
+                Strain::Trump(suit) if Self::points(hand) >= 14.0 => game_in(suit),
+                strain => cheapest(strain, last),
+            },
+            _ => Self::continue_uncontested(hand, &callers, &our_calls),
+        }
+    }
+
+    fn respond_to_interference(hand: &Hand<13>, opening: ContractBid, their_call: Bid, last: ContractBid) -> Bid {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        let points = Self::points(hand);
+
+        let their_bid = match their_call {
+            Bid::Contract(bid) => bid,
+            DOUBLE if hcp >= 10.0 && opening.level == Level::One => return REDOUBLE,
+            // ignore the double
+            _ => return Self::response(hand, opening),
+        };
+        if opening.level > Level::One || their_bid.level > Level::Two {
+            return pass();
+        }
+
+        let their_suit = match (opening.strain, their_bid.strain) {
+            // a 1NT overcall is doubled for penalties
+            (_, Strain::NoTrump) if hcp >= 9.0 => return DOUBLE,
+            (_, Strain::NoTrump) => return pass(),
+            // so is an overcall over our 1NT, with length in their suit
+            (Strain::NoTrump, Strain::Trump(suit)) if hcp >= 8.0 && hand.length_in(suit) >= 4 => return DOUBLE,
+            (Strain::NoTrump, Strain::Trump(_)) => {
+                return match hcp {
+                    x if x >= 10.0 => contract(Level::Three, Strain::NoTrump),
+                    _ => Self::competitive_suit(hand, last, &[]),
+                }
+            }
+            (_, Strain::Trump(suit)) => suit,
+        };
+        let Strain::Trump(partners_suit) = opening.strain else {
+            return pass();
+        };
+
+        let support = hand.length_in(partners_suit);
+        if partners_suit.is_major() && support >= 3 {
+            return match Self::fit_points(hand, partners_suit, 5) {
+                x if x >= 13.0 => game_in(partners_suit),
+                // cue-bid raise
+                x if x >= 10.0 => cheapest(Strain::Trump(their_suit), last),
+                x if x >= 6.0 => cheapest(Strain::Trump(partners_suit), last),
+                _ => pass(),
+            };
+        }
+
+        // a negative double shows the unbid major(s)
+        let unbid_majors = [Suit::Spades, Suit::Hearts]
+            .into_iter()
+            .filter(|&suit| suit != partners_suit && suit != their_suit)
+            .collect_vec();
+        let needed = match their_bid.level {
+            Level::One => 6.0,
+            _ => 8.0,
+        };
+        if !unbid_majors.is_empty() && unbid_majors.iter().all(|&suit| hand.length_in(suit) >= 4) && hcp >= needed {
+            return DOUBLE;
+        }
+
+        let new_suit = Self::competitive_suit(hand, last, &[partners_suit, their_suit]);
+        if let Bid::Contract(bid) = new_suit {
+            let enough = match bid.level {
+                Level::One => points >= 6.0,
+                _ => points >= 11.0,
+            };
+            if enough {
+                return new_suit;
+            }
+        }
+
+        if partners_suit.is_minor() && support >= 4 && (6.0..=9.0).contains(&points) {
+            return cheapest(Strain::Trump(partners_suit), last);
+        }
+
+        if ForumDPlus2015Evaluator::stops(their_suit, hand, true) {
+            let bid = cheapest_contract(Strain::NoTrump, last);
+            return match (hcp, bid.level) {
+                (x, _) if x >= 13.0 => contract(Level::Three, Strain::NoTrump),
+                (x, Level::One) if (6.0..=10.0).contains(&x) => Bid::Contract(bid),
+                (x, Level::Two) if (11.0..=12.0).contains(&x) => Bid::Contract(bid),
+                _ => pass(),
+            };
+        }
+        pass()
+    }
+
+    fn answer_negative_double(
+        hand: &Hand<13>,
+        auction: &[Bid],
+        opening: ContractBid,
+        their_suits: &[Suit],
+        last: ContractBid,
+    ) -> Bid {
+        if auction.last() != Some(&pass()) {
+            return pass();
+        }
+        let Strain::Trump(opened_suit) = opening.strain else {
+            return pass();
+        };
+        let points = Self::points(hand);
+
+        let unbid_major = [Suit::Spades, Suit::Hearts]
+            .into_iter()
+            .filter(|&suit| suit != opened_suit && !their_suits.contains(&suit))
+            .find(|&suit| hand.length_in(suit) >= 4);
+        if let Some(major) = unbid_major {
+            return match points {
+                x if x >= 15.0 => jump(Strain::Trump(major), last),
+                _ => cheapest(Strain::Trump(major), last),
+            };
+        }
+        if hand.length_in(opened_suit) >= 6 {
+            return cheapest(Strain::Trump(opened_suit), last);
+        }
+        let balanced = matches!(hand.hand_type(), HandType::Balanced(_));
+        let stops_their_suits = their_suits
+            .iter()
+            .all(|&suit| ForumDPlus2015Evaluator::stops(suit, hand, true));
+        if balanced && stops_their_suits {
+            return cheapest(Strain::NoTrump, last);
+        }
+        let mut excluded = their_suits.to_vec();
+        excluded.push(opened_suit);
+        match Self::competitive_suit(hand, last, &excluded) {
+            Bid::Contract(bid) if bid.level <= Level::Two => Bid::Contract(bid),
+            _ => cheapest(Strain::Trump(opened_suit), last),
+        }
+    }
+
+    // at least five cards, or four for a minor
+    fn competitive_suit(hand: &Hand<13>, last: ContractBid, excluded: &[Suit]) -> Bid {
+        SUIT_ARRAY
+            .into_iter()
+            .filter(|suit| !excluded.contains(suit))
+            .filter(|&suit| hand.length_in(suit) >= if suit.is_major() { 5 } else { 4 })
+            .max_by_key(|&suit| (hand.length_in(suit), suit))
+            .map_or(pass(), |suit| cheapest(Strain::Trump(suit), last))
+    }
+
+    // once both of us have bid naturally, the opponents' calls don't change our agreements
+    fn continue_uncontested(hand: &Hand<13>, callers: &[SubjectiveSeat], our_calls: &[Bid]) -> Bid {
+        let alternating = callers
+            .iter()
+            .rev()
+            .zip([SubjectiveSeat::Partner, SubjectiveSeat::Myself].into_iter().cycle())
+            .all(|(caller, expected)| *caller == expected);
+        let contract_bids = our_calls
+            .iter()
+            .filter_map(|bid| match bid {
+                Bid::Contract(bid) => Some(*bid),
+                _ => None,
+            })
+            .collect_vec();
+        match alternating && contract_bids.len() == our_calls.len() {
+            true => Self::uncontested(hand, 0, &contract_bids),
+            false => pass(),
+        }
+    }
+}
+
+fn is_opponent(seat: SubjectiveSeat) -> bool {
+    matches!(
+        seat,
+        SubjectiveSeat::LeftHandOpponent | SubjectiveSeat::RightHandOpponent
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::bidding_engine::forum_d_plus_2015_bidding_engine::ForumDPlus2015BiddingEngine;
+    use crate::primitives::bid::Bid;
+    use crate::primitives::Hand;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    #[test_case("S:AQJ84,H:72,D:K93,C:Q52", &["1H"], "1S"; "Overcall")]
+    #[test_case("S:AQ4,H:KJ3,D:Q987,C:K32", &["1H"], "1NT"; "1NT overcall")]
+    #[test_case("S:KJ73,H:4,D:AQ82,C:K932", &["1H"], "X"; "Takeout double")]
+    #[test_case("S:KJ73,H:4,D:Q982,C:9832", &["1H"], "P"; "Too weak to compete")]
+    #[test_case("S:KJ73,H:4,D:A982,C:J932", &["1H"], "P"; "Too weak for a direct double")]
+    #[test_case("S:KJ73,H:4,D:A982,C:J932", &["1H", "P", "P"], "X"; "Balancing double")]
+    #[test_case("S:KJ73,H:4,D:A982,C:J932", &["1NT"], "P"; "No penalty double")]
+    #[test_case("S:AQ4,H:KJ3,D:Q987,C:K32", &["1NT"], "X"; "Penalty double")]
+    fn overcall(hand: &str, auction: &[&str], expected: &str) {
+        assert_eq!(find_bid(hand, auction), Bid::from_str(expected).unwrap());
+    }
+
+    #[test_case("S:KJ73,H:842,D:982,C:J93", &["1H", "X", "P"], "1S"; "Answering a takeout double")]
+    #[test_case("S:K73,H:842,D:A982,C:J93", &["1H", "1S", "P"], "2S"; "Simple raise")]
+    #[test_case("S:K73,H:84,D:AK82,C:J932", &["1H", "1S", "P"], "2H"; "Cue-bid raise")]
+    fn advance(hand: &str, auction: &[&str], expected: &str) {
+        assert_eq!(find_bid(hand, auction), Bid::from_str(expected).unwrap());
+    }
+
+    #[test_case("S:832,H:KJ73,D:Q92,C:K93", &["1D", "1S"], "X"; "Negative double")]
+    #[test_case("S:832,H:KJ73,D:Q92,C:K93", &["1H", "1S"], "2H"; "Raise")]
+    #[test_case("S:832,H:KJ73,D:A92,C:K93", &["1H", "1S"], "2S"; "Cue-bid raise")]
+    #[test_case("S:K32,H:KJ73,D:A92,C:932", &["1C", "1NT"], "X"; "Doubling a 1NT overcall")]
+    #[test_case("S:K32,H:KJ73,D:A92,C:932", &["1NT", "2H"], "X"; "Penalty double over 1NT")]
+    #[test_case("S:K32,H:72,D:AQ92,C:KJ93", &["1H", "X"], "XX"; "Redouble")]
+    fn respond_to_interference(hand: &str, auction: &[&str], expected: &str) {
+        assert_eq!(find_bid(hand, auction), Bid::from_str(expected).unwrap());
+    }
+
+    #[test_case("S:72,H:AJ84,D:KJ943,C:K2", &["1D", "1S", "X", "P"], "2H"; "Showing the major after a negative double")]
+    #[test_case("S:72,H:AQ984,D:K94,C:Q32", &["1H", "1S", "2S", "P"], "3H"; "Minimum after a cue-bid raise")]
+    #[test_case("S:72,H:AQJ84,D:KQ4,C:A32", &["1H", "1S", "2H", "P"], "3H"; "Invitation after a raise")]
+    fn opener_rebid(hand: &str, auction: &[&str], expected: &str) {
+        assert_eq!(find_bid(hand, auction), Bid::from_str(expected).unwrap());
+    }
+
+    fn find_bid(hand: &str, auction: &[&str]) -> Bid {
+        let hand = Hand::from_str(hand).unwrap();
+        let auction = auction
+            .iter()
+            .map(|bid| Bid::from_str(bid).unwrap())
+            .collect::<Vec<_>>();
+        ForumDPlus2015BiddingEngine::new().find_bid(&hand, &auction)
+    }
+}
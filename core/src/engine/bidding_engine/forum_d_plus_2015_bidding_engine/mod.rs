@@ -0,0 +1,857 @@
+use crate::engine::bidding_engine::{is_legal, SelectBid};
+use crate::engine::engine_state::HandDescription;
+use crate::engine::hand_evaluation::ForumDPlus2015Evaluator;
+use crate::engine::reason::Reason;
+use crate::engine::subjective_game_view::SubjectiveGamePhaseStateView;
+use crate::game::game_phase_states::BiddingState;
+use crate::primitives::bid::{AuxiliaryBid, Bid, ContractBid};
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::contract::{Level, Strain};
+use crate::primitives::deal::hand::HandType;
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::hand_info::suit_quality::SuitQuality;
+use crate::primitives::{Hand, Suit};
+use itertools::Itertools;
+use std::str::FromStr;
+
+mod competitive;
+mod slam;
+
+pub use slam::KeycardResponses;
+
+#[derive(Debug, Default)]
+pub struct ForumDPlus2015BiddingEngine {
+    keycard_responses: KeycardResponses,
+}
+
+impl ForumDPlus2015BiddingEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_keycard_responses(keycard_responses: KeycardResponses) -> Self {
+        Self { keycard_responses }
+    }
+
+    pub fn find_bid(&self, hand: &Hand<13>, auction: &[Bid]) -> Bid {
+        self.find_bid_with_rule(hand, auction).0
+    }
+
+    fn find_bid_with_rule(&self, hand: &Hand<13>, auction: &[Bid]) -> (Bid, &'static str) {
+        let (bid, rule) = match Self::partnership_calls(auction) {
+            Some(calls) => match self.slam(hand, &calls) {
+                Some(bid) => (bid, "Slam bidding"),
+                None => (
+                    Self::uncontested(hand, auction.len(), &calls),
+                    Self::uncontested_rule(calls.len()),
+                ),
+            },
+            None => (Self::competitive(hand, auction), "Competitive bidding"),
+        };
+        match is_legal(auction, bid) {
+            true => (bid, rule),
+            false => (pass(), "No legal call found, passing"),
+        }
+    }
+
+    fn uncontested_rule(calls: usize) -> &'static str {
+        match calls {
+            0 => "Opening",
+            1 => "Response to partner's opening",
+            2 => "Opener's rebid",
+            3 => "Responder's rebid",
+            4 => "Opener's second rebid",
+            _ => "Passing once the auction has been described",
+        }
+    }
+
+    fn uncontested(hand: &Hand<13>, passes: usize, calls: &[ContractBid]) -> Bid {
+        match *calls {
+            [] => Self::opening(hand, passes),
+            [opening] => Self::response(hand, opening),
+            [opening, response] => Self::opener_rebid(hand, opening, response),
+            [opening, response, rebid] => Self::responder_rebid(hand, opening, response, rebid),
+            [opening, response, rebid, second_response] => {
+                Self::opener_second_rebid(hand, opening, response, rebid, second_response)
+            }
+            _ => pass(),
+        }
+    }
+
+    // none once the opponents have bid; the last call is always partner's
+    fn partnership_calls(auction: &[Bid]) -> Option<Vec<ContractBid>> {
+        let mut calls = Vec::new();
+        for (index, bid) in auction.iter().enumerate() {
+            let ours = (auction.len() - index).is_multiple_of(2);
+            match (ours, bid) {
+                (_, Bid::Auxiliary(AuxiliaryBid::Pass)) => (),
+                (true, Bid::Contract(bid)) => calls.push(*bid),
+                _ => return None,
+            }
+        }
+        Some(calls)
+    }
+
+    fn opening(hand: &Hand<13>, passes: usize) -> Bid {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        let points = Self::points(hand);
+        let balanced = matches!(hand.hand_type(), HandType::Balanced(_));
+
+        if balanced && (15.0..=17.0).contains(&hcp) {
+            return contract(Level::One, Strain::NoTrump);
+        }
+        if balanced && (20.0..=21.0).contains(&hcp) {
+            return contract(Level::Two, Strain::NoTrump);
+        }
+        if points >= 22.0 {
+            return contract(Level::Two, Strain::Trump(Suit::Clubs));
+        }
+
+        let strong_enough = match passes {
+            3 => points >= 10.0 && ForumDPlus2015Evaluator::rule_of_fifteen(hand),
+            _ => points >= 12.0 || ForumDPlus2015Evaluator::rule_of_twenty(hand),
+        };
+        if strong_enough {
+            return Self::one_of_a_suit(hand);
+        }
+        if passes < 3 {
+            return Self::preempt(hand);
+        }
+        pass()
+    }
+
+    fn one_of_a_suit(hand: &Hand<13>) -> Bid {
+        let length = |suit| hand.length_in(suit);
+        let suit = if length(Suit::Spades) >= 5 || length(Suit::Hearts) >= 5 {
+            match length(Suit::Spades) >= length(Suit::Hearts) {
+                true => Suit::Spades,
+                false => Suit::Hearts,
+            }
+        } else if length(Suit::Diamonds) > length(Suit::Clubs)
+            || length(Suit::Diamonds) == length(Suit::Clubs) && length(Suit::Diamonds) >= 4
+        {
+            Suit::Diamonds
+        } else {
+            Suit::Clubs
+        };
+        contract(Level::One, Strain::Trump(suit))
+    }
+
+    fn preempt(hand: &Hand<13>) -> Bid {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        let suit = Self::longest_suit(hand);
+        if ForumDPlus2015Evaluator::suit_quality(hand, suit) < SuitQuality::Acceptable {
+            return pass();
+        }
+        match hand.length_in(suit) {
+            6 if suit.is_major() && (6.0..=10.0).contains(&hcp) => contract(Level::Two, Strain::Trump(suit)),
+            7.. if (5.0..=10.0).contains(&hcp) => contract(Level::Three, Strain::Trump(suit)),
+            _ => pass(),
+        }
+    }
+
+    fn response(hand: &Hand<13>, opening: ContractBid) -> Bid {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        match (opening.level, opening.strain) {
+            (Level::One, Strain::NoTrump) => Self::response_to_one_no_trump(hand),
+            (Level::One, Strain::Trump(suit)) => Self::response_to_one_of_a_suit(hand, suit),
+            // waiting
+            (Level::Two, Strain::Trump(Suit::Clubs)) => contract(Level::Two, Strain::Trump(Suit::Diamonds)),
+            (Level::Two, Strain::NoTrump) if hcp >= 4.0 => contract(Level::Three, Strain::NoTrump),
+            (_, Strain::Trump(suit)) if suit.is_major() && hand.length_in(suit) >= 3 && Self::points(hand) >= 16.0 => {
+                contract(Level::Four, Strain::Trump(suit))
+            }
+            _ => pass(),
+        }
+    }
+
+    fn response_to_one_no_trump(hand: &Hand<13>) -> Bid {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        let spades = hand.length_in(Suit::Spades);
+        let hearts = hand.length_in(Suit::Hearts);
+
+        // transfers
+        if spades >= 5 && spades >= hearts {
+            return contract(Level::Two, Strain::Trump(Suit::Hearts));
+        }
+        if hearts >= 5 {
+            return contract(Level::Two, Strain::Trump(Suit::Diamonds));
+        }
+        // Stayman
+        if hcp >= 8.0 && (spades == 4 || hearts == 4) {
+            return contract(Level::Two, Strain::Trump(Suit::Clubs));
+        }
+        match hcp {
+            x if x >= 10.0 => contract(Level::Three, Strain::NoTrump),
+            x if x >= 8.0 => contract(Level::Two, Strain::NoTrump),
+            _ => pass(),
+        }
+    }
+
+    fn response_to_one_of_a_suit(hand: &Hand<13>, opening: Suit) -> Bid {
+        let points = Self::points(hand);
+        if points < 6.0 {
+            return pass();
+        }
+
+        let support = hand.length_in(opening);
+        if opening.is_major() && support >= 3 {
+            return match Self::fit_points(hand, opening, 5) {
+                x if x >= 13.0 => contract(Level::Four, Strain::Trump(opening)),
+                x if x >= 10.0 => contract(Level::Three, Strain::Trump(opening)),
+                _ => contract(Level::Two, Strain::Trump(opening)),
+            };
+        }
+
+        // a new suit on the one level, the longest first, up the line with equal length
+        let one_level_suit = SUIT_ARRAY
+            .into_iter()
+            .filter(|&suit| suit > opening && hand.length_in(suit) >= 4)
+            .max_by_key(|&suit| (hand.length_in(suit), std::cmp::Reverse(suit)));
+        if let Some(suit) = one_level_suit {
+            return contract(Level::One, Strain::Trump(suit));
+        }
+
+        // a new suit on the two level needs 11+ points
+        let two_level_suit = SUIT_ARRAY
+            .into_iter()
+            .filter(|&suit| suit < opening && hand.length_in(suit) >= if suit.is_major() { 5 } else { 4 })
+            .max_by_key(|&suit| (hand.length_in(suit), suit));
+        if let (Some(suit), true) = (two_level_suit, points >= 11.0) {
+            return contract(Level::Two, Strain::Trump(suit));
+        }
+
+        if opening.is_minor() && support >= 4 && points <= 12.0 {
+            return match points {
+                x if x >= 10.0 => contract(Level::Three, Strain::Trump(opening)),
+                _ => contract(Level::Two, Strain::Trump(opening)),
+            };
+        }
+
+        match points {
+            x if x >= 13.0 => contract(Level::Three, Strain::NoTrump),
+            x if x >= 11.0 => contract(Level::Two, Strain::NoTrump),
+            _ => contract(Level::One, Strain::NoTrump),
+        }
+    }
+
+    fn opener_rebid(hand: &Hand<13>, opening: ContractBid, response: ContractBid) -> Bid {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        match (opening.level, opening.strain, response.level, response.strain) {
+            // answering Stayman
+            (Level::One, Strain::NoTrump, Level::Two, Strain::Trump(Suit::Clubs)) => {
+                match (hand.length_in(Suit::Hearts), hand.length_in(Suit::Spades)) {
+                    (4.., _) => contract(Level::Two, Strain::Trump(Suit::Hearts)),
+                    (_, 4..) => contract(Level::Two, Strain::Trump(Suit::Spades)),
+                    _ => contract(Level::Two, Strain::Trump(Suit::Diamonds)),
+                }
+            }
+            // completing a transfer
+            (Level::One, Strain::NoTrump, Level::Two, Strain::Trump(Suit::Diamonds)) => {
+                contract(Level::Two, Strain::Trump(Suit::Hearts))
+            }
+            (Level::One, Strain::NoTrump, Level::Two, Strain::Trump(Suit::Hearts)) => {
+                contract(Level::Two, Strain::Trump(Suit::Spades))
+            }
+            (Level::One, Strain::NoTrump, Level::Two, Strain::NoTrump) if Self::points(hand) >= 17.0 => {
+                contract(Level::Three, Strain::NoTrump)
+            }
+            (Level::Two, Strain::Trump(Suit::Clubs), _, _) => match hand.hand_type() {
+                HandType::Balanced(_) => cheapest(Strain::NoTrump, response),
+                _ => cheapest(Strain::Trump(Self::longest_suit(hand)), response),
+            },
+            (Level::One, Strain::Trump(suit), _, _) => Self::rebid_after_one_of_a_suit(hand, suit, response, hcp),
+            _ => pass(),
+        }
+    }
+
+    fn rebid_after_one_of_a_suit(hand: &Hand<13>, opening: Suit, response: ContractBid, hcp: f64) -> Bid {
+        let points = Self::points(hand);
+        let balanced = matches!(hand.hand_type(), HandType::Balanced(_));
+
+        match response.strain {
+            // partner raised
+            Strain::Trump(suit) if suit == opening => match response.level {
+                Level::Two if points >= 18.0 => game_in(suit),
+                Level::Two if points >= 15.0 => contract(Level::Three, Strain::Trump(suit)),
+                Level::Three if points >= 14.0 => game_in(suit),
+                _ => pass(),
+            },
+            Strain::NoTrump => match response.level {
+                Level::One if points >= 18.0 => contract(Level::Three, Strain::NoTrump),
+                Level::One if points >= 15.0 && balanced => contract(Level::Two, Strain::NoTrump),
+                Level::One => Self::describe_distribution(hand, opening, response, points),
+                Level::Two if points >= 14.0 => contract(Level::Three, Strain::NoTrump),
+                _ => pass(),
+            },
+            Strain::Trump(partners_suit) => {
+                if hand.length_in(partners_suit) >= 4 {
+                    let raise = match Self::fit_points(hand, partners_suit, 4) {
+                        x if x >= 18.0 => return game_in(partners_suit),
+                        x if x >= 15.0 => 2,
+                        _ => 1,
+                    };
+                    return raise_by(response, raise);
+                }
+                if balanced {
+                    return match hcp {
+                        x if x >= 18.0 => jump(Strain::NoTrump, response),
+                        _ => cheapest(Strain::NoTrump, response),
+                    };
+                }
+                // a four-card suit on the one level
+                let one_level_suit = SUIT_ARRAY
+                    .into_iter()
+                    .find(|&suit| suit > partners_suit && suit != opening && hand.length_in(suit) >= 4);
+                if let (Some(suit), Level::One) = (one_level_suit, response.level) {
+                    return contract(Level::One, Strain::Trump(suit));
+                }
+                Self::describe_distribution(hand, opening, response, points)
+            }
+        }
+    }
+
+    fn describe_distribution(hand: &Hand<13>, opening: Suit, response: ContractBid, points: f64) -> Bid {
+        if hand.length_in(opening) >= 6 {
+            return match points {
+                x if x >= 15.0 => jump(Strain::Trump(opening), response),
+                _ => cheapest(Strain::Trump(opening), response),
+            };
+        }
+        let second_suit = SUIT_ARRAY
+            .into_iter()
+            .filter(|&suit| suit != opening && Strain::Trump(suit) != response.strain && hand.length_in(suit) >= 4)
+            .max_by_key(|&suit| (hand.length_in(suit), suit));
+        if let Some(suit) = second_suit {
+            // showing a higher suit on the two level is a reverse
+            if suit < opening || points >= 17.0 {
+                let bid = cheapest_contract(Strain::Trump(suit), response);
+                if bid.level <= Level::Two {
+                    return Bid::Contract(bid);
+                }
+            }
+        }
+        match response.strain {
+            Strain::NoTrump => pass(),
+            _ => cheapest(Strain::Trump(opening), response),
+        }
+    }
+
+    fn responder_rebid(hand: &Hand<13>, opening: ContractBid, response: ContractBid, rebid: ContractBid) -> Bid {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        match (opening.level, opening.strain, response.strain) {
+            // after Stayman
+            (Level::One, Strain::NoTrump, Strain::Trump(Suit::Clubs)) => match rebid.strain {
+                Strain::Trump(suit) if suit.is_major() && hand.length_in(suit) >= 4 => match hcp {
+                    x if x >= 10.0 => game_in(suit),
+                    _ => contract(Level::Three, Strain::Trump(suit)),
+                },
+                _ => match hcp {
+                    x if x >= 10.0 => contract(Level::Three, Strain::NoTrump),
+                    _ => contract(Level::Two, Strain::NoTrump),
+                },
+            },
+            // after a transfer
+            (Level::One, Strain::NoTrump, Strain::Trump(Suit::Diamonds | Suit::Hearts)) => {
+                let major = response.strain.next();
+                let length = match major {
+                    Strain::Trump(suit) => hand.length_in(suit),
+                    Strain::NoTrump => unreachable!(),
+                };
+                match (hcp, length) {
+                    (x, 6..) if x >= 10.0 => contract(Level::Four, major),
+                    (x, _) if x >= 10.0 => contract(Level::Three, Strain::NoTrump),
+                    (x, 6..) if x >= 8.0 => contract(Level::Three, major),
+                    (x, _) if x >= 8.0 => contract(Level::Two, Strain::NoTrump),
+                    _ => pass(),
+                }
+            }
+            // strong 2♣ is forcing to game
+            (Level::Two, Strain::Trump(Suit::Clubs), _) => match rebid.strain {
+                Strain::Trump(suit) if suit.is_major() && hand.length_in(suit) >= 3 => game_in(suit),
+                Strain::NoTrump if rebid.level == Level::Two && hcp < 3.0 => pass(),
+                _ => contract(Level::Three, Strain::NoTrump),
+            },
+            (Level::One, Strain::Trump(suit), _) => {
+                Self::responder_rebid_after_one_of_a_suit(hand, suit, response, rebid)
+            }
+            _ => pass(),
+        }
+    }
+
+    fn responder_rebid_after_one_of_a_suit(
+        hand: &Hand<13>,
+        opening: Suit,
+        response: ContractBid,
+        rebid: ContractBid,
+    ) -> Bid {
+        let points = Self::points(hand);
+        // what opener has shown at least
+        let openers_points = match (
+            rebid.strain,
+            rebid.level as u8 - cheapest_contract(rebid.strain, response).level as u8,
+        ) {
+            (Strain::NoTrump, 1..) => 18.0,
+            (_, 1..) => 15.0,
+            _ => 12.0,
+        };
+        let combined = points + openers_points;
+
+        // a fit in a major, either partner's opened suit or the suit partner raised
+        let fit = match rebid.strain {
+            Strain::Trump(suit) if rebid.strain == response.strain => Some(suit),
+            _ if opening.is_major() && hand.length_in(opening) >= 3 => Some(opening),
+            Strain::Trump(suit) if suit.is_major() && hand.length_in(suit) >= 4 => Some(suit),
+            _ => None,
+        };
+
+        match fit {
+            Some(suit) if suit.is_major() && combined >= 25.0 => game_in(suit),
+            Some(suit) if suit.is_major() && combined >= 23.0 => contract(Level::Three, Strain::Trump(suit)),
+            Some(suit) if Strain::Trump(suit) != rebid.strain => cheapest(Strain::Trump(suit), rebid),
+            _ if combined >= 25.0 => contract(Level::Three, Strain::NoTrump),
+            _ if combined >= 23.0 && rebid.level == Level::One => contract(Level::Two, Strain::NoTrump),
+            _ => match rebid.strain {
+                // give preference to partner's first suit
+                Strain::Trump(suit) if suit != opening && hand.length_in(opening) > hand.length_in(suit) => {
+                    cheapest(Strain::Trump(opening), rebid)
+                }
+                _ => pass(),
+            },
+        }
+    }
+
+    fn opener_second_rebid(
+        hand: &Hand<13>,
+        opening: ContractBid,
+        response: ContractBid,
+        rebid: ContractBid,
+        second_response: ContractBid,
+    ) -> Bid {
+        let points = Self::points(hand);
+        let (maximum, fit_length) = match (opening.level, opening.strain) {
+            (Level::One, Strain::NoTrump) => (points >= 17.0, 3),
+            (Level::Two, Strain::Trump(Suit::Clubs)) => (true, 6),
+            _ => (points >= 14.0, 3),
+        };
+        // the major partner has shown, via a transfer or by Stayman
+        let partners_major = match (opening.strain, response.strain, rebid.strain) {
+            (Strain::NoTrump, Strain::Trump(Suit::Diamonds), _) => Some(Suit::Hearts),
+            (Strain::NoTrump, Strain::Trump(Suit::Hearts), _) => Some(Suit::Spades),
+            (Strain::NoTrump, Strain::Trump(Suit::Clubs), Strain::Trump(Suit::Hearts)) => Some(Suit::Spades),
+            (_, _, Strain::Trump(suit)) if opening.strain != Strain::NoTrump => Some(suit),
+            _ => None,
+        };
+        let fit = partners_major.filter(|&suit| hand.length_in(suit) >= fit_length);
+
+        match (second_response.level, second_response.strain, fit) {
+            (Level::Two, Strain::NoTrump, Some(suit)) if maximum => game_in(suit),
+            (Level::Two, Strain::NoTrump, Some(suit)) => contract(Level::Three, Strain::Trump(suit)),
+            (Level::Two, Strain::NoTrump, None) if maximum => contract(Level::Three, Strain::NoTrump),
+            (Level::Three, Strain::NoTrump, Some(suit)) => game_in(suit),
+            (Level::Three, Strain::Trump(suit), _) if suit.is_major() && maximum => game_in(suit),
+            _ => pass(),
+        }
+    }
+
+    fn points(hand: &Hand<13>) -> f64 {
+        ForumDPlus2015Evaluator::hcp(hand) + ForumDPlus2015Evaluator::length_points(hand, None, &[])
+    }
+
+    // with a fit, shortness counts instead of length
+    fn support_points(hand: &Hand<13>, trump_suit: Suit) -> f64 {
+        ForumDPlus2015Evaluator::hcp(hand)
+            + ForumDPlus2015Evaluator::length_points(hand, Some(trump_suit), &[])
+            + match hand.length_in(trump_suit) {
+                4.. => ForumDPlus2015Evaluator::side_suit_distribution_points(hand, trump_suit),
+                _ => 0.0,
+            }
+    }
+
+    // adjusted for how the honors play opposite partner's `partners_length` trumps
+    fn fit_points(hand: &Hand<13>, trump_suit: Suit, partners_length: u8) -> f64 {
+        let mut suit_lengths = [None; 4];
+        suit_lengths[trump_suit as usize] = Some((partners_length, 13));
+        let mut partner = HandDescription::unknown();
+        partner.narrow(&Explanation {
+            suit_lengths,
+            ..Default::default()
+        });
+        let unknown = HandDescription::unknown();
+        Self::support_points(hand, trump_suit)
+            + ForumDPlus2015Evaluator::partnership_adjustment(hand, &partner, &unknown, &unknown, Some(trump_suit))
+    }
+
+    fn longest_suit(hand: &Hand<13>) -> Suit {
+        SUIT_ARRAY
+            .into_iter()
+            .sorted_by_key(|&suit| (hand.length_in(suit), suit))
+            .last()
+            .unwrap()
+    }
+
+    fn explain(&self, auction: &[Bid], bid: Bid) -> Option<Explanation> {
+        let calls = Self::partnership_calls(auction)?;
+        let Bid::Contract(bid) = bid else { return None };
+        if let Some(explanation) = self.explain_slam(&calls, bid) {
+            return Some(explanation);
+        }
+        let mut explanation = Explanation::default();
+        match (&calls[..], bid.level, bid.strain) {
+            ([], Level::Two, Strain::Trump(Suit::Clubs)) => explanation.total_points = Some((22, 37)),
+            ([opening], Level::Two, Strain::Trump(Suit::Diamonds))
+                if *opening == ContractBid::from_str("2C").unwrap() => {}
+            ([opening], Level::Two, Strain::Trump(Suit::Clubs))
+                if *opening == ContractBid::from_str("1NT").unwrap() =>
+            {
+                explanation.hcp = Some((8, 37))
+            }
+            ([opening], Level::Two, Strain::Trump(suit @ (Suit::Diamonds | Suit::Hearts)))
+                if *opening == ContractBid::from_str("1NT").unwrap() =>
+            {
+                explanation.suit_lengths[suit.next() as usize] = Some((5, 13))
+            }
+            _ => return None,
+        }
+        Some(explanation)
+    }
+
+    fn describe(&self, auction: &[Bid], bid: Bid) -> Explanation {
+        if let Some(explanation) = self.explain(auction, bid) {
+            return explanation;
+        }
+        let Some(calls) = Self::partnership_calls(auction) else {
+            return Explanation::default();
+        };
+        let mut explanation = Explanation::default();
+        let length = |min, max| Some((min, max));
+
+        match (&calls[..], bid) {
+            ([], Bid::Auxiliary(AuxiliaryBid::Pass)) => explanation.total_points = Some((0, 11)),
+            ([], Bid::Contract(bid)) => match (bid.level, bid.strain) {
+                (Level::One, Strain::NoTrump) => {
+                    explanation.hand_type = Some(HandType::Balanced(None));
+                    explanation.hcp = Some((15, 17));
+                }
+                (Level::Two, Strain::NoTrump) => {
+                    explanation.hand_type = Some(HandType::Balanced(None));
+                    explanation.hcp = Some((20, 21));
+                }
+                (Level::One, Strain::Trump(suit)) => {
+                    explanation.total_points = Some((10, 21));
+                    explanation.suit_lengths[suit as usize] = match suit {
+                        Suit::Spades | Suit::Hearts => length(5, 13),
+                        Suit::Diamonds | Suit::Clubs => length(3, 13),
+                    };
+                    if suit.is_minor() {
+                        explanation.suit_lengths[Suit::Spades as usize] = length(0, 4);
+                        explanation.suit_lengths[Suit::Hearts as usize] = length(0, 4);
+                    }
+                }
+                (Level::Two, Strain::Trump(suit)) if suit.is_major() => {
+                    explanation.hcp = Some((6, 10));
+                    explanation.suit_lengths[suit as usize] = length(6, 6);
+                    explanation.suit_qualities[suit as usize] = Some(SuitQuality::Acceptable);
+                }
+                (Level::Three, Strain::Trump(suit)) => {
+                    explanation.hcp = Some((5, 10));
+                    explanation.suit_lengths[suit as usize] = length(7, 13);
+                    explanation.suit_qualities[suit as usize] = Some(SuitQuality::Acceptable);
+                }
+                _ => (),
+            },
+            ([opening], bid) if *opening == ContractBid::from_str("1NT").unwrap() => {
+                match bid {
+                    Bid::Auxiliary(AuxiliaryBid::Pass) => explanation.hcp = Some((0, 7)),
+                    Bid::Contract(bid) if bid == ContractBid::from_str("2NT").unwrap() => {
+                        explanation.hcp = Some((8, 9))
+                    }
+                    Bid::Contract(bid) if bid == ContractBid::from_str("3NT").unwrap() => {
+                        explanation.hcp = Some((10, 37))
+                    }
+                    _ => return explanation,
+                }
+                explanation.suit_lengths[Suit::Spades as usize] = length(0, 4);
+                explanation.suit_lengths[Suit::Hearts as usize] = length(0, 4);
+            }
+            (
+                [ContractBid {
+                    level: Level::One,
+                    strain: Strain::Trump(opening),
+                }],
+                bid,
+            ) => match bid {
+                Bid::Auxiliary(AuxiliaryBid::Pass) => explanation.total_points = Some((0, 5)),
+                Bid::Contract(ContractBid {
+                    level,
+                    strain: Strain::Trump(suit),
+                }) if suit == *opening => {
+                    explanation.suit_lengths[suit as usize] = match suit.is_major() {
+                        true => length(3, 13),
+                        false => length(4, 13),
+                    };
+                    explanation.total_points = match level {
+                        Level::Two => Some((6, 9)),
+                        Level::Three => Some((10, 12)),
+                        _ => Some((13, 37)),
+                    };
+                }
+                Bid::Contract(ContractBid {
+                    level: Level::One,
+                    strain: Strain::Trump(suit),
+                }) => {
+                    explanation.suit_lengths[suit as usize] = length(4, 13);
+                    explanation.total_points = Some((6, 37));
+                }
+                Bid::Contract(ContractBid {
+                    level: Level::Two,
+                    strain: Strain::Trump(suit),
+                }) => {
+                    explanation.suit_lengths[suit as usize] = length(4, 13);
+                    explanation.total_points = Some((11, 37));
+                }
+                Bid::Contract(ContractBid {
+                    level,
+                    strain: Strain::NoTrump,
+                }) => {
+                    explanation.total_points = match level {
+                        Level::One => Some((6, 10)),
+                        Level::Two => Some((11, 12)),
+                        _ => Some((13, 37)),
+                    }
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+        explanation
+    }
+}
+
+impl SelectBid for ForumDPlus2015BiddingEngine {
+    fn select_bid(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> Bid {
+        let hand = state.my_starting_hand().unwrap();
+        self.find_bid(&hand, &state.bids())
+    }
+
+    fn select_bid_with_reason(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> (Bid, Reason) {
+        let hand = state.my_starting_hand().unwrap();
+        let auction = state.bids();
+        let (bid, rule) = self.find_bid_with_rule(&hand, &auction);
+        (bid, Reason::from_rule(rule).showing(self.describe(&auction, bid)))
+    }
+
+    fn alert(&self, auction: &[Bid], bid: Bid) -> Option<Explanation> {
+        self.explain(auction, bid)
+    }
+
+    fn interpret(&self, auction: &[Bid], bid: Bid) -> Explanation {
+        self.describe(auction, bid)
+    }
+}
+
+fn pass() -> Bid {
+    Bid::Auxiliary(AuxiliaryBid::Pass)
+}
+
+fn contract(level: Level, strain: Strain) -> Bid {
+    Bid::Contract(ContractBid { level, strain })
+}
+
+fn game_in(suit: Suit) -> Bid {
+    match suit.is_major() {
+        true => contract(Level::Four, Strain::Trump(suit)),
+        false => contract(Level::Three, Strain::NoTrump),
+    }
+}
+
+fn cheapest_contract(strain: Strain, last: ContractBid) -> ContractBid {
+    match strain > last.strain {
+        true => ContractBid {
+            level: last.level,
+            strain,
+        },
+        false => ContractBid {
+            level: last.level.next().unwrap_or(Level::Seven),
+            strain,
+        },
+    }
+}
+
+fn cheapest(strain: Strain, last: ContractBid) -> Bid {
+    Bid::Contract(cheapest_contract(strain, last))
+}
+
+fn jump(strain: Strain, last: ContractBid) -> Bid {
+    let cheapest = cheapest_contract(strain, last);
+    match cheapest.level.next() {
+        Ok(level) => contract(level, strain),
+        Err(_) => pass(),
+    }
+}
+
+fn raise_by(bid: ContractBid, levels: usize) -> Bid {
+    let mut level = bid.level;
+    for _ in 0..levels {
+        level = match level.next() {
+            Ok(level) => level,
+            Err(_) => return pass(),
+        };
+    }
+    contract(level, bid.strain)
+}
+
+#[cfg(test)]
+mod test {
+    use super::ForumDPlus2015BiddingEngine;
+    use crate::engine::bidding_engine::SelectBid;
+    use crate::game::bid_manager::BidManager;
+    use crate::primitives::bid::Bid;
+    use crate::primitives::contract::Contract;
+    use crate::primitives::{Deal, Hand};
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    fn find_bid(hand: &str, auction: &[&str]) -> Bid {
+        let hand = Hand::from_str(hand).unwrap();
+        let auction = auction
+            .iter()
+            .map(|bid| Bid::from_str(bid).unwrap())
+            .collect::<Vec<_>>();
+        ForumDPlus2015BiddingEngine::new().find_bid(&hand, &auction)
+    }
+
+    #[test_case("S:AQ4,H:KJ3,D:Q987,C:K32", &[], "1NT"; "1NT with 15 HCP")]
+    #[test_case("S:AK4,H:KQ3,D:AQ87,C:K32", &[], "2NT"; "2NT with 21 HCP")]
+    #[test_case("S:AKQJ54,H:AK3,D:A2,C:K2", &[], "2C"; "Strong 2C")]
+    #[test_case("S:KQ954,H:A3,D:K87,C:J32", &[], "1S"; "Five-card major")]
+    #[test_case("S:KQ95,H:A3,D:K874,C:J32", &[], "1D"; "Four diamonds")]
+    #[test_case("S:KQ9,H:A32,D:K87,C:J432", &[], "1C"; "Longer clubs")]
+    #[test_case("S:AQ4,H:KQJ953,D:72,C:32", &[], "1H"; "Length points make an opening")]
+    #[test_case("S:Q4,H:KQJ953,D:72,C:432", &["P"], "2H"; "Weak two")]
+    #[test_case("S:4,H:32,D:Q72,C:KQJ9532", &[], "3C"; "Preempt")]
+    #[test_case("S:Q42,H:J32,D:Q72,C:K532", &[], "P"; "Too weak")]
+    #[test_case("S:2,H:AJ32,D:KJ72,C:Q932", &["P", "P", "P"], "P"; "Rule of 15 not met")]
+    #[test_case("S:KJ72,H:A32,D:Q2,C:J932", &["P", "P", "P"], "1C"; "Rule of 15 met")]
+    #[test_case("S:KJ72,H:A32,D:Q2,C:J932", &[], "P"; "Rule of 20 not met")]
+    fn opening(hand: &str, auction: &[&str], expected: &str) {
+        assert_eq!(find_bid(hand, auction), Bid::from_str(expected).unwrap());
+    }
+
+    #[test_case("S:KJ752,H:32,D:Q72,C:432", "2H"; "Transfer to spades")]
+    #[test_case("S:32,H:KJ752,D:Q72,C:432", "2D"; "Transfer to hearts")]
+    #[test_case("S:KJ72,H:32,D:Q72,C:K432", "2C"; "Stayman")]
+    #[test_case("S:K72,H:Q32,D:Q72,C:K432", "3NT"; "Game")]
+    #[test_case("S:K72,H:Q32,D:J72,C:Q432", "2NT"; "Invitation")]
+    #[test_case("S:872,H:Q32,D:J72,C:Q432", "P"; "Weak")]
+    fn response_to_one_no_trump(hand: &str, expected: &str) {
+        assert_eq!(find_bid(hand, &["1NT", "P"]), Bid::from_str(expected).unwrap());
+    }
+
+    #[test_case("S:AQ4,H:KJ32,D:Q98,C:K32", &["1NT", "P", "2C", "P"], "2H"; "Answering Stayman")]
+    #[test_case("S:AQ4,H:KJ3,D:Q987,C:K32", &["1NT", "P", "2C", "P"], "2D"; "Denying a major")]
+    #[test_case("S:AQ4,H:KJ3,D:Q987,C:K32", &["1NT", "P", "2H", "P"], "2S"; "Completing a transfer")]
+    #[test_case("S:K2,H:AQ954,D:K87,C:J32", &["1H", "P", "1S", "P"], "1NT"; "Balanced minimum")]
+    #[test_case("S:KJ32,H:AQ954,D:K8,C:32", &["1H", "P", "1S", "P"], "3S"; "Jump raise")]
+    fn opener_rebid(hand: &str, auction: &[&str], expected: &str) {
+        assert_eq!(find_bid(hand, auction), Bid::from_str(expected).unwrap());
+    }
+
+    #[test_case("S:K72,H:Q32,D:J72,C:Q432", &["1H", "P"], "2H"; "Simple raise")]
+    #[test_case("S:AQ72,H:K932,D:72,C:K32", &["1H", "P"], "4H"; "Game raise")]
+    #[test_case("S:KJ72,H:32,D:Q72,C:K432", &["1H", "P"], "1S"; "New suit")]
+    #[test_case("S:K72,H:32,D:Q72,C:K8432", &["1H", "P"], "1NT"; "No fit")]
+    #[test_case("S:KJ72,H:Q972,D:Q7,C:432", &["1C", "P"], "1H"; "Up the line")]
+    #[test_case("S:KJ72,H:Q3,D:Q72,C:K432", &["1NT", "P", "2C", "P", "2H", "P"], "3NT"; "No fit after Stayman")]
+    #[test_case("S:KJ72,H:Q432,D:72,C:K32", &["1NT", "P", "2C", "P", "2H", "P"], "3H"; "Fit after Stayman")]
+    #[test_case("S:KJ72,H:Q432,D:72,C:K32", &["P", "1NT", "2C"], "P"; "Opponents interfere")]
+    fn response(hand: &str, auction: &[&str], expected: &str) {
+        assert_eq!(find_bid(hand, auction), Bid::from_str(expected).unwrap());
+    }
+
+    #[test_case("S:AQ4,H:KJ3,D:Q987,C:K32", &[], "1NT", "Opening"; "Opening")]
+    #[test_case("S:KJ72,H:Q432,D:72,C:K32", &["1NT", "P"], "2C", "Response to partner's opening"; "Response")]
+    #[test_case("S:KJ72,H:Q432,D:72,C:K32", &["P", "1NT", "2C"], "P", "Competitive bidding"; "Competitive")]
+    fn find_bid_with_rule(hand: &str, auction: &[&str], expected: &str, rule: &str) {
+        let hand = Hand::from_str(hand).unwrap();
+        let auction = auction
+            .iter()
+            .map(|bid| Bid::from_str(bid).unwrap())
+            .collect::<Vec<_>>();
+        let found = ForumDPlus2015BiddingEngine::new().find_bid_with_rule(&hand, &auction);
+        assert_eq!(found, (Bid::from_str(expected).unwrap(), rule));
+    }
+
+    #[test_case(&["P"], "2C", true; "Strong 2C")]
+    #[test_case(&["2C", "P"], "2D", true; "Waiting")]
+    #[test_case(&["1NT", "P"], "2C", true; "Stayman")]
+    #[test_case(&["1NT", "P"], "2D", true; "Transfer")]
+    #[test_case(&[], "1NT", false; "Natural")]
+    #[test_case(&["1H", "P"], "2C", false; "New suit")]
+    fn alert(auction: &[&str], bid: &str, alerted: bool) {
+        let auction = auction
+            .iter()
+            .map(|bid| Bid::from_str(bid).unwrap())
+            .collect::<Vec<_>>();
+        let bid = Bid::from_str(bid).unwrap();
+        let explanation = ForumDPlus2015BiddingEngine::new().alert(&auction, bid);
+        assert_eq!(explanation.is_some(), alerted);
+    }
+
+    #[test]
+    fn transfer_shows_five_hearts() {
+        let auction = [Bid::from_str("1NT").unwrap(), Bid::from_str("P").unwrap()];
+        let explanation = ForumDPlus2015BiddingEngine::new()
+            .alert(&auction, Bid::from_str("2D").unwrap())
+            .unwrap();
+        assert_eq!(format!("{}", explanation), "Hand has 5 to 13 cards in ♥.");
+    }
+
+    #[test_case(&[], "1NT", "Hand is balanced. Hand has 15 to 17 hcp."; "1NT")]
+    #[test_case(&["P"], "1S", "Hand has 5 to 13 cards in ♠. Hand has 10 to 21 points."; "Five-card major")]
+    #[test_case(&[], "1D", "Hand has 0 to 4 cards in ♠. Hand has 0 to 4 cards in ♥. Hand has 3 to 13 cards in ♦. Hand has 10 to 21 points."; "Minor")]
+    #[test_case(&[], "P", "Hand has 0 to 11 points."; "Opening pass")]
+    #[test_case(&["1NT", "P"], "3NT", "Hand has 0 to 4 cards in ♠. Hand has 0 to 4 cards in ♥. Hand has 10 to 37 hcp."; "Game over 1NT")]
+    #[test_case(&["1NT", "P"], "2H", "Hand has 5 to 13 cards in ♠."; "Transfer")]
+    #[test_case(&["1H", "P"], "3H", "Hand has 3 to 13 cards in ♥. Hand has 10 to 12 points."; "Limit raise")]
+    #[test_case(&["1H", "P"], "2C", "Hand has 4 to 13 cards in ♣. Hand has 11 to 37 points."; "Two over one")]
+    #[test_case(&["1H", "1S"], "2H", ""; "Competitive")]
+    fn interpret(auction: &[&str], bid: &str, expected: &str) {
+        let auction = auction
+            .iter()
+            .map(|bid| Bid::from_str(bid).unwrap())
+            .collect::<Vec<_>>();
+        let explanation = ForumDPlus2015BiddingEngine::new().interpret(&auction, Bid::from_str(bid).unwrap());
+        assert_eq!(format!("{}", explanation), expected);
+    }
+
+    fn bid_deal(deal: &Deal<13>) -> BidManager {
+        let engine = ForumDPlus2015BiddingEngine::new();
+        let mut bid_manager = BidManager::new(deal.board.dealer());
+        while !bid_manager.bidding_has_ended() {
+            let hand = deal.hand_of(bid_manager.next_to_play());
+            let bid = engine.find_bid(hand, bid_manager.bids());
+            bid_manager.bid(bid).unwrap();
+        }
+        bid_manager
+    }
+
+    #[test]
+    fn weak_two_is_doubled_in_the_pass_out_seat() {
+        let deal = Deal::from_u64_seed(9000);
+        let bid_manager = bid_deal(&deal);
+        // South's takeout double is left in by North with five good trumps
+        assert_eq!(
+            bid_manager.implied_contract(),
+            Some(Contract::from_str("W2SX").unwrap())
+        );
+    }
+
+    #[test]
+    fn always_finds_a_legal_bid() {
+        for seed in 0..200 {
+            let deal = Deal::from_u64_seed(seed);
+            bid_deal(&deal);
+        }
+    }
+}
@@ -0,0 +1,426 @@
+use super::{contract, pass, ForumDPlus2015BiddingEngine};
+use crate::engine::hand_evaluation::ForumDPlus2015Evaluator;
+use crate::primitives::bid::{Bid, ContractBid};
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::card::Rank;
+use crate::primitives::contract::{Level, Strain};
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::hand_info::keycards::Keycards;
+use crate::primitives::{Card, Hand, Suit};
+use strum::{Display, EnumString};
+
+#[derive(Debug, Default, Display, EnumString, Clone, Copy, PartialEq, Eq)]
+pub enum KeycardResponses {
+    // 5♣ shows 1 or 4 keycards, 5♦ shows 0 or 3
+    #[default]
+    #[strum(serialize = "1430")]
+    Rkcb1430,
+    // 5♣ shows 0 or 3 keycards, 5♦ shows 1 or 4
+    #[strum(serialize = "3014")]
+    Rkcb3014,
+}
+
+impl KeycardResponses {
+    fn steps(self) -> [(u8, u8); 2] {
+        match self {
+            KeycardResponses::Rkcb1430 => [(1, 4), (0, 3)],
+            KeycardResponses::Rkcb3014 => [(0, 3), (1, 4)],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AceAsk {
+    Gerber,
+    Keycards(Suit),
+}
+
+const ONE_NO_TRUMP: ContractBid = ContractBid {
+    level: Level::One,
+    strain: Strain::NoTrump,
+};
+const TWO_NO_TRUMP: ContractBid = ContractBid {
+    level: Level::Two,
+    strain: Strain::NoTrump,
+};
+const GERBER: ContractBid = ContractBid {
+    level: Level::Four,
+    strain: Strain::Trump(Suit::Clubs),
+};
+const BLACKWOOD: ContractBid = ContractBid {
+    level: Level::Four,
+    strain: Strain::NoTrump,
+};
+
+// 4NT asks for keycards once a major is agreed; 5♥/5♠ show two without/with the queen of trumps.
+// Missing two keycards we stop on the five level, missing one only with the queen of trumps.
+// Below that, the stronger hand tries with control cue-bids. Over 1NT and 2NT, 4♣ is Gerber.
+impl ForumDPlus2015BiddingEngine {
+    pub(super) fn slam(&self, hand: &Hand<13>, calls: &[ContractBid]) -> Option<Bid> {
+        if let Some((index, ask)) = Self::ace_ask(calls) {
+            return Some(match calls.len() - index {
+                1 => Bid::Contract(self.answer_ace_ask(hand, ask)),
+                2 => self.place_contract(hand, calls, ask, calls[index + 1]),
+                _ => pass(),
+            });
+        }
+        if let Some(bid) = Self::ask_for_aces(hand, calls) {
+            return Some(bid);
+        }
+        [2, 3]
+            .into_iter()
+            .filter(|&start| start <= calls.len())
+            .find_map(|start| Some((start, Self::cue_bid_sequence(&calls[..start])?)))
+            .and_then(|(start, trump)| Self::control_cue_bid(hand, calls, start, trump))
+    }
+
+    pub(super) fn explain_slam(&self, calls: &[ContractBid], bid: ContractBid) -> Option<Explanation> {
+        let calls = [calls, &[bid]].concat();
+        let mut explanation = Explanation::default();
+
+        if let Some((index, ask)) = Self::ace_ask(&calls) {
+            return match (calls.len() - index, ask) {
+                (1, AceAsk::Gerber) => Some(explanation),
+                (2, ask) => {
+                    explanation.keycards = Some(self.keycards_shown(ask, bid)?);
+                    Some(explanation)
+                }
+                _ => None,
+            };
+        }
+
+        let (start, trump) = [2, 3]
+            .into_iter()
+            .filter(|&start| start < calls.len())
+            .find_map(|start| Some((start, Self::cue_bid_sequence(&calls[..start])?)))?;
+        if !calls[start..].iter().all(|&call| is_cue_bid(call, trump)) {
+            return None;
+        }
+        match (calls.len() - start, start) {
+            // the slam try of opener after a limit raise, or of responder after Stayman
+            (1, 2) => explanation.total_points = Some((17, 21)),
+            (1, _) => explanation.hcp = Some((15, 37)),
+            (2, 2) => explanation.total_points = Some((12, 37)),
+            (2, _) => explanation.hcp = Some((16, 17)),
+            _ => return None,
+        }
+        Some(explanation)
+    }
+
+    fn ace_ask(calls: &[ContractBid]) -> Option<(usize, AceAsk)> {
+        (1..calls.len()).find_map(|index| {
+            let ask = match (&calls[..index], calls[index]) {
+                ([opening], GERBER) if *opening == ONE_NO_TRUMP || *opening == TWO_NO_TRUMP => AceAsk::Gerber,
+                (before, BLACKWOOD) => AceAsk::Keycards(Self::trump_suit(before)?),
+                _ => return None,
+            };
+            Some((index, ask))
+        })
+    }
+
+    // responder's jump to 4NT over a major opening agrees that suit
+    fn trump_suit(calls: &[ContractBid]) -> Option<Suit> {
+        match calls {
+            [opening] if opening.level == Level::One => major(*opening),
+            [opening, raise, ..] if opening.level == Level::One && raise.strain == opening.strain => major(*opening),
+            // Stayman found a fit, or a transfer was completed
+            [opening, response, answer, ..]
+                if *opening == ONE_NO_TRUMP && response.level == Level::Two && answer.level == Level::Two =>
+            {
+                major(*answer)
+            }
+            _ => None,
+        }
+    }
+
+    fn cue_bid_sequence(calls: &[ContractBid]) -> Option<Suit> {
+        match calls {
+            [opening, raise] if opening.level == Level::One && raise.strain == opening.strain => match raise.level {
+                Level::Three => major(*opening),
+                _ => None,
+            },
+            [opening, stayman, answer]
+                if *opening == ONE_NO_TRUMP
+                    && stayman.strain == Strain::Trump(Suit::Clubs)
+                    && answer.level == Level::Two =>
+            {
+                major(*answer)
+            }
+            _ => None,
+        }
+    }
+
+    fn ask_for_aces(hand: &Hand<13>, calls: &[ContractBid]) -> Option<Bid> {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        let points = Self::points(hand);
+        let no_void = SUIT_ARRAY.iter().all(|&suit| hand.length_in(suit) > 0);
+        let length = |suit| hand.length_in(suit);
+
+        let ask = match calls {
+            [opening] if *opening == ONE_NO_TRUMP => {
+                hcp >= 18.0 && length(Suit::Spades) < 4 && length(Suit::Hearts) < 4
+            }
+            [opening] if *opening == TWO_NO_TRUMP => {
+                hcp >= 13.0 && length(Suit::Spades) < 5 && length(Suit::Hearts) < 5
+            }
+            _ => false,
+        };
+        if ask {
+            return Some(Bid::Contract(GERBER));
+        }
+
+        let trump = Self::trump_suit(calls)?;
+        let ask = match calls {
+            [_] => length(trump) >= 3 && Self::support_points(hand, trump) >= 21.0,
+            [_, raise] if raise.level == Level::Four => points >= 19.0,
+            [_, raise] if raise.level == Level::Three => points >= 21.0,
+            [_, _, _] if Self::cue_bid_sequence(calls).is_some() => length(trump) >= 4 && hcp >= 18.0,
+            [opening, _, _] if *opening == ONE_NO_TRUMP => length(trump) >= 6 && hcp >= 18.0,
+            _ => false,
+        };
+        (ask && no_void).then_some(Bid::Contract(BLACKWOOD))
+    }
+
+    fn control_cue_bid(hand: &Hand<13>, calls: &[ContractBid], start: usize, trump: Suit) -> Option<Bid> {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        let points = Self::points(hand);
+        let game = ContractBid {
+            level: Level::Four,
+            strain: Strain::Trump(trump),
+        };
+
+        match calls[start..] {
+            [] => {
+                let slam_try = match start {
+                    2 => points >= 17.0,
+                    _ => hand.length_in(trump) >= 4 && hcp >= 15.0,
+                };
+                let last = *calls.last()?;
+                slam_try.then(|| Bid::Contract(cheapest_cue_bid(hand, trump, last, false).unwrap_or(game)))
+            }
+            [cue_bid] if is_cue_bid(cue_bid, trump) => {
+                let maximum = match start {
+                    2 => Self::support_points(hand, trump) >= 12.0,
+                    _ => hcp >= 16.0,
+                };
+                let cue_back = cheapest_cue_bid(hand, trump, cue_bid, true).filter(|_| maximum);
+                Some(Bid::Contract(cue_back.unwrap_or(game)))
+            }
+            [cue_bid, answer] if is_cue_bid(cue_bid, trump) => match is_cue_bid(answer, trump) {
+                true => Some(Bid::Contract(BLACKWOOD)),
+                false => Some(pass()),
+            },
+            _ => None,
+        }
+    }
+
+    fn answer_ace_ask(&self, hand: &Hand<13>, ask: AceAsk) -> ContractBid {
+        match ask {
+            AceAsk::Gerber => {
+                let strain = match ForumDPlus2015Evaluator::keycards(hand, None) % 4 {
+                    0 => Strain::Trump(Suit::Diamonds),
+                    1 => Strain::Trump(Suit::Hearts),
+                    2 => Strain::Trump(Suit::Spades),
+                    _ => Strain::NoTrump,
+                };
+                ContractBid {
+                    level: Level::Four,
+                    strain,
+                }
+            }
+            AceAsk::Keycards(trump) => {
+                let keycards = ForumDPlus2015Evaluator::keycards(hand, Some(trump));
+                let steps = self.keycard_responses.steps();
+                let suit = match keycards {
+                    _ if Keycards::aces(steps[0]).allows(keycards) => Suit::Clubs,
+                    _ if Keycards::aces(steps[1]).allows(keycards) => Suit::Diamonds,
+                    _ if has_queen(hand, trump) => Suit::Spades,
+                    _ => Suit::Hearts,
+                };
+                ContractBid {
+                    level: Level::Five,
+                    strain: Strain::Trump(suit),
+                }
+            }
+        }
+    }
+
+    fn keycards_shown(&self, ask: AceAsk, answer: ContractBid) -> Option<Keycards> {
+        match (ask, answer.level, answer.strain) {
+            (AceAsk::Gerber, Level::Four, Strain::Trump(Suit::Diamonds)) => Some(Keycards::aces((0, 4))),
+            (AceAsk::Gerber, Level::Four, Strain::Trump(Suit::Hearts)) => Some(Keycards::aces((1, 1))),
+            (AceAsk::Gerber, Level::Four, Strain::Trump(Suit::Spades)) => Some(Keycards::aces((2, 2))),
+            (AceAsk::Gerber, Level::Four, Strain::NoTrump) => Some(Keycards::aces((3, 3))),
+            (AceAsk::Keycards(trump), Level::Five, Strain::Trump(suit)) => {
+                let steps = self.keycard_responses.steps();
+                Some(match suit {
+                    Suit::Clubs => Keycards::for_trump_suit(trump, steps[0], None),
+                    Suit::Diamonds => Keycards::for_trump_suit(trump, steps[1], None),
+                    Suit::Hearts => Keycards::for_trump_suit(trump, (2, 5), Some(false)),
+                    Suit::Spades => Keycards::for_trump_suit(trump, (2, 5), Some(true)),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn place_contract(&self, hand: &Hand<13>, calls: &[ContractBid], ask: AceAsk, answer: ContractBid) -> Bid {
+        let Some(shown) = self.keycards_shown(ask, answer) else {
+            return pass();
+        };
+        let trump = shown.trump_suit;
+        let (total, strain) = match trump {
+            Some(suit) => (5, Strain::Trump(suit)),
+            None => (4, Strain::NoTrump),
+        };
+        let mine = ForumDPlus2015Evaluator::keycards(hand, trump);
+        let missing = total - (mine + partners_count(shown.counts, mine, total)).min(total);
+        let queen = match trump {
+            Some(suit) => has_queen(hand, suit) || shown.trump_queen == Some(true),
+            None => true,
+        };
+        // after Gerber, 4NT is to play
+        let sign_off = match trump {
+            Some(_) => ContractBid {
+                level: Level::Five,
+                strain,
+            },
+            None => BLACKWOOD,
+        };
+
+        let openers_hcp = match calls[0] == ONE_NO_TRUMP {
+            true => 15.0,
+            false => 20.0,
+        };
+        let grand_slam = trump.is_none() && ForumDPlus2015Evaluator::hcp(hand) + openers_hcp >= 37.0;
+
+        match missing {
+            0 if grand_slam => contract(Level::Seven, strain),
+            0 => contract(Level::Six, strain),
+            1 if queen => contract(Level::Six, strain),
+            _ if sign_off > answer => Bid::Contract(sign_off),
+            _ if sign_off == answer => pass(),
+            _ => contract(Level::Six, strain),
+        }
+    }
+}
+
+fn major(bid: ContractBid) -> Option<Suit> {
+    match bid.strain {
+        Strain::Trump(suit) if suit.is_major() => Some(suit),
+        _ => None,
+    }
+}
+
+fn has_queen(hand: &Hand<13>, suit: Suit) -> bool {
+    hand.contains(&Card {
+        suit,
+        rank: Rank::Queen,
+    })
+}
+
+fn is_cue_bid(bid: ContractBid, trump: Suit) -> bool {
+    let game = ContractBid {
+        level: Level::Four,
+        strain: Strain::Trump(trump),
+    };
+    bid < game && bid.strain != Strain::Trump(trump) && bid.strain != Strain::NoTrump
+}
+
+fn cheapest_cue_bid(hand: &Hand<13>, trump: Suit, last: ContractBid, second_round: bool) -> Option<ContractBid> {
+    [Level::Three, Level::Four]
+        .into_iter()
+        .flat_map(|level| {
+            SUIT_ARRAY.map(|suit| ContractBid {
+                level,
+                strain: Strain::Trump(suit),
+            })
+        })
+        .filter(|&bid| bid > last && is_cue_bid(bid, trump))
+        .find(|bid| {
+            let Strain::Trump(suit) = bid.strain else { return false };
+            ForumDPlus2015Evaluator::first_round_control_in(suit, hand, Some(trump))
+                || second_round && ForumDPlus2015Evaluator::second_round_control_in(suit, hand, Some(trump))
+        })
+}
+
+// the higher count, unless it is impossible or we already hold more than half of the keycards
+fn partners_count((low, high): (u8, u8), mine: u8, total: u8) -> u8 {
+    match mine + high <= total && mine <= total / 2 {
+        true => high,
+        false => low,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::KeycardResponses;
+    use crate::engine::bidding_engine::forum_d_plus_2015_bidding_engine::ForumDPlus2015BiddingEngine;
+    use crate::engine::bidding_engine::SelectBid;
+    use crate::primitives::bid::Bid;
+    use crate::primitives::Hand;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    fn bids(auction: &[&str]) -> Vec<Bid> {
+        auction.iter().map(|bid| Bid::from_str(bid).unwrap()).collect()
+    }
+
+    fn find_bid(engine: &ForumDPlus2015BiddingEngine, hand: &str, auction: &[&str]) -> Bid {
+        engine.find_bid(&Hand::from_str(hand).unwrap(), &bids(auction))
+    }
+
+    #[test_case("S:AKJ93,H:K4,D:A52,C:Q32", &["1S", "P", "3S", "P"], "4D"; "Slam try after a limit raise")]
+    #[test_case("S:Q842,H:A63,D:K4,C:K952", &["1S", "P", "3S", "P", "4D", "P"], "4H"; "Cue-bidding back")]
+    #[test_case("S:Q842,H:J63,D:K4,C:K952", &["1S", "P", "3S", "P", "4D", "P"], "4S"; "Signing off with a minimum")]
+    #[test_case("S:AKJ93,H:K4,D:A52,C:Q32", &["1S", "P", "3S", "P", "4D", "P", "4H", "P"], "4NT"; "Asking after a cue-bid")]
+    #[test_case("S:AKJ93,H:K4,D:A52,C:Q32", &["1S", "P", "3S", "P", "4D", "P", "4S", "P"], "P"; "Partner signed off")]
+    #[test_case("S:KQ2,H:AQ3,D:KQ32,C:A32", &["1NT", "P"], "4C"; "Gerber")]
+    #[test_case("S:A54,H:KJ2,D:AJ54,C:K32", &["1NT", "P", "4C", "P"], "4S"; "Two aces")]
+    #[test_case("S:KQ2,H:AQ3,D:KQ32,C:A32", &["1NT", "P", "4C", "P", "4S", "P"], "6NT"; "No ace missing")]
+    #[test_case("S:KQ2,H:KQ3,D:KQ32,C:A32", &["1NT", "P", "4C", "P", "4H", "P"], "4NT"; "Two aces missing")]
+    #[test_case("S:KQ2,H:AKJ95,D:A2,C:QJ2", &["1H", "P"], "4NT"; "Keycard Blackwood over an opening")]
+    #[test_case("S:KQ2,H:KJ953,D:KQ2,C:KQ", &["1H", "P", "4H", "P", "4NT", "P", "5C", "P"], "6H"; "Four keycards")]
+    #[test_case("S:KQ2,H:KJ953,D:KQ2,C:KQ", &["1H", "P", "4H", "P", "4NT", "P", "5D", "P"], "5H"; "Missing a keycard and the queen")]
+    #[test_case("S:KQ2,H:KJ953,D:KQ2,C:KQ", &["1H", "P", "4H", "P", "4NT", "P", "5H", "P"], "P"; "Missing two keycards")]
+    #[test_case("S:KQ2,H:KQ953,D:KQ2,C:KQ", &["1H", "P", "4H", "P", "4NT", "P", "5D", "P", "6H", "P"], "P"; "Partner placed the contract")]
+    fn slam_bidding(hand: &str, auction: &[&str], expected: &str) {
+        let engine = ForumDPlus2015BiddingEngine::new();
+        assert_eq!(find_bid(&engine, hand, auction), Bid::from_str(expected).unwrap());
+    }
+
+    #[test_case(KeycardResponses::Rkcb1430, "S:Q842,H:A63,D:K4,C:K952", "5C"; "One keycard in 1430")]
+    #[test_case(KeycardResponses::Rkcb3014, "S:Q842,H:A63,D:K4,C:K952", "5D"; "One keycard in 3014")]
+    #[test_case(KeycardResponses::Rkcb1430, "S:Q842,H:863,D:K4,C:K952", "5D"; "No keycard in 1430")]
+    #[test_case(KeycardResponses::Rkcb3014, "S:Q842,H:863,D:K4,C:K952", "5C"; "No keycard in 3014")]
+    #[test_case(KeycardResponses::Rkcb1430, "S:Q842,H:A63,D:A4,C:K952", "5S"; "Two keycards with the queen")]
+    #[test_case(KeycardResponses::Rkcb3014, "S:8542,H:A63,D:A4,C:K952", "5H"; "Two keycards without the queen")]
+    fn keycard_responses(responses: KeycardResponses, hand: &str, expected: &str) {
+        let engine = ForumDPlus2015BiddingEngine::with_keycard_responses(responses);
+        let auction = ["1S", "P", "3S", "P", "4D", "P", "4H", "P", "4NT", "P"];
+        assert_eq!(find_bid(&engine, hand, &auction), Bid::from_str(expected).unwrap());
+    }
+
+    #[test_case(KeycardResponses::Rkcb1430, &["1S", "P", "4NT", "P"], "5C", "Hand has 1 or 4 keycards for ♠."; "Keycards in 1430")]
+    #[test_case(KeycardResponses::Rkcb3014, &["1S", "P", "4NT", "P"], "5C", "Hand has 0 or 3 keycards for ♠."; "Keycards in 3014")]
+    #[test_case(KeycardResponses::Rkcb1430, &["1NT", "P", "2C", "P", "2H", "P", "4NT", "P"], "5S", "Hand has 2 or 5 keycards for ♥ with the queen."; "Keycards after Stayman")]
+    #[test_case(KeycardResponses::Rkcb1430, &["2NT", "P", "4C", "P"], "4D", "Hand has 0 or 4 aces."; "Gerber")]
+    #[test_case(KeycardResponses::Rkcb1430, &["1H", "P", "3H", "P"], "4C", "Hand has 17 to 21 points."; "Cue-bid")]
+    #[test_case(KeycardResponses::Rkcb1430, &["1NT", "P", "2D", "P", "2H", "P", "4NT", "P"], "5H", "Hand has 2 or 5 keycards for ♥ without the queen."; "Keycards after a transfer")]
+    #[test_case(KeycardResponses::Rkcb1430, &["1NT", "P", "2NT", "P", "4NT", "P"], "5C", ""; "Not an ace ask")]
+    fn interpret(responses: KeycardResponses, auction: &[&str], bid: &str, expected: &str) {
+        let engine = ForumDPlus2015BiddingEngine::with_keycard_responses(responses);
+        let explanation = engine.interpret(&bids(auction), Bid::from_str(bid).unwrap());
+        assert_eq!(format!("{}", explanation), expected);
+    }
+
+    #[test_case(&["1NT", "P"], "4C", true; "Gerber")]
+    #[test_case(&["1S", "P"], "4NT", false; "Keycard Blackwood")]
+    #[test_case(&["1S", "P", "4NT", "P"], "5D", true; "Answering keycard Blackwood")]
+    #[test_case(&["1S", "P", "3S", "P"], "4C", true; "Cue-bid")]
+    #[test_case(&["1S", "P", "2S", "P"], "3C", false; "Not a cue-bid")]
+    fn alert(auction: &[&str], bid: &str, alerted: bool) {
+        let explanation = ForumDPlus2015BiddingEngine::new().alert(&bids(auction), Bid::from_str(bid).unwrap());
+        assert_eq!(explanation.is_some(), alerted);
+    }
+}
@@ -1,9 +1,38 @@
+use crate::engine::reason::Reason;
 use crate::engine::subjective_game_view::SubjectiveGamePhaseStateView;
+use crate::game::bid_manager::BidManager;
 use crate::game::game_phase_states::BiddingState;
 use crate::primitives::bid::Bid;
+use crate::primitives::deal::Seat;
+use crate::primitives::hand_info::explanation::Explanation;
 
+pub mod bidding_system;
+pub mod forum_d_plus_2015_bidding_engine;
 pub mod mock_bidding_engine;
+pub mod system_bidding_engine;
 
-pub trait SelectBid {
-    fn select_bid(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> Bid;
+pub trait SelectBid<const N: usize = 13> {
+    fn select_bid(&self, state: SubjectiveGamePhaseStateView<BiddingState<N>>) -> Bid;
+
+    // engines that can't name the rule they applied at least tell what the call shows
+    fn select_bid_with_reason(&self, state: SubjectiveGamePhaseStateView<BiddingState<N>>) -> (Bid, Reason) {
+        let auction = state.bids();
+        let bid = self.select_bid(state);
+        (bid, Reason::default().showing(self.interpret(&auction, bid)))
+    }
+
+    fn alert(&self, _auction: &[Bid], _bid: Bid) -> Option<Explanation> {
+        None
+    }
+
+    // used to draw inferences from all calls, assuming the opponents play the same system
+    fn interpret(&self, auction: &[Bid], bid: Bid) -> Explanation {
+        self.alert(auction, bid).unwrap_or_default()
+    }
+}
+
+pub(crate) fn is_legal(auction: &[Bid], bid: Bid) -> bool {
+    // legality doesn't depend on who dealt
+    let mut bid_manager = BidManager::new(Seat::North);
+    auction.iter().all(|&call| bid_manager.bid(call).is_ok()) && bid_manager.bid(bid).is_ok()
 }
@@ -0,0 +1,235 @@
+use crate::engine::bidding_engine::bidding_system::{BidRule, BiddingSystem};
+use crate::engine::bidding_engine::{is_legal, SelectBid};
+use crate::engine::hand_evaluation::ForumDPlus2015Evaluator;
+use crate::engine::reason::Reason;
+use crate::engine::subjective_game_view::SubjectiveGamePhaseStateView;
+use crate::game::game_phase_states::BiddingState;
+use crate::primitives::bid::{AuxiliaryBid, Bid};
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::card::Rank;
+use crate::primitives::deal::hand::HandType;
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::{Card, Hand};
+
+pub struct SystemBiddingEngine {
+    system: BiddingSystem,
+}
+
+impl SystemBiddingEngine {
+    pub fn new(system: BiddingSystem) -> Self {
+        Self { system }
+    }
+
+    pub fn system(&self) -> &BiddingSystem {
+        &self.system
+    }
+
+    pub fn find_bid(&self, hand: &Hand<13>, auction: &[Bid]) -> Bid {
+        self.find_rule(hand, auction)
+            .map(|rule| rule.bid)
+            .unwrap_or(Bid::Auxiliary(AuxiliaryBid::Pass))
+    }
+
+    // `None` if `find_bid` passes because no rule fits
+    pub fn find_rule(&self, hand: &Hand<13>, auction: &[Bid]) -> Option<&BidRule> {
+        self.system
+            .rules_for(auction)
+            .into_iter()
+            .filter(|rule| Self::hand_fits(hand, &rule.shows))
+            .find(|rule| is_legal(auction, rule.bid))
+    }
+
+    // suit qualities are minimums, a balanced hand may have a five-card suit unless one is named
+    pub fn hand_fits(hand: &Hand<13>, explanation: &Explanation) -> bool {
+        let in_range = |value: f64, range: Option<(u8, u8)>| match range {
+            Some((min, max)) => (min as f64..=max as f64).contains(&value),
+            None => true,
+        };
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        let total_points = hcp + ForumDPlus2015Evaluator::length_points(hand, None, &[]);
+
+        let hand_type_fits = match (explanation.hand_type, hand.hand_type()) {
+            (None, _) => true,
+            (Some(HandType::Balanced(None)), HandType::Balanced(_)) => true,
+            (Some(expected), actual) => expected == actual,
+        };
+
+        let keycards_fit = match explanation.keycards {
+            Some(keycards) => {
+                let queen = |suit| {
+                    hand.contains(&Card {
+                        suit,
+                        rank: Rank::Queen,
+                    })
+                };
+                keycards.allows(ForumDPlus2015Evaluator::keycards(hand, keycards.trump_suit))
+                    && match (keycards.trump_suit, keycards.trump_queen) {
+                        (Some(suit), Some(trump_queen)) => queen(suit) == trump_queen,
+                        _ => true,
+                    }
+            }
+            None => true,
+        };
+
+        hand_type_fits
+            && keycards_fit
+            && in_range(hcp, explanation.hcp)
+            && in_range(total_points, explanation.total_points)
+            && SUIT_ARRAY.iter().all(|&suit| {
+                in_range(hand.length_in(suit) as f64, explanation.suit_lengths[suit as usize])
+                    && match explanation.suit_qualities[suit as usize] {
+                        Some(quality) => ForumDPlus2015Evaluator::suit_quality(hand, suit) >= quality,
+                        None => true,
+                    }
+            })
+    }
+}
+
+impl SelectBid for SystemBiddingEngine {
+    fn select_bid(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> Bid {
+        let hand = state.my_starting_hand().unwrap();
+        self.find_bid(&hand, &state.bids())
+    }
+
+    fn select_bid_with_reason(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> (Bid, Reason) {
+        let hand = state.my_starting_hand().unwrap();
+        match self.find_rule(&hand, &state.bids()) {
+            Some(rule) => (
+                rule.bid,
+                Reason::from_rule(&format!(
+                    "{} rule for {} with priority {}",
+                    self.system.name(),
+                    rule.bid,
+                    rule.priority
+                ))
+                .showing(rule.shows),
+            ),
+            None => (
+                Bid::Auxiliary(AuxiliaryBid::Pass),
+                Reason::from_rule(&format!("No rule of {} fits the hand, passing", self.system.name())),
+            ),
+        }
+    }
+
+    fn alert(&self, auction: &[Bid], bid: Bid) -> Option<Explanation> {
+        self.system
+            .rules_for(auction)
+            .into_iter()
+            .find(|rule| rule.bid == bid && rule.alert)
+            .map(|rule| rule.shows)
+    }
+
+    // only what all rules for `bid` have in common, we can't know which of them was applied
+    fn interpret(&self, auction: &[Bid], bid: Bid) -> Explanation {
+        self.system
+            .rules_for(auction)
+            .into_iter()
+            .filter(|rule| rule.bid == bid)
+            .map(|rule| rule.shows)
+            .reduce(|shown, other| Explanation {
+                hand_type: shown.hand_type.filter(|&hand_type| other.hand_type == Some(hand_type)),
+                hcp: hull(shown.hcp, other.hcp),
+                total_points: hull(shown.total_points, other.total_points),
+                suit_lengths: std::array::from_fn(|i| hull(shown.suit_lengths[i], other.suit_lengths[i])),
+                suit_qualities: std::array::from_fn(|i| shown.suit_qualities[i].min(other.suit_qualities[i])),
+                keycards: shown.keycards.filter(|&keycards| other.keycards == Some(keycards)),
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn hull(range: Option<(u8, u8)>, other: Option<(u8, u8)>) -> Option<(u8, u8)> {
+    let ((min, max), (other_min, other_max)) = (range?, other?);
+    Some((min.min(other_min), max.max(other_max)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::SystemBiddingEngine;
+    use crate::engine::bidding_engine::bidding_system::BiddingSystem;
+    use crate::engine::bidding_engine::SelectBid;
+    use crate::game::bid_manager::BidManager;
+    use crate::primitives::bid::Bid;
+    use crate::primitives::{Deal, Hand};
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    const SYSTEM: &str = "system Test
+- => 1NT : Hand is balanced. Hand has 15 to 17 hcp.
+- => 1S @-1 : Hand has 5 to 13 cards in ♠. Hand has 12 to 21 points.
+- => 2H @-2 : Hand has 6 to 6 cards in ♥. Hand's ♥ are good. Hand has 6 to 10 hcp.
+1NT P => 2C! : Hand has 8 to 37 hcp. Hand has 4 to 4 cards in ♥.
+1NT P => 2C! : Hand has 8 to 37 hcp. Hand has 4 to 4 cards in ♠.
+1NT X => 1S
+1S P 4NT P => 5S! : Hand has 2 keycards for ♠ with the queen.
+1S P 4NT P => 5H! : Hand has 2 keycards for ♠ without the queen.";
+
+    fn bids(auction: &[&str]) -> Vec<Bid> {
+        auction.iter().map(|bid| Bid::from_str(bid).unwrap()).collect()
+    }
+
+    #[test_case("S:AQ4,H:KJ3,D:Q987,C:K32", &[], "1NT"; "1NT")]
+    #[test_case("S:AQ954,H:KJ3,D:Q98,C:K3", &["P"], "1NT"; "1NT with a five-card major")]
+    #[test_case("S:AQ954,H:KJ3,D:Q98,C:32", &[], "1S"; "1S")]
+    #[test_case("S:Q4,H:KQJ953,D:72,C:432", &["P", "P"], "2H"; "Weak two")]
+    #[test_case("S:Q4,H:KT8753,D:Q2,C:432", &[], "P"; "Poor suit")]
+    #[test_case("S:KJ72,H:32,D:Q72,C:K432", &["1NT", "P"], "2C"; "Stayman with spades")]
+    #[test_case("S:K2,H:KJ72,D:Q72,C:K432", &["1NT", "P"], "2C"; "Stayman with hearts")]
+    #[test_case("S:K72,H:K32,D:Q72,C:K432", &["1NT", "P"], "P"; "No rule fits")]
+    #[test_case("S:K72,H:K32,D:Q72,C:K432", &["1NT", "X"], "P"; "Illegal call")]
+    #[test_case("S:AQ954,H:A32,D:Q98,C:32", &["1S", "P", "4NT", "P"], "5S"; "Keycards with the queen")]
+    #[test_case("S:AJ954,H:A32,D:Q98,C:32", &["1S", "P", "4NT", "P"], "5H"; "Keycards without the queen")]
+    #[test_case("S:KJ954,H:K32,D:Q98,C:32", &["1S", "P", "4NT", "P"], "P"; "Wrong number of keycards")]
+    fn find_bid(hand: &str, auction: &[&str], expected: &str) {
+        let engine = SystemBiddingEngine::new(BiddingSystem::from_str(SYSTEM).unwrap());
+        let hand = Hand::from_str(hand).unwrap();
+        assert_eq!(engine.find_bid(&hand, &bids(auction)), Bid::from_str(expected).unwrap());
+    }
+
+    #[test]
+    fn find_rule() {
+        let engine = SystemBiddingEngine::new(BiddingSystem::from_str(SYSTEM).unwrap());
+        let hand = Hand::from_str("S:AQ954,H:KJ3,D:Q98,C:32").unwrap();
+        let rule = engine.find_rule(&hand, &[]).unwrap();
+        assert_eq!((rule.bid, rule.priority), (Bid::from_str("1S").unwrap(), -1));
+
+        let hand = Hand::from_str("S:K72,H:K32,D:Q72,C:K432").unwrap();
+        assert!(engine.find_rule(&hand, &bids(&["1NT", "P"])).is_none());
+    }
+
+    #[test_case(&["1NT", "P"], "2C", true; "Stayman")]
+    #[test_case(&[], "1NT", false; "Natural")]
+    fn alert(auction: &[&str], bid: &str, alerted: bool) {
+        let engine = SystemBiddingEngine::new(BiddingSystem::from_str(SYSTEM).unwrap());
+        let explanation = engine.alert(&bids(auction), Bid::from_str(bid).unwrap());
+        assert_eq!(explanation.is_some(), alerted);
+    }
+
+    #[test]
+    fn interpret() {
+        let engine = SystemBiddingEngine::new(BiddingSystem::from_str(SYSTEM).unwrap());
+        let stayman = engine.interpret(&bids(&["1NT", "P"]), Bid::from_str("2C").unwrap());
+        assert_eq!(stayman.hcp, Some((8, 37)));
+        assert_eq!(stayman.suit_lengths, [None; 4]);
+
+        let weak_two = engine.interpret(&bids(&["P"]), Bid::from_str("2H").unwrap());
+        assert_eq!(weak_two.hcp, Some((6, 10)));
+    }
+
+    #[test]
+    fn standard_american() {
+        let system = BiddingSystem::from_str(include_str!("../../../systems/standard_american.system")).unwrap();
+        assert_eq!(system.name(), "Standard American");
+        let engine = SystemBiddingEngine::new(system);
+
+        for seed in 0..100 {
+            let deal: Deal<13> = Deal::from_u64_seed(seed);
+            let mut bid_manager = BidManager::new(deal.board.dealer());
+            while !bid_manager.bidding_has_ended() {
+                let hand = deal.hand_of(bid_manager.next_to_play());
+                let bid = engine.find_bid(hand, bid_manager.bids());
+                bid_manager.bid(bid).unwrap();
+            }
+        }
+    }
+}
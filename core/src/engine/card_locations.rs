@@ -0,0 +1,365 @@
+use crate::engine::engine_state::HandDescription;
+use crate::impossible_book::helper::choose;
+use crate::primitives::card::rank::RANK_ARRAY;
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::deal::seat::SEAT_ARRAY;
+use crate::primitives::deal::{Hand, Seat};
+use crate::primitives::{Card, Suit};
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone)]
+pub struct CardLocationTracker {
+    holders: BTreeMap<Card, Seat>,
+    played_cards: BTreeSet<Card>,
+    voids: [[bool; 4]; 4],
+    descriptions: [Option<HandDescription>; 4],
+}
+
+impl CardLocationTracker {
+    pub fn new() -> Self {
+        Self {
+            holders: BTreeMap::new(),
+            played_cards: BTreeSet::new(),
+            voids: [[false; 4]; 4],
+            descriptions: Default::default(),
+        }
+    }
+
+    pub fn add_known_hand(&mut self, seat: Seat, hand: Hand<13>) {
+        for &card in hand.cards() {
+            self.holders.insert(card, seat);
+        }
+    }
+
+    // not following suit shows a void
+    pub fn add_played_card(&mut self, seat: Seat, card: Card, suit_led: Option<Suit>) {
+        self.holders.insert(card, seat);
+        self.played_cards.insert(card);
+        if let Some(suit) = suit_led {
+            if card.suit != suit {
+                self.voids[seat as usize][suit as usize] = true;
+            }
+        }
+    }
+
+    // only the suit lengths are taken into account
+    pub fn add_description(&mut self, seat: Seat, description: HandDescription) {
+        self.descriptions[seat as usize] = Some(description);
+    }
+
+    // every deal of the unseen cards that agrees with the voids and the suit lengths from the auction
+    // is equally likely; if there is none, e.g. after a misbid, the auction is ignored
+    pub fn locations(&self) -> CardLocations {
+        let hidden_seats = SEAT_ARRAY
+            .into_iter()
+            .filter(|&seat| self.holders.values().filter(|&&holder| holder == seat).count() < 13)
+            .collect_vec();
+        let unseen_cards = SUIT_ARRAY.map(|suit| {
+            RANK_ARRAY
+                .iter()
+                .map(|&rank| Card { suit, rank })
+                .filter(|card| !self.holders.contains_key(card))
+                .collect_vec()
+        });
+
+        let mut spread = Spread::new(self, &hidden_seats, &unseen_cards, true);
+        if spread.deals == 0.0 {
+            spread = Spread::new(self, &hidden_seats, &unseen_cards, false);
+        }
+
+        let mut probabilities = BTreeMap::new();
+        for (&card, &holder) in self
+            .holders
+            .iter()
+            .filter(|(card, _)| !self.played_cards.contains(card))
+        {
+            let mut probability = [0.0; 4];
+            probability[holder as usize] = 1.0;
+            probabilities.insert(card, probability);
+        }
+        for cards in &unseen_cards {
+            for &card in cards {
+                let suit = card.suit as usize;
+                let probability =
+                    std::array::from_fn(|seat| spread.cards[seat][suit] / spread.deals / cards.len() as f64);
+                probabilities.insert(card, probability);
+            }
+        }
+        CardLocations { probabilities }
+    }
+
+    fn known_length(&self, seat: Seat, suit: Suit) -> usize {
+        self.holders
+            .iter()
+            .filter(|&(card, &holder)| holder == seat && card.suit == suit)
+            .count()
+    }
+}
+
+impl Default for CardLocationTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardLocations {
+    probabilities: BTreeMap<Card, [f64; 4]>,
+}
+
+impl CardLocations {
+    // 0 for a card that has been played
+    pub fn probability(&self, card: Card, seat: Seat) -> f64 {
+        self.probabilities
+            .get(&card)
+            .map_or(0.0, |probability| probability[seat as usize])
+    }
+
+    pub fn expected_length(&self, seat: Seat, suit: Suit) -> f64 {
+        self.probabilities
+            .iter()
+            .filter(|(card, _)| card.suit == suit)
+            .map(|(_, probability)| probability[seat as usize])
+            .sum()
+    }
+
+    pub fn uncertain_cards(&self) -> Vec<Card> {
+        self.probabilities
+            .iter()
+            .filter(|(_, probability)| !probability.contains(&1.0))
+            .map(|(&card, _)| card)
+            .collect()
+    }
+}
+
+// counts the ways to deal the unseen cards, suit by suit, and the cards of each suit every hand gets
+struct Spread {
+    hidden_seats: Vec<Seat>,
+    unseen: [usize; 4],
+    allowed: [[RangeInclusive<usize>; 4]; 4],
+    deals: f64,
+    cards: [[f64; 4]; 4],
+}
+
+impl Spread {
+    fn new(
+        tracker: &CardLocationTracker,
+        hidden_seats: &[Seat],
+        unseen_cards: &[Vec<Card>; 4],
+        use_descriptions: bool,
+    ) -> Self {
+        let unseen = unseen_cards.each_ref().map(|cards| cards.len());
+        let allowed = SEAT_ARRAY.map(|seat| {
+            SUIT_ARRAY.map(|suit| {
+                let length = match (&tracker.descriptions[seat as usize], use_descriptions) {
+                    (Some(description), true) => description.suit_length(suit).0.clone(),
+                    _ => 0..=13,
+                };
+                let known = tracker.known_length(seat, suit);
+                match (
+                    tracker.voids[seat as usize][suit as usize],
+                    length.end().checked_sub(known),
+                ) {
+                    (true, _) => 0..=0,
+                    (false, Some(most)) => length.start().saturating_sub(known)..=most,
+                    // more cards have been seen than the auction showed
+                    #[allow(clippy::reversed_empty_ranges)]
+                    (false, None) => 1..=0,
+                }
+            })
+        });
+        let mut spread = Spread {
+            hidden_seats: hidden_seats.to_vec(),
+            unseen,
+            allowed,
+            deals: 0.0,
+            cards: [[0.0; 4]; 4],
+        };
+        let vacancies = SEAT_ARRAY.map(|seat| match hidden_seats.contains(&seat) {
+            true => 13 - tracker.holders.values().filter(|&&holder| holder == seat).count(),
+            false => 0,
+        });
+        spread.deal_suit(0, 0, unseen[0], vacancies, [[0; 4]; 4], 1.0);
+        spread
+    }
+
+    // gives some of the `left` cards of the suit to the seat at `seat_index`, then moves on to the next seat or suit
+    fn deal_suit(
+        &mut self,
+        suit: usize,
+        seat_index: usize,
+        left: usize,
+        vacancies: [usize; 4],
+        lengths: [[usize; 4]; 4],
+        ways: f64,
+    ) {
+        if seat_index == self.hidden_seats.len() {
+            if left > 0 {
+                return;
+            }
+            match suit + 1 {
+                4 if vacancies.iter().all(|&vacancy| vacancy == 0) => {
+                    self.deals += ways;
+                    for (cards, lengths) in self.cards.iter_mut().zip(lengths) {
+                        for (cards, length) in cards.iter_mut().zip(lengths) {
+                            *cards += ways * length as f64;
+                        }
+                    }
+                }
+                4 => (),
+                next => self.deal_suit(next, 0, self.unseen[next], vacancies, lengths, ways),
+            }
+            return;
+        }
+
+        let seat = self.hidden_seats[seat_index] as usize;
+        let allowed = self.allowed[seat][suit].clone();
+        let most = left.min(vacancies[seat]).min(*allowed.end());
+        for length in *allowed.start()..=most {
+            let mut vacancies = vacancies;
+            vacancies[seat] -= length;
+            let mut lengths = lengths;
+            lengths[seat][suit] = length;
+            let ways = ways * choose(left as u8, length as u8) as f64;
+            self.deal_suit(suit, seat_index + 1, left - length, vacancies, lengths, ways);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CardLocationTracker;
+    use crate::actors::game_manager::GameManager;
+    use crate::engine::engine_state::HandDescription;
+    use crate::engine::subjective_game_view::SubjectiveGameStateView;
+    use crate::game::GameState;
+    use crate::primitives::bid::Bid;
+    use crate::primitives::deal::Seat;
+    use crate::primitives::game_event::GameEvent;
+    use crate::primitives::hand_info::explanation::Explanation;
+    use crate::primitives::player_event::{BidEvent, CardEvent, PlayerEvent};
+    use crate::primitives::{Card, Deal, Hand, Suit};
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    // I am South and declarer, North is dummy
+    fn tracker() -> CardLocationTracker {
+        let mut tracker = CardLocationTracker::new();
+        tracker.add_known_hand(Seat::South, Hand::from_str("S:AQ32,H:AK2,D:A32,C:432").unwrap());
+        tracker.add_known_hand(Seat::North, Hand::from_str("S:J54,H:543,D:KQJ4,C:AK5").unwrap());
+        tracker
+    }
+
+    fn card(card: &str) -> Card {
+        Card::from_str(card).unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} is not {}", actual, expected);
+    }
+
+    #[test]
+    fn nothing_seen() {
+        let locations = tracker().locations();
+        assert_close(locations.probability(card("SK"), Seat::West), 0.5);
+        assert_close(locations.probability(card("SK"), Seat::South), 0.0);
+        assert_close(locations.probability(card("SA"), Seat::South), 1.0);
+        assert_close(locations.expected_length(Seat::East, Suit::Hearts), 3.5);
+        assert_eq!(locations.uncertain_cards().len(), 26);
+    }
+
+    #[test]
+    fn shown_out() {
+        let mut tracker = tracker();
+        // West leads a club, East shows out
+        tracker.add_played_card(Seat::West, card("CQ"), None);
+        tracker.add_played_card(Seat::North, card("CA"), Some(Suit::Clubs));
+        tracker.add_played_card(Seat::East, card("D5"), Some(Suit::Clubs));
+        tracker.add_played_card(Seat::South, card("C2"), Some(Suit::Clubs));
+        let locations = tracker.locations();
+
+        assert_close(locations.probability(card("CJ"), Seat::West), 1.0);
+        assert_close(locations.probability(card("CQ"), Seat::West), 0.0);
+        // West has 6 vacant places left for the other suits after the clubs, East 12
+        assert_close(locations.probability(card("SK"), Seat::West), 6.0 / 18.0);
+        assert_close(locations.probability(card("SK"), Seat::East), 12.0 / 18.0);
+    }
+
+    #[test_case("Hand has 6 to 13 cards in ♥.", 6.0, 7.0; "Long suit")]
+    #[test_case("Hand has 0 to 0 cards in ♥.", 0.0, 0.0; "Void")]
+    fn auction(description: &str, min: f64, max: f64) {
+        let mut tracker = tracker();
+        let mut west = HandDescription::unknown();
+        west.narrow(&Explanation::from_str(description).unwrap());
+        tracker.add_description(Seat::West, west);
+        let locations = tracker.locations();
+
+        let length = locations.expected_length(Seat::West, Suit::Hearts);
+        assert!(min - 1e-9 <= length && length <= max + 1e-9, "{}", length);
+        let total = locations.probability(card("HQ"), Seat::West) + locations.probability(card("HQ"), Seat::East);
+        assert_close(total, 1.0);
+    }
+
+    #[test]
+    fn subjective_view() {
+        let mut manager = GameManager::new_from_deal(Deal::from_u64_seed(9000));
+        manager.start_game().unwrap();
+        for bid in ["p", "1NT", "p", "2C", "p", "2S", "p", "4S", "p", "p", "p"] {
+            let player = manager.next_to_play().unwrap();
+            let bid = Bid::from_str(bid).unwrap();
+            let event = PlayerEvent::Bid(BidEvent {
+                player,
+                bid,
+                alert: false,
+                explanation: None,
+            });
+            manager.process_player_event(event).unwrap();
+        }
+        for card in ["C2", "C7", "CK", "C3", "CJ"] {
+            let player = manager.next_to_play().unwrap();
+            let event = PlayerEvent::Card(CardEvent {
+                player,
+                card: Card::from_str(card).unwrap(),
+            });
+            manager.process_player_event(event).unwrap();
+        }
+        let history = manager.history();
+        let GameEvent::NewGame(new_game) = history[0] else {
+            panic!("game has not started")
+        };
+        let mut game = GameState::from_new_game_event(new_game);
+        for event in &history[1..] {
+            game.process_game_event(*event).unwrap();
+        }
+
+        // North declares, South is dummy
+        let SubjectiveGameStateView::CardPlay(view) = SubjectiveGameStateView::new(&game, Seat::North) else {
+            panic!("not in card play")
+        };
+        let locations = view.card_locations(None);
+        assert_eq!(locations.probability(card("C2"), Seat::East), 0.0);
+        for own_card in view.my_remaining_cards() {
+            assert_eq!(locations.probability(own_card, Seat::North), 1.0);
+        }
+        for uncertain in locations.uncertain_cards() {
+            let total = [Seat::North, Seat::East, Seat::South, Seat::West]
+                .map(|seat| locations.probability(uncertain, seat))
+                .iter()
+                .sum();
+            assert_close(total, 1.0);
+        }
+        let seen = view.my_remaining_cards().len() + view.dummys_remaining_cards().len();
+        assert_eq!(locations.uncertain_cards().len(), 47 - seen);
+    }
+
+    #[test]
+    fn impossible_auction() {
+        let mut tracker = tracker();
+        let mut west = HandDescription::unknown();
+        west.narrow(&Explanation::from_str("Hand has 8 to 13 cards in ♣.").unwrap());
+        tracker.add_description(Seat::West, west);
+        assert_eq!(tracker.locations(), self::tracker().locations());
+    }
+}
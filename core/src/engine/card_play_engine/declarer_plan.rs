@@ -0,0 +1,574 @@
+use crate::engine::distribution_odds::DistributionOdds;
+use crate::primitives::card::rank::RANK_ARRAY;
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::card::Rank;
+use crate::primitives::deal::Seat;
+use crate::primitives::{Contract, Hand, Suit};
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+// count the sure winners (no-trump) or the losers (suit contract), then the extra tricks and the entries;
+// each suit on its own from declarer's and dummy's cards, a rough guide short of the double dummy solver
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeclarerPlan {
+    pub contract: Contract,
+    // the hand with more trumps, whose losers are counted in a suit contract
+    pub master_hand: Seat,
+    // spades first
+    pub suits: Vec<SuitPlan>,
+    pub entry_problems: Vec<EntryProblem>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuitPlan {
+    pub suit: Suit,
+    pub declarer: Vec<Rank>,
+    pub dummy: Vec<Rank>,
+    // tricks that can be cashed without giving up the lead
+    pub sure_winners: usize,
+    // among the first three cards of the master hand, only in a suit contract
+    pub losers: usize,
+    pub sources: Vec<TrickSource>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrickSource {
+    pub suit: Suit,
+    pub kind: SourceKind,
+    // on top of the sure winners and of the sources listed before it in the same suit
+    pub tricks: usize,
+    pub tricks_given_up: usize,
+    // by the odds of the cards missing in the suit
+    pub probability: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    Promotion,
+    Length,
+    Finesse { card: Rank, against: Rank, towards: Seat },
+    Ruff { ruffing_hand: Seat },
+}
+
+// tricks in a suit that need an entry to the long hand outside of it, when there is none
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryProblem {
+    pub suit: Suit,
+    pub long_hand: Seat,
+}
+
+impl DeclarerPlan {
+    pub fn new(declarers_hand: &Hand<13>, dummys_hand: &Hand<13>, contract: Contract) -> Self {
+        let declarer = contract.declarer;
+        let dummy = declarer.partner();
+        let master_hand = match contract.trump_suit() {
+            Some(trumps) if dummys_hand.length_in(trumps) > declarers_hand.length_in(trumps) => dummy,
+            _ => declarer,
+        };
+        let ranks = |hand: &Hand<13>, suit: Suit| hand.cards_in(suit).rev().map(|card| card.rank).collect_vec();
+
+        let mut suits = SUIT_ARRAY
+            .iter()
+            .rev()
+            .map(|&suit| {
+                let holding = Holding {
+                    suit,
+                    declarer: ranks(declarers_hand, suit),
+                    dummy: ranks(dummys_hand, suit),
+                    seats: [declarer, dummy],
+                };
+                let (master, other) = match master_hand == declarer {
+                    true => (holding.declarer.len(), holding.dummy.len()),
+                    false => (holding.dummy.len(), holding.declarer.len()),
+                };
+                let sure_winners = holding.sure_winners();
+                let sources = holding.sources();
+                let promoted = sources
+                    .iter()
+                    .filter(|source| source.kind == SourceKind::Promotion)
+                    .map(|source| source.tricks)
+                    .sum::<usize>();
+                // in a suit contract, promoted honours are no losers, and long cards help only by discarding
+                // the master hand's losers
+                let sources = sources
+                    .into_iter()
+                    .filter(|source| match (contract.trump_suit(), source.kind) {
+                        (Some(_), SourceKind::Promotion) => false,
+                        (Some(trumps), SourceKind::Length) => suit != trumps && other > master,
+                        _ => true,
+                    })
+                    .collect_vec();
+                SuitPlan {
+                    suit,
+                    losers: match contract.trump_suit() {
+                        Some(_) => master.min(3).saturating_sub(sure_winners + promoted),
+                        None => 0,
+                    },
+                    sure_winners,
+                    sources,
+                    declarer: holding.declarer,
+                    dummy: holding.dummy,
+                }
+            })
+            .collect_vec();
+
+        if let Some(trumps) = contract.trump_suit() {
+            let ruffing_hand = master_hand.partner();
+            let (master, ruffing) = match master_hand == declarer {
+                true => (declarers_hand, dummys_hand),
+                false => (dummys_hand, declarers_hand),
+            };
+            let mut trumps_left = ruffing.length_in(trumps) as usize;
+            for plan in suits.iter_mut().filter(|plan| plan.suit != trumps) {
+                let shorter_by = master.length_in(plan.suit).saturating_sub(ruffing.length_in(plan.suit)) as usize;
+                let ruffs = plan.losers.min(shorter_by).min(trumps_left);
+                if ruffs > 0 {
+                    trumps_left -= ruffs;
+                    plan.sources.push(TrickSource {
+                        suit: plan.suit,
+                        kind: SourceKind::Ruff { ruffing_hand },
+                        tricks: ruffs,
+                        tricks_given_up: 0,
+                        probability: 1.0,
+                    });
+                }
+            }
+        }
+
+        let entry_problems = suits
+            .iter()
+            .filter_map(|plan| {
+                let long_hand = plan.long_hand(declarer)?;
+                let entries = suits
+                    .iter()
+                    .filter(|other| other.suit != plan.suit)
+                    .map(|other| other.winners_in(long_hand == declarer))
+                    .sum::<usize>();
+                match entries {
+                    0 => Some(EntryProblem {
+                        suit: plan.suit,
+                        long_hand,
+                    }),
+                    _ => None,
+                }
+            })
+            .collect_vec();
+
+        DeclarerPlan {
+            contract,
+            master_hand,
+            suits,
+            entry_problems,
+        }
+    }
+
+    pub fn needed_tricks(&self) -> usize {
+        self.contract.expected_tricks()
+    }
+
+    pub fn sure_winners(&self) -> usize {
+        self.suits.iter().map(|plan| plan.sure_winners).sum()
+    }
+
+    pub fn losers(&self) -> usize {
+        self.suits.iter().map(|plan| plan.losers).sum()
+    }
+
+    // winners short of the tricks needed in no-trump, losers above the affordable ones in a suit contract
+    pub fn shortfall(&self) -> usize {
+        match self.contract.trump_suit() {
+            Some(_) => self.losers().saturating_sub(13 - self.needed_tricks()),
+            None => self.needed_tricks().saturating_sub(self.sure_winners()),
+        }
+    }
+
+    // the safest first
+    pub fn sources(&self) -> Vec<&TrickSource> {
+        self.suits
+            .iter()
+            .flat_map(|plan| &plan.sources)
+            .sorted_by(|source, other| other.probability.total_cmp(&source.probability))
+            .collect_vec()
+    }
+}
+
+impl SuitPlan {
+    // the hand that takes tricks after the other has run out of cards, if it can't get there with the suit itself
+    fn long_hand(&self, declarer: Seat) -> Option<Seat> {
+        let (long, short, long_hand) = match self.declarer.len() >= self.dummy.len() {
+            true => (&self.declarer, &self.dummy, declarer),
+            false => (&self.dummy, &self.declarer, declarer.partner()),
+        };
+        let tricks = self.sure_winners
+            + self
+                .sources
+                .iter()
+                .filter(|source| !matches!(source.kind, SourceKind::Ruff { .. }))
+                .map(|source| source.tricks)
+                .sum::<usize>();
+        let blocked = match (short.last(), long.first()) {
+            (Some(lowest), Some(highest)) => lowest > highest,
+            _ => false,
+        };
+        match blocked && tricks > short.len() {
+            true => Some(long_hand),
+            false => None,
+        }
+    }
+
+    fn winners_in(&self, declarers_hand: bool) -> usize {
+        let (hand, other) = match declarers_hand {
+            true => (&self.declarer, &self.dummy),
+            false => (&self.dummy, &self.declarer),
+        };
+        let top = RANK_ARRAY
+            .iter()
+            .rev()
+            .take_while(|rank| hand.contains(rank) || other.contains(rank))
+            .filter(|rank| hand.contains(rank))
+            .count();
+        top.min(self.sure_winners)
+    }
+}
+
+struct Holding {
+    suit: Suit,
+    declarer: Vec<Rank>,
+    dummy: Vec<Rank>,
+    seats: [Seat; 2],
+}
+
+impl Holding {
+    fn longer(&self) -> usize {
+        self.declarer.len().max(self.dummy.len())
+    }
+
+    fn missing(&self) -> usize {
+        13 - self.declarer.len() - self.dummy.len()
+    }
+
+    // runs of our cards and of missing cards from the ace down, starting with ours
+    fn runs(&self) -> Vec<Vec<Rank>> {
+        let ours = |rank: &Rank| self.declarer.contains(rank) || self.dummy.contains(rank);
+        let mut runs = vec![Vec::new()];
+        for rank in RANK_ARRAY.iter().rev() {
+            if ours(rank) != (runs.len() % 2 == 1) {
+                runs.push(Vec::new());
+            }
+            runs.last_mut().unwrap().push(*rank);
+        }
+        runs
+    }
+
+    fn sure_winners(&self) -> usize {
+        self.runs()[0].len().min(self.longer())
+    }
+
+    fn sources(&self) -> Vec<TrickSource> {
+        let runs = self.runs();
+        let sure_winners = self.sure_winners();
+        let source = |kind, tricks, tricks_given_up, probability| TrickSource {
+            suit: self.suit,
+            kind,
+            tricks,
+            tricks_given_up,
+            probability,
+        };
+        let mut sources = Vec::new();
+
+        // our second run of cards, after the opponents' first
+        let (missing_above, next) = match (runs.get(1), runs.get(2)) {
+            (Some(missing), Some(next)) => (missing.len(), next.as_slice()),
+            _ => (0, &[][..]),
+        };
+        // the run's cards are shared between both hands, so no more than the longer hand's rounds are played,
+        // and the opponents win the first ones with their higher cards
+        let promoted = (sure_winners + next.len())
+            .min(self.longer())
+            .saturating_sub(sure_winners + missing_above);
+        if promoted > 0 {
+            sources.push(source(SourceKind::Promotion, promoted, missing_above, 1.0));
+        }
+
+        // once the opponents are out of cards, after as many rounds as the longer of their holdings
+        // if the suit splits evenly, all cards left are winners
+        let missing = self.missing();
+        let rounds = missing.div_ceil(2);
+        let tricks_with = |honours: usize| match self.longer() > rounds {
+            true => honours.min(rounds) + self.longer() - rounds,
+            false => honours.min(self.longer()),
+        };
+        let honour_tricks = sure_winners + promoted;
+        let with_length = tricks_with(honour_tricks);
+        if with_length > honour_tricks {
+            let odds = DistributionOdds::opponents(self.suit, missing as u8).unwrap();
+            let given_up = rounds - honour_tricks.min(rounds);
+            sources.push(source(
+                SourceKind::Length,
+                with_length - honour_tricks,
+                given_up - given_up.min(missing_above * (promoted > 0) as usize),
+                odds.split(self.suit, rounds as u8, (missing - rounds) as u8),
+            ));
+        }
+
+        // a finesse against the opponents' only card above our second run
+        if let (1, Some(&card)) = (missing_above, next.first()) {
+            let (holder, other, towards) = match self.declarer.contains(&card) {
+                true => (&self.declarer, &self.dummy, self.seats[0]),
+                false => (&self.dummy, &self.declarer, self.seats[1]),
+            };
+            let finessed = tricks_with(sure_winners + next.len());
+            let against = runs[1][0];
+            // the ace can't be caught without promoting the cards below it anyway
+            let promotes = against == Rank::Ace && promoted > 0;
+            if holder.len() >= 2 && !other.is_empty() && finessed > with_length && !promotes {
+                sources.push(source(
+                    SourceKind::Finesse { card, against, towards },
+                    finessed - with_length,
+                    0,
+                    0.5,
+                ));
+            }
+        }
+        sources
+    }
+}
+
+impl Display for DeclarerPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let declarer = self.contract.declarer;
+        writeln!(f, "{}: {} tricks needed.", self.contract, self.needed_tricks())?;
+        for plan in &self.suits {
+            write!(
+                f,
+                "{} {}: {}, {}: {}: {} sure winner(s)",
+                plan.suit,
+                declarer,
+                ranks_text(&plan.declarer),
+                declarer.partner(),
+                ranks_text(&plan.dummy),
+                plan.sure_winners
+            )?;
+            match self.contract.trump_suit() {
+                Some(_) => writeln!(f, ", {} loser(s)", plan.losers)?,
+                None => writeln!(f)?,
+            }
+        }
+        match self.contract.trump_suit() {
+            Some(_) => writeln!(
+                f,
+                "{} loser(s) in {}'s hand, {} allowed.",
+                self.losers(),
+                self.master_hand,
+                13 - self.needed_tricks()
+            )?,
+            None => writeln!(f, "{} sure winner(s).", self.sure_winners())?,
+        }
+        match self.shortfall() {
+            0 => writeln!(f, "The contract is safe on top.")?,
+            n => writeln!(f, "{} more trick(s) needed.", n)?,
+        }
+        let sources = self.sources();
+        if !sources.is_empty() {
+            writeln!(f, "Sources of extra tricks:")?;
+        }
+        for source in sources {
+            writeln!(f, "  {}", source)?;
+        }
+        for problem in &self.entry_problems {
+            writeln!(
+                f,
+                "Entry problem: {}'s {} need an entry outside the suit, and there is none.",
+                problem.long_hand, problem.suit
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for TrickSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let given_up = match self.tricks_given_up {
+            0 => String::new(),
+            n => format!(" after giving up {}", n),
+        };
+        match self.kind {
+            SourceKind::Promotion => write!(f, "{} promotion: {} trick(s){}", self.suit, self.tricks, given_up),
+            SourceKind::Length => write!(
+                f,
+                "{} length: {} trick(s){}, {:.2}%",
+                self.suit,
+                self.tricks,
+                given_up,
+                self.probability * 100.0
+            ),
+            SourceKind::Finesse { card, against, towards } => write!(
+                f,
+                "{} finesse of the {} against the {}, leading towards {}: {} trick(s), {:.2}%",
+                self.suit,
+                card,
+                against,
+                towards,
+                self.tricks,
+                self.probability * 100.0
+            ),
+            SourceKind::Ruff { ruffing_hand } => {
+                write!(f, "{} ruffs in {}: {} trick(s)", self.suit, ruffing_hand, self.tricks)
+            }
+        }
+    }
+}
+
+fn ranks_text(ranks: &[Rank]) -> String {
+    match ranks.is_empty() {
+        true => "-".into(),
+        false => ranks.iter().join(""),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DeclarerPlan, EntryProblem, SourceKind, TrickSource};
+    use crate::primitives::card::Rank;
+    use crate::primitives::deal::Seat;
+    use crate::primitives::{Contract, Hand, Suit};
+    use std::str::FromStr;
+
+    fn plan(declarer: &str, dummy: &str, contract: &str) -> DeclarerPlan {
+        DeclarerPlan::new(
+            &Hand::from_str(declarer).unwrap(),
+            &Hand::from_str(dummy).unwrap(),
+            Contract::from_str(contract).unwrap(),
+        )
+    }
+
+    fn sources_in(plan: &DeclarerPlan, suit: Suit) -> Vec<TrickSource> {
+        plan.suits
+            .iter()
+            .find(|plan| plan.suit == suit)
+            .unwrap()
+            .sources
+            .clone()
+    }
+
+    #[test]
+    fn no_trump() {
+        let plan = plan("S:AK2,H:AQ2,D:KQJ32,C:32", "S:543,H:543,D:54,C:AKQ65", "S3NT");
+
+        assert_eq!(plan.sure_winners(), 6);
+        assert_eq!(plan.shortfall(), 3);
+        assert!(plan.entry_problems.is_empty());
+
+        let diamonds = sources_in(&plan, Suit::Diamonds);
+        assert_eq!(diamonds[0].kind, SourceKind::Promotion);
+        assert_eq!((diamonds[0].tricks, diamonds[0].tricks_given_up), (2, 1));
+        assert_eq!(diamonds[1].kind, SourceKind::Length);
+        assert_eq!(diamonds[1].tricks, 2);
+
+        let hearts = sources_in(&plan, Suit::Hearts);
+        assert_eq!(
+            hearts[0].kind,
+            SourceKind::Finesse {
+                card: Rank::Queen,
+                against: Rank::King,
+                towards: Seat::South
+            }
+        );
+        assert_eq!(hearts[0].probability, 0.5);
+
+        let clubs = sources_in(&plan, Suit::Clubs);
+        assert_eq!(clubs[0].kind, SourceKind::Length);
+        assert_eq!(clubs[0].tricks, 2);
+        assert!((clubs[0].probability - 0.3553).abs() < 1e-4);
+
+        let probabilities = plan
+            .sources()
+            .iter()
+            .map(|source| source.probability)
+            .collect::<Vec<_>>();
+        assert_eq!(probabilities[0], 1.0);
+        assert!(probabilities.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[test]
+    fn blocked_suit() {
+        let plan = plan("S:AK,H:AKQ2,D:AKQ2,C:432", "S:QJ432,H:543,D:543,C:65", "S3NT");
+
+        assert_eq!(plan.suits[0].sure_winners, 4);
+        assert_eq!(
+            plan.entry_problems,
+            vec![EntryProblem {
+                suit: Suit::Spades,
+                long_hand: Seat::North
+            }]
+        );
+    }
+
+    #[test]
+    fn suit_contract() {
+        let plan = plan("S:AKQ32,H:K32,D:A32,C:32", "S:654,H:6,D:6543,C:A6543", "S4S");
+
+        assert_eq!(plan.master_hand, Seat::South);
+        assert_eq!(
+            plan.suits.iter().map(|suit| suit.losers).collect::<Vec<_>>(),
+            vec![0, 3, 2, 1]
+        );
+        assert_eq!(plan.shortfall(), 3);
+
+        let hearts = sources_in(&plan, Suit::Hearts);
+        assert!(matches!(hearts[0].kind, SourceKind::Finesse { card: Rank::King, .. }));
+        assert_eq!(
+            hearts[1].kind,
+            SourceKind::Ruff {
+                ruffing_hand: Seat::North
+            }
+        );
+        assert_eq!(hearts[1].tricks, 2);
+        assert_eq!(sources_in(&plan, Suit::Clubs)[0].kind, SourceKind::Length);
+        assert!(sources_in(&plan, Suit::Spades).is_empty());
+    }
+
+    #[test]
+    fn promotion_is_capped_by_the_longer_hand() {
+        let plan = plan("S:KQ,H:AKQ32,D:AKQ2,C:AK", "S:J,H:654,D:543,C:765432", "S3NT");
+
+        assert_eq!(
+            sources_in(&plan, Suit::Spades),
+            vec![TrickSource {
+                suit: Suit::Spades,
+                kind: SourceKind::Promotion,
+                tricks: 1,
+                tricks_given_up: 1,
+                probability: 1.0,
+            }]
+        );
+
+        let clubs = sources_in(&plan, Suit::Clubs);
+        assert_eq!(clubs.len(), 1);
+        assert_eq!(clubs[0].kind, SourceKind::Length);
+        assert_eq!((clubs[0].tricks, clubs[0].tricks_given_up), (3, 1));
+    }
+
+    #[test]
+    fn small_cards_are_losers() {
+        let plan = plan("S:AKQJ2,H:A2,D:432,C:432", "S:T543,H:K43,D:A5,C:8765", "S4S");
+
+        assert_eq!(
+            plan.suits.iter().map(|suit| suit.losers).collect::<Vec<_>>(),
+            vec![0, 0, 2, 3]
+        );
+        assert_eq!(plan.losers(), 5);
+        assert_eq!(plan.shortfall(), 2);
+    }
+
+    #[test]
+    fn display() {
+        let plan = plan("S:AK2,H:AQ2,D:KQJ32,C:32", "S:543,H:543,D:54,C:AKQ65", "S3NT");
+        let text = format!("{}", plan);
+
+        assert!(text.starts_with("3NT by S: 9 tricks needed.\n♠ S: AK2, N: 543: 2 sure winner(s)\n"));
+        assert!(text.contains("6 sure winner(s).\n3 more trick(s) needed.\n"));
+        assert!(text.contains("♦ promotion: 2 trick(s) after giving up 1\n"));
+        assert!(text.contains("♥ finesse of the Q against the K, leading towards S: 1 trick(s), 50.00%\n"));
+    }
+}
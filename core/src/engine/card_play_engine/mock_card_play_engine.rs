@@ -1,28 +1,51 @@
+use crate::engine::card_play_engine::opening_leads::{LeadAgreement, OpeningLeads};
+use crate::engine::card_play_engine::signals::Signals;
 use crate::engine::card_play_engine::SelectCard;
+use crate::engine::reason::Reason;
+use crate::engine::subjective_game_view::subjectiviser::Subjectiviser;
 use crate::engine::subjective_game_view::{SubjectiveGamePhaseStateView, SubjectiveSeat};
 use crate::game::game_phase_states::{CardPlayState, OpeningLeadState};
+use crate::primitives::card::rank::RANK_ARRAY;
+use crate::primitives::card::suit::SUIT_ARRAY;
 use crate::primitives::deal::Seat;
+use crate::primitives::game_event::ClaimEvent;
 use crate::primitives::{Card, Suit};
 use itertools::Itertools;
 
 pub struct MockCardPlayEngine {
-    _seat: Seat,
+    seat: Seat,
+    opening_leads: OpeningLeads,
+    signals: Signals,
 }
 
 impl MockCardPlayEngine {
     pub fn new(seat: Seat) -> Self {
-        Self { _seat: seat }
+        Self::with_lead_agreement(seat, LeadAgreement::default())
+    }
+
+    pub fn with_lead_agreement(seat: Seat, agreement: LeadAgreement) -> Self {
+        Self {
+            seat,
+            opening_leads: OpeningLeads::new(agreement),
+            signals: Signals::default(),
+        }
+    }
+
+    pub fn with_signals(mut self, signals: Signals) -> Self {
+        self.signals = signals;
+        self
     }
 
     fn pick_opening_lead(&self, data: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Card {
-        let hand = data.my_remaining_cards();
-        let card = hand.first().unwrap();
-        *card
+        let hand = data.my_starting_hand().unwrap();
+        let contract = data.contract().unwrap();
+        let dealer = Subjectiviser::new(self.seat).objective_seat(data.dealer());
+        self.opening_leads.find_lead(&hand, contract, dealer, &data.bids())
     }
 
-    fn pick_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card {
+    fn pick_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> (Card, &'static str) {
         match state.suit_to_follow() {
-            None => self.pick_lead(state),
+            None => (self.pick_lead(state), "Leads the lowest card of its lowest suit"),
             Some(suit) => self.pick_card_to_trick(suit, state),
         }
     }
@@ -37,7 +60,11 @@ impl MockCardPlayEngine {
         *card
     }
 
-    fn pick_card_to_trick(&self, suit: Suit, state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card {
+    fn pick_card_to_trick(
+        &self,
+        suit: Suit,
+        state: SubjectiveGamePhaseStateView<CardPlayState>,
+    ) -> (Card, &'static str) {
         let remaining_cards = match state.next_to_play() {
             SubjectiveSeat::Myself => state.my_remaining_cards(),
             SubjectiveSeat::Partner => state.dummys_remaining_cards(),
@@ -46,17 +73,83 @@ impl MockCardPlayEngine {
 
         let cards_in_suit = remaining_cards.iter().filter(|x| x.suit == suit).collect_vec();
 
+        if let Some(card) = self.pick_signal(&cards_in_suit, &remaining_cards, &state) {
+            return (card, "Signals to partner");
+        }
+
         if cards_in_suit.is_empty() {
-            self.pick_discard(&remaining_cards, state)
+            (
+                self.pick_discard(&remaining_cards, state),
+                "Discards the lowest card of its lowest suit",
+            )
         } else {
-            self.pick_card_from(&cards_in_suit, state)
+            (
+                self.pick_card_from(&cards_in_suit, state),
+                "Follows suit with its lowest card",
+            )
         }
     }
 
+    fn pick_signal(
+        &self,
+        cards_in_suit: &[&Card],
+        remaining_cards: &[Card],
+        state: &SubjectiveGamePhaseStateView<CardPlayState>,
+    ) -> Option<Card> {
+        let candidates = match cards_in_suit.is_empty() {
+            true => remaining_cards.to_vec(),
+            false => cards_in_suit.iter().map(|&&card| card).collect_vec(),
+        };
+        self.signals.choose_card(
+            &candidates,
+            &state.my_starting_hand().ok()?,
+            &state.dummys_starting_hand().ok()?,
+            state.contract()?,
+            &state.played_cards(),
+        )
+    }
+
     fn pick_discard(&self, cards: &[Card], _state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card {
         *cards.first().unwrap()
     }
 
+    fn try_claim(&self, state: &SubjectiveGamePhaseStateView<CardPlayState>) -> Option<usize> {
+        if state.suit_to_follow().is_some() {
+            return None;
+        }
+
+        let leading_hand = match state.next_to_play() {
+            SubjectiveSeat::Myself => state.my_remaining_cards(),
+            SubjectiveSeat::Partner => state.dummys_remaining_cards(),
+            _ => return None,
+        };
+
+        let played_cards = state.played_cards();
+        let outstanding_cards = SUIT_ARRAY
+            .iter()
+            .cartesian_product(RANK_ARRAY.iter())
+            .map(|(&suit, &rank)| Card { suit, rank })
+            .filter(|card| !played_cards.contains(card) && !leading_hand.contains(card))
+            .collect_vec();
+
+        let all_cards_are_winners = leading_hand.iter().all(|card| {
+            !outstanding_cards
+                .iter()
+                .any(|other| other.suit == card.suit && other.rank > card.rank)
+        });
+
+        let no_trumps_outstanding = match state.trump_suit() {
+            None => true,
+            Some(trump_suit) => !outstanding_cards.iter().any(|card| card.suit == trump_suit),
+        };
+
+        if all_cards_are_winners && no_trumps_outstanding {
+            Some(state.tricks_left())
+        } else {
+            None
+        }
+    }
+
     fn pick_card_from(&self, choices: &[&Card], _state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card {
         **choices.first().unwrap()
     }
@@ -64,12 +157,32 @@ impl MockCardPlayEngine {
 
 impl SelectCard for MockCardPlayEngine {
     fn select_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card {
-        self.pick_card(state)
+        self.pick_card(state).0
     }
 
     fn select_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Card {
         self.pick_opening_lead(state)
     }
+
+    fn select_card_with_reason(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> (Card, Reason) {
+        let (card, rule) = self.pick_card(state);
+        (card, Reason::from_rule(rule))
+    }
+
+    fn select_opening_lead_with_reason(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> (Card, Reason) {
+        (
+            self.pick_opening_lead(state),
+            Reason::from_rule("Textbook opening lead against the contract and auction"),
+        )
+    }
+
+    fn claim(&self, state: &SubjectiveGamePhaseStateView<CardPlayState>) -> Option<usize> {
+        self.try_claim(state)
+    }
+
+    fn respond_to_claim(&self, _state: &SubjectiveGamePhaseStateView<CardPlayState>, _claim: ClaimEvent) -> bool {
+        true
+    }
 }
 
 impl Default for MockCardPlayEngine {
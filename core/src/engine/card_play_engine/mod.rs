@@ -1,11 +1,36 @@
+use crate::engine::reason::Reason;
 use crate::engine::subjective_game_view::SubjectiveGamePhaseStateView;
 use crate::game::game_phase_states::{CardPlayState, OpeningLeadState};
+use crate::primitives::game_event::ClaimEvent;
 use crate::primitives::Card;
 
+pub mod declarer_plan;
 pub mod mock_card_play_engine;
+pub mod opening_leads;
+pub mod signals;
 
-pub trait SelectCard {
-    fn select_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card;
+pub trait SelectCard<const N: usize = 13> {
+    fn select_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState<N>>) -> Card;
 
-    fn select_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Card;
+    fn select_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState<N>>) -> Card;
+
+    fn select_card_with_reason(&self, state: SubjectiveGamePhaseStateView<CardPlayState<N>>) -> (Card, Reason) {
+        (self.select_card(state), Reason::default())
+    }
+
+    fn select_opening_lead_with_reason(
+        &self,
+        state: SubjectiveGamePhaseStateView<OpeningLeadState<N>>,
+    ) -> (Card, Reason) {
+        (self.select_opening_lead(state), Reason::default())
+    }
+
+    // claiming no tricks concedes
+    fn claim(&self, _state: &SubjectiveGamePhaseStateView<CardPlayState<N>>) -> Option<usize> {
+        None
+    }
+
+    fn respond_to_claim(&self, _state: &SubjectiveGamePhaseStateView<CardPlayState<N>>, _claim: ClaimEvent) -> bool {
+        false
+    }
 }
@@ -0,0 +1,225 @@
+use crate::engine::hand_evaluation::ForumDPlus2015Evaluator;
+use crate::primitives::bid::Bid;
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::card::Rank;
+use crate::primitives::contract::{Level, State, Strain};
+use crate::primitives::deal::Seat;
+use crate::primitives::{Card, Contract, Hand, Suit};
+use itertools::Itertools;
+use strum::{Display, EnumString};
+
+// the card led from a suit headed by an honor that isn't part of a sequence
+#[derive(Debug, Default, Display, EnumString, Clone, Copy, PartialEq, Eq)]
+pub enum LeadAgreement {
+    #[default]
+    #[strum(serialize = "4th")]
+    FourthBest,
+    // third highest from an even number of cards, fifth highest from an odd number
+    #[strum(serialize = "3rd5th")]
+    ThirdAndFifth,
+}
+
+// partner's suit first; against no-trump our longest and strongest suit, preferably unbid;
+// against a suit contract sequences and singletons, never away from an ace
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OpeningLeads {
+    agreement: LeadAgreement,
+}
+
+struct Auction {
+    partners_suits: Vec<Suit>,
+    declarers_suits: Vec<Suit>,
+    dummys_suits: Vec<Suit>,
+}
+
+impl Auction {
+    fn new(contract: Contract, dealer: Seat, bids: &[Bid]) -> Self {
+        let suits_bid_by = |player: Seat| {
+            bids.iter()
+                .enumerate()
+                .filter(|&(index, _)| dealer + index == player)
+                .filter_map(|(_, bid)| match bid {
+                    Bid::Contract(contract_bid) => match contract_bid.strain {
+                        Strain::Trump(suit) => Some(suit),
+                        Strain::NoTrump => None,
+                    },
+                    Bid::Auxiliary(_) => None,
+                })
+                .unique()
+                .collect_vec()
+        };
+        let declarer = contract.declarer;
+        Auction {
+            partners_suits: suits_bid_by(declarer + 3),
+            declarers_suits: suits_bid_by(declarer),
+            dummys_suits: suits_bid_by(declarer + 2),
+        }
+    }
+
+    fn opponents_bid(&self, suit: Suit) -> bool {
+        self.declarers_suits.contains(&suit) || self.dummys_suits.contains(&suit)
+    }
+}
+
+impl OpeningLeads {
+    pub fn new(agreement: LeadAgreement) -> Self {
+        OpeningLeads { agreement }
+    }
+
+    pub fn find_lead(&self, hand: &Hand<13>, contract: Contract, dealer: Seat, bids: &[Bid]) -> Card {
+        let auction = Auction::new(contract, dealer, bids);
+        match contract.strain {
+            Strain::Trump(trump_suit) if Self::lead_trumps(hand, trump_suit, contract, &auction) => {
+                *hand.cards_in(trump_suit).next().unwrap()
+            }
+            strain => {
+                let suit = SUIT_ARRAY
+                    .into_iter()
+                    .filter(|&suit| hand.length_in(suit) > 0)
+                    .max_by_key(|&suit| {
+                        let score = match strain {
+                            Strain::NoTrump => Self::no_trump_score(hand, suit, &auction),
+                            Strain::Trump(trump_suit) => Self::suit_contract_score(hand, suit, trump_suit, &auction),
+                        };
+                        (score, hand.length_in(suit))
+                    })
+                    .unwrap();
+                self.card_to_lead(hand, suit, strain)
+            }
+        }
+    }
+
+    // after a penalty pass of our double on a low level, or when dummy prefers declarer's second suit
+    fn lead_trumps(hand: &Hand<13>, trump_suit: Suit, contract: Contract, auction: &Auction) -> bool {
+        let trumps = hand.cards_in(trump_suit).rev().collect_vec();
+        let may_lose_an_honor = trumps.len() == 2 && trumps[0].rank >= Rank::Jack;
+        if trumps.len() < 2 || may_lose_an_honor {
+            return false;
+        }
+        let penalty_pass = contract.state == State::Doubled && contract.level <= Level::Two;
+        let declarer_has_two_suits = auction.declarers_suits.contains(&trump_suit)
+            && auction.declarers_suits.iter().any(|&suit| suit != trump_suit)
+            && !auction.dummys_suits.contains(&trump_suit);
+        penalty_pass || declarer_has_two_suits
+    }
+
+    fn no_trump_score(hand: &Hand<13>, suit: Suit, auction: &Auction) -> isize {
+        let cards = hand.cards_in(suit).rev().collect_vec();
+        if auction.partners_suits.contains(&suit) {
+            return 100;
+        }
+        let mut score = 10 * cards.len() as isize + ForumDPlus2015Evaluator::hcp_in(suit, hand) as isize;
+        if Self::is_sequence(&cards, Strain::NoTrump) {
+            score += 15;
+        }
+        if auction.opponents_bid(suit) {
+            score -= 40;
+        }
+        score
+    }
+
+    fn suit_contract_score(hand: &Hand<13>, suit: Suit, trump_suit: Suit, auction: &Auction) -> isize {
+        if suit == trump_suit {
+            return 0;
+        }
+        if auction.partners_suits.contains(&suit) {
+            return 100;
+        }
+        let cards = hand.cards_in(suit).rev().collect_vec();
+        let trumps = hand.length_in(trump_suit);
+        let top = cards[0].rank;
+        let score = match cards.len() {
+            _ if Self::is_sequence(&cards, Strain::Trump(trump_suit)) && top >= Rank::King => 80,
+            _ if Self::is_sequence(&cards, Strain::Trump(trump_suit)) => 70,
+            1 if top < Rank::Jack && (1..=3).contains(&trumps) => 65,
+            _ if top == Rank::Ace => 10,
+            2 if top >= Rank::Jack => 20,
+            1 => 15,
+            _ if top >= Rank::Jack => 50,
+            _ => 45,
+        };
+        match auction.opponents_bid(suit) {
+            true => score - 30,
+            false => score,
+        }
+    }
+
+    // two touching honors against a suit contract, three against no-trump, where the third may be a gap below
+    fn is_sequence(cards: &[&Card], strain: Strain) -> bool {
+        let touching = |high: &Card, low: &Card| high.rank as usize == low.rank as usize + 1;
+        if cards.len() < 2 || cards[0].rank < Rank::Ten || !touching(cards[0], cards[1]) {
+            return false;
+        }
+        match strain {
+            Strain::Trump(_) => true,
+            Strain::NoTrump => cards.len() >= 3 && cards[1].rank as usize - cards[2].rank as usize <= 2,
+        }
+    }
+
+    fn card_to_lead(&self, hand: &Hand<13>, suit: Suit, strain: Strain) -> Card {
+        let cards = hand.cards_in(suit).rev().collect_vec();
+        let top = cards[0].rank;
+        let index = match cards.len() {
+            1 => 0,
+            // we don't underlead aces against suit contracts
+            _ if top == Rank::Ace && strain != Strain::NoTrump => 0,
+            _ if Self::is_sequence(&cards, strain) => 0,
+            2 => 0,
+            // top of nothing
+            3 if top < Rank::Jack => 0,
+            _ if top < Rank::Jack => 1,
+            3 => 2,
+            length => match self.agreement {
+                LeadAgreement::FourthBest => 3,
+                LeadAgreement::ThirdAndFifth if length.is_multiple_of(2) => 2,
+                LeadAgreement::ThirdAndFifth => 4,
+            },
+        };
+        *cards[index]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LeadAgreement, OpeningLeads};
+    use crate::primitives::bid::Bid;
+    use crate::primitives::deal::Seat;
+    use crate::primitives::{Card, Contract, Hand};
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    #[test_case("S:KQJ74,H:853,D:K92,C:64", "S3NT", "1NT p 3NT p p p", "SK"; "Top of a sequence")]
+    #[test_case("S:KQT74,H:853,D:K92,C:64", "S3NT", "1NT p 3NT p p p", "SK"; "Top of a broken sequence")]
+    #[test_case("S:KT742,H:853,D:K92,C:64", "S3NT", "1NT p 3NT p p p", "S4"; "Fourth best")]
+    #[test_case("S:A853,H:KT742,D:92,C:64", "S3NT", "1H p 1NT p 3NT p p p", "S3"; "Not the opponents' suit")]
+    #[test_case("S:KT74,H:8532,D:Q92,C:64", "S3NT", "p p p 1D 1NT p 3NT p p p", "D2"; "Partner's suit")]
+    #[test_case("S:AK73,H:853,D:Q92,C:642", "S4H", "1H p 4H p p p", "SA"; "Ace from ace-king")]
+    #[test_case("S:A873,H:853,D:Q92,C:642", "S4H", "1H p 4H p p p", "D2"; "No unsupported ace")]
+    #[test_case("S:A8732,H:853,D:Q942,C:6", "S4H", "1H p 4H p p p", "C6"; "Singleton")]
+    #[test_case("S:A873,H:K53,D:942,C:Q42", "S4H", "1H p 2H 2S 4H p p p", "SA"; "Partner's suit against a suit contract")]
+    #[test_case("S:A873,H:853,D:942,C:A42", "S4H", "1H p 4H p p p", "D9"; "Top of nothing")]
+    #[test_case("S:K873,H:853,D:A942,C:Q4", "S2H", "1S p 1NT p 2H p p p", "H3"; "Trumps after preference")]
+    #[test_case("S:K873,H:Q5,D:A942,C:Q42", "S1HX", "1H X p p p", "S3"; "Penalty pass, but honor doubleton")]
+    #[test_case("S:KJ93,H:A653,D:A94,C:Q4", "S1HX", "1H X p p p", "H3"; "Penalty pass")]
+    fn find_lead(hand: &str, contract: &str, bids: &str, expected: &str) {
+        let hand = Hand::from_str(hand).unwrap();
+        let contract = Contract::from_str(contract).unwrap();
+        let bids = bids
+            .split(' ')
+            .map(|bid| Bid::from_str(bid).unwrap())
+            .collect::<Vec<_>>();
+        let lead = OpeningLeads::default().find_lead(&hand, contract, Seat::South, &bids);
+        assert_eq!(lead, Card::from_str(expected).unwrap());
+    }
+
+    #[test_case("S:KT742,H:853,D:K92,C:64", "S2"; "Fifth from five")]
+    #[test_case("S:KT74,H:853,D:K962,C:64", "S7"; "Third from four")]
+    #[test_case("S:K74,H:8532,D:KT92,C:64", "D9"; "Third from four in the longest suit")]
+    fn third_and_fifth(hand: &str, expected: &str) {
+        let hand = Hand::from_str(hand).unwrap();
+        let contract = Contract::from_str("S3NT").unwrap();
+        let bids = ["1NT", "p", "3NT", "p", "p", "p"].map(|bid| Bid::from_str(bid).unwrap());
+        let lead = OpeningLeads::new(LeadAgreement::ThirdAndFifth).find_lead(&hand, contract, Seat::South, &bids);
+        assert_eq!(lead, Card::from_str(expected).unwrap());
+    }
+}
@@ -0,0 +1,421 @@
+use crate::engine::hand_evaluation::ForumDPlus2015Evaluator;
+use crate::game::trick_manager::TrickManager;
+use crate::primitives::card::rank::RANK_ARRAY;
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::card::Rank;
+use crate::primitives::contract::Strain;
+use crate::primitives::trick::Trick;
+use crate::primitives::{Card, Contract, Hand, Suit};
+use itertools::Itertools;
+use std::cmp::Reverse;
+use strum::{Display, EnumString};
+
+#[derive(Debug, Default, Display, EnumString, Clone, Copy, PartialEq, Eq)]
+pub enum Carding {
+    // high encourages and shows an even number of cards
+    #[default]
+    #[strum(serialize = "standard")]
+    Standard,
+    // low encourages and shows an even number of cards
+    #[strum(serialize = "upside-down")]
+    UpsideDown,
+}
+
+#[derive(Debug, Default, Display, EnumString, Clone, Copy, PartialEq, Eq)]
+pub enum Discards {
+    #[default]
+    #[strum(serialize = "attitude")]
+    Attitude,
+    // an odd card encourages the suit, an even card discourages it and asks for the higher or the lower other suit
+    #[strum(serialize = "odd-even")]
+    OddEven,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    // holds the ace, king or queen of the suit
+    Encouraging(Suit),
+    Discouraging(Suit),
+    EvenCount(Suit),
+    OddCount(Suit),
+    // at least as many hcp in the first suit as in the second
+    SuitPreference(Suit, Suit),
+}
+
+impl Signal {
+    pub fn admits(&self, hand: &Hand<13>) -> bool {
+        match *self {
+            Signal::Encouraging(suit) => has_top_honor(hand, suit),
+            Signal::Discouraging(suit) => !has_top_honor(hand, suit),
+            Signal::EvenCount(suit) => hand.length_in(suit).is_multiple_of(2),
+            Signal::OddCount(suit) => !hand.length_in(suit).is_multiple_of(2),
+            Signal::SuitPreference(preferred, other) => hcp_in(hand, preferred) >= hcp_in(hand, other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Occasion {
+    Attitude(Suit),
+    Count(Suit),
+    // partner leads a suit dummy ruffs, asking for the higher or the lower of the other side suits
+    SuitPreference(Suit, Suit),
+    Discard,
+}
+
+// attitude on partner's lead of a new suit, count on declarer's, and the first discard;
+// only spot cards up to the nine carry a signal
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Signals {
+    carding: Carding,
+    discards: Discards,
+}
+
+impl Signals {
+    pub fn new(carding: Carding, discards: Discards) -> Self {
+        Signals { carding, discards }
+    }
+
+    // `None` if the next card carries no signal
+    pub fn choose_card(
+        &self,
+        candidates: &[Card],
+        hand: &Hand<13>,
+        dummy: &Hand<13>,
+        contract: Contract,
+        played_cards: &[Card],
+    ) -> Option<Card> {
+        let trick_manager = replay(contract, played_cards);
+        let suit_led = trick_manager.suit_to_follow()?;
+        let following = candidates.iter().any(|card| card.suit == suit_led);
+        let spots = candidates
+            .iter()
+            .copied()
+            .filter(|card| card.rank <= Rank::Nine && Some(card.suit) != trick_manager.trump_suit())
+            .sorted_by_key(|card| card.rank)
+            .collect_vec();
+        match occasion(&trick_manager, contract, dummy, following)? {
+            Occasion::Attitude(suit) => high_or_low(&spots, self.high_for(has_top_honor(hand, suit))),
+            Occasion::Count(suit) => high_or_low(&spots, self.high_for(hand.length_in(suit).is_multiple_of(2))),
+            Occasion::SuitPreference(higher, lower) => high_or_low(&spots, hcp_in(hand, higher) > hcp_in(hand, lower)),
+            Occasion::Discard => self.choose_discard(&spots, hand, suit_led, trick_manager.trump_suit()),
+        }
+    }
+
+    // judged against the cards partner has `seen`
+    pub fn read(&self, played_cards: &[Card], contract: Contract, dummy: &Hand<13>, seen: &[Card]) -> Vec<Signal> {
+        let Some((&card, before)) = played_cards.split_last() else {
+            return Vec::new();
+        };
+        let trick_manager = replay(contract, before);
+        let following = trick_manager.suit_to_follow() == Some(card.suit);
+        if card.rank > Rank::Nine || Some(card.suit) == trick_manager.trump_suit() && !following {
+            return Vec::new();
+        }
+        let Some(occasion) = occasion(&trick_manager, contract, dummy, following) else {
+            return Vec::new();
+        };
+        let high = is_high(card, seen);
+        match occasion {
+            Occasion::Attitude(suit) => high
+                .map(|high| match self.yes_for(high) {
+                    true => Signal::Encouraging(suit),
+                    false => Signal::Discouraging(suit),
+                })
+                .into_iter()
+                .collect(),
+            Occasion::Count(suit) => high
+                .map(|high| match self.yes_for(high) {
+                    true => Signal::EvenCount(suit),
+                    false => Signal::OddCount(suit),
+                })
+                .into_iter()
+                .collect(),
+            Occasion::SuitPreference(higher, lower) => high
+                .map(|high| match high {
+                    true => Signal::SuitPreference(higher, lower),
+                    false => Signal::SuitPreference(lower, higher),
+                })
+                .into_iter()
+                .collect(),
+            Occasion::Discard => self.read_discard(
+                card,
+                high,
+                trick_manager.suit_to_follow().unwrap(),
+                trick_manager.trump_suit(),
+            ),
+        }
+    }
+
+    fn choose_discard(&self, spots: &[Card], hand: &Hand<13>, suit_led: Suit, trumps: Option<Suit>) -> Option<Card> {
+        let (wanted, unwanted): (Vec<Card>, Vec<Card>) = spots.iter().partition(|card| has_top_honor(hand, card.suit));
+        let best_suit = |cards: &[Card]| {
+            cards
+                .iter()
+                .map(|card| card.suit)
+                .max_by_key(|&suit| hcp_in(hand, suit))
+        };
+        let longest_suit = |cards: &[Card]| {
+            cards
+                .iter()
+                .map(|card| card.suit)
+                .max_by_key(|&suit| hand.length_in(suit))
+        };
+        let in_suit = |cards: &[Card], suit: Suit| cards.iter().copied().filter(|card| card.suit == suit).collect_vec();
+        match self.discards {
+            Discards::Attitude => match best_suit(&wanted) {
+                Some(suit) => high_or_low(&in_suit(&wanted, suit), self.high_for(true)),
+                None => {
+                    let suit = longest_suit(&unwanted)?;
+                    high_or_low(&in_suit(&unwanted, suit), self.high_for(false))
+                }
+            },
+            Discards::OddEven => {
+                let odd = wanted.iter().filter(|card| is_odd(card));
+                if let Some(&odd) = odd.max_by_key(|card| (hcp_in(hand, card.suit), card.rank)) {
+                    return Some(odd);
+                }
+                let evens = unwanted.into_iter().filter(|card| !is_odd(card)).collect_vec();
+                let suit = longest_suit(&evens)?;
+                let high = match &other_suits(&[suit, suit_led], trumps)[..] {
+                    &[higher, lower] => hcp_in(hand, higher) > hcp_in(hand, lower),
+                    _ => false,
+                };
+                high_or_low(&in_suit(&evens, suit), high)
+            }
+        }
+    }
+
+    fn read_discard(&self, card: Card, high: Option<bool>, suit_led: Suit, trumps: Option<Suit>) -> Vec<Signal> {
+        match self.discards {
+            Discards::Attitude => high
+                .map(|high| match self.yes_for(high) {
+                    true => Signal::Encouraging(card.suit),
+                    false => Signal::Discouraging(card.suit),
+                })
+                .into_iter()
+                .collect(),
+            Discards::OddEven if is_odd(&card) => vec![Signal::Encouraging(card.suit)],
+            Discards::OddEven => {
+                let mut signals = vec![Signal::Discouraging(card.suit)];
+                if let (&[higher, lower], Some(high)) = (&other_suits(&[card.suit, suit_led], trumps)[..], high) {
+                    signals.push(match high {
+                        true => Signal::SuitPreference(higher, lower),
+                        false => Signal::SuitPreference(lower, higher),
+                    });
+                }
+                signals
+            }
+        }
+    }
+
+    fn high_for(&self, yes: bool) -> bool {
+        match self.carding {
+            Carding::Standard => yes,
+            Carding::UpsideDown => !yes,
+        }
+    }
+
+    fn yes_for(&self, high: bool) -> bool {
+        // the carding swaps both ways
+        self.high_for(high)
+    }
+}
+
+// led to by declarer's left-hand opponent
+fn replay(contract: Contract, played_cards: &[Card]) -> TrickManager<13> {
+    let trumps = match contract.strain {
+        Strain::Trump(suit) => Some(suit),
+        Strain::NoTrump => None,
+    };
+    let mut trick_manager = TrickManager::new(contract.declarer + 1, trumps);
+    for &card in played_cards {
+        trick_manager.play(card);
+    }
+    trick_manager
+}
+
+fn occasion(
+    trick_manager: &TrickManager<13>,
+    contract: Contract,
+    dummy: &Hand<13>,
+    following: bool,
+) -> Option<Occasion> {
+    let player = trick_manager.next_to_play();
+    if player.same_axis(&contract.declarer) {
+        return None;
+    }
+    let suit_led = trick_manager.suit_to_follow()?;
+    let trumps = trick_manager.trump_suit();
+    let played_tricks = trick_manager.played_tricks();
+    if !following {
+        let discarded_before = played_tricks.iter().any(|trick| {
+            let led = trick.cards()[0].suit;
+            let card = trick.cards()[(0..4).find(|&offset| trick.lead() + offset == player).unwrap()];
+            card.suit != led && Some(card.suit) != trumps
+        });
+        return (!discarded_before).then_some(Occasion::Discard);
+    }
+    if played_tricks.iter().any(|trick| trick.cards()[0].suit == suit_led) {
+        return None;
+    }
+    if trick_manager.trick_leader() != player.partner() {
+        return Some(Occasion::Count(suit_led));
+    }
+    match (trumps, &other_suits(&[suit_led], trumps)[..]) {
+        (Some(_), &[higher, lower]) if dummy.length_in(suit_led) == 0 => Some(Occasion::SuitPreference(higher, lower)),
+        _ => Some(Occasion::Attitude(suit_led)),
+    }
+}
+
+fn other_suits(excluded: &[Suit], trumps: Option<Suit>) -> Vec<Suit> {
+    SUIT_ARRAY
+        .into_iter()
+        .filter(|suit| !excluded.contains(suit) && Some(*suit) != trumps)
+        .sorted_by_key(|&suit| Reverse(suit))
+        .collect()
+}
+
+// `spots` are sorted from low to high
+fn high_or_low(spots: &[Card], high: bool) -> Option<Card> {
+    match high {
+        true => spots.last().copied(),
+        false => spots.first().copied(),
+    }
+}
+
+// whether clearly more of the spot cards partner can't see are below `card` than above, or the other way round
+fn is_high(card: Card, seen: &[Card]) -> Option<bool> {
+    let unseen = RANK_ARRAY
+        .into_iter()
+        .filter(|&rank| rank <= Rank::Nine && rank != card.rank)
+        .filter(|&rank| !seen.contains(&Card { suit: card.suit, rank }))
+        .collect_vec();
+    let below = unseen.iter().filter(|&&rank| rank < card.rank).count();
+    let above = unseen.len() - below;
+    match (below, above) {
+        (below, above) if below > above + 1 => Some(true),
+        (below, above) if above > below + 1 => Some(false),
+        _ => None,
+    }
+}
+
+fn is_odd(card: &Card) -> bool {
+    // the two comes first
+    !(card.rank as usize).is_multiple_of(2)
+}
+
+fn has_top_honor(hand: &Hand<13>, suit: Suit) -> bool {
+    hand.cards_in(suit).any(|card| card.rank >= Rank::Queen)
+}
+
+fn hcp_in(hand: &Hand<13>, suit: Suit) -> usize {
+    ForumDPlus2015Evaluator::hcp_in(suit, hand) as usize
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Carding, Discards, Signal, Signals};
+    use crate::primitives::{Card, Contract, Hand, Suit};
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    // 4♠ by South, so West leads and East is third hand
+    const DUMMY: &str = "S:A32,H:KQJ4,D:K32,C:432";
+
+    fn cards(cards: &str) -> Vec<Card> {
+        cards.split(' ').map(|card| Card::from_str(card).unwrap()).collect()
+    }
+
+    #[test_case(Carding::Standard, "S:K432,H:A87,D:9542,C:65", "H2 H4", "H8"; "High encourages")]
+    #[test_case(Carding::Standard, "S:K432,H:987,D:A542,C:65", "H2 H4", "H7"; "Low discourages")]
+    #[test_case(Carding::UpsideDown, "S:K432,H:A87,D:9542,C:65", "H2 H4", "H7"; "Upside-down low encourages")]
+    #[test_case(Carding::Standard, "S:K432,H:87,D:AJ542,C:65", "C7 C4 C5 CA H3 H5 HK", "H8"; "High shows even")]
+    #[test_case(Carding::UpsideDown, "S:K432,H:87,D:AJ542,C:65", "C7 C4 C5 CA H3 H5 HK", "H7"; "Upside-down low shows even")]
+    #[test_case(Carding::Standard, "S:K432,H:876,D:AJ54,C:65", "C7 C4 C5 CA H3 H5 HK", "H6"; "Low shows odd")]
+    fn choose_card(carding: Carding, hand: &str, played: &str, expected: &str) {
+        let signals = Signals::new(carding, Discards::Attitude);
+        let contract = Contract::from_str("S4S").unwrap();
+        let hand = Hand::from_str(hand).unwrap();
+        let dummy = Hand::from_str(DUMMY).unwrap();
+        let candidates = hand.cards_in(Suit::Hearts).copied().collect::<Vec<_>>();
+        let card = signals.choose_card(&candidates, &hand, &dummy, contract, &cards(played));
+        assert_eq!(card, Some(Card::from_str(expected).unwrap()));
+    }
+
+    #[test]
+    fn suit_preference_when_dummy_ruffs() {
+        let signals = Signals::new(Carding::UpsideDown, Discards::Attitude);
+        let contract = Contract::from_str("S4S").unwrap();
+        let hand = Hand::from_str("S:K4,H:A8762,D:9542,C:Q7").unwrap();
+        let dummy = Hand::from_str("S:AJ32,H:KQJ4,D:,C:65432").unwrap();
+        let card = signals.choose_card(&cards("D9 D5 D4 D2"), &hand, &dummy, contract, &cards("DA S2"));
+        assert_eq!(card, Some(Card::from_str("D9").unwrap()));
+    }
+
+    #[test_case("H2 H4 HA"; "Declarer does not signal")]
+    #[test_case("H2 H4 HA H3 H8 H9"; "Only on the first round")]
+    fn no_signal(played: &str) {
+        let signals = Signals::default();
+        let contract = Contract::from_str("S4S").unwrap();
+        let hand = Hand::from_str("S:K432,H:T762,D:954,C:65").unwrap();
+        let dummy = Hand::from_str(DUMMY).unwrap();
+        let card = signals.choose_card(&cards("H7 H6"), &hand, &dummy, contract, &cards(played));
+        assert_eq!(card, None);
+    }
+
+    #[test_case(Discards::Attitude, "S:4,H:A8762,D:95,C:AQ653", "C6"; "High in the suit wanted")]
+    #[test_case(Discards::OddEven, "S:4,H:A8762,D:95,C:AQ653", "C5"; "Odd in the suit wanted")]
+    #[test_case(Discards::OddEven, "S:4,H:98654,D:975,C:AKQ2", "H4"; "Low even for the lower suit")]
+    fn choose_discard(discards: Discards, hand: &str, expected: &str) {
+        let signals = Signals::new(Carding::Standard, discards);
+        let contract = Contract::from_str("S3NT").unwrap();
+        let hand = Hand::from_str(hand).unwrap();
+        let dummy = Hand::from_str(DUMMY).unwrap();
+        // dummy runs spades and East shows out on the second round
+        let played = cards("D7 DK D5 D6 SA S4 S5 S6 S2");
+        let candidates = hand
+            .cards()
+            .filter(|card| !played.contains(card))
+            .copied()
+            .collect::<Vec<_>>();
+        let card = signals.choose_card(&candidates, &hand, &dummy, contract, &played);
+        assert_eq!(card, Some(Card::from_str(expected).unwrap()));
+    }
+
+    #[test_case(Carding::Standard, "H8", &[Signal::Encouraging(Suit::Hearts)]; "High encourages")]
+    #[test_case(Carding::Standard, "H3", &[Signal::Discouraging(Suit::Hearts)]; "Low discourages")]
+    #[test_case(Carding::UpsideDown, "H3", &[Signal::Encouraging(Suit::Hearts)]; "Upside-down low encourages")]
+    #[test_case(Carding::Standard, "H7", &[]; "Neither high nor low")]
+    #[test_case(Carding::Standard, "HT", &[]; "Not a spot card")]
+    fn read(carding: Carding, card: &str, expected: &[Signal]) {
+        let signals = Signals::new(carding, Discards::Attitude);
+        let contract = Contract::from_str("S4S").unwrap();
+        let dummy = Hand::from_str(DUMMY).unwrap();
+        let played = cards(&format!("HA H4 {}", card));
+        let seen = cards("HA H4 H2 H5");
+        assert_eq!(signals.read(&played, contract, &dummy, &seen), expected);
+    }
+
+    #[test_case("C5", &[Signal::Encouraging(Suit::Clubs)]; "Odd encourages")]
+    #[test_case("H8", &[Signal::Discouraging(Suit::Hearts), Signal::SuitPreference(Suit::Diamonds, Suit::Clubs)]; "High even asks for the higher suit")]
+    #[test_case("H3", &[Signal::Encouraging(Suit::Hearts)]; "Odd, even though low")]
+    fn read_odd_even_discard(card: &str, expected: &[Signal]) {
+        let signals = Signals::new(Carding::Standard, Discards::OddEven);
+        let contract = Contract::from_str("S3NT").unwrap();
+        let dummy = Hand::from_str(DUMMY).unwrap();
+        let played = cards(&format!("D7 DK D5 D6 SA S4 S5 S6 S2 {}", card));
+        assert_eq!(signals.read(&played, contract, &dummy, &played), expected);
+    }
+
+    #[test_case(Signal::Encouraging(Suit::Clubs), true; "Encouraging")]
+    #[test_case(Signal::Discouraging(Suit::Hearts), false; "Discouraging")]
+    #[test_case(Signal::OddCount(Suit::Hearts), true; "Odd count")]
+    #[test_case(Signal::EvenCount(Suit::Diamonds), true; "Even count")]
+    #[test_case(Signal::SuitPreference(Suit::Clubs, Suit::Hearts), true; "Suit preference")]
+    #[test_case(Signal::SuitPreference(Suit::Diamonds, Suit::Hearts), false; "Wrong suit preference")]
+    fn admits(signal: Signal, expected: bool) {
+        let hand = Hand::from_str("S:K4,H:A8762,D:95,C:AQ65").unwrap();
+        assert_eq!(signal.admits(&hand), expected);
+    }
+}
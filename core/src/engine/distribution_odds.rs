@@ -0,0 +1,217 @@
+use crate::error::BBError;
+use crate::impossible_book::helper::choose;
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::{Card, Suit};
+use itertools::Itertools;
+use std::collections::BTreeSet;
+
+// exact odds for one hidden hand: every deal of the unseen cards that fills the vacant places is equally likely
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DistributionOdds {
+    unseen: [u8; 4],
+    known: [u8; 4],
+    vacant_places: u8,
+}
+
+impl DistributionOdds {
+    pub fn new(unseen: [u8; 4], known: [u8; 4], vacant_places: u8) -> Result<Self, BBError> {
+        let total_unseen = unseen.iter().sum::<u8>();
+        let total_known = known.iter().sum::<u8>();
+        match vacant_places <= total_unseen && total_known + vacant_places <= 13 {
+            true => Ok(DistributionOdds {
+                unseen,
+                known,
+                vacant_places,
+            }),
+            false => Err(BBError::InsufficientInfo),
+        }
+    }
+
+    // before the opening lead; only questions about `suit` make sense
+    pub fn opponents(suit: Suit, missing: u8) -> Result<Self, BBError> {
+        if missing > 13 {
+            return Err(BBError::InsufficientInfo);
+        }
+        let mut unseen = [0; 4];
+        unseen[suit as usize] = missing;
+        let mut others = 26 - missing;
+        for other in SUIT_ARRAY.iter().filter(|&&other| other != suit) {
+            unseen[*other as usize] = others.min(13);
+            others -= unseen[*other as usize];
+        }
+        Self::new(unseen, [0; 4], 13)
+    }
+
+    // the `seen` cards are elsewhere, the rest is shared with `other_vacant_places` in the other hidden hands
+    pub fn for_hand(known: &[Card], seen: &[Card], other_vacant_places: u8) -> Result<Self, BBError> {
+        let mut unseen = [13u8; 4];
+        let mut known_per_suit = [0u8; 4];
+        for card in known.iter().chain(seen).collect::<BTreeSet<_>>() {
+            unseen[card.suit as usize] -= 1;
+        }
+        for card in known.iter().collect::<BTreeSet<_>>() {
+            known_per_suit[card.suit as usize] += 1;
+        }
+        let vacant_places = 13u8
+            .checked_sub(known_per_suit.iter().sum())
+            .ok_or(BBError::InsufficientInfo)?;
+        let places = vacant_places
+            .checked_add(other_vacant_places)
+            .ok_or(BBError::InsufficientInfo)?;
+        match places == unseen.iter().sum::<u8>() {
+            true => Self::new(unseen, known_per_suit, vacant_places),
+            false => Err(BBError::InsufficientInfo),
+        }
+    }
+
+    fn total_unseen(&self) -> u8 {
+        self.unseen.iter().sum()
+    }
+
+    fn deals(&self) -> f64 {
+        choose(self.total_unseen(), self.vacant_places) as f64
+    }
+
+    pub fn unseen_length(&self, suit: Suit, length: u8) -> f64 {
+        self.holding(suit, length, 0)
+    }
+
+    // `particular` of them named in advance, e.g. the queen
+    pub fn holding(&self, suit: Suit, length: u8, particular: u8) -> f64 {
+        let in_suit = self.unseen[suit as usize];
+        if length < particular || particular > in_suit || length > self.vacant_places {
+            return 0.0;
+        }
+        let holdings = choose(in_suit - particular, length - particular);
+        let others = choose(self.total_unseen() - in_suit, self.vacant_places - length);
+        (holdings * others) as f64 / self.deals()
+    }
+
+    // either way, e.g. 3-2 with five cards missing
+    pub fn split(&self, suit: Suit, longer: u8, shorter: u8) -> f64 {
+        if longer + shorter != self.unseen[suit as usize] {
+            return 0.0;
+        }
+        match longer == shorter {
+            true => self.unseen_length(suit, longer),
+            false => self.unseen_length(suit, longer) + self.unseen_length(suit, shorter),
+        }
+    }
+
+    // the most even split first
+    pub fn splits(&self, suit: Suit) -> Vec<((u8, u8), f64)> {
+        let in_suit = self.unseen[suit as usize];
+        (in_suit.div_ceil(2)..=in_suit)
+            .map(|longer| ((longer, in_suit - longer), self.split(suit, longer, in_suit - longer)))
+            .collect_vec()
+    }
+
+    // the lengths count the known cards and are indexed by suit
+    pub fn pattern(&self, condition: impl Fn([u8; 4]) -> bool) -> f64 {
+        let favourable = SUIT_ARRAY
+            .iter()
+            .map(|&suit| 0..=self.unseen[suit as usize])
+            .multi_cartesian_product()
+            .filter(|lengths| lengths.iter().sum::<u8>() == self.vacant_places)
+            .map(|lengths| {
+                let lengths: [u8; 4] = lengths.try_into().unwrap();
+                let hand = [0, 1, 2, 3].map(|suit| self.known[suit] + lengths[suit]);
+                match condition(hand) {
+                    true => (0..4)
+                        .map(|suit| choose(self.unseen[suit], lengths[suit]))
+                        .product::<u128>(),
+                    false => 0,
+                }
+            })
+            .sum::<u128>();
+        favourable as f64 / self.deals()
+    }
+
+    pub fn suit_length(&self, suit: Suit, length: u8) -> f64 {
+        match length.checked_sub(self.known[suit as usize]) {
+            Some(unseen) => self.unseen_length(suit, unseen),
+            None => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DistributionOdds;
+    use crate::primitives::{Card, Hand, Suit};
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-4, "{} != {}", actual, expected);
+    }
+
+    #[test_case(2, 1, 1, 0.52; "Two missing, one one")]
+    #[test_case(3, 2, 1, 0.78; "Three missing, two one")]
+    #[test_case(4, 3, 1, 0.4974; "Four missing, three one")]
+    #[test_case(4, 2, 2, 0.4070; "Four missing, two two")]
+    #[test_case(5, 3, 2, 0.6783; "Five missing, three two")]
+    #[test_case(5, 5, 0, 0.0391; "Five missing, five nil")]
+    #[test_case(6, 4, 2, 0.4845; "Six missing, four two")]
+    #[test_case(6, 3, 3, 0.3553; "Six missing, three three")]
+    #[test_case(5, 4, 2, 0.0; "Too many cards")]
+    fn split(missing: u8, longer: u8, shorter: u8, expected: f64) {
+        let odds = DistributionOdds::opponents(Suit::Clubs, missing).unwrap();
+        assert_close(odds.split(Suit::Clubs, longer, shorter), expected);
+    }
+
+    #[test_case(5; "Five missing")]
+    #[test_case(7; "Seven missing")]
+    fn splits_add_up(missing: u8) {
+        let odds = DistributionOdds::opponents(Suit::Clubs, missing).unwrap();
+        let splits = odds.splits(Suit::Clubs);
+        assert_eq!(splits[0].0, (missing.div_ceil(2), missing / 2));
+        assert_close(splits.iter().map(|(_, probability)| probability).sum(), 1.0);
+    }
+
+    #[test]
+    fn particular_cards() {
+        let odds = DistributionOdds::opponents(Suit::Clubs, 5).unwrap();
+        // the queen singleton or doubleton with this opponent
+        assert_close(odds.holding(Suit::Clubs, 1, 1), 0.2826 / 2.0 / 5.0);
+        assert_close(odds.holding(Suit::Clubs, 2, 1), 0.3391 * 2.0 / 5.0);
+        assert_eq!(odds.holding(Suit::Clubs, 0, 1), 0.0);
+    }
+
+    #[test]
+    fn patterns_before_the_deal() {
+        let odds = DistributionOdds::for_hand(&[], &[], 39).unwrap();
+        // 4-3-3-3 and 4-4-3-2
+        assert_close(
+            odds.pattern(|lengths| lengths.iter().all(|&length| (2..=4).contains(&length))),
+            0.3209,
+        );
+        assert_close(odds.pattern(|lengths| lengths.contains(&0)), 0.0511);
+        assert_close(odds.suit_length(Suit::Spades, 4), 0.2386);
+        assert_close(odds.pattern(|_| true), 1.0);
+    }
+
+    #[test]
+    fn partner_holds_a_major() {
+        let my_hand = Hand::<13>::from_str("S:AKQ,H:J32,D:5432,C:432").unwrap();
+        let odds = DistributionOdds::for_hand(&[], &my_hand.cards().copied().collect::<Vec<_>>(), 26).unwrap();
+        let four_card_major =
+            odds.pattern(|lengths| lengths[Suit::Hearts as usize] >= 4 || lengths[Suit::Spades as usize] >= 4);
+        let spades = odds.pattern(|lengths| lengths[Suit::Spades as usize] >= 4);
+        assert!(four_card_major > spades && spades > 0.0);
+        assert_close(
+            spades,
+            (4..=10).map(|length| odds.suit_length(Suit::Spades, length)).sum(),
+        );
+    }
+
+    #[test]
+    fn known_cards() {
+        let known = ["SA", "SK", "SQ", "SJ"].map(|card| Card::from_str(card).unwrap());
+        let odds = DistributionOdds::for_hand(&known, &[], 39).unwrap();
+        assert_eq!(odds.suit_length(Suit::Spades, 3), 0.0);
+        assert_close(odds.pattern(|lengths| lengths[Suit::Spades as usize] >= 4), 1.0);
+        assert!(DistributionOdds::for_hand(&known, &[], 10).is_err());
+        assert!(DistributionOdds::for_hand(&known, &[], 250).is_err());
+    }
+}
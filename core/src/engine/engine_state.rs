@@ -1,23 +1,321 @@
+use crate::engine::hand_evaluation::ForumDPlus2015Evaluator;
+use crate::engine::subjective_game_view::subjectiviser::Subjectiviser;
+use crate::engine::subjective_game_view::SubjectiveSeat;
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::card::Rank;
+use crate::primitives::deal::hand::HandType;
+use crate::primitives::deal::Hand;
+use crate::primitives::deal::Seat;
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::hand_info::keycards::Keycards;
 use crate::primitives::hand_info::ranges::{HcpRange, LengthRange, PointRange};
 use crate::primitives::hand_info::suit_quality::SuitQuality;
+use crate::primitives::{Card, Suit};
+use std::ops::RangeInclusive;
 
-pub struct EngineState {}
+pub struct EngineState {
+    seat: Seat,
+    calls: Vec<(Seat, Explanation)>,
+    hand_descriptions: [HandDescription; 4],
+}
 
 impl EngineState {
-    pub fn empty() -> Self {
-        EngineState {}
+    pub fn new(seat: Seat) -> Self {
+        EngineState {
+            seat,
+            calls: Vec::new(),
+            hand_descriptions: std::array::from_fn(|_| HandDescription::unknown()),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.seat);
+    }
+
+    pub fn process_call(&mut self, player: Seat, explanation: Explanation) {
+        self.calls.push((player, explanation));
+        self.hand_descriptions[player as usize].narrow(&explanation);
+    }
+
+    pub fn undo_calls(&mut self, calls: usize) {
+        let remaining = self.calls.len().saturating_sub(calls);
+        let kept = self.calls[..remaining].to_vec();
+        self.reset();
+        for (player, explanation) in kept {
+            self.process_call(player, explanation);
+        }
+    }
+
+    pub fn hand_description(&self, seat: SubjectiveSeat) -> &HandDescription {
+        let seat = Subjectiviser::new(self.seat).objective_seat(seat);
+        &self.hand_descriptions[seat as usize]
     }
 }
 
-#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct HandDescription {
     suit_lengths: [LengthRange; 4],
     hcp: HcpRange,
     total_points: PointRange,
+    suit_qualities: [Option<SuitQuality>; 4],
+    keycards: Option<Keycards>,
 }
 
-#[allow(dead_code)]
+impl HandDescription {
+    pub fn unknown() -> Self {
+        HandDescription {
+            suit_lengths: std::array::from_fn(|_| LengthRange(0..=13)),
+            hcp: HcpRange(0.0..=37.0),
+            total_points: PointRange(0.0..=37.0),
+            suit_qualities: [None; 4],
+            keycards: None,
+        }
+    }
+
+    pub fn of_hand(hand: &Hand<13>) -> Self {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        let total_points = hcp + ForumDPlus2015Evaluator::length_points(hand, None, &[]);
+        HandDescription {
+            suit_lengths: SUIT_ARRAY.map(|suit| {
+                let length = hand.length_in(suit) as usize;
+                LengthRange(length..=length)
+            }),
+            hcp: HcpRange(hcp..=hcp),
+            total_points: PointRange(total_points..=total_points),
+            suit_qualities: SUIT_ARRAY.map(|suit| Some(ForumDPlus2015Evaluator::suit_quality(hand, suit))),
+            keycards: None,
+        }
+    }
+
+    pub fn suit_length(&self, suit: Suit) -> &LengthRange {
+        &self.suit_lengths[suit as usize]
+    }
+
+    pub fn hcp(&self) -> &HcpRange {
+        &self.hcp
+    }
+
+    pub fn total_points(&self) -> &PointRange {
+        &self.total_points
+    }
+
+    pub fn keycards(&self) -> Option<&Keycards> {
+        self.keycards.as_ref()
+    }
+
+    pub fn suit_hint(&self, suit: Suit) -> Option<SuitHint> {
+        let min_quality = self.suit_qualities[suit as usize]?;
+        Some(SuitHint {
+            min_length: *self.suit_length(suit).0.start(),
+            min_quality,
+        })
+    }
+
+    pub fn admits(&self, hand: &Hand<13>) -> bool {
+        let hcp = ForumDPlus2015Evaluator::hcp(hand);
+        let total_points = hcp + ForumDPlus2015Evaluator::length_points(hand, None, &[]);
+        let keycards_fit = match self.keycards {
+            Some(keycards) => {
+                let trump_queen = keycards.trump_suit.map(|suit| {
+                    hand.contains(&Card {
+                        suit,
+                        rank: Rank::Queen,
+                    })
+                });
+                keycards.allows(ForumDPlus2015Evaluator::keycards(hand, keycards.trump_suit))
+                    && (keycards.trump_queen.is_none() || keycards.trump_queen == trump_queen)
+            }
+            None => true,
+        };
+        keycards_fit
+            && self.hcp.0.contains(&hcp)
+            && self.total_points.0.contains(&total_points)
+            && SUIT_ARRAY.iter().all(|&suit| {
+                self.suit_length(suit).0.contains(&(hand.length_in(suit) as usize))
+                    && match self.suit_qualities[suit as usize] {
+                        Some(quality) => ForumDPlus2015Evaluator::suit_quality(hand, suit) >= quality,
+                        None => true,
+                    }
+            })
+    }
+
+    // where the new information contradicts what was known, e.g. after a misbid, it wins
+    pub fn narrow(&mut self, explanation: &Explanation) {
+        let mut lengths = [None; 4];
+        if let Some(hand_type) = explanation.hand_type {
+            for suit in SUIT_ARRAY {
+                lengths[suit as usize] = match hand_type {
+                    HandType::Balanced(None) => Some((2, 5)),
+                    HandType::Balanced(Some(five_card_suit)) if suit == five_card_suit => Some((5, 5)),
+                    HandType::Balanced(Some(_)) => Some((2, 4)),
+                    _ => None,
+                };
+            }
+        }
+        for suit in SUIT_ARRAY {
+            if let Some((min, max)) = lengths[suit as usize] {
+                let range = &mut self.suit_lengths[suit as usize].0;
+                *range = intersect(range, &(min as usize..=max as usize));
+            }
+            if let Some((min, max)) = explanation.suit_lengths[suit as usize] {
+                let range = &mut self.suit_lengths[suit as usize].0;
+                *range = intersect(range, &(min as usize..=max as usize));
+            }
+            if let Some(quality) = explanation.suit_qualities[suit as usize] {
+                let known = &mut self.suit_qualities[suit as usize];
+                *known = Some(known.map_or(quality, |known| known.max(quality)));
+            }
+        }
+        if let Some((min, max)) = explanation.hcp {
+            self.hcp.0 = intersect(&self.hcp.0, &(min as f64..=max as f64));
+        }
+        if let Some((min, max)) = explanation.total_points {
+            self.total_points.0 = intersect(&self.total_points.0, &(min as f64..=max as f64));
+        }
+        if explanation.keycards.is_some() {
+            self.keycards = explanation.keycards;
+        }
+        self.apply_suit_length_sum();
+    }
+
+    fn apply_suit_length_sum(&mut self) {
+        let min_total: usize = self.suit_lengths.iter().map(|range| range.0.start()).sum();
+        let max_total: usize = self.suit_lengths.iter().map(|range| range.0.end()).sum();
+        if min_total > 13 || max_total < 13 {
+            return;
+        }
+        for range in self.suit_lengths.iter_mut() {
+            let (min, max) = (*range.0.start(), *range.0.end());
+            let max = max.min(13 - (min_total - min));
+            let min = min.max(13usize.saturating_sub(max_total - *range.0.end()));
+            range.0 = min..=max;
+        }
+    }
+}
+
+// `new` if the ranges don't overlap
+fn intersect<T: PartialOrd + Copy>(known: &RangeInclusive<T>, new: &RangeInclusive<T>) -> RangeInclusive<T> {
+    let start = if known.start() > new.start() {
+        *known.start()
+    } else {
+        *new.start()
+    };
+    let end = if known.end() < new.end() {
+        *known.end()
+    } else {
+        *new.end()
+    };
+    match start <= end {
+        true => start..=end,
+        false => new.clone(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SuitHint {
-    min_length: usize,
-    min_quality: SuitQuality,
+    pub min_length: usize,
+    pub min_quality: SuitQuality,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EngineState, HandDescription};
+    use crate::engine::subjective_game_view::SubjectiveSeat;
+    use crate::primitives::deal::Hand;
+    use crate::primitives::deal::Seat;
+    use crate::primitives::hand_info::explanation::Explanation;
+    use crate::primitives::Suit::*;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    fn explanation(text: &str) -> Explanation {
+        Explanation::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn narrow() {
+        let mut description = HandDescription::unknown();
+        description.narrow(&explanation("Hand is balanced. Hand has 15 to 17 hcp."));
+        assert_eq!(description.hcp().0, 15.0..=17.0);
+        assert_eq!(description.suit_length(Spades).0, 2..=5);
+
+        description.narrow(&explanation("Hand has 4 to 5 cards in ♥. Hand has 8 to 37 hcp."));
+        assert_eq!(description.hcp().0, 15.0..=17.0);
+        assert_eq!(description.suit_length(Hearts).0, 4..=5);
+    }
+
+    #[test]
+    fn suit_length_sum() {
+        let mut description = HandDescription::unknown();
+        description.narrow(&explanation(
+            "Hand has 7 to 13 cards in ♠. Hand has 4 to 13 cards in ♥. Hand's ♠ are good.",
+        ));
+        assert_eq!(description.suit_length(Spades).0, 7..=9);
+        assert_eq!(description.suit_length(Hearts).0, 4..=6);
+        assert_eq!(description.suit_length(Diamonds).0, 0..=2);
+        assert_eq!(description.suit_hint(Spades).unwrap().min_length, 7);
+        assert!(description.suit_hint(Hearts).is_none());
+    }
+
+    #[test]
+    fn contradiction() {
+        let mut description = HandDescription::unknown();
+        description.narrow(&explanation("Hand has 15 to 17 hcp."));
+        description.narrow(&explanation("Hand has 8 to 9 hcp."));
+        assert_eq!(description.hcp().0, 8.0..=9.0);
+    }
+
+    #[test]
+    fn keycards() {
+        let mut description = HandDescription::unknown();
+        description.narrow(&explanation("Hand has 1 or 4 keycards for ♠."));
+        description.narrow(&explanation("Hand has 15 to 17 hcp."));
+        assert!(description.keycards().unwrap().allows(4));
+    }
+
+    #[test_case("S:AQJ54,H:K32,D:Q7,C:985", true; "Fits")]
+    #[test_case("S:AQJ54,H:K32,D:Q7,C:K85", false; "Too strong")]
+    #[test_case("S:AQJ5,H:K32,D:Q74,C:985", false; "Too short")]
+    #[test_case("S:KQ954,H:AK2,D:Q7,C:985", false; "Wrong number of keycards")]
+    fn admits(hand: &str, expected: bool) {
+        let mut description = HandDescription::unknown();
+        description.narrow(&explanation("Hand has 5 to 13 cards in ♠. Hand has 12 to 14 hcp."));
+        description.narrow(&explanation("Hand has 1 or 4 keycards for ♠."));
+        let hand = Hand::from_str(hand).unwrap();
+        assert_eq!(description.admits(&hand), expected);
+    }
+
+    #[test]
+    fn of_hand() {
+        let hand = Hand::from_str("S:AQJ54,H:K32,D:Q7,C:985").unwrap();
+        let description = HandDescription::of_hand(&hand);
+        assert_eq!(description.suit_length(Spades).0, 5..=5);
+        assert!(description.admits(&hand));
+        assert!(!description.admits(&Hand::from_str("S:AQJ54,H:K32,D:Q,C:9875").unwrap()));
+    }
+
+    #[test]
+    fn engine_state() {
+        let mut state = EngineState::new(Seat::South);
+        state.process_call(Seat::West, explanation("Hand has 15 to 17 hcp."));
+        state.process_call(Seat::North, explanation("Hand has 5 to 13 cards in ♥."));
+        assert_eq!(
+            state.hand_description(SubjectiveSeat::LeftHandOpponent).hcp().0,
+            15.0..=17.0
+        );
+        assert_eq!(
+            state.hand_description(SubjectiveSeat::Partner).suit_length(Hearts).0,
+            5..=13
+        );
+
+        state.undo_calls(1);
+        assert_eq!(
+            state.hand_description(SubjectiveSeat::Partner),
+            &HandDescription::unknown()
+        );
+        assert_eq!(
+            state.hand_description(SubjectiveSeat::LeftHandOpponent).hcp().0,
+            15.0..=17.0
+        );
+    }
 }
@@ -0,0 +1,54 @@
+use crate::engine::hand_evaluation::HandEvaluator;
+use crate::primitives::card::Rank::*;
+use crate::primitives::Hand;
+
+// 5-4-3-2-1 from the ace down to the ten
+#[derive(Debug)]
+pub struct BanzaiEvaluator {}
+
+impl BanzaiEvaluator {
+    pub fn banzai_points(hand: &Hand<13>) -> f64 {
+        hand.cards()
+            .map(|card| match card.rank {
+                Ace => 5.0,
+                King => 4.0,
+                Queen => 3.0,
+                Jack => 2.0,
+                Ten => 1.0,
+                _ => 0.0,
+            })
+            .sum()
+    }
+}
+
+impl HandEvaluator for BanzaiEvaluator {
+    fn name(&self) -> &'static str {
+        "Banzai"
+    }
+
+    fn breakdown(&self, hand: &Hand<13>) -> Vec<(&'static str, f64)> {
+        vec![("banzai points", Self::banzai_points(hand))]
+    }
+
+    // twelve hcp, scaled to the 60 points in the pack
+    fn opening_value(&self) -> f64 {
+        18.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::hand_evaluation::banzai_evaluator::BanzaiEvaluator;
+    use crate::engine::hand_evaluation::HandEvaluator;
+    use crate::primitives::Hand;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    #[test_case("S:T93,H:AKQ5,D:QJ,C:T542", 19.0; "Board 1.N")]
+    #[test_case("S:AKJ52,H:943,D:982,C:87", 11.0; "Board 1.W")]
+    #[test_case("S:AT9,H:AT9,D:AT98,C:T98", 19.0; "Tens count")]
+    fn evaluate(hand: &str, expected: f64) {
+        let hand = Hand::from_str(hand).unwrap();
+        assert_eq!(BanzaiEvaluator {}.evaluate(&hand), expected);
+    }
+}
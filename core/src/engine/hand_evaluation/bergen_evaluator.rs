@@ -0,0 +1,90 @@
+use crate::engine::hand_evaluation::{ForumDPlus2015Evaluator, HandEvaluator};
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::card::Rank::*;
+use crate::primitives::Hand;
+use itertools::Itertools;
+
+#[derive(Debug)]
+pub struct BergenEvaluator {}
+
+impl BergenEvaluator {
+    pub fn length_points(hand: &Hand<13>) -> f64 {
+        SUIT_ARRAY
+            .iter()
+            .map(|&suit| hand.length_in(suit).saturating_sub(4) as f64)
+            .sum()
+    }
+
+    // three of the top five honors in a suit of four or more cards
+    pub fn quality_suits(hand: &Hand<13>) -> f64 {
+        SUIT_ARRAY
+            .iter()
+            .filter(|&&suit| {
+                hand.length_in(suit) >= 4 && hand.cards_in(suit).filter(|card| card.rank >= Ten).count() >= 3
+            })
+            .count() as f64
+    }
+
+    // aces and tens against queens and jacks, if one side outnumbers the other by three or more
+    pub fn aces_and_tens(hand: &Hand<13>) -> f64 {
+        let count = |ranks: [_; 2]| hand.cards().filter(|card| ranks.contains(&card.rank)).count() as isize;
+        match count([Ace, Ten]) - count([Queen, Jack]) {
+            difference if difference >= 3 => 1.0,
+            difference if difference <= -3 => -1.0,
+            _ => 0.0,
+        }
+    }
+
+    // singleton K, Q or J, and KQ, KJ, QJ, Qx and Jx
+    pub fn short_honors(hand: &Hand<13>) -> f64 {
+        SUIT_ARRAY
+            .iter()
+            .map(|&suit| {
+                let ranks = hand.cards_in(suit).rev().map(|card| card.rank).collect_vec();
+                match ranks[..] {
+                    [King | Queen | Jack] => -1.0,
+                    [King, Queen | Jack] | [Queen | Jack, _] => -1.0,
+                    _ => 0.0,
+                }
+            })
+            .sum()
+    }
+}
+
+impl HandEvaluator for BergenEvaluator {
+    fn name(&self) -> &'static str {
+        "Bergen"
+    }
+
+    fn breakdown(&self, hand: &Hand<13>) -> Vec<(&'static str, f64)> {
+        vec![
+            ("high-card points", ForumDPlus2015Evaluator::hcp(hand)),
+            ("length points", Self::length_points(hand)),
+            ("quality suits", Self::quality_suits(hand)),
+            ("aces and tens", Self::aces_and_tens(hand)),
+            ("short honors", Self::short_honors(hand)),
+        ]
+    }
+
+    fn opening_value(&self) -> f64 {
+        12.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::hand_evaluation::bergen_evaluator::BergenEvaluator;
+    use crate::engine::hand_evaluation::HandEvaluator;
+    use crate::primitives::Hand;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    #[test_case("S:T93,H:AKQ5,D:QJ,C:T542", 12.0; "Quality suit and short honors")]
+    #[test_case("S:AKJ52,H:943,D:982,C:87", 10.0; "Length and quality")]
+    #[test_case("S:K,H:AT98,D:AT98,C:AT98", 15.0; "Aces and tens and a singleton king")]
+    #[test_case("S:QJ2,H:QJ2,D:QJ32,C:K32", 11.0; "Queens and jacks")]
+    fn evaluate(hand: &str, expected: f64) {
+        let hand = Hand::from_str(hand).unwrap();
+        assert_eq!(BergenEvaluator {}.evaluate(&hand), expected);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::engine::hand_evaluation::HandEvaluator;
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::card::Rank;
+use crate::primitives::card::Rank::*;
+use crate::primitives::{Hand, Suit};
+use itertools::Itertools;
+
+// a simplified CCCC: A=3, K=2, Q=1, less when short or unsupported, J and T only next to higher honors.
+// Every suit adds its length times its honors over ten, and honors and suit quality are scaled by 4/3
+// to the range of hcp. Shortness counts one point per missing card below three, less one for the first
+// doubleton, which also takes one point off a 4-3-3-3.
+#[derive(Debug)]
+pub struct KaplanRubensEvaluator {}
+
+impl KaplanRubensEvaluator {
+    const SCALE: f64 = 4.0 / 3.0;
+
+    pub fn honor_points(hand: &Hand<13>) -> f64 {
+        SUIT_ARRAY
+            .iter()
+            .map(|&suit| Self::honors_in(hand, suit).iter().sum::<f64>())
+            .sum::<f64>()
+            * Self::SCALE
+    }
+
+    pub fn suit_quality(hand: &Hand<13>) -> f64 {
+        SUIT_ARRAY
+            .iter()
+            .map(|&suit| {
+                let honors = Self::honors_in(hand, suit).iter().filter(|&&value| value > 0.0).count();
+                hand.length_in(suit) as f64 * honors as f64 / 10.0
+            })
+            .sum::<f64>()
+            * Self::SCALE
+    }
+
+    pub fn shape_points(hand: &Hand<13>) -> f64 {
+        let short_cards: f64 = SUIT_ARRAY
+            .iter()
+            .map(|&suit| 3.0 - hand.length_in(suit).min(3) as f64)
+            .sum();
+        short_cards - 1.0
+    }
+
+    fn honors_in(hand: &Hand<13>, suit: Suit) -> Vec<f64> {
+        let ranks = hand.cards_in(suit).rev().map(|card| card.rank).collect_vec();
+        let length = ranks.len();
+        let has = |rank: Rank| ranks.contains(&rank);
+        let higher_honors = |rank: Rank| ranks.iter().filter(|&&other| other > rank).count();
+        ranks
+            .iter()
+            .map(|&rank| match rank {
+                Ace => 3.0,
+                King if length == 1 => 0.5,
+                King => 2.0,
+                Queen => match (length, has(Ace) || has(King)) {
+                    (1, _) => 0.0,
+                    (2, true) => 0.5,
+                    (2, false) => 0.25,
+                    (_, true) => 1.0,
+                    (_, false) => 0.75,
+                },
+                Jack => match higher_honors(Jack) {
+                    0 => 0.0,
+                    1 => 0.25,
+                    _ => 0.5,
+                },
+                Ten if higher_honors(Jack) >= 2 || has(Jack) => 0.25,
+                _ => 0.0,
+            })
+            .collect()
+    }
+}
+
+impl HandEvaluator for KaplanRubensEvaluator {
+    fn name(&self) -> &'static str {
+        "Kaplan-Rubens"
+    }
+
+    fn breakdown(&self, hand: &Hand<13>) -> Vec<(&'static str, f64)> {
+        vec![
+            ("honors", Self::honor_points(hand)),
+            ("suit quality", Self::suit_quality(hand)),
+            ("shape", Self::shape_points(hand)),
+        ]
+    }
+
+    fn opening_value(&self) -> f64 {
+        12.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::hand_evaluation::kaplan_rubens_evaluator::KaplanRubensEvaluator;
+    use crate::engine::hand_evaluation::HandEvaluator;
+    use crate::primitives::Hand;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    #[test_case("S:AKJ52,H:943,D:982,C:87", 9.33; "Long suit with honors")]
+    #[test_case("S:T93,H:AKQ5,D:QJ,C:T542", 10.8; "Unsupported honors")]
+    #[test_case("S:AT9,H:AT9,D:AT98,C:T98", 12.33; "Flat")]
+    #[test_case("S:AQJ42,H:KJ95432,D:A,C:", 21.0; "Two-suiter")]
+    fn evaluate(hand: &str, expected: f64) {
+        let hand = Hand::from_str(hand).unwrap();
+        let value = KaplanRubensEvaluator {}.evaluate(&hand);
+        assert!((value - expected).abs() < 0.01, "{} is not {}", value, expected);
+    }
+}
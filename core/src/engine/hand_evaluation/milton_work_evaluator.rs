@@ -0,0 +1,36 @@
+use crate::engine::hand_evaluation::{ForumDPlus2015Evaluator, HandEvaluator};
+use crate::primitives::Hand;
+
+#[derive(Debug)]
+pub struct MiltonWorkEvaluator {}
+
+impl HandEvaluator for MiltonWorkEvaluator {
+    fn name(&self) -> &'static str {
+        "Milton Work"
+    }
+
+    fn breakdown(&self, hand: &Hand<13>) -> Vec<(&'static str, f64)> {
+        vec![("high-card points", ForumDPlus2015Evaluator::hcp(hand))]
+    }
+
+    fn opening_value(&self) -> f64 {
+        12.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::hand_evaluation::milton_work_evaluator::MiltonWorkEvaluator;
+    use crate::engine::hand_evaluation::HandEvaluator;
+    use crate::primitives::Hand;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    #[test_case("S:T93,H:AKQ5,D:QJ,C:T542", 12.0; "Unguarded honors don't count less")]
+    #[test_case("S:AKJ52,H:943,D:982,C:87", 8.0; "Length doesn't count")]
+    #[test_case("S:AT9,H:AT9,D:AT98,C:T98", 12.0; "Aces and tens don't count more")]
+    fn evaluate(hand: &str, expected: f64) {
+        let hand = Hand::from_str(hand).unwrap();
+        assert_eq!(MiltonWorkEvaluator {}.evaluate(&hand), expected);
+    }
+}
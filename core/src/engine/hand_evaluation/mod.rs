@@ -1,3 +1,4 @@
+use crate::engine::engine_state::HandDescription;
 use crate::primitives::card::rank::RANK_ARRAY;
 use crate::primitives::card::suit::SUIT_ARRAY;
 use crate::primitives::card::Rank::*;
@@ -5,10 +6,94 @@ use crate::primitives::hand_info::suit_quality::SuitQuality;
 use crate::primitives::{card::Rank, Card, Hand, Suit};
 use itertools::Itertools;
 use std::cmp::Ordering;
+use strum::{Display, EnumString};
+
+pub mod banzai_evaluator;
+pub mod bergen_evaluator;
+pub mod kaplan_rubens_evaluator;
+pub mod milton_work_evaluator;
+pub mod zar_evaluator;
+
+use banzai_evaluator::BanzaiEvaluator;
+use bergen_evaluator::BergenEvaluator;
+use kaplan_rubens_evaluator::KaplanRubensEvaluator;
+use milton_work_evaluator::MiltonWorkEvaluator;
+use zar_evaluator::ZarEvaluator;
+
+pub trait HandEvaluator {
+    fn name(&self) -> &'static str;
+
+    fn breakdown(&self, hand: &Hand<13>) -> Vec<(&'static str, f64)>;
+
+    // relates the values of the different methods to each other
+    fn opening_value(&self) -> f64;
+
+    fn evaluate(&self, hand: &Hand<13>) -> f64 {
+        self.breakdown(hand).iter().map(|(_, value)| value).sum()
+    }
+}
+
+#[derive(Debug, Default, Display, EnumString, Clone, Copy, PartialEq, Eq)]
+pub enum EvaluationMethod {
+    #[default]
+    #[strum(serialize = "forum-d")]
+    ForumDPlus2015,
+    #[strum(serialize = "milton")]
+    MiltonWork,
+    #[strum(serialize = "bergen")]
+    Bergen,
+    #[strum(serialize = "zar")]
+    Zar,
+    #[strum(serialize = "knr")]
+    KaplanRubens,
+    #[strum(serialize = "banzai")]
+    Banzai,
+}
+
+pub const EVALUATION_METHODS: [EvaluationMethod; 6] = [
+    EvaluationMethod::ForumDPlus2015,
+    EvaluationMethod::MiltonWork,
+    EvaluationMethod::Bergen,
+    EvaluationMethod::Zar,
+    EvaluationMethod::KaplanRubens,
+    EvaluationMethod::Banzai,
+];
+
+impl EvaluationMethod {
+    pub fn evaluator(&self) -> Box<dyn HandEvaluator> {
+        match self {
+            EvaluationMethod::ForumDPlus2015 => Box::new(ForumDPlus2015Evaluator {}),
+            EvaluationMethod::MiltonWork => Box::new(MiltonWorkEvaluator {}),
+            EvaluationMethod::Bergen => Box::new(BergenEvaluator {}),
+            EvaluationMethod::Zar => Box::new(ZarEvaluator {}),
+            EvaluationMethod::KaplanRubens => Box::new(KaplanRubensEvaluator {}),
+            EvaluationMethod::Banzai => Box::new(BanzaiEvaluator {}),
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct ForumDPlus2015Evaluator {}
 
+impl HandEvaluator for ForumDPlus2015Evaluator {
+    fn name(&self) -> &'static str {
+        "Forum D Plus 2015"
+    }
+
+    fn breakdown(&self, hand: &Hand<13>) -> Vec<(&'static str, f64)> {
+        vec![
+            ("high-card points", Self::hcp(hand)),
+            ("length points", Self::length_points(hand, None, &[])),
+            ("aces and tens", Self::adjustment_aces_and_tens(hand)),
+            ("unguarded honors", Self::adjustment_unguarded_honors(hand)),
+        ]
+    }
+
+    fn opening_value(&self) -> f64 {
+        12.0
+    }
+}
+
 impl ForumDPlus2015Evaluator {
     pub fn hcp(hand: &Hand<13>) -> f64 {
         Self::hcp_for_cards(&mut hand.cards())
@@ -18,7 +103,7 @@ impl ForumDPlus2015Evaluator {
         Self::hcp_for_cards(&mut hand.cards_in(suit))
     }
 
-    fn hcp_for_cards<'a>(cards: &mut impl DoubleEndedIterator<Item = &'a Card>) -> f64 {
+    pub fn hcp_for_cards<'a>(cards: &mut impl DoubleEndedIterator<Item = &'a Card>) -> f64 {
         cards.fold(0.0, |total_hcp, card| {
             total_hcp + ForumDPlus2015Evaluator::card_value(card) as f64
         })
@@ -184,49 +269,183 @@ impl ForumDPlus2015Evaluator {
     // adjustment on bids by other players
     //
 
-    // pub fn adjustment_partners_suits(hand: &Hand, suits: &[Suit]) -> f64 {
-    //     // honors and honor combinations in partner's suits gain 0.5 HCP
-    //     todo!()
-    // }
+    pub fn partnership_adjustments(
+        hand: &Hand<13>,
+        partner: &HandDescription,
+        left_opponent: &HandDescription,
+        right_opponent: &HandDescription,
+        trump_suit: Option<Suit>,
+    ) -> Vec<(&'static str, f64)> {
+        let partners_suits = Self::shown_suits(partner);
+        let left_opponents_suits = Self::shown_suits(left_opponent);
+        let right_opponents_suits = Self::shown_suits(right_opponent);
+        let opponents_suits = [left_opponents_suits.clone(), right_opponents_suits.clone()].concat();
+        let mut adjustments = vec![
+            (
+                "partner's suits",
+                Self::adjustment_partners_suits(hand, &partners_suits),
+            ),
+            (
+                "right opponent's suits",
+                Self::adjustment_right_opponents_suits(hand, &right_opponents_suits),
+            ),
+            (
+                "left opponent's suits",
+                Self::adjustment_left_opponents_suits(hand, &left_opponents_suits),
+            ),
+            ("misfit", Self::adjustment_misfit(hand, &partners_suits, trump_suit)),
+        ];
+        if let Some(trump) = trump_suit {
+            let partners_lengths = SUIT_ARRAY.map(|suit| *partner.suit_length(suit).0.start() as u8);
+            let partners_short_suits = SUIT_ARRAY
+                .into_iter()
+                .filter(|&suit| *partner.suit_length(suit).0.end() <= 1)
+                .collect_vec();
+            // partner's short suits are devalued on their own
+            let unbid_suits = SUIT_ARRAY
+                .into_iter()
+                .filter(|suit| {
+                    !partners_suits.contains(suit)
+                        && !opponents_suits.contains(suit)
+                        && !partners_short_suits.contains(suit)
+                })
+                .collect_vec();
+            // with equal length, either hand may become declarer
+            let is_dummy = partners_lengths[trump as usize] > hand.length_in(trump);
+            // until a side suit has been shown, the auction says nothing about where partner's values are
+            let side_suit_shown = [&partners_suits[..], &opponents_suits[..], &partners_short_suits[..]]
+                .concat()
+                .iter()
+                .any(|&suit| suit != trump);
+            adjustments.extend([
+                (
+                    "double fit",
+                    Self::adjustment_double_fit(hand, trump, &partners_lengths),
+                ),
+                (
+                    "shortness in opponents' suits",
+                    Self::adjustment_shortness_in_opponents_suit(hand, trump, &opponents_suits),
+                ),
+                (
+                    "partner's short suits",
+                    Self::adjustment_partners_short_suit(hand, trump, &partners_short_suits),
+                ),
+                (
+                    "unguarded queens and jacks in dummy",
+                    match is_dummy && side_suit_shown {
+                        true => Self::adjustment_unguarded_queen_and_jack_in_dummy(hand, trump, &unbid_suits),
+                        false => 0.0,
+                    },
+                ),
+            ]);
+        }
+        adjustments
+    }
+
+    pub fn partnership_adjustment(
+        hand: &Hand<13>,
+        partner: &HandDescription,
+        left_opponent: &HandDescription,
+        right_opponent: &HandDescription,
+        trump_suit: Option<Suit>,
+    ) -> f64 {
+        Self::partnership_adjustments(hand, partner, left_opponent, right_opponent, trump_suit)
+            .iter()
+            .map(|(_, adjustment)| adjustment)
+            .sum()
+    }
+
+    fn shown_suits(description: &HandDescription) -> Vec<Suit> {
+        SUIT_ARRAY
+            .into_iter()
+            .filter(|&suit| *description.suit_length(suit).0.start() >= 4)
+            .collect()
+    }
 
-    // pub fn adjustment_right_opponents_suits(hand: &Hand, suits: &[Suit]) -> f64 {
-    //     // we gain 1 HCP if we have one or more of the top three honors in a suit named by our right-hand opponent
-    //     todo!()
-    // }
+    pub fn adjustment_partners_suits(hand: &Hand<13>, suits: &[Suit]) -> f64 {
+        // honors and honor combinations in partner's suits gain 0.5 HCP
+        suits.iter().filter(|&&suit| Self::honor_in(suit, hand)).count() as f64 * 0.5
+    }
 
-    // pub fn adjustment_left_opponents_suits(hand: &Hand, suits: &[Suit]) -> f64 {
-    //     // we lose 1 HCP if we have honors in a suit named by our left-hand opponent.
-    //     todo!()
-    // }
+    pub fn adjustment_right_opponents_suits(hand: &Hand<13>, suits: &[Suit]) -> f64 {
+        // we gain 1 HCP if we have one or more of the top three honors in a suit named by our right-hand opponent
+        suits
+            .iter()
+            .filter(|&&suit| hand.cards_in(suit).any(|card| card.rank >= Queen))
+            .count() as f64
+    }
 
-    // pub fn adjustment_misfit() -> f64 {
-    //     // disregard all length points if we are in misfit with partner
-    //     todo!()
-    // }
+    pub fn adjustment_left_opponents_suits(hand: &Hand<13>, suits: &[Suit]) -> f64 {
+        // we lose 1 HCP if we have honors below the ace in a suit named by our left-hand opponent.
+        -(suits
+            .iter()
+            .filter(|&&suit| hand.cards_in(suit).any(|card| [King, Queen, Jack].contains(&card.rank)))
+            .count() as f64)
+    }
+
+    pub fn adjustment_misfit(hand: &Hand<13>, partners_suits: &[Suit], trump_suit: Option<Suit>) -> f64 {
+        // disregard all length points if we are in misfit with partner, holding at most one card in each of their suits
+        let misfit = !partners_suits.is_empty() && partners_suits.iter().all(|&suit| hand.length_in(suit) <= 1);
+        match misfit {
+            true => -Self::length_points(hand, trump_suit, &[]),
+            false => 0.0,
+        }
+    }
 
     //
     // adjustments for suit contracts
     //
 
-    // pub fn adjustment_double_fit(hand: &Hand) {
-    //     // for a suit-contract +1 V
-    //     todo!()
-    // }
-
-    // pub fn adjustment_shortness_in_opponents_suit(hand: &Hand, trump: Suit, suits: &[Suit]) -> f64 {
-    //     // for a suit-contract, this increases ruffing opportunity, +1 V
-    //     todo!()
-    // }
-
-    // pub fn adjustment_partners_short_suit(hand: &Hand, trump: Suit, short_suits: &[Suit]) -> f64 {
-    //     // for a suit-contract, this decreases the value of K,D or B by at least -1 HCP
-    //     todo!()
-    // }
+    pub fn adjustment_double_fit(hand: &Hand<13>, trump: Suit, partners_lengths: &[u8; 4]) -> f64 {
+        // for a suit-contract +1 V, if we have a second eight-card fit besides trumps
+        let double_fit = SUIT_ARRAY
+            .iter()
+            .any(|&suit| suit != trump && hand.length_in(suit) + partners_lengths[suit as usize] >= 8);
+        match double_fit {
+            true => 1.0,
+            false => 0.0,
+        }
+    }
 
-    // pub fn adjustment_unguarded_queen_and_jack_in_dummy(hand: &Hand, trump: Suit, unbid_suits: &[Suit]) -> f64 {
-    //     // for a suit-contract, if we are going to be dummy, low honors in unbid suits are mostly worthless
-    //     todo!()
-    // }
+    pub fn adjustment_shortness_in_opponents_suit(hand: &Hand<13>, trump: Suit, suits: &[Suit]) -> f64 {
+        // for a suit-contract, this increases ruffing opportunity, +1 V
+        if hand.length_in(trump) < 3 {
+            return 0.0;
+        }
+        suits
+            .iter()
+            .filter(|&&suit| suit != trump && hand.length_in(suit) <= 1)
+            .count() as f64
+    }
+
+    pub fn adjustment_partners_short_suit(hand: &Hand<13>, trump: Suit, short_suits: &[Suit]) -> f64 {
+        // for a suit-contract, this decreases the value of K,D or B by at least -1 HCP
+        -(short_suits
+            .iter()
+            .filter(|&&suit| suit != trump)
+            .map(|&suit| {
+                hand.cards_in(suit)
+                    .filter(|card| [King, Queen, Jack].contains(&card.rank))
+                    .count()
+            })
+            .sum::<usize>() as f64)
+    }
+
+    pub fn adjustment_unguarded_queen_and_jack_in_dummy(hand: &Hand<13>, trump: Suit, unbid_suits: &[Suit]) -> f64 {
+        // for a suit-contract, if we are going to be dummy, low honors in unbid suits are mostly worthless,
+        // unless the ace or king is there to back them up
+        -unbid_suits
+            .iter()
+            .filter(|&&suit| suit != trump)
+            .filter(|&&suit| !hand.cards_in(suit).any(|card| card.rank >= King))
+            .map(|&suit| {
+                hand.cards_in(suit)
+                    .filter(|card| [Queen, Jack].contains(&card.rank))
+                    .map(Self::card_value)
+                    .sum::<u8>() as f64
+            })
+            .sum::<f64>()
+    }
 
     //
     // Playing Trick Count (PTC)
@@ -380,6 +599,15 @@ impl ForumDPlus2015Evaluator {
         }
     }
 
+    pub fn keycards(hand: &Hand<13>, trump: Option<Suit>) -> u8 {
+        let aces = SUIT_ARRAY
+            .iter()
+            .filter(|&&suit| hand.contains(&Card { suit, rank: Ace }))
+            .count();
+        let trump_king = trump.is_some_and(|suit| hand.contains(&Card { suit, rank: King }));
+        (aces + trump_king as usize) as u8
+    }
+
     pub fn honor_in(suit: Suit, hand: &Hand<13>) -> bool {
         let card_vec = hand.cards_in(suit).rev().map(|c| c.rank).collect_vec();
         RANK_ARRAY.iter().rev().take(5).filter(|x| card_vec.contains(x)).count() >= 1
@@ -453,6 +681,7 @@ impl ForumDPlus2015Evaluator {
 #[cfg(test)]
 mod test {
     use crate::engine::hand_evaluation::*;
+    use crate::primitives::hand_info::explanation::Explanation;
 
     use std::str::FromStr;
     use test_case::test_case;
@@ -608,6 +837,14 @@ mod test {
         )
     }
 
+    #[test_case("S:AKQJ96,H:T,D:A,C:Q9763", None, 2; "Aces")]
+    #[test_case("S:AKQJ96,H:T,D:A,C:Q9763", Some(Suit::Spades), 3; "King of trumps")]
+    #[test_case("S:AKQJ96,H:T,D:A,C:Q9763", Some(Suit::Hearts), 2; "No king of trumps")]
+    fn keycards(hand_str: &str, trump_suit: Option<Suit>, expected: u8) {
+        let hand = Hand::from_str(hand_str).unwrap();
+        assert_eq!(ForumDPlus2015Evaluator::keycards(&hand, trump_suit), expected)
+    }
+
     #[test_case("S:AKQJ96,H:T,D:A,C:Q9763", Suit::Diamonds, true)]
     #[test_case("S:AKQJ6,H:KT,D:A,C:Q9763", Suit::Hearts, true)]
     #[test_case("S:AKQJ96,H:9,D:A,C:Q9763", Suit::Hearts, false)]
@@ -642,4 +879,67 @@ mod test {
         let hand = Hand::from_str(hand_str).unwrap();
         assert_eq!(ForumDPlus2015Evaluator::rule_of_fifteen(&hand), exp)
     }
+
+    #[test_case("forum-d", "Forum D Plus 2015", 11.0)]
+    #[test_case("milton", "Milton Work", 12.0)]
+    #[test_case("bergen", "Bergen", 12.0)]
+    #[test_case("zar", "Zar", 25.0)]
+    #[test_case("banzai", "Banzai", 19.0)]
+    fn evaluation_method(method: &str, name: &str, value: f64) {
+        let hand = Hand::from_str("S:T93,H:AKQ5,D:QJ,C:T542").unwrap();
+        let evaluator = EvaluationMethod::from_str(method).unwrap().evaluator();
+        assert_eq!(evaluator.name(), name);
+        assert_eq!(evaluator.evaluate(&hand), value);
+    }
+
+    fn description(explanation: &str) -> HandDescription {
+        let mut description = HandDescription::unknown();
+        if !explanation.is_empty() {
+            description.narrow(&Explanation::from_str(explanation).unwrap());
+        }
+        description
+    }
+
+    #[test_case("S:KQ52,H:AJ93,D:Q4,C:872", Some(Suit::Spades), 1.0; "Double fit, but honors in partner's singleton")]
+    #[test_case("S:2,H:KQJ9652,D:A43,C:72", None, -3.0; "Misfit")]
+    fn pair_of_hands(hand: &str, trump_suit: Option<Suit>, expected: f64) {
+        let hand = Hand::from_str(hand).unwrap();
+        let partner = HandDescription::of_hand(&Hand::from_str("S:AJ943,H:T874,D:7,C:AK3").unwrap());
+        let partner = match trump_suit {
+            Some(_) => partner,
+            None => description("Hand has 5 to 13 cards in ♠."),
+        };
+        let unknown = HandDescription::unknown();
+        let adjustment =
+            ForumDPlus2015Evaluator::partnership_adjustment(&hand, &partner, &unknown, &unknown, trump_suit);
+        assert_eq!(adjustment, expected);
+    }
+
+    #[test_case("S:AK52,H:K93,D:KQ4,C:872", None, 0.0; "Honors behind and in front of the opponents")]
+    #[test_case("S:AK52,H:A93,D:KQ4,C:872", None, 1.0; "Aces keep their value")]
+    #[test_case("S:Q52,H:J93,D:AQ4,C:QJ72", Some(Suit::Spades), -4.5; "Unguarded queens and jacks in dummy")]
+    #[test_case("S:KJ52,H:A943,D:7,C:Q872", Some(Suit::Spades), -0.5; "Shortness in the opponents' suit")]
+    fn partnership_adjustment(hand: &str, trump_suit: Option<Suit>, expected: f64) {
+        let hand = Hand::from_str(hand).unwrap();
+        let (partner, left_opponent, right_opponent) = match trump_suit {
+            Some(_) => (
+                description("Hand has 5 to 13 cards in ♠."),
+                description("Hand has 5 to 13 cards in ♦."),
+                description(""),
+            ),
+            None => (
+                description(""),
+                description("Hand has 5 to 13 cards in ♥."),
+                description("Hand has 5 to 13 cards in ♦."),
+            ),
+        };
+        let adjustment = ForumDPlus2015Evaluator::partnership_adjustment(
+            &hand,
+            &partner,
+            &left_opponent,
+            &right_opponent,
+            trump_suit,
+        );
+        assert_eq!(adjustment, expected);
+    }
 }
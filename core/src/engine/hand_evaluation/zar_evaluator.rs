@@ -0,0 +1,65 @@
+use crate::engine::hand_evaluation::{ForumDPlus2015Evaluator, HandEvaluator};
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::card::Rank::*;
+use crate::primitives::Hand;
+use itertools::Itertools;
+
+#[derive(Debug)]
+pub struct ZarEvaluator {}
+
+impl ZarEvaluator {
+    pub fn control_points(hand: &Hand<13>) -> f64 {
+        hand.cards()
+            .map(|card| match card.rank {
+                Ace => 2.0,
+                King => 1.0,
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    // the two longest suits, plus the difference between the longest and the shortest
+    pub fn distribution_points(hand: &Hand<13>) -> f64 {
+        let lengths = SUIT_ARRAY
+            .iter()
+            .map(|&suit| hand.length_in(suit) as f64)
+            .sorted_by(|a, b| b.total_cmp(a))
+            .collect_vec();
+        lengths[0] + lengths[1] + lengths[0] - lengths[3]
+    }
+}
+
+impl HandEvaluator for ZarEvaluator {
+    fn name(&self) -> &'static str {
+        "Zar"
+    }
+
+    fn breakdown(&self, hand: &Hand<13>) -> Vec<(&'static str, f64)> {
+        vec![
+            ("high-card points", ForumDPlus2015Evaluator::hcp(hand)),
+            ("controls", Self::control_points(hand)),
+            ("distribution", Self::distribution_points(hand)),
+        ]
+    }
+
+    fn opening_value(&self) -> f64 {
+        26.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::hand_evaluation::zar_evaluator::ZarEvaluator;
+    use crate::engine::hand_evaluation::HandEvaluator;
+    use crate::primitives::Hand;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    #[test_case("S:T93,H:AKQ5,D:QJ,C:T542", 25.0; "Board 1.N")]
+    #[test_case("S:AKJ52,H:943,D:982,C:87", 22.0; "Board 1.W")]
+    #[test_case("S:AKQJ2,H:AK943,D:98,C:7", 37.0; "Two-suiter")]
+    fn evaluate(hand: &str, expected: f64) {
+        let hand = Hand::from_str(hand).unwrap();
+        assert_eq!(ZarEvaluator {}.evaluate(&hand), expected);
+    }
+}
@@ -3,6 +3,7 @@ use crate::engine::bidding_engine::SelectBid;
 use crate::engine::card_play_engine::mock_card_play_engine::MockCardPlayEngine;
 use crate::engine::card_play_engine::SelectCard;
 use crate::engine::engine_state::EngineState;
+use crate::engine::reason::Reason;
 use crate::engine::subjective_game_view::{SubjectiveGamePhaseStateView, SubjectiveGameStateView};
 use crate::engine::SelectMove;
 use crate::error::BBError;
@@ -11,40 +12,94 @@ use crate::game::game_phase_states::{BiddingState, CardPlayState, OpeningLeadSta
 use crate::primitives::bid::Bid;
 use crate::primitives::deal::Seat;
 use crate::primitives::game_event::{
-    BidEvent, BiddingEndedEvent, CardEvent, DiscloseHandEvent, DummyUncoveredEvent, GameEndedEvent, GameEvent,
-    NewGameEvent,
+    BidEvent, BiddingEndedEvent, CardEvent, ClaimEvent, ClaimResponseEvent, ClaimRulingEvent, ConcedeEvent,
+    DiscloseHandEvent, DummyUncoveredEvent, ExposedCardEvent, GameEndedEvent, GameEvent, IrregularityEvent,
+    IrregularityResponseEvent, LeadPenaltyEvent, NewGameEvent, UndoEvent, UndoRequestEvent, UndoResponseEvent,
 };
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::player_event::PlayerEvent;
 use crate::primitives::Card;
+use std::cell::RefCell;
 
 pub struct MockBridgeEngine {
-    bidding_engine: MockBiddingEngine,
-    card_play_engine: MockCardPlayEngine,
-    _engine_state: EngineState,
+    bidding_engine: Box<dyn SelectBid>,
+    card_play_engine: Box<dyn SelectCard>,
+    engine_state: EngineState,
+    last_bid: RefCell<Option<(Bid, Reason)>>,
+    last_card: RefCell<Option<(Card, Reason)>>,
 }
 
 impl MockBridgeEngine {
     pub fn new(seat: Seat) -> Self {
+        Self::with_bidding_engine(seat, MockBiddingEngine::new())
+    }
+
+    pub fn with_bidding_engine<T: SelectBid + 'static>(seat: Seat, bidding_engine: T) -> Self {
         Self {
-            bidding_engine: MockBiddingEngine::new(),
-            card_play_engine: MockCardPlayEngine::new(seat),
-            _engine_state: EngineState::empty(),
+            bidding_engine: Box::new(bidding_engine),
+            card_play_engine: Box::new(MockCardPlayEngine::new(seat)),
+            engine_state: EngineState::new(seat),
+            last_bid: RefCell::new(None),
+            last_card: RefCell::new(None),
         }
     }
+
+    pub fn set_card_play_engine<T: SelectCard + 'static>(&mut self, card_play_engine: T) {
+        self.card_play_engine = Box::new(card_play_engine);
+    }
+
+    pub fn engine_state(&self) -> &EngineState {
+        &self.engine_state
+    }
 }
 
 impl SelectBid for MockBridgeEngine {
     fn select_bid(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> Bid {
-        self.bidding_engine.select_bid(state)
+        self.select_bid_with_reason(state).0
+    }
+
+    fn select_bid_with_reason(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> (Bid, Reason) {
+        let (bid, reason) = self.bidding_engine.select_bid_with_reason(state);
+        *self.last_bid.borrow_mut() = Some((bid, reason.clone()));
+        (bid, reason)
+    }
+
+    fn alert(&self, auction: &[Bid], bid: Bid) -> Option<Explanation> {
+        self.bidding_engine.alert(auction, bid)
+    }
+
+    fn interpret(&self, auction: &[Bid], bid: Bid) -> Explanation {
+        self.bidding_engine.interpret(auction, bid)
     }
 }
 
 impl SelectCard for MockBridgeEngine {
     fn select_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card {
-        self.card_play_engine.select_card(state)
+        self.select_card_with_reason(state).0
     }
 
     fn select_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Card {
-        self.card_play_engine.select_opening_lead(state)
+        self.select_opening_lead_with_reason(state).0
+    }
+
+    fn select_card_with_reason(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> (Card, Reason) {
+        let (card, reason) = self.card_play_engine.select_card_with_reason(state);
+        *self.last_card.borrow_mut() = Some((card, reason.clone()));
+        (card, reason)
+    }
+
+    fn select_opening_lead_with_reason(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> (Card, Reason) {
+        let (card, reason) = self.card_play_engine.select_opening_lead_with_reason(state);
+        *self.last_card.borrow_mut() = Some((card, reason.clone()));
+        (card, reason)
+    }
+
+    fn claim(&self, state: &SubjectiveGamePhaseStateView<CardPlayState>) -> Option<usize> {
+        self.card_play_engine.claim(state)
+    }
+
+    fn respond_to_claim(&self, state: &SubjectiveGamePhaseStateView<CardPlayState>, claim: ClaimEvent) -> bool {
+        self.card_play_engine.respond_to_claim(state, claim)
     }
 }
 
@@ -57,9 +112,34 @@ impl SelectMove for MockBridgeEngine {
             GameEvent::BiddingEnded(event) => self.process_bidding_ended_event(event, game_state),
             GameEvent::Card(event) => self.process_card_event(event, game_state),
             GameEvent::DummyUncovered(event) => self.process_dummy_uncovered_event(event, game_state),
+            GameEvent::Claim(event) => self.process_claim_event(event, game_state),
+            GameEvent::Concede(event) => self.process_concede_event(event, game_state),
+            GameEvent::ClaimResponse(event) => self.process_claim_response_event(event, game_state),
+            GameEvent::ClaimRuling(event) => self.process_claim_ruling_event(event, game_state),
+            GameEvent::UndoRequest(event) => self.process_undo_request_event(event, game_state),
+            GameEvent::UndoResponse(event) => self.process_undo_response_event(event, game_state),
+            GameEvent::Undo(event) => self.process_undo_event(event, game_state),
+            GameEvent::Irregularity(event) => self.process_irregularity_event(event, game_state),
+            GameEvent::IrregularityResponse(event) => self.process_irregularity_response_event(event, game_state),
+            GameEvent::ExposedCard(event) => self.process_exposed_card_event(event, game_state),
+            GameEvent::LeadPenalty(event) => self.process_lead_penalty_event(event, game_state),
             GameEvent::GameEnded(event) => self.process_game_ended_event(event, game_state),
         }
     }
+
+    fn explain_move(&self, event: PlayerEvent) -> Option<Reason> {
+        match event {
+            PlayerEvent::Bid(bid_event) => match self.last_bid.borrow().as_ref() {
+                Some((bid, reason)) if *bid == bid_event.bid => Some(reason.clone()),
+                _ => None,
+            },
+            PlayerEvent::Card(card_event) => match self.last_card.borrow().as_ref() {
+                Some((card, reason)) if *card == card_event.card => Some(reason.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
 
 impl MockBridgeEngine {
@@ -74,24 +154,35 @@ impl MockBridgeEngine {
     fn process_disclose_hand_event(
         &mut self,
         _event: DiscloseHandEvent,
-        _game_state: SubjectiveGameStateView,
+        game_state: SubjectiveGameStateView,
     ) -> Result<(), BBError> {
+        // hands are dealt at the start of a new game
+        if let SubjectiveGameStateView::Bidding(state) = game_state {
+            if state.bids().is_empty() {
+                self.engine_state.reset();
+            }
+        }
         Ok(())
     }
 
     fn process_bid_event(&mut self, event: BidEvent, game_state: SubjectiveGameStateView) -> Result<(), BBError> {
         match game_state {
             SubjectiveGameStateView::Bidding(state) => self.interpret_bid(event, state),
-            _ => Err(BBError::InvalidEvent(Box::new(GameEvent::Bid(event)))),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", event))),
         }
     }
 
     fn interpret_bid(
         &mut self,
-        _event: BidEvent,
-        _data: SubjectiveGamePhaseStateView<BiddingState>,
+        event: BidEvent,
+        data: SubjectiveGamePhaseStateView<BiddingState>,
     ) -> Result<(), BBError> {
-        // let player = data.next_to_play();
+        // alerted calls of the opponents come with their explanation
+        let explanation = match event.explanation {
+            Some(explanation) => explanation,
+            None => self.interpret(&data.bids(), event.bid),
+        };
+        self.engine_state.process_call(event.player, explanation);
         Ok(())
     }
 
@@ -107,7 +198,7 @@ impl MockBridgeEngine {
         match game_state {
             SubjectiveGameStateView::OpeningLead(state) => self.interpret_opening_lead(event, state),
             SubjectiveGameStateView::CardPlay(state) => self.interpret_card(event, state),
-            _ => Err(BBError::InvalidEvent(Box::new(GameEvent::Card(event)))),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", event))),
         }
     }
 
@@ -135,6 +226,89 @@ impl MockBridgeEngine {
         Ok(())
     }
 
+    fn process_claim_event(&mut self, _event: ClaimEvent, _game_state: SubjectiveGameStateView) -> Result<(), BBError> {
+        Ok(())
+    }
+
+    fn process_concede_event(
+        &mut self,
+        _event: ConcedeEvent,
+        _game_state: SubjectiveGameStateView,
+    ) -> Result<(), BBError> {
+        Ok(())
+    }
+
+    fn process_claim_response_event(
+        &mut self,
+        _event: ClaimResponseEvent,
+        _game_state: SubjectiveGameStateView,
+    ) -> Result<(), BBError> {
+        Ok(())
+    }
+
+    fn process_claim_ruling_event(
+        &mut self,
+        _event: ClaimRulingEvent,
+        _game_state: SubjectiveGameStateView,
+    ) -> Result<(), BBError> {
+        Ok(())
+    }
+
+    fn process_undo_request_event(
+        &mut self,
+        _event: UndoRequestEvent,
+        _game_state: SubjectiveGameStateView,
+    ) -> Result<(), BBError> {
+        Ok(())
+    }
+
+    fn process_undo_response_event(
+        &mut self,
+        _event: UndoResponseEvent,
+        _game_state: SubjectiveGameStateView,
+    ) -> Result<(), BBError> {
+        Ok(())
+    }
+
+    fn process_undo_event(&mut self, event: UndoEvent, game_state: SubjectiveGameStateView) -> Result<(), BBError> {
+        if let SubjectiveGameStateView::Bidding(_) = game_state {
+            self.engine_state.undo_calls(event.moves);
+        }
+        Ok(())
+    }
+
+    fn process_irregularity_event(
+        &mut self,
+        _event: IrregularityEvent,
+        _game_state: SubjectiveGameStateView,
+    ) -> Result<(), BBError> {
+        Ok(())
+    }
+
+    fn process_irregularity_response_event(
+        &mut self,
+        _event: IrregularityResponseEvent,
+        _game_state: SubjectiveGameStateView,
+    ) -> Result<(), BBError> {
+        Ok(())
+    }
+
+    fn process_exposed_card_event(
+        &mut self,
+        _event: ExposedCardEvent,
+        _game_state: SubjectiveGameStateView,
+    ) -> Result<(), BBError> {
+        Ok(())
+    }
+
+    fn process_lead_penalty_event(
+        &mut self,
+        _event: LeadPenaltyEvent,
+        _game_state: SubjectiveGameStateView,
+    ) -> Result<(), BBError> {
+        Ok(())
+    }
+
     fn process_game_ended_event(
         &mut self,
         _event: GameEndedEvent,
@@ -143,3 +317,104 @@ impl MockBridgeEngine {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::MockBridgeEngine;
+    use crate::engine::bidding_engine::forum_d_plus_2015_bidding_engine::ForumDPlus2015BiddingEngine;
+    use crate::engine::bidding_engine::SelectBid;
+    use crate::engine::engine_state::HandDescription;
+    use crate::engine::subjective_game_view::{SubjectiveGameStateView, SubjectiveSeat};
+    use crate::engine::SelectMove;
+    use crate::game::GameState;
+    use crate::primitives::bid::Bid;
+    use crate::primitives::deal::seat::SEAT_ARRAY;
+    use crate::primitives::deal::Seat;
+    use crate::primitives::game_event::{BidEvent, DiscloseHandEvent, GameEvent, NewGameEvent};
+    use crate::primitives::Deal;
+    use crate::primitives::Suit::*;
+    use std::str::FromStr;
+
+    fn process(engine: &mut MockBridgeEngine, game: &mut GameState, event: GameEvent) {
+        engine
+            .process_game_event(event, SubjectiveGameStateView::new(game, Seat::South))
+            .unwrap();
+        game.process_game_event(event).unwrap();
+    }
+
+    #[test]
+    fn infers_hands_from_the_auction() {
+        let deal: Deal<13> = Deal::from_u64_seed(9000);
+        let mut game = GameState::from_new_game_event(NewGameEvent {
+            board: deal.board,
+            director: false,
+            mini_bridge: false,
+        });
+        let mut engine = MockBridgeEngine::with_bidding_engine(Seat::South, ForumDPlus2015BiddingEngine::new());
+        for seat in SEAT_ARRAY {
+            let event = GameEvent::DiscloseHand(DiscloseHandEvent {
+                seat,
+                hand: *deal.hand_of(seat),
+            });
+            process(&mut engine, &mut game, event);
+        }
+
+        // West deals
+        for (index, bid) in ["P", "1NT", "P", "2H"].into_iter().enumerate() {
+            let event = GameEvent::Bid(BidEvent {
+                player: Seat::West + index,
+                bid: Bid::from_str(bid).unwrap(),
+                alert: false,
+                explanation: None,
+            });
+            process(&mut engine, &mut game, event);
+        }
+
+        let state = engine.engine_state();
+        let west = state.hand_description(SubjectiveSeat::LeftHandOpponent);
+        assert_eq!(west.total_points().0, 0.0..=11.0);
+        let north = state.hand_description(SubjectiveSeat::Partner);
+        assert_eq!(north.hcp().0, 15.0..=17.0);
+        assert_eq!(north.suit_length(Spades).0, 2..=5);
+        let east = state.hand_description(SubjectiveSeat::RightHandOpponent);
+        assert_eq!(east, &HandDescription::unknown());
+        let me = state.hand_description(SubjectiveSeat::Myself);
+        assert_eq!(me.suit_length(Spades).0, 5..=13);
+    }
+
+    #[test]
+    fn explains_its_call() {
+        let deal: Deal<13> = Deal::from_u64_seed(9000);
+        let mut game = GameState::from_new_game_event(NewGameEvent {
+            board: deal.board,
+            director: false,
+            mini_bridge: false,
+        });
+        for seat in SEAT_ARRAY {
+            let event = GameEvent::DiscloseHand(DiscloseHandEvent {
+                seat,
+                hand: *deal.hand_of(seat),
+            });
+            game.process_game_event(event).unwrap();
+        }
+        // West deals
+        for (index, bid) in ["P", "1NT", "P"].into_iter().enumerate() {
+            let event = GameEvent::Bid(BidEvent {
+                player: Seat::West + index,
+                bid: Bid::from_str(bid).unwrap(),
+                alert: false,
+                explanation: None,
+            });
+            game.process_game_event(event).unwrap();
+        }
+
+        let engine = MockBridgeEngine::with_bidding_engine(Seat::South, ForumDPlus2015BiddingEngine::new());
+        let SubjectiveGameStateView::Bidding(view) = SubjectiveGameStateView::new(&game, Seat::South) else {
+            panic!("not bidding")
+        };
+        let (bid, reason) = engine.select_bid_with_reason(view);
+        assert_eq!(bid, engine.select_bid(view));
+        assert_eq!(reason.rule, "Response to partner's opening");
+        assert!(reason.shows.is_some());
+    }
+}
@@ -1,42 +1,127 @@
+use crate::actors::card_analyser::CardAnalysis;
 use crate::engine::bidding_engine::SelectBid;
 use crate::engine::card_play_engine::SelectCard;
-use crate::engine::subjective_game_view::SubjectiveGameStateView;
+use crate::engine::reason::Reason;
+use crate::engine::subjective_game_view::{SubjectiveGamePhaseStateView, SubjectiveGameStateView};
 use crate::error::BBError;
-use crate::primitives::bid::Bid;
-use crate::primitives::game_event::GameEvent;
+use crate::game::game_phase_states::{CardPlayState, OpeningLeadState};
+use crate::primitives::bid::{AuxiliaryBid, Bid};
+use crate::primitives::game_event::{GameEvent, IrregularityEvent, LeadPenalty, UndoRequestEvent};
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::player_event::PlayerEvent;
 use crate::primitives::Card;
 
 pub mod bidding_engine;
+pub mod card_locations;
 pub mod card_play_engine;
-mod engine_state;
+pub mod distribution_odds;
+pub mod engine_state;
 pub mod hand_evaluation;
 pub mod mock_bridge_engine;
+pub mod observer_game_view;
+pub mod reason;
 pub mod subjective_game_view;
 
 pub enum Move {
     Bid(Bid),
+    AlertedBid(Bid, Option<Explanation>),
     Card(Card),
+    Claim(usize),
+    Concede,
+    ClaimResponse(bool),
+    UndoRequest,
+    UndoResponse(bool),
+    IrregularityResponse(bool),
+    LeadPenalty(LeadPenalty),
 }
 
-pub trait SelectMove: SelectCard + SelectBid {
-    fn select_move(&self, game: SubjectiveGameStateView) -> Result<Move, BBError> {
+pub trait SelectMove<const N: usize = 13>: SelectCard<N> + SelectBid<N> {
+    fn select_move(&self, game: SubjectiveGameStateView<N>) -> Result<Move, BBError> {
+        if let Some(request) = game.pending_undo_request() {
+            return Ok(Move::UndoResponse(self.respond_to_undo_request(&game, request)));
+        }
+        if let Some(irregularity) = game.pending_irregularity() {
+            return Ok(Move::IrregularityResponse(
+                self.respond_to_irregularity(&game, irregularity),
+            ));
+        }
+        if game.lead_penalty_is_due() {
+            return Ok(Move::LeadPenalty(self.choose_lead_penalty(&game)));
+        }
         match game {
+            SubjectiveGameStateView::Bidding(state) if state.must_pass() => {
+                Ok(Move::Bid(Bid::Auxiliary(AuxiliaryBid::Pass)))
+            }
             SubjectiveGameStateView::Bidding(state) => {
+                let auction = state.bids();
                 let bid = self.select_bid(state);
-                Ok(Move::Bid(bid))
+                match self.alert(&auction, bid) {
+                    Some(explanation) if explanation.is_empty() => Ok(Move::AlertedBid(bid, None)),
+                    Some(explanation) => Ok(Move::AlertedBid(bid, Some(explanation))),
+                    None => Ok(Move::Bid(bid)),
+                }
             }
             SubjectiveGameStateView::OpeningLead(state) => {
-                let card = self.select_opening_lead(state);
-                Ok(Move::Card(card))
-            }
-            SubjectiveGameStateView::CardPlay(state) => {
-                let card = self.select_card(state);
+                let card = self.select_legal_opening_lead(state);
                 Ok(Move::Card(card))
             }
+            SubjectiveGameStateView::CardPlay(state) => match state.pending_claim() {
+                Some(claim) => Ok(Move::ClaimResponse(self.respond_to_claim(&state, claim))),
+                None if state.may_claim() => match self.claim(&state) {
+                    Some(0) => Ok(Move::Concede),
+                    Some(tricks) => Ok(Move::Claim(tricks)),
+                    None => Ok(Move::Card(self.select_legal_card(state))),
+                },
+                None => Ok(Move::Card(self.select_legal_card(state))),
+            },
             SubjectiveGameStateView::WaitingForDummy(_) => Err(BBError::OutOfTurn(None)),
             SubjectiveGameStateView::Ended(_) => Err(BBError::GameHasEnded),
         }
     }
 
-    fn process_game_event(&mut self, event: GameEvent, game_state: SubjectiveGameStateView) -> Result<(), BBError>;
+    fn respond_to_undo_request(&self, _game: &SubjectiveGameStateView<N>, _request: UndoRequestEvent) -> bool {
+        true
+    }
+
+    fn respond_to_irregularity(&self, _game: &SubjectiveGameStateView<N>, _irregularity: IrregularityEvent) -> bool {
+        false
+    }
+
+    fn choose_lead_penalty(&self, _game: &SubjectiveGameStateView<N>) -> LeadPenalty {
+        LeadPenalty::PenaltyCardsRemain
+    }
+
+    // engines don't know about penalty cards, so fall back to a legal card if needed
+    fn select_legal_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState<N>>) -> Card {
+        let legal_cards = state.legal_cards(state.next_to_play());
+        let card = self.select_card(state);
+        match legal_cards.first() {
+            Some(&legal_card) if !legal_cards.contains(&card) => legal_card,
+            _ => card,
+        }
+    }
+
+    fn select_legal_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState<N>>) -> Card {
+        let legal_cards = state.legal_cards();
+        let card = self.select_opening_lead(state);
+        match legal_cards.first() {
+            Some(&legal_card) if !legal_cards.contains(&card) => legal_card,
+            _ => card,
+        }
+    }
+
+    fn process_game_event(
+        &mut self,
+        event: GameEvent<N>,
+        game_state: SubjectiveGameStateView<N>,
+    ) -> Result<(), BBError>;
+
+    fn process_card_analysis(&mut self, _analysis: CardAnalysis) {}
+
+    // only for the engine's last move
+    fn explain_move(&self, _event: PlayerEvent) -> Option<Reason> {
+        None
+    }
+
+    fn process_move_reason(&mut self, _event: PlayerEvent, _reason: Reason) {}
 }
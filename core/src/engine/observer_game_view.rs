@@ -0,0 +1,141 @@
+use crate::game::game_phase_states::GamePhaseState;
+use crate::game::hand_manager::HandManager;
+use crate::game::GameState;
+use crate::primitives::bid::Bid;
+use crate::primitives::bid_line::BidLine;
+use crate::primitives::deal::{Board, Seat, Vulnerability};
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::trick::{ActiveTrick, PlayedTrick};
+use crate::primitives::{Card, Contract, Hand};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Observer {
+    Kibitzer,
+    Public,
+}
+
+pub struct ObserverGameStateView<'a, const N: usize = 13> {
+    observer: Observer,
+    game_state: &'a GameState<N>,
+}
+
+impl<'a, const N: usize> ObserverGameStateView<'a, N> {
+    pub fn new(game_state: &'a GameState<N>, observer: Observer) -> Self {
+        Self { observer, game_state }
+    }
+
+    pub fn observer(&self) -> Observer {
+        self.observer
+    }
+
+    pub fn board(&self) -> Board {
+        self.game_state.board()
+    }
+
+    pub fn dealer(&self) -> Seat {
+        self.board().dealer()
+    }
+
+    pub fn vulnerability(&self) -> Vulnerability {
+        self.board().vulnerability()
+    }
+
+    pub fn next_to_play(&self) -> Option<Seat> {
+        self.game_state.next_to_play()
+    }
+
+    pub fn bids(&self) -> Vec<Bid> {
+        self.bid_line().bids().to_vec()
+    }
+
+    // alerts are announced to the opponents, so observers of both sides learn all of them
+    pub fn alerts(&self) -> Vec<bool> {
+        self.bid_line().alerts().to_vec()
+    }
+
+    pub fn explanations(&self) -> Vec<Option<Explanation>> {
+        self.bid_line().explanations().to_vec()
+    }
+
+    pub fn contract(&self) -> Option<Contract> {
+        match self.game_state {
+            GameState::Bidding(_) => None,
+            GameState::OpeningLead(state) => Some(state.contract),
+            GameState::WaitingForDummy(state) => Some(state.contract),
+            GameState::CardPlay(state) => Some(state.contract),
+            GameState::Ended(state) => state.implied_contract(),
+        }
+    }
+
+    pub fn declarer(&self) -> Option<Seat> {
+        self.game_state.declarer()
+    }
+
+    pub fn dummy(&self) -> Option<Seat> {
+        self.declarer().map(|declarer| declarer.partner())
+    }
+
+    pub fn can_see_hand_of(&self, seat: Seat) -> bool {
+        match self.observer {
+            Observer::Kibitzer => true,
+            Observer::Public => self.dummy_is_uncovered() && self.dummy() == Some(seat),
+        }
+    }
+
+    pub fn hand_of(&self, seat: Seat) -> Option<Hand<N>> {
+        match self.can_see_hand_of(seat) {
+            true => self.game_state.hand_of(seat).ok(),
+            false => None,
+        }
+    }
+
+    pub fn remaining_cards_of(&self, seat: Seat) -> Option<Vec<Card>> {
+        match self.can_see_hand_of(seat) {
+            true => Some(self.hand_manager().known_remaining_cards_of(seat)),
+            false => None,
+        }
+    }
+
+    pub fn played_tricks(&self) -> Vec<PlayedTrick> {
+        match self.game_state {
+            GameState::Bidding(_) => Vec::new(),
+            GameState::OpeningLead(state) => state.trick_manager.played_tricks(),
+            GameState::WaitingForDummy(state) => state.trick_manager.played_tricks(),
+            GameState::CardPlay(state) => state.trick_manager.played_tricks(),
+            GameState::Ended(state) => state.tricks.clone(),
+        }
+    }
+
+    pub fn active_trick(&self) -> Option<ActiveTrick> {
+        match self.game_state {
+            GameState::OpeningLead(state) => Some(state.trick_manager.current_trick()),
+            GameState::WaitingForDummy(state) => Some(state.trick_manager.current_trick()),
+            GameState::CardPlay(state) => Some(state.trick_manager.current_trick()),
+            _ => None,
+        }
+    }
+
+    fn dummy_is_uncovered(&self) -> bool {
+        matches!(self.game_state, GameState::CardPlay(_) | GameState::Ended(_))
+    }
+
+    fn bid_line(&self) -> BidLine {
+        match self.game_state {
+            GameState::Bidding(state) => state.bid_manager.bid_line(),
+            GameState::OpeningLead(state) => state.bids.clone(),
+            GameState::WaitingForDummy(state) => state.bids.clone(),
+            GameState::CardPlay(state) => state.bids.clone(),
+            GameState::Ended(state) => state.bids.clone(),
+        }
+    }
+
+    fn hand_manager(&self) -> &HandManager<N> {
+        match self.game_state {
+            GameState::Bidding(state) => &state.hand_manager,
+            GameState::OpeningLead(state) => &state.hand_manager,
+            GameState::WaitingForDummy(state) => &state.hand_manager,
+            GameState::CardPlay(state) => &state.hand_manager,
+            GameState::Ended(state) => &state.hands,
+        }
+    }
+}
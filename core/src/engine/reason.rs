@@ -0,0 +1,75 @@
+use crate::primitives::hand_info::explanation::Explanation;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Reason {
+    // empty if the engine can't tell
+    pub rule: String,
+    pub shows: Option<Explanation>,
+    // e.g. how the cards did in the double dummy samples
+    pub details: Vec<String>,
+}
+
+impl Reason {
+    pub fn from_rule(rule: &str) -> Self {
+        Reason {
+            rule: rule.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn showing(mut self, shows: Explanation) -> Self {
+        self.shows = Some(shows);
+        self
+    }
+
+    pub fn with_detail(mut self, detail: String) -> Self {
+        self.details.push(detail);
+        self
+    }
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.rule.is_empty() {
+            writeln!(f, "Rule: {}", self.rule)?;
+        }
+        match &self.shows {
+            Some(shows) if shows.hand_info().is_empty() => writeln!(f, "Shows nothing in particular.")?,
+            Some(shows) => {
+                writeln!(f, "Shows:")?;
+                for info in shows.hand_info() {
+                    writeln!(f, "  {}", info)?;
+                }
+            }
+            None => (),
+        }
+        for detail in &self.details {
+            writeln!(f, "{}", detail)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Reason;
+    use crate::primitives::hand_info::explanation::Explanation;
+    use std::str::FromStr;
+
+    #[test]
+    fn display() {
+        let reason = Reason::from_rule("Opening")
+            .showing(Explanation::from_str("Hand is balanced. Hand has 15 to 17 hcp.").unwrap())
+            .with_detail("No five-card major.".into());
+        assert_eq!(
+            format!("{}", reason),
+            "Rule: Opening\nShows:\n  Hand is balanced.\n  Hand has 15 to 17 hcp.\nNo five-card major.\n"
+        );
+    }
+
+    #[test]
+    fn unknown_reason() {
+        assert_eq!(format!("{}", Reason::default()), "");
+    }
+}
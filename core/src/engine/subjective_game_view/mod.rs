@@ -1,15 +1,20 @@
+use crate::engine::card_locations::{CardLocationTracker, CardLocations};
+use crate::engine::engine_state::EngineState;
 use crate::engine::subjective_game_view::subjective_vulnerability::SubjectiveVulnerability;
 use crate::engine::subjective_game_view::subjectiviser::Subjectiviser;
 use crate::error::BBError;
 use crate::game::game_phase_states::{BiddingState, CardPlayState, EndedState, GamePhaseState, NextToPlay};
 use crate::game::game_phase_states::{OpeningLeadState, WaitingForDummyState};
+use crate::game::irregularity_manager::IrregularityManager;
 use crate::game::GameState;
 use crate::primitives::bid::{Bid, ContractBid};
+use crate::primitives::deal::seat::SEAT_ARRAY;
 use crate::primitives::deal::Seat;
-use crate::primitives::{Card, Hand, Suit};
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::{Card, Contract, Hand, Suit};
 
 use crate::engine::subjective_game_view::subjective_trick::SubjectiveTrick;
-use crate::primitives::game_event::CardEvent;
+use crate::primitives::game_event::{CardEvent, ClaimEvent, IrregularityEvent, UndoRequestEvent};
 use crate::primitives::trick::Trick;
 pub use subjective_seat::SubjectiveSeat;
 
@@ -19,15 +24,15 @@ mod subjective_trick;
 mod subjective_vulnerability;
 pub mod subjectiviser;
 
-pub enum SubjectiveGameStateView<'a> {
-    Bidding(SubjectiveGamePhaseStateView<'a, BiddingState>),
-    OpeningLead(SubjectiveGamePhaseStateView<'a, OpeningLeadState>),
-    WaitingForDummy(SubjectiveGamePhaseStateView<'a, WaitingForDummyState>),
-    CardPlay(SubjectiveGamePhaseStateView<'a, CardPlayState>),
-    Ended(SubjectiveGamePhaseStateView<'a, EndedState>),
+pub enum SubjectiveGameStateView<'a, const N: usize = 13> {
+    Bidding(SubjectiveGamePhaseStateView<'a, BiddingState<N>>),
+    OpeningLead(SubjectiveGamePhaseStateView<'a, OpeningLeadState<N>>),
+    WaitingForDummy(SubjectiveGamePhaseStateView<'a, WaitingForDummyState<N>>),
+    CardPlay(SubjectiveGamePhaseStateView<'a, CardPlayState<N>>),
+    Ended(SubjectiveGamePhaseStateView<'a, EndedState<N>>),
 }
 
-impl<'a> SubjectiveGameStateView<'a> {
+impl<'a, const N: usize> SubjectiveGameStateView<'a, N> {
     pub fn next_to_play(&self) -> Option<SubjectiveSeat> {
         match &self {
             SubjectiveGameStateView::Bidding(state) => Some(state.next_to_play()),
@@ -37,7 +42,7 @@ impl<'a> SubjectiveGameStateView<'a> {
             SubjectiveGameStateView::Ended(_) => None,
         }
     }
-    pub fn new(game_state: &'a GameState, seat: Seat) -> Self {
+    pub fn new(game_state: &'a GameState<N>, seat: Seat) -> Self {
         match game_state {
             GameState::Bidding(data) => Self::Bidding(SubjectiveGamePhaseStateView::new_bidding(data, seat)),
             GameState::OpeningLead(data) => {
@@ -51,7 +56,7 @@ impl<'a> SubjectiveGameStateView<'a> {
         }
     }
 
-    pub fn my_starting_hand(&self) -> Result<Hand<13>, BBError> {
+    pub fn my_starting_hand(&self) -> Result<Hand<N>, BBError> {
         match self {
             Self::Bidding(data) => data.my_starting_hand(),
             Self::OpeningLead(data) => data.my_starting_hand(),
@@ -100,6 +105,32 @@ impl<'a> SubjectiveGameStateView<'a> {
             Self::Ended(data) => data.declarer(),
         }
     }
+
+    pub fn pending_undo_request(&self) -> Option<UndoRequestEvent> {
+        match &self {
+            Self::Bidding(data) => data.pending_undo_request(),
+            Self::OpeningLead(data) => data.pending_undo_request(),
+            Self::CardPlay(data) => data.pending_undo_request(),
+            _ => None,
+        }
+    }
+
+    pub fn pending_irregularity(&self) -> Option<IrregularityEvent> {
+        match &self {
+            Self::Bidding(data) => data.pending_irregularity(),
+            Self::OpeningLead(data) => data.pending_irregularity(),
+            Self::CardPlay(data) => data.pending_irregularity(),
+            _ => None,
+        }
+    }
+
+    pub fn lead_penalty_is_due(&self) -> bool {
+        match &self {
+            Self::OpeningLead(data) => data.lead_penalty_is_due(),
+            Self::CardPlay(data) => data.lead_penalty_is_due(),
+            _ => false,
+        }
+    }
 }
 
 impl<'a, T> SubjectiveGamePhaseStateView<'a, T>
@@ -110,6 +141,11 @@ where
         let dealer = self.game_data.dealer();
         self.subjectiviser.subjective_seat(dealer)
     }
+
+    // unlike the dealer, declarer is an absolute seat
+    pub fn contract(&self) -> Option<Contract> {
+        self.game_data.implied_contract()
+    }
 }
 
 pub struct SubjectiveGamePhaseStateView<'a, T> {
@@ -118,6 +154,15 @@ pub struct SubjectiveGamePhaseStateView<'a, T> {
     game_data: &'a T,
 }
 
+// derived, these would require `T: Clone`
+impl<T> Clone for SubjectiveGamePhaseStateView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SubjectiveGamePhaseStateView<'_, T> {}
+
 impl<'a, T> SubjectiveGamePhaseStateView<'a, T>
 where
     T: NextToPlay,
@@ -128,8 +173,8 @@ where
     }
 }
 
-impl<'a> SubjectiveGamePhaseStateView<'a, BiddingState> {
-    pub fn new_bidding(game_data: &'a BiddingState, seat: Seat) -> Self {
+impl<'a, const N: usize> SubjectiveGamePhaseStateView<'a, BiddingState<N>> {
+    pub fn new_bidding(game_data: &'a BiddingState<N>, seat: Seat) -> Self {
         Self {
             seat,
             subjectiviser: Subjectiviser::new(seat),
@@ -141,11 +186,43 @@ impl<'a> SubjectiveGamePhaseStateView<'a, BiddingState> {
         self.game_data.bid_manager.bids().to_vec()
     }
 
+    pub fn alerts(&self) -> Vec<bool> {
+        let bid_line = self.game_data.bid_manager.bid_line();
+        hide_partners_annotations(bid_line.alerts(), self.game_data.dealer(), self.seat)
+    }
+
+    pub fn explanations(&self) -> Vec<Option<Explanation>> {
+        let bid_line = self.game_data.bid_manager.bid_line();
+        hide_partners_annotations(bid_line.explanations(), self.game_data.dealer(), self.seat)
+    }
+
     pub fn validate_bid(&self, bid: Bid) -> Result<(), BBError> {
+        self.game_data.irregularity_manager.validate_call(self.seat, bid)?;
         self.game_data.bid_manager.validate_bid(bid)
     }
 
-    pub fn my_starting_hand(&self) -> Result<Hand<13>, BBError> {
+    pub fn director_mode(&self) -> bool {
+        self.game_data.irregularity_manager.director()
+    }
+
+    pub fn is_insufficient_bid(&self, bid: Bid) -> bool {
+        self.game_data.bid_manager.is_insufficient_bid(&bid)
+    }
+
+    pub fn must_pass(&self) -> bool {
+        self.game_data.must_pass(self.seat)
+    }
+
+    pub fn pending_irregularity(&self) -> Option<IrregularityEvent> {
+        self.game_data.irregularity_manager.pending_irregularity()
+    }
+
+    pub fn may_request_undo(&self) -> bool {
+        let dealer = self.game_data.dealer();
+        (0..self.game_data.bid_manager.bids().len()).any(|index| dealer + index == self.seat)
+    }
+
+    pub fn my_starting_hand(&self) -> Result<Hand<N>, BBError> {
         self.game_data.hand_of(self.seat)
     }
 
@@ -166,6 +243,10 @@ impl<'a> SubjectiveGamePhaseStateView<'a, BiddingState> {
         self.subjectiviser.subjective_vulnerability(vul)
     }
 
+    pub fn pending_undo_request(&self) -> Option<UndoRequestEvent> {
+        self.game_data.undo_manager.pending_request()
+    }
+
     pub fn last_contract_bid(&self) -> Option<ContractBid> {
         self.game_data.bid_manager.last_contract_bid().copied()
     }
@@ -175,8 +256,8 @@ impl<'a> SubjectiveGamePhaseStateView<'a, BiddingState> {
     }
 }
 
-impl<'a> SubjectiveGamePhaseStateView<'a, OpeningLeadState> {
-    pub fn new_opening_lead(game_data: &'a OpeningLeadState, seat: Seat) -> Self {
+impl<'a, const N: usize> SubjectiveGamePhaseStateView<'a, OpeningLeadState<N>> {
+    pub fn new_opening_lead(game_data: &'a OpeningLeadState<N>, seat: Seat) -> Self {
         Self {
             seat,
             subjectiviser: Subjectiviser::new(seat),
@@ -184,7 +265,7 @@ impl<'a> SubjectiveGamePhaseStateView<'a, OpeningLeadState> {
         }
     }
 
-    pub fn my_starting_hand(&self) -> Result<Hand<13>, BBError> {
+    pub fn my_starting_hand(&self) -> Result<Hand<N>, BBError> {
         self.game_data.hand_of(self.seat)
     }
 
@@ -206,6 +287,22 @@ impl<'a> SubjectiveGamePhaseStateView<'a, OpeningLeadState> {
         self.subjectiviser.subjective_vulnerability(vul)
     }
 
+    pub fn pending_undo_request(&self) -> Option<UndoRequestEvent> {
+        self.game_data.undo_manager.pending_request()
+    }
+
+    pub fn bids(&self) -> Vec<Bid> {
+        self.game_data.bids.bids().to_vec()
+    }
+
+    pub fn alerts(&self) -> Vec<bool> {
+        hide_partners_annotations(self.game_data.bids.alerts(), self.game_data.dealer(), self.seat)
+    }
+
+    pub fn explanations(&self) -> Vec<Option<Explanation>> {
+        hide_partners_annotations(self.game_data.bids.explanations(), self.game_data.dealer(), self.seat)
+    }
+
     pub fn validate_lead(&self, card: Card) -> Result<(), BBError> {
         let card_play_event = CardEvent {
             player: self.seat,
@@ -213,10 +310,30 @@ impl<'a> SubjectiveGamePhaseStateView<'a, OpeningLeadState> {
         };
         self.game_data.validate_play_card_event(card_play_event)
     }
+
+    pub fn pending_irregularity(&self) -> Option<IrregularityEvent> {
+        self.game_data.irregularity_manager.pending_irregularity()
+    }
+
+    pub fn lead_penalty_is_due(&self) -> bool {
+        self.game_data.lead_penalty_is_due()
+    }
+
+    pub fn penalty_cards_of(&self, seat: SubjectiveSeat) -> Vec<Card> {
+        let seat = self.subjectiviser.objective_seat(seat);
+        penalty_cards_of(&self.game_data.irregularity_manager, seat)
+    }
+
+    pub fn legal_cards(&self) -> Vec<Card> {
+        self.my_remaining_cards()
+            .into_iter()
+            .filter(|&card| self.validate_lead(card).is_ok())
+            .collect()
+    }
 }
 
-impl<'a> SubjectiveGamePhaseStateView<'a, WaitingForDummyState> {
-    pub fn new_waiting_for_dummy(game_data: &'a WaitingForDummyState, seat: Seat) -> Self {
+impl<'a, const N: usize> SubjectiveGamePhaseStateView<'a, WaitingForDummyState<N>> {
+    pub fn new_waiting_for_dummy(game_data: &'a WaitingForDummyState<N>, seat: Seat) -> Self {
         Self {
             seat,
             subjectiviser: Subjectiviser::new(seat),
@@ -224,7 +341,7 @@ impl<'a> SubjectiveGamePhaseStateView<'a, WaitingForDummyState> {
         }
     }
 
-    pub fn my_starting_hand(&self) -> Result<Hand<13>, BBError> {
+    pub fn my_starting_hand(&self) -> Result<Hand<N>, BBError> {
         self.game_data.hand_of(self.seat)
     }
 
@@ -247,8 +364,8 @@ impl<'a> SubjectiveGamePhaseStateView<'a, WaitingForDummyState> {
     }
 }
 
-impl<'a> SubjectiveGamePhaseStateView<'a, CardPlayState> {
-    pub fn new_card_play(game_data: &'a CardPlayState, seat: Seat) -> Self {
+impl<'a, const N: usize> SubjectiveGamePhaseStateView<'a, CardPlayState<N>> {
+    pub fn new_card_play(game_data: &'a CardPlayState<N>, seat: Seat) -> Self {
         Self {
             seat,
             subjectiviser: Subjectiviser::new(seat),
@@ -260,7 +377,7 @@ impl<'a> SubjectiveGamePhaseStateView<'a, CardPlayState> {
         self.game_data.trick_manager.suit_to_follow()
     }
 
-    pub fn my_starting_hand(&self) -> Result<Hand<13>, BBError> {
+    pub fn my_starting_hand(&self) -> Result<Hand<N>, BBError> {
         self.game_data.hand_of(self.seat)
     }
 
@@ -274,7 +391,7 @@ impl<'a> SubjectiveGamePhaseStateView<'a, CardPlayState> {
         self.game_data.validate_play_card_event(card_play_event)
     }
 
-    pub fn dummys_starting_hand(&self) -> Result<Hand<13>, BBError> {
+    pub fn dummys_starting_hand(&self) -> Result<Hand<N>, BBError> {
         self.game_data.hand_of(self.game_data.declarer().partner())
     }
 
@@ -298,6 +415,73 @@ impl<'a> SubjectiveGamePhaseStateView<'a, CardPlayState> {
         self.subjectiviser.subjective_vulnerability(vul)
     }
 
+    pub fn bids(&self) -> Vec<Bid> {
+        self.game_data.bids.bids().to_vec()
+    }
+
+    pub fn alerts(&self) -> Vec<bool> {
+        hide_partners_annotations(self.game_data.bids.alerts(), self.game_data.dealer(), self.seat)
+    }
+
+    pub fn explanations(&self) -> Vec<Option<Explanation>> {
+        hide_partners_annotations(self.game_data.bids.explanations(), self.game_data.dealer(), self.seat)
+    }
+
+    pub fn trump_suit(&self) -> Option<Suit> {
+        self.game_data.trick_manager.trump_suit()
+    }
+
+    pub fn tricks_left(&self) -> usize {
+        self.game_data.trick_manager.tricks_left()
+    }
+
+    pub fn played_cards(&self) -> Vec<Card> {
+        self.game_data.trick_manager.played_cards().to_vec()
+    }
+
+    pub fn pending_claim(&self) -> Option<ClaimEvent> {
+        self.game_data.pending_claim()
+    }
+
+    pub fn may_claim(&self) -> bool {
+        self.game_data.may_claim()
+    }
+
+    pub fn pending_undo_request(&self) -> Option<UndoRequestEvent> {
+        self.game_data.undo_manager.pending_request()
+    }
+
+    pub fn claimer(&self) -> Option<SubjectiveSeat> {
+        self.pending_claim()
+            .map(|claim| self.subjectiviser.subjective_seat(claim.player))
+    }
+
+    pub fn pending_irregularity(&self) -> Option<IrregularityEvent> {
+        self.game_data.irregularity_manager.pending_irregularity()
+    }
+
+    pub fn lead_penalty_is_due(&self) -> bool {
+        self.pending_claim().is_none() && self.game_data.lead_penalty_is_due()
+    }
+
+    pub fn penalty_cards_of(&self, seat: SubjectiveSeat) -> Vec<Card> {
+        let seat = self.subjectiviser.objective_seat(seat);
+        penalty_cards_of(&self.game_data.irregularity_manager, seat)
+    }
+
+    // unlike the game in director mode, this never allows a revoke
+    pub fn legal_cards(&self, seat: SubjectiveSeat) -> Vec<Card> {
+        let player = self.subjectiviser.objective_seat(seat);
+        self.game_data
+            .hand_manager
+            .known_remaining_cards_of(player)
+            .into_iter()
+            .filter(|&card| {
+                self.game_data.validate_suit_rule(player, card).is_ok() && self.validate_card_play(card, seat).is_ok()
+            })
+            .collect()
+    }
+
     pub fn active_trick(&self) -> SubjectiveTrick {
         let active_trick = self.game_data.trick_manager.current_trick();
         let lead = self.subjectiviser.subjective_seat(active_trick.lead());
@@ -305,8 +489,62 @@ impl<'a> SubjectiveGamePhaseStateView<'a, CardPlayState> {
     }
 }
 
-impl<'a> SubjectiveGamePhaseStateView<'a, EndedState> {
-    pub fn new_ended(game_data: &'a EndedState, seat: Seat) -> Self {
+// card locations are only tracked for full hands
+impl SubjectiveGamePhaseStateView<'_, CardPlayState> {
+    pub fn card_locations(&self, auction: Option<&EngineState>) -> CardLocations {
+        let mut tracker = CardLocationTracker::new();
+        let dummy = self.game_data.declarer().partner();
+        for (seat, hand) in [
+            (self.seat, self.my_starting_hand()),
+            (dummy, self.dummys_starting_hand()),
+        ] {
+            if let Ok(hand) = hand {
+                tracker.add_known_hand(seat, hand);
+            }
+        }
+        let trick_manager = &self.game_data.trick_manager;
+        let players = trick_manager.players_of_played_cards();
+        for (cards, players) in trick_manager.played_cards().chunks(4).zip(players.chunks(4)) {
+            for (&card, &player) in cards.iter().zip(players) {
+                let suit_led = (card != cards[0]).then_some(cards[0].suit);
+                tracker.add_played_card(player, card, suit_led);
+            }
+        }
+        if let Some(engine_state) = auction {
+            for seat in SEAT_ARRAY {
+                let description = engine_state.hand_description(self.subjectiviser.subjective_seat(seat));
+                tracker.add_description(seat, description.clone());
+            }
+        }
+        tracker.locations()
+    }
+}
+
+// alerts and explanations are for the opponents only
+fn hide_partners_annotations<T: Copy + Default>(annotations: &[T], dealer: Seat, seat: Seat) -> Vec<T> {
+    annotations
+        .iter()
+        .enumerate()
+        .map(|(index, &annotation)| {
+            if dealer + index == seat.partner() {
+                T::default()
+            } else {
+                annotation
+            }
+        })
+        .collect()
+}
+
+fn penalty_cards_of(manager: &IrregularityManager, seat: Seat) -> Vec<Card> {
+    manager
+        .penalty_cards_of(seat)
+        .iter()
+        .map(|penalty_card| penalty_card.card)
+        .collect()
+}
+
+impl<'a, const N: usize> SubjectiveGamePhaseStateView<'a, EndedState<N>> {
+    pub fn new_ended(game_data: &'a EndedState<N>, seat: Seat) -> Self {
         Self {
             seat,
             subjectiviser: Subjectiviser::new(seat),
@@ -314,7 +552,7 @@ impl<'a> SubjectiveGamePhaseStateView<'a, EndedState> {
         }
     }
 
-    pub fn my_starting_hand(&self) -> Result<Hand<13>, BBError> {
+    pub fn my_starting_hand(&self) -> Result<Hand<N>, BBError> {
         self.game_data.hand_of(self.seat)
     }
 
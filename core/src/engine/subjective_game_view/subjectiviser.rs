@@ -4,6 +4,7 @@ use crate::engine::subjective_game_view::subjective_vulnerability::SubjectiveVul
 use crate::primitives::deal::axis::Axis;
 use crate::primitives::deal::{Seat, Vulnerability};
 
+#[derive(Debug, Clone, Copy)]
 pub struct Subjectiviser {
     seat: Seat,
 }
@@ -52,4 +53,15 @@ impl Subjectiviser {
             (Vulnerability::EastWest, _) => SubjectiveVulnerability::Them,
         }
     }
+
+    pub const fn objective_vulnerability(&self, vulnerability: SubjectiveVulnerability) -> Vulnerability {
+        match (vulnerability, self.seat) {
+            (SubjectiveVulnerability::All, _) => Vulnerability::All,
+            (SubjectiveVulnerability::None, _) => Vulnerability::None,
+            (SubjectiveVulnerability::Us, Seat::North | Seat::South) => Vulnerability::NorthSouth,
+            (SubjectiveVulnerability::Us, _) => Vulnerability::EastWest,
+            (SubjectiveVulnerability::Them, Seat::North | Seat::South) => Vulnerability::EastWest,
+            (SubjectiveVulnerability::Them, _) => Vulnerability::NorthSouth,
+        }
+    }
 }
@@ -1,6 +1,5 @@
 use crate::primitives::bid::Bid;
 use crate::primitives::deal::Seat;
-use crate::primitives::game_event::GameEvent;
 use crate::primitives::{Card, Suit};
 use std::fmt::{Debug, Display, Formatter};
 
@@ -22,7 +21,7 @@ pub enum BBError {
     OutOfTurn(Option<Seat>),
     PlayerUnreachable(Seat),
     SeatTaken(Seat),
-    InvalidEvent(Box<GameEvent>),
+    InvalidEvent(String),
     InvalidCard(Card),
     NotAuthorized(Seat),
     InsufficientInfo,
@@ -35,6 +34,15 @@ pub enum BBError {
     NotYourCard(Card),
     AlreadyPlayed(Card),
     WrongBidType(Bid),
+    ClaimPending,
+    NoClaimPending,
+    InvalidClaim(usize),
+    UndoPending,
+    NothingToUndo,
+    IrregularityPending,
+    MustPass,
+    MustPlayPenaltyCard(Card),
+    LeadRestricted(Suit),
 }
 
 impl Display for BBError {
@@ -58,7 +66,7 @@ impl Display for BBError {
             },
             BBError::SeatTaken(seat) => writeln!(f, "There is already a player at {}", seat),
             BBError::InvalidEvent(game_event) => {
-                writeln!(f, "This event is not valid: {:?}", game_event)
+                writeln!(f, "This event is not valid: {}", game_event)
             }
 
             BBError::InvalidCard(card) => writeln!(f, "You cannot play {}!", card),
@@ -77,6 +85,15 @@ impl Display for BBError {
             BBError::NotYourCard(card) => writeln!(f, "Card {} belongs to another player.", card),
             BBError::AlreadyPlayed(card) => writeln!(f, "Card {} has already been played.", card),
             BBError::WrongBidType(bid) => writeln!(f, "Bid has wrong type: {}", bid),
+            BBError::ClaimPending => writeln!(f, "There is a claim waiting for a response."),
+            BBError::NoClaimPending => writeln!(f, "There is no claim to respond to."),
+            BBError::InvalidClaim(tricks) => writeln!(f, "You cannot claim {} tricks.", tricks),
+            BBError::UndoPending => writeln!(f, "There is a request to undo waiting for a response."),
+            BBError::NothingToUndo => writeln!(f, "There is nothing to undo."),
+            BBError::IrregularityPending => writeln!(f, "The director has to rule on an irregularity first."),
+            BBError::MustPass => writeln!(f, "You have to pass."),
+            BBError::MustPlayPenaltyCard(card) => writeln!(f, "You have to play your penalty card {}.", card),
+            BBError::LeadRestricted(suit) => writeln!(f, "Declarer has restricted your lead in {}.", suit),
         }
     }
 }
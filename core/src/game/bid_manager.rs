@@ -4,6 +4,7 @@ use crate::primitives::bid_line::BidLine;
 use crate::primitives::contract::{Level, State, Strain};
 use crate::primitives::deal::axis::Axis;
 use crate::primitives::deal::Seat;
+use crate::primitives::hand_info::explanation::Explanation;
 use crate::primitives::{Contract, Suit};
 use std::fmt::Display;
 
@@ -21,6 +22,10 @@ impl BidManager {
         }
     }
 
+    pub fn from_bid_line(dealer: Seat, bid_line: BidLine) -> Self {
+        BidManager { dealer, bid_line }
+    }
+
     pub fn bids(&self) -> &[Bid] {
         self.bid_line.bids()
     }
@@ -30,13 +35,46 @@ impl BidManager {
     }
 
     pub fn bid(&mut self, bid: Bid) -> Result<(), BBError> {
+        self.bid_with_alert(bid, false, None)
+    }
+
+    pub fn bid_with_alert(&mut self, bid: Bid, alert: bool, explanation: Option<Explanation>) -> Result<(), BBError> {
         self.validate_bid(bid)?;
-        self.append_bid(bid);
+        self.bid_line.bid_with_alert(bid, alert, explanation);
+        Ok(())
+    }
+
+    // the next player has accepted the insufficient bid (Law 27A)
+    pub fn accept_insufficient_bid(
+        &mut self,
+        bid: ContractBid,
+        alert: bool,
+        explanation: Option<Explanation>,
+    ) -> Result<(), BBError> {
+        if self.bidding_has_ended() {
+            return Err(BBError::InvalidBid(Bid::Contract(bid)));
+        }
+        self.bid_line.bid_with_alert(Bid::Contract(bid), alert, explanation);
         Ok(())
     }
 
-    fn append_bid(&mut self, bid: Bid) {
-        self.bid_line.bid(bid);
+    pub fn is_insufficient_bid(&self, bid: &Bid) -> bool {
+        match (bid, self.last_contract_bid()) {
+            (Bid::Contract(new), Some(last)) => new <= last && !self.bidding_has_ended(),
+            _ => false,
+        }
+    }
+
+    pub fn lowest_sufficient_bid_in_strain(&self, bid: &ContractBid) -> Option<ContractBid> {
+        let mut candidate = self.lowest_available_contract_bid()?;
+        while candidate.strain != bid.strain {
+            candidate = candidate.next().ok()?;
+        }
+        Some(candidate)
+    }
+
+    pub fn undo(&mut self) -> Option<Bid> {
+        self.bid_line.undo()
     }
 
     pub fn validate_bid(&self, bid: Bid) -> Result<(), BBError> {
@@ -262,7 +300,7 @@ impl Display for BidManager {
 mod test {
     use crate::error::BBError;
     use crate::game::bid_manager::BidManager;
-    use crate::primitives::bid::Bid;
+    use crate::primitives::bid::{Bid, ContractBid};
     use crate::primitives::bid_line::BidLine;
     use crate::primitives::deal::Seat;
     use crate::primitives::deal::Seat::*;
@@ -338,4 +376,37 @@ mod test {
         }
         assert_eq!(manager.bidding_has_ended(), expected);
     }
+
+    #[test_case("1H", "1C", "2C"; "Clubs")]
+    #[test_case("1H", "1D", "2D"; "Diamonds")]
+    #[test_case("2NT", "2S", "3S"; "Spades")]
+    #[test_case("1S", "1NT", "1NT"; "Not insufficient")]
+    fn lowest_sufficient_bid_in_strain(input: &str, insufficient: &str, expected: &str) {
+        let bid_line = BidLine::from_str(input).unwrap();
+        let mut manager = BidManager::new(Seat::South);
+        for &bid in bid_line.bids() {
+            manager.bid(bid).unwrap();
+        }
+        let insufficient = ContractBid::from_str(insufficient).unwrap();
+        assert_eq!(
+            manager.lowest_sufficient_bid_in_strain(&insufficient),
+            Some(ContractBid::from_str(expected).unwrap())
+        );
+    }
+
+    #[test]
+    fn accept_insufficient_bid() {
+        let mut manager = BidManager::new(Seat::South);
+        manager.bid(Bid::from_str("1H").unwrap()).unwrap();
+
+        let insufficient = Bid::from_str("1C").unwrap();
+        assert!(manager.is_insufficient_bid(&insufficient));
+        assert!(manager.bid(insufficient).is_err());
+
+        manager
+            .accept_insufficient_bid(ContractBid::from_str("1C").unwrap(), false, None)
+            .unwrap();
+        assert_eq!(manager.last_contract_bid(), Some(&ContractBid::from_str("1C").unwrap()));
+        assert!(manager.is_valid_bid(&Bid::from_str("1D").unwrap()));
+    }
 }
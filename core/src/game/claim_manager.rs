@@ -0,0 +1,245 @@
+use crate::error::BBError;
+use crate::primitives::deal::Seat;
+use crate::primitives::game_event::{ClaimEvent, ClaimResponseEvent, ConcedeEvent};
+
+#[derive(Debug, Clone)]
+pub struct ClaimManager {
+    declarer: Seat,
+    claim: Option<ClaimEvent>,
+    accepted_by: Vec<Seat>,
+    accepted: bool,
+    tricks_left_at_claim: usize,
+    rejected_with_tricks_left: Option<usize>,
+}
+
+impl ClaimManager {
+    pub fn new(declarer: Seat) -> Self {
+        ClaimManager {
+            declarer,
+            claim: None,
+            accepted_by: Vec::new(),
+            accepted: false,
+            tricks_left_at_claim: 0,
+            rejected_with_tricks_left: None,
+        }
+    }
+
+    pub fn pending_claim(&self) -> Option<ClaimEvent> {
+        match self.accepted {
+            true => None,
+            false => self.claim,
+        }
+    }
+
+    pub fn accepted_claim(&self) -> Option<ClaimEvent> {
+        match self.accepted {
+            true => self.claim,
+            false => None,
+        }
+    }
+
+    pub fn claim_is_pending(&self) -> bool {
+        self.pending_claim().is_some()
+    }
+
+    pub fn claim_has_been_accepted(&self) -> bool {
+        self.accepted
+    }
+
+    // dummy never takes part, so a claim by the defenders only needs declarer's approval
+    pub fn responders(&self) -> Vec<Seat> {
+        match self.pending_claim() {
+            None => Vec::new(),
+            Some(claim) if claim.player.same_axis(&self.declarer) => vec![self.declarer + 1, self.declarer + 3],
+            Some(_) => vec![self.declarer],
+        }
+    }
+
+    pub fn next_to_respond(&self) -> Option<Seat> {
+        self.responders()
+            .into_iter()
+            .find(|responder| !self.accepted_by.contains(responder))
+    }
+
+    // after a rejected claim, play continues for at least one more trick
+    pub fn may_claim(&self, tricks_left: usize) -> bool {
+        self.claim.is_none() && self.rejected_with_tricks_left != Some(tricks_left)
+    }
+
+    pub fn validate_claim_event(&self, event: ClaimEvent, tricks_left: usize) -> Result<(), BBError> {
+        if self.claim.is_some() {
+            return Err(BBError::ClaimPending);
+        }
+        if event.tricks > tricks_left || !self.may_claim(tricks_left) {
+            return Err(BBError::InvalidClaim(event.tricks));
+        }
+        Ok(())
+    }
+
+    pub fn process_claim_event(&mut self, event: ClaimEvent, tricks_left: usize) -> Result<(), BBError> {
+        self.validate_claim_event(event, tricks_left)?;
+        self.claim = Some(event);
+        self.accepted_by.clear();
+        self.tricks_left_at_claim = tricks_left;
+        Ok(())
+    }
+
+    pub fn process_concede_event(&mut self, event: ConcedeEvent) -> Result<(), BBError> {
+        if self.claim.is_some() {
+            return Err(BBError::ClaimPending);
+        }
+        self.claim = Some(ClaimEvent {
+            player: event.player,
+            tricks: 0,
+        });
+        self.accepted = true;
+        Ok(())
+    }
+
+    pub fn process_claim_response_event(&mut self, event: ClaimResponseEvent) -> Result<(), BBError> {
+        match self.next_to_respond() {
+            None => Err(BBError::NoClaimPending),
+            Some(responder) if responder != event.player => Err(BBError::OutOfTurn(Some(responder))),
+            Some(_) if event.accept => {
+                self.accepted_by.push(event.player);
+                if self.next_to_respond().is_none() {
+                    self.accepted = true;
+                }
+                Ok(())
+            }
+            Some(_) => {
+                self.withdraw_claim();
+                Ok(())
+            }
+        }
+    }
+
+    pub fn process_claim_ruling(&mut self, claim: ClaimEvent, valid: bool) -> Result<(), BBError> {
+        if self.pending_claim() != Some(claim) {
+            return Err(BBError::NoClaimPending);
+        }
+        match valid {
+            true => self.accepted = true,
+            false => self.withdraw_claim(),
+        }
+        Ok(())
+    }
+
+    fn withdraw_claim(&mut self) {
+        self.rejected_with_tricks_left = Some(self.tricks_left_at_claim);
+        self.claim = None;
+        self.accepted_by.clear();
+    }
+
+    pub fn claimed_tricks_for_axis(&self, player: Seat, tricks_left: usize) -> usize {
+        match self.accepted_claim() {
+            None => 0,
+            Some(claim) if claim.player.same_axis(&player) => claim.tricks,
+            Some(claim) => tricks_left - claim.tricks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::BBError;
+    use crate::game::claim_manager::ClaimManager;
+    use crate::primitives::deal::Seat;
+    use crate::primitives::game_event::{ClaimEvent, ClaimResponseEvent, ConcedeEvent};
+    use test_case::test_case;
+
+    #[test_case(Seat::South, Seat::South, &[Seat::West, Seat::East]; "Declarer")]
+    #[test_case(Seat::South, Seat::North, &[Seat::West, Seat::East]; "Dummy")]
+    #[test_case(Seat::South, Seat::West, &[Seat::South]; "Defender")]
+    fn responders(declarer: Seat, claimer: Seat, expected: &[Seat]) {
+        let mut manager = ClaimManager::new(declarer);
+        manager
+            .process_claim_event(
+                ClaimEvent {
+                    player: claimer,
+                    tricks: 3,
+                },
+                5,
+            )
+            .unwrap();
+        assert_eq!(manager.responders(), expected);
+    }
+
+    #[test]
+    fn claim_accepted_by_both_defenders() {
+        let mut manager = ClaimManager::new(Seat::South);
+        let claim = ClaimEvent {
+            player: Seat::South,
+            tricks: 4,
+        };
+        manager.process_claim_event(claim, 5).unwrap();
+
+        let response = ClaimResponseEvent {
+            player: Seat::West,
+            accept: true,
+        };
+        manager.process_claim_response_event(response).unwrap();
+        assert_eq!(manager.pending_claim(), Some(claim));
+        assert_eq!(manager.next_to_respond(), Some(Seat::East));
+
+        let response = ClaimResponseEvent {
+            player: Seat::East,
+            accept: true,
+        };
+        manager.process_claim_response_event(response).unwrap();
+        assert_eq!(manager.accepted_claim(), Some(claim));
+        assert_eq!(manager.claimed_tricks_for_axis(Seat::North, 5), 4);
+        assert_eq!(manager.claimed_tricks_for_axis(Seat::East, 5), 1);
+    }
+
+    #[test]
+    fn claim_rejected() {
+        let mut manager = ClaimManager::new(Seat::South);
+        let claim = ClaimEvent {
+            player: Seat::West,
+            tricks: 2,
+        };
+        manager.process_claim_event(claim, 5).unwrap();
+
+        let response = ClaimResponseEvent {
+            player: Seat::East,
+            accept: false,
+        };
+        assert_eq!(
+            manager.process_claim_response_event(response),
+            Err(BBError::OutOfTurn(Some(Seat::South)))
+        );
+
+        let response = ClaimResponseEvent {
+            player: Seat::South,
+            accept: false,
+        };
+        manager.process_claim_response_event(response).unwrap();
+        assert!(!manager.claim_is_pending());
+        assert!(!manager.claim_has_been_accepted());
+        assert_eq!(manager.claimed_tricks_for_axis(Seat::South, 5), 0);
+        assert!(!manager.may_claim(5));
+        assert!(manager.may_claim(4));
+    }
+
+    #[test]
+    fn concession() {
+        let mut manager = ClaimManager::new(Seat::South);
+        manager
+            .process_concede_event(ConcedeEvent { player: Seat::East })
+            .unwrap();
+        assert!(manager.claim_has_been_accepted());
+        assert_eq!(manager.claimed_tricks_for_axis(Seat::South, 6), 6);
+        assert_eq!(manager.claimed_tricks_for_axis(Seat::West, 6), 0);
+    }
+
+    #[test]
+    fn invalid_claim() {
+        let mut manager = ClaimManager::new(Seat::South);
+        let claim = ClaimEvent {
+            player: Seat::South,
+            tricks: 6,
+        };
+        assert_eq!(manager.process_claim_event(claim, 5), Err(BBError::InvalidClaim(6)));
+    }
+}
@@ -4,21 +4,30 @@ use crate::game::game_phase_states::ended_state::EndedState;
 use crate::game::game_phase_states::opening_lead_state::OpeningLeadState;
 use crate::game::game_phase_states::{GamePhaseState, NextToPlay};
 use crate::game::hand_manager::HandManager;
+use crate::game::irregularity_manager::IrregularityManager;
 use crate::game::trick_manager::TrickManager;
+use crate::game::undo_manager::UndoManager;
 
+use crate::primitives::bid::{AuxiliaryBid, Bid};
 use crate::primitives::deal::{Board, Seat};
-use crate::primitives::game_event::{BidEvent, DiscloseHandEvent};
+use crate::primitives::game_event::{
+    BidEvent, DiscloseHandEvent, IrregularityEvent, IrregularityResponseEvent, UndoRequestEvent, UndoResponseEvent,
+};
 use crate::primitives::game_result::GameResult;
 use crate::primitives::{Contract, Hand};
 
 #[derive(Debug, Clone)]
-pub struct BiddingState {
+pub struct BiddingState<const N: usize = 13> {
     pub bid_manager: BidManager,
-    pub hand_manager: HandManager,
+    pub hand_manager: HandManager<N>,
+    pub undo_manager: UndoManager,
+    pub irregularity_manager: IrregularityManager,
     pub board: Board,
+    // Mini-bridge has no auction, the game manager sets the contract right away
+    pub mini_bridge: bool,
 }
 
-impl GamePhaseState for BiddingState {
+impl<const N: usize> GamePhaseState for BiddingState<N> {
     fn implied_contract(&self) -> Option<Contract> {
         self.bid_manager.implied_contract()
     }
@@ -28,12 +37,15 @@ impl GamePhaseState for BiddingState {
     }
 }
 
-impl BiddingState {
-    pub fn new(board: Board) -> Self {
+impl<const N: usize> BiddingState<N> {
+    pub fn new(board: Board, director: bool, mini_bridge: bool) -> Self {
         BiddingState {
             bid_manager: BidManager::new(board.dealer()),
             hand_manager: HandManager::new(),
+            undo_manager: UndoManager::new(),
+            irregularity_manager: IrregularityManager::new(director),
             board,
+            mini_bridge,
         }
     }
 
@@ -41,7 +53,7 @@ impl BiddingState {
         None
     }
 
-    pub fn hand_of(&self, player: Seat) -> Result<Hand<13>, BBError> {
+    pub fn hand_of(&self, player: Seat) -> Result<Hand<N>, BBError> {
         self.hand_manager.hand_of(player)
     }
 
@@ -54,35 +66,138 @@ impl BiddingState {
     }
 
     pub fn validate_make_bid_event(&self, bid_event: BidEvent) -> Result<(), BBError> {
+        if self.mini_bridge {
+            return Err(BBError::InvalidEvent(format!("{:?}", bid_event)));
+        }
+        if self.undo_manager.request_is_open() {
+            return Err(BBError::UndoPending);
+        }
         self.validate_turn_order(bid_event.player)?;
+        self.irregularity_manager
+            .validate_call(bid_event.player, bid_event.bid)?;
         if !self.bid_manager.is_valid_bid(&bid_event.bid) {
             return Err(BBError::InvalidBid(bid_event.bid));
         }
         Ok(())
     }
 
-    pub fn process_disclose_hand_event(&mut self, event: DiscloseHandEvent) -> Result<(), BBError> {
+    pub fn irregularity(&self, bid_event: BidEvent) -> Option<IrregularityEvent> {
+        if !self.irregularity_manager.director()
+            || self.mini_bridge
+            || self.undo_manager.request_is_open()
+            || self.irregularity_manager.pending_irregularity().is_some()
+            || self.bidding_has_ended()
+        {
+            return None;
+        }
+        if bid_event.player != self.bid_manager.next_to_play() {
+            Some(IrregularityEvent::CallOutOfTurn(bid_event))
+        } else if self.bid_manager.is_insufficient_bid(&bid_event.bid)
+            && !self.irregularity_manager.must_pass(bid_event.player)
+        {
+            Some(IrregularityEvent::InsufficientBid(bid_event))
+        } else {
+            None
+        }
+    }
+
+    pub fn process_disclose_hand_event(&mut self, event: DiscloseHandEvent<N>) -> Result<(), BBError> {
         self.hand_manager.register_known_hand(event.hand, event.seat)
     }
 
     pub fn process_make_bid_event(&mut self, bid_event: BidEvent) -> Result<(), BBError> {
         self.validate_make_bid_event(bid_event)?;
-        self.bid_manager.bid(bid_event.bid)?;
+        if let Some(insufficient) = self.irregularity_manager.cancelled_insufficient_bid() {
+            let without_rectification = match insufficient.bid {
+                Bid::Contract(bid) => self
+                    .bid_manager
+                    .lowest_sufficient_bid_in_strain(&bid)
+                    .is_some_and(|lowest| bid_event.bid == Bid::Contract(lowest)),
+                Bid::Auxiliary(_) => false,
+            };
+            self.irregularity_manager
+                .replace_insufficient_bid(without_rectification);
+        }
+        self.bid_manager
+            .bid_with_alert(bid_event.bid, bid_event.alert, bid_event.explanation)?;
+        self.irregularity_manager.register_call(bid_event.player);
 
         Ok(())
     }
 
-    pub fn move_to_opening_lead(self, contract: Contract) -> OpeningLeadState {
+    pub fn process_irregularity_event(&mut self, irregularity: IrregularityEvent) -> Result<(), BBError> {
+        if self.irregularity(irregularity_call(irregularity)?) != Some(irregularity) {
+            return Err(BBError::InvalidEvent(format!("{:?}", irregularity)));
+        }
+        let decider = irregularity.offender() + 1;
+        self.irregularity_manager
+            .process_irregularity_event(irregularity, Some(decider))
+    }
+
+    // accepting a call out of turn forfeits the calls of the skipped players (Law 28, 29A)
+    pub fn process_irregularity_response_event(&mut self, event: IrregularityResponseEvent) -> Result<(), BBError> {
+        let irregularity = self.irregularity_manager.validate_irregularity_response_event(event)?;
+        match (irregularity, event.accept) {
+            (IrregularityEvent::InsufficientBid(bid_event), true) => match bid_event.bid {
+                Bid::Contract(bid) => {
+                    self.bid_manager
+                        .accept_insufficient_bid(bid, bid_event.alert, bid_event.explanation)?
+                }
+                Bid::Auxiliary(_) => unreachable!(),
+            },
+            (IrregularityEvent::InsufficientBid(bid_event), false) => {
+                self.irregularity_manager.reject_insufficient_bid(bid_event)
+            }
+            (IrregularityEvent::CallOutOfTurn(bid_event), true) => {
+                let mut bid_manager = self.bid_manager.clone();
+                while bid_manager.next_to_play() != bid_event.player {
+                    bid_manager.bid(Bid::Auxiliary(AuxiliaryBid::Pass))?;
+                }
+                bid_manager.bid_with_alert(bid_event.bid, bid_event.alert, bid_event.explanation)?;
+                self.bid_manager = bid_manager;
+            }
+            (IrregularityEvent::CallOutOfTurn(bid_event), false) => {
+                self.irregularity_manager.reject_call_out_of_turn(bid_event)
+            }
+            _ => Err(BBError::InvalidEvent(format!("{:?}", event)))?,
+        }
+        if event.accept {
+            self.irregularity_manager.register_call(irregularity.offender());
+        }
+        self.irregularity_manager.resolve();
+        Ok(())
+    }
+
+    pub fn must_pass(&self, player: Seat) -> bool {
+        self.irregularity_manager.must_pass(player)
+    }
+
+    pub fn process_undo_request_event(&mut self, event: UndoRequestEvent) -> Result<(), BBError> {
+        let responders = UndoManager::responders_in_bidding(event.player);
+        self.undo_manager.process_undo_request_event(event, responders)
+    }
+
+    pub fn process_undo_response_event(&mut self, event: UndoResponseEvent) -> Result<(), BBError> {
+        self.undo_manager.process_undo_response_event(event)
+    }
+
+    pub fn undo_last_bid(&mut self) -> Result<Bid, BBError> {
+        self.bid_manager.undo().ok_or(BBError::NothingToUndo)
+    }
+
+    pub fn move_to_opening_lead(self, contract: Contract) -> OpeningLeadState<N> {
         OpeningLeadState {
             bids: self.bid_manager.bid_line().clone(),
             trick_manager: TrickManager::new(contract.declarer + 1, contract.trump_suit()),
             hand_manager: self.hand_manager,
+            undo_manager: UndoManager::new(),
+            irregularity_manager: self.irregularity_manager,
             contract,
             board: self.board,
         }
     }
 
-    pub fn move_to_ended_without_card_play(self) -> EndedState {
+    pub fn move_to_ended_without_card_play(self) -> EndedState<N> {
         EndedState {
             bids: self.bid_manager.bid_line(),
             tricks: Vec::new(),
@@ -93,8 +208,21 @@ impl BiddingState {
     }
 }
 
-impl NextToPlay for BiddingState {
+impl<const N: usize> NextToPlay for BiddingState<N> {
     fn next_to_play(&self) -> Seat {
-        self.bid_manager.next_to_play()
+        match (
+            self.undo_manager.next_to_respond(),
+            self.irregularity_manager.next_to_respond(),
+        ) {
+            (Some(responder), _) | (None, Some(responder)) => responder,
+            (None, None) => self.bid_manager.next_to_play(),
+        }
+    }
+}
+
+fn irregularity_call(irregularity: IrregularityEvent) -> Result<BidEvent, BBError> {
+    match irregularity {
+        IrregularityEvent::InsufficientBid(bid_event) | IrregularityEvent::CallOutOfTurn(bid_event) => Ok(bid_event),
+        _ => Err(BBError::InvalidEvent(format!("{:?}", irregularity))),
     }
 }
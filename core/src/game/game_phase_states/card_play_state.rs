@@ -1,25 +1,34 @@
 use crate::error::BBError;
+use crate::game::claim_manager::ClaimManager;
 use crate::game::game_phase_states::ended_state::EndedState;
-use crate::game::game_phase_states::{GamePhaseState, NextToPlay};
+use crate::game::game_phase_states::{GamePhaseState, NextToPlay, OpeningLeadState};
 use crate::game::hand_manager::HandManager;
+use crate::game::irregularity_manager::{IrregularityManager, PenaltyCard, Revoke};
 use crate::game::trick_manager::TrickManager;
+use crate::game::undo_manager::UndoManager;
 
 use crate::primitives::bid_line::BidLine;
 use crate::primitives::deal::{Board, Seat};
-use crate::primitives::game_event::CardEvent;
+use crate::primitives::game_event::{
+    CardEvent, ClaimEvent, ClaimResponseEvent, ConcedeEvent, ExposedCardEvent, IrregularityEvent,
+    IrregularityResponseEvent, LeadPenaltyEvent, UndoRequestEvent, UndoResponseEvent,
+};
 use crate::primitives::game_result::GameResult;
 use crate::primitives::{Card, Contract, Hand};
 
 #[derive(Debug, Clone)]
-pub struct CardPlayState {
+pub struct CardPlayState<const N: usize = 13> {
     pub bids: BidLine,
-    pub trick_manager: TrickManager<13>,
-    pub hand_manager: HandManager,
+    pub trick_manager: TrickManager<N>,
+    pub hand_manager: HandManager<N>,
+    pub claim_manager: ClaimManager,
+    pub undo_manager: UndoManager,
+    pub irregularity_manager: IrregularityManager,
     pub contract: Contract,
     pub board: Board,
 }
 
-impl GamePhaseState for CardPlayState {
+impl<const N: usize> GamePhaseState for CardPlayState<N> {
     fn implied_contract(&self) -> Option<Contract> {
         Some(self.contract)
     }
@@ -29,14 +38,22 @@ impl GamePhaseState for CardPlayState {
     }
 }
 
-impl NextToPlay for CardPlayState {
+impl<const N: usize> NextToPlay for CardPlayState<N> {
     fn next_to_play(&self) -> Seat {
-        self.trick_manager.next_to_play()
+        match (
+            self.claim_manager.next_to_respond(),
+            self.undo_manager.next_to_respond(),
+            self.irregularity_manager.next_to_respond(),
+        ) {
+            (Some(responder), _, _) | (None, Some(responder), _) | (None, None, Some(responder)) => responder,
+            (None, None, None) if self.lead_penalty_is_due() => self.declarer(),
+            (None, None, None) => self.trick_manager.next_to_play(),
+        }
     }
 }
 
-impl CardPlayState {
-    pub fn hand_of(&self, player: Seat) -> Result<Hand<13>, BBError> {
+impl<const N: usize> CardPlayState<N> {
+    pub fn hand_of(&self, player: Seat) -> Result<Hand<N>, BBError> {
         self.hand_manager.hand_of(player)
     }
 
@@ -49,18 +66,139 @@ impl CardPlayState {
         self.trick_manager.play(card_event.card);
         self.hand_manager
             .process_play_card_event(card_event.card, card_event.player)?;
+        self.irregularity_manager.play_card(card_event.player, card_event.card);
         Ok(())
     }
 
     pub fn validate_play_card_event(&self, card_event: CardEvent) -> Result<(), BBError> {
+        if self.claim_manager.claim_is_pending() {
+            return Err(BBError::ClaimPending);
+        }
+        if self.undo_manager.request_is_open() {
+            return Err(BBError::UndoPending);
+        }
         self.validate_turn_order(card_event.player)?;
         self.hand_manager
             .validate_play_card_event(card_event.card, card_event.player)?;
 
-        self.validate_suit_rule(card_event.player, card_event.card)?;
+        // the director rules on revokes at the end of the hand
+        if !self.irregularity_manager.director() {
+            self.validate_suit_rule(card_event.player, card_event.card)?;
+        }
+        self.irregularity_manager.validate_card(
+            card_event.player,
+            card_event.card,
+            self.trick_manager.suit_to_follow(),
+            self.trick_manager.count_played_tricks(),
+            &self.hand_manager,
+        )
+    }
+
+    pub fn irregularity(&self, card_event: CardEvent) -> Option<IrregularityEvent> {
+        if !self.irregularity_manager.director()
+            || self.claim_manager.claim_is_pending()
+            || self.undo_manager.request_is_open()
+            || self.irregularity_manager.pending_irregularity().is_some()
+            || self.lead_penalty_is_due()
+            || self.card_play_has_ended()
+            || card_event.player == self.trick_manager.next_to_play()
+            || self
+                .hand_manager
+                .validate_play_card_event(card_event.card, card_event.player)
+                .is_err()
+        {
+            return None;
+        }
+        match self.trick_manager.suit_to_follow() {
+            None => Some(IrregularityEvent::LeadOutOfTurn(card_event)),
+            Some(_) => Some(IrregularityEvent::PlayOutOfTurn(card_event)),
+        }
+    }
+
+    // a defender's card played out of turn becomes a major penalty card (Law 57),
+    // a card of the declaring side is simply withdrawn
+    pub fn process_irregularity_event(&mut self, irregularity: IrregularityEvent) -> Result<(), BBError> {
+        let card_event = match irregularity {
+            IrregularityEvent::LeadOutOfTurn(card_event) | IrregularityEvent::PlayOutOfTurn(card_event)
+                if self.irregularity(card_event) == Some(irregularity) =>
+            {
+                card_event
+            }
+            _ => Err(BBError::InvalidEvent(format!("{:?}", irregularity)))?,
+        };
+        let defender = !card_event.player.same_axis(&self.declarer());
+        if defender {
+            self.hand_manager
+                .register_known_card(card_event.card, card_event.player)?;
+        }
+        let decider = match (irregularity, defender) {
+            (IrregularityEvent::LeadOutOfTurn(_), true) => Some(self.declarer()),
+            (IrregularityEvent::LeadOutOfTurn(_), false) => Some(card_event.player + 1),
+            _ => None,
+        };
+        self.irregularity_manager
+            .process_irregularity_event(irregularity, decider)?;
+        if decider.is_none() && defender {
+            self.irregularity_manager
+                .add_penalty_card(PenaltyCard::major(card_event.player, card_event.card));
+        }
         Ok(())
     }
 
+    pub fn process_irregularity_response_event(&mut self, event: IrregularityResponseEvent) -> Result<(), BBError> {
+        let irregularity = self.irregularity_manager.validate_irregularity_response_event(event)?;
+        let IrregularityEvent::LeadOutOfTurn(card_event) = irregularity else {
+            Err(BBError::InvalidEvent(format!("{:?}", event)))?
+        };
+        if event.accept {
+            let mut state = self.clone();
+            state.irregularity_manager.resolve();
+            state.trick_manager.change_leader(card_event.player)?;
+            state.process_play_card_event(card_event)?;
+            *self = state;
+        } else {
+            if !card_event.player.same_axis(&self.declarer()) {
+                self.irregularity_manager
+                    .add_penalty_card(PenaltyCard::major(card_event.player, card_event.card));
+            }
+            self.irregularity_manager.resolve();
+        }
+        Ok(())
+    }
+
+    pub fn process_exposed_card_event(&mut self, event: ExposedCardEvent) -> Result<(), BBError> {
+        self.irregularity_manager
+            .process_exposed_card_event(event, self.declarer(), &mut self.hand_manager)
+    }
+
+    pub fn lead_penalty_is_due(&self) -> bool {
+        self.trick_manager.suit_to_follow().is_none()
+            && !self.trick_manager.card_play_has_ended()
+            && self.irregularity_manager.lead_penalty_is_due(
+                self.trick_manager.next_to_play(),
+                self.declarer(),
+                self.trick_manager.count_played_tricks(),
+            )
+    }
+
+    pub fn process_lead_penalty_event(&mut self, event: LeadPenaltyEvent) -> Result<(), BBError> {
+        if self.claim_manager.claim_is_pending() {
+            return Err(BBError::ClaimPending);
+        }
+        if self.undo_manager.request_is_open() {
+            return Err(BBError::UndoPending);
+        }
+        if self.irregularity_manager.pending_irregularity().is_some() {
+            return Err(BBError::IrregularityPending);
+        }
+        self.irregularity_manager.process_lead_penalty_event(
+            event,
+            self.declarer(),
+            self.trick_manager.next_to_play(),
+            self.trick_manager.count_played_tricks(),
+        )
+    }
+
     pub fn validate_suit_rule(&self, player: Seat, card: Card) -> Result<(), BBError> {
         if let Some(suit) = &self.trick_manager.suit_to_follow() {
             if card.suit != *suit
@@ -77,15 +215,79 @@ impl CardPlayState {
         }
     }
 
+    pub fn process_claim_event(&mut self, claim_event: ClaimEvent) -> Result<(), BBError> {
+        if self.undo_manager.request_is_open() {
+            return Err(BBError::UndoPending);
+        }
+        if self.irregularity_manager.pending_irregularity().is_some() {
+            return Err(BBError::IrregularityPending);
+        }
+        self.validate_turn_order(claim_event.player)?;
+        self.claim_manager
+            .process_claim_event(claim_event, self.trick_manager.tricks_left())
+    }
+
+    pub fn process_concede_event(&mut self, concede_event: ConcedeEvent) -> Result<(), BBError> {
+        if self.undo_manager.request_is_open() {
+            return Err(BBError::UndoPending);
+        }
+        self.validate_turn_order(concede_event.player)?;
+        self.claim_manager.process_concede_event(concede_event)
+    }
+
+    pub fn process_claim_response_event(&mut self, response_event: ClaimResponseEvent) -> Result<(), BBError> {
+        self.claim_manager.process_claim_response_event(response_event)
+    }
+
+    pub fn process_claim_ruling(&mut self, claim: ClaimEvent, valid: bool) -> Result<(), BBError> {
+        self.claim_manager.process_claim_ruling(claim, valid)
+    }
+
+    pub fn process_undo_request_event(&mut self, event: UndoRequestEvent) -> Result<(), BBError> {
+        if self.claim_manager.claim_is_pending() {
+            return Err(BBError::ClaimPending);
+        }
+        let responders = UndoManager::responders_in_card_play(event.player, self.declarer());
+        self.undo_manager.process_undo_request_event(event, responders)
+    }
+
+    pub fn process_undo_response_event(&mut self, event: UndoResponseEvent) -> Result<(), BBError> {
+        self.undo_manager.process_undo_response_event(event)
+    }
+
+    pub fn undo_last_card(&mut self) -> Result<Card, BBError> {
+        let card = self.trick_manager.undo().ok_or(BBError::NothingToUndo)?;
+        self.hand_manager.undo_play_card(card)?;
+        Ok(card)
+    }
+
+    pub fn move_back_to_opening_lead(self) -> OpeningLeadState<N> {
+        OpeningLeadState {
+            bids: self.bids,
+            trick_manager: self.trick_manager,
+            hand_manager: self.hand_manager,
+            undo_manager: UndoManager::new(),
+            irregularity_manager: self.irregularity_manager,
+            contract: self.contract,
+            board: self.board,
+        }
+    }
+
+    pub fn may_claim(&self) -> bool {
+        self.claim_manager.may_claim(self.trick_manager.tricks_left())
+    }
+
+    pub fn pending_claim(&self) -> Option<ClaimEvent> {
+        self.claim_manager.pending_claim()
+    }
+
     pub fn card_play_has_ended(&self) -> bool {
-        self.trick_manager.card_play_has_ended()
+        self.trick_manager.card_play_has_ended() || self.claim_manager.claim_has_been_accepted()
     }
 
-    pub fn move_from_card_play_to_ended(self) -> EndedState {
+    pub fn move_from_card_play_to_ended(self, result: GameResult) -> EndedState<N> {
         let tricks = self.trick_manager.played_tricks();
 
-        let result = self.calculate_game_result();
-
         EndedState {
             bids: self.bids,
             tricks,
@@ -96,10 +298,36 @@ impl CardPlayState {
     }
 
     pub fn calculate_game_result(&self) -> GameResult {
-        GameResult::calculate_game_result(
-            self.contract,
-            self.trick_manager.tricks_won_by_axis(self.contract.declarer),
-        )
+        let declarer = self.contract.declarer;
+        let tricks_won = self.trick_manager.tricks_won_by_axis(declarer)
+            + self
+                .claim_manager
+                .claimed_tricks_for_axis(declarer, self.trick_manager.tricks_left());
+        let tricks_won = match self.irregularity_manager.director() {
+            true => self.apply_revoke_transfers(tricks_won),
+            false => tricks_won,
+        };
+        GameResult::calculate_reduced_game_result(self.contract, tricks_won, N)
+    }
+
+    pub fn established_revokes(&self) -> Vec<Revoke> {
+        IrregularityManager::established_revokes(&self.trick_manager.played_tricks(), &self.hand_manager)
+    }
+
+    fn apply_revoke_transfers(&self, tricks_won: usize) -> usize {
+        let tricks = self.trick_manager.played_tricks();
+        let transfers = IrregularityManager::revoke_trick_transfers(&tricks, &self.established_revokes(), self.dummy());
+        transfers.iter().fold(tricks_won, |tricks_won, (revoke, transferred)| {
+            if revoke.player.same_axis(&self.declarer()) {
+                tricks_won.saturating_sub(*transferred)
+            } else {
+                (tricks_won + transferred).min(N)
+            }
+        })
+    }
+
+    pub fn dummy(&self) -> Seat {
+        self.declarer().partner()
     }
 
     pub fn board(&self) -> Board {
@@ -9,15 +9,15 @@ use crate::primitives::trick::PlayedTrick;
 use crate::primitives::{Contract, Hand};
 
 #[derive(Debug, Clone)]
-pub struct EndedState {
+pub struct EndedState<const N: usize = 13> {
     pub bids: BidLine,
     pub tricks: Vec<PlayedTrick>,
-    pub hands: HandManager,
+    pub hands: HandManager<N>,
     pub result: GameResult,
     pub board: Board,
 }
 
-impl GamePhaseState for EndedState {
+impl<const N: usize> GamePhaseState for EndedState<N> {
     fn implied_contract(&self) -> Option<Contract> {
         self.result.played_contract()
     }
@@ -26,8 +26,8 @@ impl GamePhaseState for EndedState {
     }
 }
 
-impl EndedState {
-    pub fn hand_of(&self, player: Seat) -> Result<Hand<13>, BBError> {
+impl<const N: usize> EndedState<N> {
+    pub fn hand_of(&self, player: Seat) -> Result<Hand<N>, BBError> {
         self.hands.hand_of(player)
     }
 
@@ -1,24 +1,33 @@
 use crate::error::BBError;
+use crate::game::bid_manager::BidManager;
+use crate::game::game_phase_states::bidding_state::BiddingState;
 use crate::game::game_phase_states::waiting_for_dummy_state::WaitingForDummyState;
 use crate::game::game_phase_states::{GamePhaseState, NextToPlay};
 use crate::game::hand_manager::HandManager;
+use crate::game::irregularity_manager::{IrregularityManager, PenaltyCard};
 use crate::game::trick_manager::TrickManager;
+use crate::game::undo_manager::UndoManager;
 
 use crate::primitives::bid_line::BidLine;
 use crate::primitives::deal::{Board, Seat};
-use crate::primitives::game_event::CardEvent;
+use crate::primitives::game_event::{
+    CardEvent, ExposedCardEvent, IrregularityEvent, IrregularityResponseEvent, LeadPenaltyEvent, UndoRequestEvent,
+    UndoResponseEvent,
+};
 use crate::primitives::{Card, Contract, Hand};
 
 #[derive(Debug, Clone)]
-pub struct OpeningLeadState {
+pub struct OpeningLeadState<const N: usize = 13> {
     pub bids: BidLine,
-    pub trick_manager: TrickManager<13>,
-    pub hand_manager: HandManager,
+    pub trick_manager: TrickManager<N>,
+    pub hand_manager: HandManager<N>,
+    pub undo_manager: UndoManager,
+    pub irregularity_manager: IrregularityManager,
     pub contract: Contract,
     pub board: Board,
 }
 
-impl GamePhaseState for OpeningLeadState {
+impl<const N: usize> GamePhaseState for OpeningLeadState<N> {
     fn implied_contract(&self) -> Option<Contract> {
         Some(self.contract)
     }
@@ -28,14 +37,21 @@ impl GamePhaseState for OpeningLeadState {
     }
 }
 
-impl NextToPlay for OpeningLeadState {
+impl<const N: usize> NextToPlay for OpeningLeadState<N> {
     fn next_to_play(&self) -> Seat {
-        self.trick_manager.next_to_play()
+        match (
+            self.undo_manager.next_to_respond(),
+            self.irregularity_manager.next_to_respond(),
+        ) {
+            (Some(responder), _) | (None, Some(responder)) => responder,
+            (None, None) if self.lead_penalty_is_due() => self.declarer(),
+            (None, None) => self.trick_manager.next_to_play(),
+        }
     }
 }
 
-impl OpeningLeadState {
-    pub fn hand_of(&self, player: Seat) -> Result<Hand<13>, BBError> {
+impl<const N: usize> OpeningLeadState<N> {
+    pub fn hand_of(&self, player: Seat) -> Result<Hand<N>, BBError> {
         self.hand_manager.hand_of(player)
     }
 
@@ -48,10 +64,14 @@ impl OpeningLeadState {
         self.trick_manager.play(card_event.card);
         self.hand_manager
             .process_play_card_event(card_event.card, card_event.player)?;
+        self.irregularity_manager.play_card(card_event.player, card_event.card);
         Ok(())
     }
 
     pub fn validate_play_card_event(&self, card_event: CardEvent) -> Result<(), BBError> {
+        if self.undo_manager.request_is_open() {
+            return Err(BBError::UndoPending);
+        }
         self.validate_turn_order(card_event.player)?;
         self.hand_manager
             .validate_play_card_event(card_event.card, card_event.player)?;
@@ -59,7 +79,84 @@ impl OpeningLeadState {
         if self.player_violates_suit_rule(card_event.player, card_event.card) {
             return Err(BBError::InvalidCard(card_event.card));
         }
-        Ok(())
+        self.irregularity_manager
+            .validate_card(card_event.player, card_event.card, None, 0, &self.hand_manager)
+    }
+
+    pub fn irregularity(&self, card_event: CardEvent) -> Option<IrregularityEvent> {
+        if !self.irregularity_manager.director()
+            || self.undo_manager.request_is_open()
+            || self.irregularity_manager.pending_irregularity().is_some()
+            || self.lead_penalty_is_due()
+            || card_event.player == self.trick_manager.next_to_play()
+            || self
+                .hand_manager
+                .validate_play_card_event(card_event.card, card_event.player)
+                .is_err()
+        {
+            return None;
+        }
+        Some(IrregularityEvent::LeadOutOfTurn(card_event))
+    }
+
+    // a lead faced by declarer or dummy is withdrawn without rectification (Law 54)
+    pub fn process_irregularity_event(&mut self, irregularity: IrregularityEvent) -> Result<(), BBError> {
+        let card_event = match irregularity {
+            IrregularityEvent::LeadOutOfTurn(card_event) if self.irregularity(card_event) == Some(irregularity) => {
+                card_event
+            }
+            _ => Err(BBError::InvalidEvent(format!("{:?}", irregularity)))?,
+        };
+        let defender_led = !card_event.player.same_axis(&self.declarer());
+        if defender_led {
+            self.hand_manager
+                .register_known_card(card_event.card, card_event.player)?;
+        }
+        let decider = defender_led.then_some(self.declarer());
+        self.irregularity_manager
+            .process_irregularity_event(irregularity, decider)
+    }
+
+    // returns whether the accepted lead has been played
+    pub fn process_irregularity_response_event(&mut self, event: IrregularityResponseEvent) -> Result<bool, BBError> {
+        let irregularity = self.irregularity_manager.validate_irregularity_response_event(event)?;
+        let IrregularityEvent::LeadOutOfTurn(card_event) = irregularity else {
+            Err(BBError::InvalidEvent(format!("{:?}", event)))?
+        };
+        if event.accept {
+            let mut state = self.clone();
+            state.irregularity_manager.resolve();
+            state.trick_manager.change_leader(card_event.player)?;
+            state.process_play_card_event(card_event)?;
+            *self = state;
+        } else {
+            self.irregularity_manager
+                .add_penalty_card(PenaltyCard::major(card_event.player, card_event.card));
+            self.irregularity_manager.resolve();
+        }
+        Ok(event.accept)
+    }
+
+    pub fn process_exposed_card_event(&mut self, event: ExposedCardEvent) -> Result<(), BBError> {
+        self.irregularity_manager
+            .process_exposed_card_event(event, self.declarer(), &mut self.hand_manager)
+    }
+
+    pub fn lead_penalty_is_due(&self) -> bool {
+        self.irregularity_manager
+            .lead_penalty_is_due(self.trick_manager.next_to_play(), self.declarer(), 0)
+    }
+
+    pub fn process_lead_penalty_event(&mut self, event: LeadPenaltyEvent) -> Result<(), BBError> {
+        if self.irregularity_manager.pending_irregularity().is_some() {
+            return Err(BBError::IrregularityPending);
+        }
+        self.irregularity_manager.process_lead_penalty_event(
+            event,
+            self.declarer(),
+            self.trick_manager.next_to_play(),
+            0,
+        )
     }
 
     pub fn player_violates_suit_rule(&self, player: Seat, card: Card) -> bool {
@@ -73,11 +170,35 @@ impl OpeningLeadState {
         }
     }
 
-    pub fn move_to_waiting_for_dummy(self) -> WaitingForDummyState {
+    // no card has been played yet, so any takeback reopens the auction
+    pub fn process_undo_request_event(&mut self, event: UndoRequestEvent) -> Result<(), BBError> {
+        let responders = UndoManager::responders_in_bidding(event.player);
+        self.undo_manager.process_undo_request_event(event, responders)
+    }
+
+    pub fn process_undo_response_event(&mut self, event: UndoResponseEvent) -> Result<(), BBError> {
+        self.undo_manager.process_undo_response_event(event)
+    }
+
+    pub fn move_back_to_bidding(self) -> BiddingState<N> {
+        // only Mini-bridge gets to the opening lead without any calls
+        let mini_bridge = self.bids.is_empty();
+        BiddingState {
+            bid_manager: BidManager::from_bid_line(self.board.dealer(), self.bids),
+            hand_manager: self.hand_manager,
+            undo_manager: UndoManager::new(),
+            irregularity_manager: self.irregularity_manager,
+            board: self.board,
+            mini_bridge,
+        }
+    }
+
+    pub fn move_to_waiting_for_dummy(self) -> WaitingForDummyState<N> {
         WaitingForDummyState {
             bids: self.bids,
             trick_manager: self.trick_manager,
             hand_manager: self.hand_manager,
+            irregularity_manager: self.irregularity_manager,
             contract: self.contract,
             board: self.board,
         }
@@ -1,23 +1,27 @@
 use crate::error::BBError;
-use crate::game::game_phase_states::{CardPlayState, GamePhaseState, NextToPlay};
+use crate::game::claim_manager::ClaimManager;
+use crate::game::game_phase_states::{CardPlayState, GamePhaseState, NextToPlay, OpeningLeadState};
 use crate::game::hand_manager::HandManager;
+use crate::game::irregularity_manager::IrregularityManager;
 use crate::game::trick_manager::TrickManager;
+use crate::game::undo_manager::UndoManager;
 
 use crate::primitives::bid_line::BidLine;
 use crate::primitives::deal::{Board, Seat};
 use crate::primitives::game_event::DummyUncoveredEvent;
-use crate::primitives::{Contract, Hand};
+use crate::primitives::{Card, Contract, Hand};
 
 #[derive(Debug, Clone)]
-pub struct WaitingForDummyState {
+pub struct WaitingForDummyState<const N: usize = 13> {
     pub bids: BidLine,
-    pub trick_manager: TrickManager<13>,
-    pub hand_manager: HandManager,
+    pub trick_manager: TrickManager<N>,
+    pub hand_manager: HandManager<N>,
+    pub irregularity_manager: IrregularityManager,
     pub contract: Contract,
     pub board: Board,
 }
 
-impl GamePhaseState for WaitingForDummyState {
+impl<const N: usize> GamePhaseState for WaitingForDummyState<N> {
     fn implied_contract(&self) -> Option<Contract> {
         Some(self.contract)
     }
@@ -27,13 +31,13 @@ impl GamePhaseState for WaitingForDummyState {
     }
 }
 
-impl NextToPlay for WaitingForDummyState {
+impl<const N: usize> NextToPlay for WaitingForDummyState<N> {
     fn next_to_play(&self) -> Seat {
         self.trick_manager.next_to_play()
     }
 }
 
-impl WaitingForDummyState {
+impl<const N: usize> WaitingForDummyState<N> {
     pub fn board(&self) -> Board {
         self.board
     }
@@ -46,22 +50,43 @@ impl WaitingForDummyState {
         self.declarer().partner()
     }
 
-    pub fn hand_of(&self, player: Seat) -> Result<Hand<13>, BBError> {
+    pub fn hand_of(&self, player: Seat) -> Result<Hand<N>, BBError> {
         self.hand_manager.hand_of(player)
     }
 
-    pub fn process_dummy_uncovered_event(&mut self, event: DummyUncoveredEvent) -> Result<(), BBError> {
+    pub fn process_dummy_uncovered_event(&mut self, event: DummyUncoveredEvent<N>) -> Result<(), BBError> {
         self.hand_manager
             .register_known_hand(event.dummy, self.contract.declarer.partner())?;
 
         Ok(())
     }
 
-    pub fn move_to_card_play(self) -> CardPlayState {
+    pub fn undo_last_card(&mut self) -> Result<Card, BBError> {
+        let card = self.trick_manager.undo().ok_or(BBError::NothingToUndo)?;
+        self.hand_manager.undo_play_card(card)?;
+        Ok(card)
+    }
+
+    pub fn move_back_to_opening_lead(self) -> OpeningLeadState<N> {
+        OpeningLeadState {
+            bids: self.bids,
+            trick_manager: self.trick_manager,
+            hand_manager: self.hand_manager,
+            undo_manager: UndoManager::new(),
+            irregularity_manager: self.irregularity_manager,
+            contract: self.contract,
+            board: self.board,
+        }
+    }
+
+    pub fn move_to_card_play(self) -> CardPlayState<N> {
         CardPlayState {
             bids: self.bids,
             trick_manager: self.trick_manager,
             hand_manager: self.hand_manager,
+            claim_manager: ClaimManager::new(self.contract.declarer),
+            undo_manager: UndoManager::new(),
+            irregularity_manager: self.irregularity_manager,
             contract: self.contract,
             board: self.board,
         }
@@ -4,12 +4,12 @@ use crate::primitives::{Card, Hand, Suit};
 use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Debug, Clone)]
-pub struct HandManager {
+pub struct HandManager<const N: usize = 13> {
     pub known_cards: BTreeMap<Card, Seat>,
     pub played_cards: BTreeSet<Card>,
 }
 
-impl HandManager {
+impl<const N: usize> HandManager<N> {
     pub fn new() -> Self {
         HandManager {
             known_cards: BTreeMap::new(),
@@ -17,7 +17,7 @@ impl HandManager {
         }
     }
 
-    pub fn register_known_hand(&mut self, hand: Hand<13>, player: Seat) -> Result<(), BBError> {
+    pub fn register_known_hand(&mut self, hand: Hand<N>, player: Seat) -> Result<(), BBError> {
         for &card in hand.cards() {
             self.register_known_card(card, player)?;
         }
@@ -25,7 +25,7 @@ impl HandManager {
     }
 
     pub fn register_known_card(&mut self, card: Card, player: Seat) -> Result<(), BBError> {
-        if self.known_cards_of(player).len() >= 13 && !self.known_cards_of(player).contains(&card) {
+        if self.known_cards_of(player).len() >= N && !self.known_cards_of(player).contains(&card) {
             return Err(BBError::InvalidHandInfo);
         }
 
@@ -75,6 +75,14 @@ impl HandManager {
         Ok(())
     }
 
+    // what we learned about the owner of the card is kept
+    pub fn undo_play_card(&mut self, card: Card) -> Result<(), BBError> {
+        match self.played_cards.remove(&card) {
+            true => Ok(()),
+            false => Err(BBError::InvalidCard(card)),
+        }
+    }
+
     pub fn player_is_known_to_have_cards_left_in_suit(&self, player: Seat, suit: Suit) -> bool {
         let known_cards_in_suit: BTreeSet<_> = self
             .known_cards
@@ -107,12 +115,12 @@ impl HandManager {
     }
 
     pub fn full_hand_known_for(&self, player: Seat) -> bool {
-        self.count_known_cards_of(player) == 13
+        self.count_known_cards_of(player) == N
     }
 
-    pub fn hand_of(&self, player: Seat) -> Result<Hand<13>, BBError> {
+    pub fn hand_of(&self, player: Seat) -> Result<Hand<N>, BBError> {
         let cards = self.known_cards_of(player);
-        if cards.len() == 13 {
+        if cards.len() == N {
             Hand::from_cards(&cards)
         } else {
             Err(BBError::InsufficientInfo)
@@ -120,7 +128,7 @@ impl HandManager {
     }
 }
 
-impl Default for HandManager {
+impl<const N: usize> Default for HandManager<N> {
     fn default() -> Self {
         Self::new()
     }
@@ -0,0 +1,591 @@
+use crate::error::BBError;
+use crate::game::hand_manager::HandManager;
+use crate::primitives::bid::{AuxiliaryBid, Bid};
+use crate::primitives::card::rank::Rank;
+use crate::primitives::deal::Seat;
+use crate::primitives::game_event::{
+    BidEvent, ExposedCardEvent, IrregularityEvent, IrregularityResponseEvent, LeadPenalty, LeadPenaltyEvent,
+};
+use crate::primitives::trick::{PlayedTrick, Trick};
+use crate::primitives::{Card, Suit};
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PenaltyCard {
+    pub player: Seat,
+    pub card: Card,
+    pub major: bool,
+}
+
+impl PenaltyCard {
+    // a single card below honour rank exposed inadvertently is a minor penalty card (Law 50)
+    pub fn from_exposed_card(player: Seat, card: Card) -> Self {
+        PenaltyCard {
+            player,
+            card,
+            major: card.rank >= Rank::Ten,
+        }
+    }
+
+    pub fn major(player: Seat, card: Card) -> Self {
+        PenaltyCard {
+            player,
+            card,
+            major: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Revoke {
+    pub player: Seat,
+    pub trick: usize,
+    pub suit: Suit,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IrregularityManager {
+    director: bool,
+    pending: Option<(IrregularityEvent, Seat)>,
+    must_pass_once: Vec<Seat>,
+    must_pass_throughout: Vec<Seat>,
+    insufficient_bid: Option<BidEvent>,
+    penalty_cards: Vec<PenaltyCard>,
+    lead_penalty: Option<(usize, LeadPenalty)>,
+}
+
+impl IrregularityManager {
+    pub fn new(director: bool) -> Self {
+        IrregularityManager {
+            director,
+            ..Default::default()
+        }
+    }
+
+    pub fn director(&self) -> bool {
+        self.director
+    }
+
+    pub fn pending_irregularity(&self) -> Option<IrregularityEvent> {
+        self.pending.map(|(irregularity, _)| irregularity)
+    }
+
+    pub fn next_to_respond(&self) -> Option<Seat> {
+        self.pending.map(|(_, decider)| decider)
+    }
+
+    pub fn process_irregularity_event(
+        &mut self,
+        irregularity: IrregularityEvent,
+        decider: Option<Seat>,
+    ) -> Result<(), BBError> {
+        if !self.director {
+            return Err(BBError::InvalidEvent(format!("{:?}", irregularity)));
+        }
+        if self.pending.is_some() {
+            return Err(BBError::IrregularityPending);
+        }
+        self.pending = decider.map(|decider| (irregularity, decider));
+        Ok(())
+    }
+
+    pub fn validate_irregularity_response_event(
+        &self,
+        event: IrregularityResponseEvent,
+    ) -> Result<IrregularityEvent, BBError> {
+        match self.pending {
+            None => Err(BBError::InvalidEvent(format!("{:?}", event))),
+            Some((_, decider)) if decider != event.player => Err(BBError::OutOfTurn(Some(decider))),
+            Some((irregularity, _)) => Ok(irregularity),
+        }
+    }
+
+    pub fn resolve(&mut self) {
+        self.pending = None;
+    }
+
+    pub fn must_pass(&self, player: Seat) -> bool {
+        self.must_pass_once.contains(&player) || self.must_pass_throughout.contains(&player)
+    }
+
+    pub fn validate_call(&self, player: Seat, bid: Bid) -> Result<(), BBError> {
+        if self.pending.is_some() {
+            return Err(BBError::IrregularityPending);
+        }
+        if self.must_pass(player) && bid != Bid::Auxiliary(AuxiliaryBid::Pass) {
+            return Err(BBError::MustPass);
+        }
+        Ok(())
+    }
+
+    // a legal call fulfills the obligation to pass at the next turn (Law 30A)
+    pub fn register_call(&mut self, player: Seat) {
+        self.must_pass_once.retain(|&seat| seat != player);
+    }
+
+    // a pass out of turn forces the offender to pass at their next turn (Law 30A),
+    // any other call silences the offender's partner (Law 31B, 32B)
+    pub fn reject_call_out_of_turn(&mut self, event: BidEvent) {
+        match event.bid {
+            Bid::Auxiliary(AuxiliaryBid::Pass) => self.must_pass_once.push(event.player),
+            _ => self.must_pass_throughout.push(event.player.partner()),
+        }
+    }
+
+    // the offender has to replace the insufficient bid (Law 27B)
+    pub fn reject_insufficient_bid(&mut self, event: BidEvent) {
+        self.insufficient_bid = Some(event);
+    }
+
+    pub fn cancelled_insufficient_bid(&self) -> Option<BidEvent> {
+        self.insufficient_bid
+    }
+
+    // only the lowest sufficient bid in the same strain comes without rectification (Law 27B1, 27B2)
+    pub fn replace_insufficient_bid(&mut self, without_rectification: bool) {
+        if let Some(event) = self.insufficient_bid.take() {
+            if !without_rectification {
+                self.must_pass_throughout.push(event.player.partner());
+            }
+        }
+    }
+
+    pub fn penalty_cards(&self) -> &[PenaltyCard] {
+        &self.penalty_cards
+    }
+
+    pub fn penalty_cards_of(&self, player: Seat) -> Vec<PenaltyCard> {
+        self.penalty_cards
+            .iter()
+            .filter(|penalty_card| penalty_card.player == player)
+            .copied()
+            .collect_vec()
+    }
+
+    pub fn add_penalty_card(&mut self, penalty_card: PenaltyCard) {
+        self.penalty_cards.push(penalty_card);
+    }
+
+    // cards exposed by declarer or dummy are not penalty cards (Law 48A)
+    pub fn process_exposed_card_event<const N: usize>(
+        &mut self,
+        event: ExposedCardEvent,
+        declarer: Seat,
+        hands: &mut HandManager<N>,
+    ) -> Result<(), BBError> {
+        if !self.director {
+            return Err(BBError::InvalidEvent(format!("{:?}", event)));
+        }
+        hands.validate_play_card_event(event.card, event.player)?;
+        hands.register_known_card(event.card, event.player)?;
+        if !event.player.same_axis(&declarer) {
+            self.add_penalty_card(PenaltyCard::from_exposed_card(event.player, event.card));
+        }
+        Ok(())
+    }
+
+    // Law 50D2
+    pub fn lead_penalty_is_due(&self, leader: Seat, declarer: Seat, trick: usize) -> bool {
+        !leader.same_axis(&declarer)
+            && self.penalty_cards_of(leader.partner()).iter().any(|card| card.major)
+            && self.lead_penalty.map(|(decided_for, _)| decided_for) != Some(trick)
+    }
+
+    pub fn process_lead_penalty_event(
+        &mut self,
+        event: LeadPenaltyEvent,
+        declarer: Seat,
+        leader: Seat,
+        trick: usize,
+    ) -> Result<(), BBError> {
+        if !self.lead_penalty_is_due(leader, declarer, trick) {
+            return Err(BBError::InvalidEvent(format!("{:?}", event)));
+        }
+        if event.player != declarer {
+            return Err(BBError::OutOfTurn(Some(declarer)));
+        }
+        let offender = leader.partner();
+        match event.penalty {
+            LeadPenalty::RequireSuit(suit) | LeadPenalty::ForbidSuit(suit) => {
+                let has_major_penalty_card_in_suit = self
+                    .penalty_cards_of(offender)
+                    .iter()
+                    .any(|penalty_card| penalty_card.major && penalty_card.card.suit == suit);
+                if !has_major_penalty_card_in_suit {
+                    return Err(BBError::InvalidEvent(format!("{:?}", event)));
+                }
+                // the penalty cards in that suit may be picked up
+                self.penalty_cards
+                    .retain(|penalty_card| penalty_card.player != offender || penalty_card.card.suit != suit);
+            }
+            LeadPenalty::PenaltyCardsRemain => (),
+        }
+        self.lead_penalty = Some((trick, event.penalty));
+        Ok(())
+    }
+
+    pub fn validate_card<const N: usize>(
+        &self,
+        player: Seat,
+        card: Card,
+        suit_to_follow: Option<Suit>,
+        trick: usize,
+        hands: &HandManager<N>,
+    ) -> Result<(), BBError> {
+        if self.pending.is_some() {
+            return Err(BBError::IrregularityPending);
+        }
+
+        if suit_to_follow.is_none() {
+            self.validate_lead_restriction(player, card, trick, hands)?;
+        }
+
+        let penalty_cards = self.penalty_cards_of(player);
+        if penalty_cards.iter().any(|penalty_card| penalty_card.card == card) {
+            return Ok(());
+        }
+
+        let playable_major_penalty_card = penalty_cards.iter().find(|penalty_card| {
+            penalty_card.major
+                && match suit_to_follow {
+                    None => true,
+                    Some(suit) => {
+                        penalty_card.card.suit == suit
+                            || !hands.player_is_known_to_have_cards_left_in_suit(player, suit)
+                    }
+                }
+        });
+        if let Some(penalty_card) = playable_major_penalty_card {
+            return Err(BBError::MustPlayPenaltyCard(penalty_card.card));
+        }
+
+        let minor_penalty_card_in_suit = penalty_cards
+            .iter()
+            .find(|penalty_card| !penalty_card.major && penalty_card.card.suit == card.suit && card.rank < Rank::Ten);
+        if let Some(penalty_card) = minor_penalty_card_in_suit {
+            return Err(BBError::MustPlayPenaltyCard(penalty_card.card));
+        }
+
+        Ok(())
+    }
+
+    fn validate_lead_restriction<const N: usize>(
+        &self,
+        player: Seat,
+        card: Card,
+        trick: usize,
+        hands: &HandManager<N>,
+    ) -> Result<(), BBError> {
+        match self.lead_penalty {
+            Some((restricted_trick, LeadPenalty::RequireSuit(suit)))
+                if restricted_trick == trick
+                    && card.suit != suit
+                    && hands.player_is_known_to_have_cards_left_in_suit(player, suit) =>
+            {
+                Err(BBError::LeadRestricted(suit))
+            }
+            Some((restricted_trick, LeadPenalty::ForbidSuit(suit)))
+                if restricted_trick == trick
+                    && card.suit == suit
+                    && hands
+                        .known_remaining_cards_of(player)
+                        .iter()
+                        .any(|other| other.suit != suit) =>
+            {
+                Err(BBError::LeadRestricted(suit))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn play_card(&mut self, player: Seat, card: Card) {
+        self.penalty_cards
+            .retain(|penalty_card| penalty_card.player != player || penalty_card.card != card);
+    }
+
+    // a revoke is established by a later play of the offending side (Law 63),
+    // one on the twelfth trick has to be corrected instead (Law 62D)
+    pub fn established_revokes<const N: usize>(tricks: &[PlayedTrick], hands: &HandManager<N>) -> Vec<Revoke> {
+        let mut revokes = Vec::new();
+        for (index, trick) in tricks.iter().enumerate().take(N.saturating_sub(2)) {
+            let cards = trick.cards();
+            let suit = cards[0].suit;
+            let gone = tricks[..=index].iter().flat_map(|trick| trick.cards()).collect_vec();
+            for (offset, card) in cards.iter().enumerate().skip(1) {
+                let player = trick.lead() + offset;
+                let could_have_followed = hands
+                    .known_cards_of(player)
+                    .iter()
+                    .any(|held| held.suit == suit && !gone.contains(&held));
+                if card.suit != suit && could_have_followed {
+                    revokes.push(Revoke {
+                        player,
+                        trick: index,
+                        suit,
+                    });
+                }
+            }
+        }
+        revokes
+    }
+
+    // Law 64
+    pub fn revoke_trick_transfers(tricks: &[PlayedTrick], revokes: &[Revoke], dummy: Seat) -> Vec<(Revoke, usize)> {
+        let mut rectified: Vec<Revoke> = Vec::new();
+        let mut transfers = Vec::new();
+        for &revoke in revokes {
+            // no rectification for failing to play a card from dummy or for a second revoke in the same suit
+            let repeated = rectified
+                .iter()
+                .any(|earlier| earlier.player == revoke.player && earlier.suit == revoke.suit);
+            if revoke.player == dummy || repeated {
+                continue;
+            }
+            rectified.push(revoke);
+
+            let revoke_trick = &tricks[revoke.trick];
+            let subsequent_tricks_won = tricks[revoke.trick + 1..]
+                .iter()
+                .filter(|trick| trick.winner().same_axis(&revoke.player))
+                .count();
+            let transferred = if revoke_trick.is_won_by(revoke.player) {
+                1 + subsequent_tricks_won.min(1)
+            } else if revoke_trick.winner().same_axis(&revoke.player) || subsequent_tricks_won > 0 {
+                1
+            } else {
+                0
+            };
+            transfers.push((revoke, transferred));
+        }
+        transfers
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::BBError;
+    use crate::game::hand_manager::HandManager;
+    use crate::game::irregularity_manager::{IrregularityManager, PenaltyCard, Revoke};
+    use crate::primitives::bid::Bid;
+    use crate::primitives::deal::Seat;
+    use crate::primitives::deal::Seat::*;
+    use crate::primitives::game_event::{BidEvent, LeadPenalty, LeadPenaltyEvent};
+    use crate::primitives::trick::PlayedTrick;
+    use crate::primitives::{Card, Hand, Suit};
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    fn card(card: &str) -> Card {
+        Card::from_str(card).unwrap()
+    }
+
+    fn trick(lead: Seat, cards: [&str; 4], winner: Seat) -> PlayedTrick {
+        PlayedTrick::new(lead, cards.map(card), winner)
+    }
+
+    #[test_case("p", &[North], &[]; "Pass")]
+    #[test_case("1H", &[], &[South]; "Bid")]
+    #[test_case("X", &[], &[South]; "Double")]
+    fn call_out_of_turn_rejected(bid: &str, must_pass_once: &[Seat], must_pass_throughout: &[Seat]) {
+        let mut manager = IrregularityManager::new(true);
+        manager.reject_call_out_of_turn(BidEvent {
+            player: North,
+            bid: Bid::from_str(bid).unwrap(),
+            alert: false,
+            explanation: None,
+        });
+
+        assert_eq!(manager.must_pass_once, must_pass_once);
+        assert_eq!(manager.must_pass_throughout, must_pass_throughout);
+        assert_eq!(
+            manager.validate_call(North, Bid::from_str("1S").unwrap()).is_err(),
+            !must_pass_once.is_empty()
+        );
+
+        manager.register_call(North);
+        assert!(!manager.must_pass(North));
+    }
+
+    #[test_case(true, false; "Lowest sufficient bid")]
+    #[test_case(false, true; "Other call")]
+    fn insufficient_bid_replaced(without_rectification: bool, partner_must_pass: bool) {
+        let mut manager = IrregularityManager::new(true);
+        let event = BidEvent {
+            player: East,
+            bid: Bid::from_str("1C").unwrap(),
+            alert: false,
+            explanation: None,
+        };
+        manager.reject_insufficient_bid(event);
+        assert_eq!(manager.cancelled_insufficient_bid(), Some(event));
+
+        manager.replace_insufficient_bid(without_rectification);
+        assert_eq!(manager.cancelled_insufficient_bid(), None);
+        assert_eq!(manager.must_pass(West), partner_must_pass);
+    }
+
+    #[test]
+    fn major_penalty_card_must_be_played() {
+        let mut hands = HandManager::new();
+        let hand = Hand::<13>::from_str("S:AK,H:QJ73,D:8642,C:975").unwrap();
+        hands.register_known_hand(hand, West).unwrap();
+
+        let mut manager = IrregularityManager::new(true);
+        manager.add_penalty_card(PenaltyCard::major(West, card("HQ")));
+
+        // when following suit in another suit, the penalty card has to wait
+        assert!(manager
+            .validate_card(West, card("D8"), Some(Suit::Diamonds), 1, &hands)
+            .is_ok());
+        // when following to hearts, leading, or discarding, it has to be played
+        assert_eq!(
+            manager.validate_card(West, card("H3"), Some(Suit::Hearts), 1, &hands),
+            Err(BBError::MustPlayPenaltyCard(card("HQ")))
+        );
+        assert_eq!(
+            manager.validate_card(West, card("SA"), None, 1, &hands),
+            Err(BBError::MustPlayPenaltyCard(card("HQ")))
+        );
+        assert!(manager.validate_card(West, card("HQ"), None, 1, &hands).is_ok());
+
+        manager.play_card(West, card("HQ"));
+        assert!(manager.penalty_cards().is_empty());
+    }
+
+    #[test]
+    fn minor_penalty_card() {
+        let mut hands = HandManager::new();
+        let hand = Hand::<13>::from_str("S:AK,H:QJ73,D:8642,C:975").unwrap();
+        hands.register_known_hand(hand, West).unwrap();
+
+        let mut manager = IrregularityManager::new(true);
+        manager.add_penalty_card(PenaltyCard::from_exposed_card(West, card("H3")));
+
+        assert!(manager.validate_card(West, card("SA"), None, 1, &hands).is_ok());
+        assert!(manager
+            .validate_card(West, card("HQ"), Some(Suit::Hearts), 1, &hands)
+            .is_ok());
+        assert_eq!(
+            manager.validate_card(West, card("H7"), Some(Suit::Hearts), 1, &hands),
+            Err(BBError::MustPlayPenaltyCard(card("H3")))
+        );
+    }
+
+    #[test]
+    fn lead_penalty() {
+        let mut hands = HandManager::new();
+        let hand = Hand::<13>::from_str("S:AK,H:QJ73,D:8642,C:975").unwrap();
+        hands.register_known_hand(hand, West).unwrap();
+
+        let mut manager = IrregularityManager::new(true);
+        manager.add_penalty_card(PenaltyCard::major(East, card("CK")));
+
+        assert!(manager.lead_penalty_is_due(West, South, 0));
+        assert!(!manager.lead_penalty_is_due(East, South, 0));
+
+        let event = LeadPenaltyEvent {
+            player: North,
+            penalty: LeadPenalty::RequireSuit(Suit::Clubs),
+        };
+        assert_eq!(
+            manager.process_lead_penalty_event(event, South, West, 0),
+            Err(BBError::OutOfTurn(Some(South)))
+        );
+
+        let event = LeadPenaltyEvent {
+            player: South,
+            penalty: LeadPenalty::RequireSuit(Suit::Clubs),
+        };
+        manager.process_lead_penalty_event(event, South, West, 0).unwrap();
+
+        assert!(manager.penalty_cards().is_empty());
+        assert!(!manager.lead_penalty_is_due(West, South, 0));
+        assert_eq!(
+            manager.validate_card(West, card("SA"), None, 0, &hands),
+            Err(BBError::LeadRestricted(Suit::Clubs))
+        );
+        assert!(manager.validate_card(West, card("C9"), None, 0, &hands).is_ok());
+        assert!(manager.validate_card(West, card("SA"), None, 1, &hands).is_ok());
+    }
+
+    fn hands() -> HandManager {
+        let mut hands = HandManager::new();
+        for (seat, hand) in [
+            (North, "S:AK,H:AK2,D:AKQJ,C:AKQJ"),
+            (East, "S:QJT9,H:QJT9,D:T98,C:T9"),
+            (South, "S:8765,H:8765,D:765,C:87"),
+            (West, "S:432,H:43,D:432,C:65432"),
+        ] {
+            hands
+                .register_known_hand(Hand::<13>::from_str(hand).unwrap(), seat)
+                .unwrap();
+        }
+        hands
+    }
+
+    #[test]
+    fn established_revokes() {
+        let tricks = [
+            trick(North, ["SA", "SQ", "S5", "S2"], North),
+            // East has spades left, but ruffs
+            trick(North, ["SK", "C9", "S6", "S3"], North),
+            trick(North, ["HA", "HQ", "H5", "H3"], North),
+        ];
+        let revokes = IrregularityManager::established_revokes(&tricks, &hands());
+        assert_eq!(
+            revokes,
+            [Revoke {
+                player: East,
+                trick: 1,
+                suit: Suit::Spades
+            }]
+        );
+    }
+
+    #[test_case(East, 2, 1; "Revoke trick won by offender and a later one")]
+    #[test_case(East, 1, 0; "Revoke trick won by offender only")]
+    #[test_case(West, 1, 1; "Revoke trick won by partner")]
+    #[test_case(North, 0, 0; "Declarer won the revoke trick")]
+    fn revoke_trick_transfers(revoke_trick_winner: Seat, expected: usize, later_tricks_won_by_defenders: usize) {
+        let mut tricks = vec![trick(North, ["SA", "C9", "S5", "S2"], revoke_trick_winner)];
+        for _ in 0..later_tricks_won_by_defenders {
+            tricks.push(trick(East, ["HQ", "H5", "H3", "H2"], East));
+        }
+        tricks.push(trick(North, ["HA", "HJ", "H6", "H4"], North));
+
+        let revoke = Revoke {
+            player: East,
+            trick: 0,
+            suit: Suit::Spades,
+        };
+        let transfers = IrregularityManager::revoke_trick_transfers(&tricks, &[revoke], South);
+        assert_eq!(transfers, [(revoke, expected)]);
+    }
+
+    #[test]
+    fn no_transfer_for_dummy_or_repeated_revokes() {
+        let tricks = [
+            trick(North, ["SA", "C9", "C8", "S2"], North),
+            trick(North, ["SK", "CT", "C7", "S3"], East),
+        ];
+        let revokes = [
+            Revoke {
+                player: East,
+                trick: 0,
+                suit: Suit::Spades,
+            },
+            Revoke {
+                player: South,
+                trick: 0,
+                suit: Suit::Spades,
+            },
+            Revoke {
+                player: East,
+                trick: 1,
+                suit: Suit::Spades,
+            },
+        ];
+        let transfers = IrregularityManager::revoke_trick_transfers(&tricks, &revokes, South);
+        assert_eq!(transfers, [(revokes[0], 1)]);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::engine::hand_evaluation::ForumDPlus2015Evaluator;
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::contract::{Level, State, Strain};
+use crate::primitives::deal::Seat;
+use crate::primitives::Suit::{Clubs, Diamonds, Hearts, Spades};
+use crate::primitives::{Contract, Deal, Hand, Suit};
+
+// no auction: the side with more hcp declares, and the contract is chosen for declarer from the combined hands
+pub struct MiniBridge;
+
+impl MiniBridge {
+    // combined hcp for a game in no-trump or a major, or in a minor
+    const GAME_POINTS: f64 = 25.0;
+    const MINOR_GAME_POINTS: f64 = 29.0;
+
+    // on a tie, dealer's side or the player closer to dealer
+    pub fn declarer<const N: usize>(deal: &Deal<N>) -> Seat {
+        let dealer = deal.board.dealer();
+        let points = |seat: Seat| Self::hcp(deal.hand_of(seat));
+        let side = match points(dealer + 1) + points(dealer + 3) > points(dealer) + points(dealer + 2) {
+            true => dealer + 1,
+            false => dealer,
+        };
+        match points(side.partner()) > points(side) {
+            true => side.partner(),
+            false => side,
+        }
+    }
+
+    pub fn contract<const N: usize>(deal: &Deal<N>) -> Contract {
+        let declarer = Self::declarer(deal);
+        Self::choose_contract(declarer, [deal.hand_of(declarer), deal.hand_of(declarer.partner())])
+    }
+
+    // a major with an eight-card fit, a minor with one only when a hand is short somewhere, no-trump otherwise
+    fn choose_contract<const N: usize>(declarer: Seat, hands: [&Hand<N>; 2]) -> Contract {
+        let length = |suit: Suit| hands.iter().map(|hand| hand.length_in(suit)).sum::<u8>();
+        let short = hands
+            .iter()
+            .any(|hand| SUIT_ARRAY.iter().any(|&suit| hand.length_in(suit) <= 1));
+        let fit = |suits: [Suit; 2]| {
+            suits
+                .into_iter()
+                .filter(|&suit| length(suit) >= 8)
+                .max_by_key(|&suit| length(suit))
+        };
+        let strain = match fit([Hearts, Spades]).or(fit([Clubs, Diamonds]).filter(|_| short)) {
+            Some(suit) => Strain::Trump(suit),
+            None => Strain::NoTrump,
+        };
+
+        let points = hands.iter().map(|hand| Self::hcp(hand)).sum::<f64>();
+        let game_points = match strain {
+            Strain::Trump(suit) if suit.is_minor() => Self::MINOR_GAME_POINTS,
+            _ => Self::GAME_POINTS,
+        };
+        let level = match points >= game_points {
+            true => Self::game_level(strain),
+            false => Level::One,
+        };
+
+        Contract {
+            level,
+            strain,
+            state: State::Passed,
+            declarer,
+        }
+    }
+
+    // partscores on the one level and games, never doubled
+    pub fn is_valid_contract(contract: Contract) -> bool {
+        contract.state == State::Passed
+            && (contract.level == Level::One || contract.level == Self::game_level(contract.strain))
+    }
+
+    fn hcp<const N: usize>(hand: &Hand<N>) -> f64 {
+        ForumDPlus2015Evaluator::hcp_for_cards(&mut hand.cards())
+    }
+
+    fn game_level(strain: Strain) -> Level {
+        match strain {
+            Strain::NoTrump => Level::Three,
+            Strain::Trump(suit) if suit.is_major() => Level::Four,
+            Strain::Trump(_) => Level::Five,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MiniBridge;
+    use crate::primitives::deal::Seat;
+    use crate::primitives::{Contract, Deal, Hand};
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    const HANDS: [&str; 4] = [
+        "S:AKQ2,H:K32,D:A32,C:432",
+        "S:543,H:QJ4,D:KQ4,C:8765",
+        "S:JT96,H:A765,D:65,C:AKQ",
+        "S:87,H:T98,D:JT987,C:JT9",
+    ];
+
+    #[test_case(0, "N4S"; "North")]
+    #[test_case(1, "E4S"; "East")]
+    #[test_case(2, "S4S"; "South")]
+    #[test_case(3, "W4S"; "West")]
+    fn declarer(rotation: usize, expected: &str) {
+        let hands = [0, 1, 2, 3].map(|seat| Hand::<13>::from_str(HANDS[(seat + 4 - rotation) % 4]).unwrap());
+        let deal = Deal::from_hands(hands);
+        let expected = Contract::from_str(expected).unwrap();
+
+        assert_eq!(MiniBridge::declarer(&deal), expected.declarer);
+        assert_eq!(MiniBridge::contract(&deal), expected);
+    }
+
+    #[test_case("S:AKQ2,H:K32,D:A32,C:432", "S:JT96,H:A765,D:65,C:AKQ", "N4S"; "Major game")]
+    #[test_case("S:AK32,H:K32,D:432,C:432", "S:QJ96,H:A765,D:65,C:KQ2", "N1S"; "Major partscore")]
+    #[test_case("S:AK2,H:K32,D:A432,C:432", "S:QJ6,H:A765,D:K5,C:KQJ2", "N3NT"; "No major fit")]
+    #[test_case("S:A2,H:K32,D:AQ432,C:432", "S:K,H:A765,D:KJ765,C:KQ2", "N5D"; "Minor game")]
+    #[test_case("S:A2,H:K32,D:AQ432,C:432", "S:K,H:Q765,D:KJ765,C:Q32", "N1D"; "Minor partscore")]
+    fn contract(declarer: &str, dummy: &str, expected: &str) {
+        let declarer = Hand::<13>::from_str(declarer).unwrap();
+        let dummy = Hand::<13>::from_str(dummy).unwrap();
+
+        let contract = MiniBridge::choose_contract(Seat::North, [&declarer, &dummy]);
+        assert_eq!(contract, Contract::from_str(expected).unwrap());
+    }
+
+    #[test_case("N1S", true; "Partscore")]
+    #[test_case("N2S", false; "Higher partscore")]
+    #[test_case("N3NT", true; "No-trump game")]
+    #[test_case("N4C", false; "Short of a minor game")]
+    #[test_case("N5C", true; "Minor game")]
+    #[test_case("N1NTX", false; "Doubled")]
+    fn is_valid_contract(contract: &str, expected: bool) {
+        assert_eq!(
+            MiniBridge::is_valid_contract(Contract::from_str(contract).unwrap()),
+            expected
+        );
+    }
+}
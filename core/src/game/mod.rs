@@ -4,32 +4,41 @@ pub mod trick_manager;
 pub mod game_phase_states;
 // mod bid_manager;
 pub mod bid_manager;
+pub mod claim_manager;
 pub mod hand_manager;
+pub mod irregularity_manager;
+pub mod mini_bridge;
 pub mod scoring;
+pub mod undo_manager;
 
 use crate::error::BBError;
 use crate::game::game_phase_states::{
     BiddingState, CardPlayState, EndedState, GamePhaseState, NextToPlay, OpeningLeadState, WaitingForDummyState,
 };
+use crate::game::mini_bridge::MiniBridge;
+use crate::game::undo_manager::UndoManager;
 
+use crate::primitives::deal::seat::SEAT_ARRAY;
 use crate::primitives::deal::{Board, Seat};
 use crate::primitives::game_event::{
-    BidEvent, BiddingEndedEvent, CardEvent, DiscloseHandEvent, DummyUncoveredEvent, GameEndedEvent, GameEvent,
-    NewGameEvent,
+    BidEvent, BiddingEndedEvent, CardEvent, ClaimEvent, ClaimResponseEvent, ClaimRulingEvent, ConcedeEvent,
+    DiscloseHandEvent, DummyUncoveredEvent, ExposedCardEvent, GameEndedEvent, GameEvent, IrregularityEvent,
+    IrregularityResponseEvent, LeadPenaltyEvent, NewGameEvent, UndoEvent, UndoRequestEvent, UndoResponseEvent,
 };
 use crate::primitives::game_result::GameResult;
+use crate::primitives::player_event::PlayerEvent;
 use crate::primitives::Hand;
 
 #[derive(Debug, Clone)]
-pub enum GameState {
-    Bidding(BiddingState),
-    OpeningLead(OpeningLeadState),
-    WaitingForDummy(WaitingForDummyState),
-    CardPlay(CardPlayState),
-    Ended(EndedState),
+pub enum GameState<const N: usize = 13> {
+    Bidding(BiddingState<N>),
+    OpeningLead(OpeningLeadState<N>),
+    WaitingForDummy(WaitingForDummyState<N>),
+    CardPlay(CardPlayState<N>),
+    Ended(EndedState<N>),
 }
 
-impl GameState {
+impl<const N: usize> GameState<N> {
     pub fn next_to_play(&self) -> Option<Seat> {
         match &self {
             GameState::Bidding(state) => Some(state.next_to_play()),
@@ -50,7 +59,7 @@ impl GameState {
         }
     }
 
-    pub fn hand_of(&self, player: Seat) -> Result<Hand<13>, BBError> {
+    pub fn hand_of(&self, player: Seat) -> Result<Hand<N>, BBError> {
         match &self {
             GameState::Bidding(state) => state.hand_of(player),
             GameState::OpeningLead(state) => state.hand_of(player),
@@ -70,7 +79,7 @@ impl GameState {
         }
     }
 
-    pub fn process_game_event(&mut self, event: GameEvent) -> Result<(), BBError> {
+    pub fn process_game_event(&mut self, event: GameEvent<N>) -> Result<(), BBError> {
         match event {
             GameEvent::NewGame(_) => Err(BBError::GameAlreadyStarted),
             GameEvent::DiscloseHand(disclose_hand_event) => self.process_disclose_hand_event(disclose_hand_event),
@@ -80,6 +89,17 @@ impl GameState {
             GameEvent::DummyUncovered(dummy_uncovered_event) => {
                 self.process_dummy_uncovered_event(dummy_uncovered_event)
             }
+            GameEvent::Claim(claim_event) => self.process_claim_event(claim_event),
+            GameEvent::Concede(concede_event) => self.process_concede_event(concede_event),
+            GameEvent::ClaimResponse(response_event) => self.process_claim_response_event(response_event),
+            GameEvent::ClaimRuling(ruling_event) => self.process_claim_ruling_event(ruling_event),
+            GameEvent::UndoRequest(request_event) => self.process_undo_request_event(request_event),
+            GameEvent::UndoResponse(response_event) => self.process_undo_response_event(response_event),
+            GameEvent::Undo(undo_event) => self.process_undo_event(undo_event),
+            GameEvent::Irregularity(irregularity) => self.process_irregularity_event(irregularity),
+            GameEvent::IrregularityResponse(response_event) => self.process_irregularity_response_event(response_event),
+            GameEvent::ExposedCard(exposed_card_event) => self.process_exposed_card_event(exposed_card_event),
+            GameEvent::LeadPenalty(lead_penalty_event) => self.process_lead_penalty_event(lead_penalty_event),
             GameEvent::GameEnded(game_ended_event) => self.process_game_ended_event(game_ended_event),
         }
     }
@@ -87,22 +107,28 @@ impl GameState {
     pub fn process_bidding_ended_event(&mut self, bidding_ended_event: BiddingEndedEvent) -> Result<(), BBError> {
         match self {
             GameState::Bidding(state) => {
-                if state.implied_contract() != Some(bidding_ended_event.final_contract) || !state.bidding_has_ended() {
-                    Err(BBError::InvalidEvent(Box::new(GameEvent::BiddingEnded(
-                        bidding_ended_event,
-                    ))))?
+                let valid = match state.mini_bridge {
+                    true => {
+                        state.bid_manager.bids().is_empty()
+                            && MiniBridge::is_valid_contract(bidding_ended_event.final_contract)
+                    }
+                    false => {
+                        state.implied_contract() == Some(bidding_ended_event.final_contract)
+                            && state.bidding_has_ended()
+                    }
+                };
+                if !valid {
+                    Err(BBError::InvalidEvent(format!("{:?}", bidding_ended_event)))?
                 }
                 let new_state = state.clone().move_to_opening_lead(bidding_ended_event.final_contract);
                 *self = GameState::OpeningLead(new_state);
                 Ok(())
             }
-            _ => Err(BBError::InvalidEvent(Box::new(GameEvent::BiddingEnded(
-                bidding_ended_event,
-            )))),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", bidding_ended_event))),
         }
     }
 
-    pub fn process_game_ended_event(&mut self, game_ended_event: GameEndedEvent) -> Result<(), BBError> {
+    pub fn process_game_ended_event(&mut self, game_ended_event: GameEndedEvent<N>) -> Result<(), BBError> {
         match self {
             GameState::Bidding(state) => {
                 assert_eq!(game_ended_event.result, GameResult::Unplayed);
@@ -110,11 +136,16 @@ impl GameState {
                 *self = GameState::Ended(new_state);
             }
             GameState::CardPlay(state) => {
+                // rectifications for revokes need all four hands, which the deal discloses now
+                for seat in SEAT_ARRAY {
+                    let hand = *game_ended_event.deal.hand_of(seat);
+                    state.hand_manager.register_known_hand(hand, seat)?;
+                }
                 assert_eq!(state.calculate_game_result(), game_ended_event.result);
-                let new_state = state.clone().move_from_card_play_to_ended();
+                let new_state = state.clone().move_from_card_play_to_ended(game_ended_event.result);
                 *self = GameState::Ended(new_state);
             }
-            _ => Err(BBError::InvalidEvent(Box::new(GameEvent::GameEnded(game_ended_event))))?,
+            _ => Err(BBError::InvalidEvent(format!("{:?}", game_ended_event)))?,
         };
         Ok(())
     }
@@ -122,7 +153,7 @@ impl GameState {
     pub fn process_make_bid_event(&mut self, bid_event: BidEvent) -> Result<(), BBError> {
         match self {
             GameState::Bidding(state) => state.process_make_bid_event(bid_event),
-            _ => Err(BBError::InvalidEvent(Box::new(GameEvent::Bid(bid_event)))),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", bid_event))),
         }
     }
 
@@ -138,11 +169,222 @@ impl GameState {
                 state.process_play_card_event(card_event)?;
                 Ok(())
             }
-            _ => Err(BBError::InvalidEvent(Box::new(GameEvent::Card(card_event)))),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", card_event))),
+        }
+    }
+
+    pub fn process_claim_event(&mut self, claim_event: ClaimEvent) -> Result<(), BBError> {
+        match self {
+            GameState::CardPlay(state) => state.process_claim_event(claim_event),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", claim_event))),
+        }
+    }
+
+    pub fn process_concede_event(&mut self, concede_event: ConcedeEvent) -> Result<(), BBError> {
+        match self {
+            GameState::CardPlay(state) => state.process_concede_event(concede_event),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", concede_event))),
+        }
+    }
+
+    pub fn process_claim_response_event(&mut self, response_event: ClaimResponseEvent) -> Result<(), BBError> {
+        match self {
+            GameState::CardPlay(state) => state.process_claim_response_event(response_event),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", response_event))),
+        }
+    }
+
+    pub fn process_claim_ruling_event(&mut self, ruling_event: ClaimRulingEvent) -> Result<(), BBError> {
+        match self {
+            GameState::CardPlay(state) => state.process_claim_ruling(ruling_event.claim, ruling_event.valid),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", ruling_event))),
+        }
+    }
+
+    pub fn process_undo_request_event(&mut self, request_event: UndoRequestEvent) -> Result<(), BBError> {
+        self.moves_to_undo(request_event.player)?;
+        match self {
+            GameState::Bidding(state) => state.process_undo_request_event(request_event),
+            GameState::OpeningLead(state) => state.process_undo_request_event(request_event),
+            GameState::CardPlay(state) => state.process_undo_request_event(request_event),
+            GameState::Ended(_) => Err(BBError::GameHasEnded),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", request_event))),
+        }
+    }
+
+    pub fn process_undo_response_event(&mut self, response_event: UndoResponseEvent) -> Result<(), BBError> {
+        match self {
+            GameState::Bidding(state) => state.process_undo_response_event(response_event),
+            GameState::OpeningLead(state) => state.process_undo_response_event(response_event),
+            GameState::CardPlay(state) => state.process_undo_response_event(response_event),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", response_event))),
+        }
+    }
+
+    pub fn process_undo_event(&mut self, undo_event: UndoEvent) -> Result<(), BBError> {
+        let request = UndoRequestEvent {
+            player: undo_event.player,
+        };
+        if self.approved_undo_request() != Some(request) || self.moves_to_undo(undo_event.player)? != undo_event.moves {
+            return Err(BBError::InvalidEvent(format!("{:?}", undo_event)));
+        }
+
+        if let Some(manager) = self.undo_manager_mut() {
+            manager.clear();
+        }
+
+        for _ in 0..undo_event.moves {
+            self.undo_last_move()?;
+        }
+        Ok(())
+    }
+
+    pub fn pending_undo_request(&self) -> Option<UndoRequestEvent> {
+        self.undo_manager().and_then(|manager| manager.pending_request())
+    }
+
+    pub fn approved_undo_request(&self) -> Option<UndoRequestEvent> {
+        self.undo_manager().and_then(|manager| manager.approved_request())
+    }
+
+    fn undo_manager(&self) -> Option<&UndoManager> {
+        match self {
+            GameState::Bidding(state) => Some(&state.undo_manager),
+            GameState::OpeningLead(state) => Some(&state.undo_manager),
+            GameState::CardPlay(state) => Some(&state.undo_manager),
+            _ => None,
+        }
+    }
+
+    fn undo_manager_mut(&mut self) -> Option<&mut UndoManager> {
+        match self {
+            GameState::Bidding(state) => Some(&mut state.undo_manager),
+            GameState::OpeningLead(state) => Some(&mut state.undo_manager),
+            GameState::CardPlay(state) => Some(&mut state.undo_manager),
+            _ => None,
         }
     }
 
-    fn process_dummy_uncovered_event(&mut self, event: DummyUncoveredEvent) -> Result<(), BBError> {
+    // when declarer asks, dummy's cards count as their own
+    pub fn moves_to_undo(&self, player: Seat) -> Result<usize, BBError> {
+        let declarer = self.declarer();
+        self.players_of_moves()
+            .into_iter()
+            .rev()
+            .position(|mover| mover == player || Some(player) == declarer && mover == player.partner())
+            .map(|position| position + 1)
+            .ok_or(BBError::NothingToUndo)
+    }
+
+    fn players_of_moves(&self) -> Vec<Seat> {
+        let (bids, card_players) = match self {
+            GameState::Bidding(state) => (state.bid_manager.bids().len(), Vec::new()),
+            GameState::OpeningLead(state) => (state.bids.len(), state.trick_manager.players_of_played_cards()),
+            GameState::WaitingForDummy(state) => (state.bids.len(), state.trick_manager.players_of_played_cards()),
+            GameState::CardPlay(state) => (state.bids.len(), state.trick_manager.players_of_played_cards()),
+            GameState::Ended(_) => (0, Vec::new()),
+        };
+        let dealer = self.board().dealer();
+        (0..bids).map(|index| dealer + index).chain(card_players).collect()
+    }
+
+    fn undo_last_move(&mut self) -> Result<(), BBError> {
+        match self {
+            GameState::Bidding(state) => {
+                state.undo_last_bid()?;
+            }
+            GameState::OpeningLead(state) => {
+                let mut new_state = state.clone().move_back_to_bidding();
+                new_state.undo_last_bid()?;
+                *self = GameState::Bidding(new_state);
+            }
+            GameState::WaitingForDummy(state) => {
+                state.undo_last_card()?;
+                let new_state = state.clone().move_back_to_opening_lead();
+                *self = GameState::OpeningLead(new_state);
+            }
+            GameState::CardPlay(state) => {
+                state.undo_last_card()?;
+                if state.trick_manager.count_played_cards() == 0 {
+                    let new_state = state.clone().move_back_to_opening_lead();
+                    *self = GameState::OpeningLead(new_state);
+                }
+            }
+            GameState::Ended(_) => Err(BBError::GameHasEnded)?,
+        }
+        Ok(())
+    }
+
+    pub fn irregularity(&self, event: PlayerEvent) -> Option<IrregularityEvent> {
+        match (self, event) {
+            (GameState::Bidding(state), PlayerEvent::Bid(bid_event)) => state.irregularity(bid_event),
+            (GameState::OpeningLead(state), PlayerEvent::Card(card_event)) => state.irregularity(card_event),
+            (GameState::CardPlay(state), PlayerEvent::Card(card_event)) => state.irregularity(card_event),
+            _ => None,
+        }
+    }
+
+    pub fn process_irregularity_event(&mut self, irregularity: IrregularityEvent) -> Result<(), BBError> {
+        match self {
+            GameState::Bidding(state) => state.process_irregularity_event(irregularity),
+            GameState::OpeningLead(state) => state.process_irregularity_event(irregularity),
+            GameState::CardPlay(state) => state.process_irregularity_event(irregularity),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", irregularity))),
+        }
+    }
+
+    pub fn process_irregularity_response_event(
+        &mut self,
+        response_event: IrregularityResponseEvent,
+    ) -> Result<(), BBError> {
+        match self {
+            GameState::Bidding(state) => state.process_irregularity_response_event(response_event),
+            GameState::OpeningLead(state) => {
+                if state.process_irregularity_response_event(response_event)? {
+                    let new_state = state.clone().move_to_waiting_for_dummy();
+                    *self = GameState::WaitingForDummy(new_state);
+                }
+                Ok(())
+            }
+            GameState::CardPlay(state) => state.process_irregularity_response_event(response_event),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", response_event))),
+        }
+    }
+
+    pub fn process_exposed_card_event(&mut self, event: ExposedCardEvent) -> Result<(), BBError> {
+        match self {
+            GameState::OpeningLead(state) => state.process_exposed_card_event(event),
+            GameState::CardPlay(state) => state.process_exposed_card_event(event),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", event))),
+        }
+    }
+
+    pub fn process_lead_penalty_event(&mut self, event: LeadPenaltyEvent) -> Result<(), BBError> {
+        match self {
+            GameState::OpeningLead(state) => state.process_lead_penalty_event(event),
+            GameState::CardPlay(state) => state.process_lead_penalty_event(event),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", event))),
+        }
+    }
+
+    pub fn pending_irregularity(&self) -> Option<IrregularityEvent> {
+        match self {
+            GameState::Bidding(state) => state.irregularity_manager.pending_irregularity(),
+            GameState::OpeningLead(state) => state.irregularity_manager.pending_irregularity(),
+            GameState::CardPlay(state) => state.irregularity_manager.pending_irregularity(),
+            _ => None,
+        }
+    }
+
+    pub fn lead_penalty_is_due(&self) -> bool {
+        match self {
+            GameState::OpeningLead(state) => state.lead_penalty_is_due(),
+            GameState::CardPlay(state) => state.lead_penalty_is_due(),
+            _ => false,
+        }
+    }
+
+    fn process_dummy_uncovered_event(&mut self, event: DummyUncoveredEvent<N>) -> Result<(), BBError> {
         match self {
             GameState::WaitingForDummy(state) => {
                 state.process_dummy_uncovered_event(event)?;
@@ -151,24 +393,20 @@ impl GameState {
                 *self = GameState::CardPlay(new_state);
                 Ok(())
             }
-            _ => Err(BBError::InvalidEvent(Box::new(GameEvent::DummyUncovered(event))))?,
+            _ => Err(BBError::InvalidEvent(format!("{:?}", event)))?,
         }
     }
 
-    fn process_disclose_hand_event(&mut self, event: DiscloseHandEvent) -> Result<(), BBError> {
+    fn process_disclose_hand_event(&mut self, event: DiscloseHandEvent<N>) -> Result<(), BBError> {
         match self {
             GameState::Bidding(state) => state.process_disclose_hand_event(event),
-            _ => Err(BBError::InvalidEvent(Box::new(GameEvent::DiscloseHand(event)))),
+            _ => Err(BBError::InvalidEvent(format!("{:?}", event))),
         }
     }
 
-    pub fn new_from_board(board: Board) -> Self {
-        let state = BiddingState::new(board);
-        GameState::Bidding(state)
-    }
-
     pub fn from_new_game_event(event: NewGameEvent) -> Self {
-        Self::new_from_board(event.board)
+        let state = BiddingState::new(event.board, event.director, event.mini_bridge);
+        GameState::Bidding(state)
     }
 
     pub fn declarer(&self) -> Option<Seat> {
@@ -182,19 +420,37 @@ impl GameState {
     }
 }
 
+impl GameState {
+    pub fn new_from_board(board: Board) -> Self {
+        let state = BiddingState::new(board, false, false);
+        GameState::Bidding(state)
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use crate::engine::subjective_game_view::SubjectiveGameStateView;
+    use crate::error::BBError;
+    use crate::game::mini_bridge::MiniBridge;
     use crate::game::scoring::ScoreCalculator;
     use crate::game::{GamePhaseState, GameState};
     use crate::primitives::bid::Bid;
+    use crate::primitives::deal::seat::SEAT_ARRAY;
     use crate::primitives::deal::Seat;
     use crate::primitives::game_event::{
-        BidEvent, BiddingEndedEvent, CardEvent, DummyUncoveredEvent, GameEndedEvent, GameEvent,
+        BidEvent, BiddingEndedEvent, CardEvent, ClaimEvent, ClaimResponseEvent, ConcedeEvent, DummyUncoveredEvent,
+        GameEndedEvent, GameEvent, UndoEvent, UndoRequestEvent, UndoResponseEvent,
+    };
+    use crate::primitives::game_event::{
+        DiscloseHandEvent, IrregularityEvent, IrregularityResponseEvent, LeadPenalty, LeadPenaltyEvent, NewGameEvent,
     };
     use crate::primitives::game_result::GameResult;
-    use crate::primitives::{Card, Contract, Deal};
+    use crate::primitives::hand_info::explanation::Explanation;
+    use crate::primitives::player_event::PlayerEvent;
+    use crate::primitives::{Card, Contract, Deal, Suit};
     use rand::thread_rng;
     use std::str::FromStr;
+    use test_case::test_case;
 
     #[test]
     fn init() {
@@ -216,6 +472,8 @@ mod test {
             let bid_event = BidEvent {
                 player: game.next_to_play().unwrap(),
                 bid: Bid::from_str(bid).unwrap(),
+                alert: false,
+                explanation: None,
             };
             let game_event = GameEvent::Bid(bid_event);
             game.process_game_event(game_event).unwrap();
@@ -246,6 +504,8 @@ mod test {
             let bid_event = BidEvent {
                 player: game.next_to_play().unwrap(),
                 bid: Bid::from_str(bid).unwrap(),
+                alert: false,
+                explanation: None,
             };
             let game_event = GameEvent::Bid(bid_event);
             game.process_game_event(game_event).unwrap();
@@ -328,4 +588,739 @@ mod test {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn game_with_three_cards_per_hand() {
+        let deal: Deal<3> = Deal::from_u64_seed(9000);
+        let mut game = GameState::from_new_game_event(NewGameEvent {
+            board: deal.board,
+            director: false,
+            mini_bridge: false,
+        });
+        for seat in SEAT_ARRAY {
+            let disclose_hand_event = DiscloseHandEvent {
+                seat,
+                hand: *deal.hand_of(seat),
+            };
+            game.process_game_event(GameEvent::DiscloseHand(disclose_hand_event))
+                .unwrap();
+        }
+
+        for bid in ["1NT", "p", "p", "p"] {
+            let bid_event = BidEvent {
+                player: game.next_to_play().unwrap(),
+                bid: Bid::from_str(bid).unwrap(),
+                alert: false,
+                explanation: None,
+            };
+            game.process_game_event(GameEvent::Bid(bid_event)).unwrap();
+        }
+        let final_contract = Contract::from_str("W1NT").unwrap();
+        game.process_game_event(GameEvent::BiddingEnded(BiddingEndedEvent { final_contract }))
+            .unwrap();
+
+        loop {
+            let (hands, tricks) = match &game {
+                GameState::WaitingForDummy(state) => {
+                    let dummy_event = DummyUncoveredEvent {
+                        dummy: *deal.hand_of(state.dummy()),
+                    };
+                    game.process_game_event(GameEvent::DummyUncovered(dummy_event)).unwrap();
+                    continue;
+                }
+                GameState::OpeningLead(state) => (&state.hand_manager, &state.trick_manager),
+                GameState::CardPlay(state) if !state.card_play_has_ended() => {
+                    (&state.hand_manager, &state.trick_manager)
+                }
+                _ => break,
+            };
+            let player = tricks.next_to_play();
+            let cards = hands.known_remaining_cards_of(player);
+            let card = cards
+                .iter()
+                .find(|card| Some(card.suit) == tricks.suit_to_follow())
+                .unwrap_or(&cards[0]);
+            let card_event = CardEvent { player, card: *card };
+            game.process_game_event(GameEvent::Card(card_event)).unwrap();
+        }
+
+        let result = match &game {
+            GameState::CardPlay(state) => {
+                assert_eq!(state.trick_manager.count_played_tricks(), 3);
+                assert_eq!(state.trick_manager.tricks_won_by_axis(Seat::West), 2);
+                state.calculate_game_result()
+            }
+            _ => panic!(),
+        };
+        // two of the three tricks are needed for 1NT
+        assert_eq!(
+            result,
+            GameResult::Made {
+                contract: final_contract,
+                overtricks: 0
+            }
+        );
+        let score = ScoreCalculator::score_result(result, deal.vulnerable());
+        game.process_game_event(GameEvent::GameEnded(GameEndedEvent { result, deal, score }))
+            .unwrap();
+
+        match game {
+            GameState::Ended(state) => assert_eq!(state.hands.count_played_cards(), 12),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn mini_bridge_starts_with_the_opening_lead() {
+        let deal: Deal<13> = Deal::from_u64_seed(9000);
+        let mut game: GameState = GameState::from_new_game_event(NewGameEvent {
+            board: deal.board,
+            director: false,
+            mini_bridge: true,
+        });
+
+        let bid_event = BidEvent {
+            player: Seat::West,
+            bid: Bid::from_str("1NT").unwrap(),
+            alert: false,
+            explanation: None,
+        };
+        assert_eq!(
+            game.process_game_event(GameEvent::Bid(bid_event)),
+            Err(BBError::InvalidEvent(format!("{:?}", bid_event)))
+        );
+
+        let final_contract = Contract::from_str("N2S").unwrap();
+        assert!(game
+            .process_game_event(GameEvent::BiddingEnded(BiddingEndedEvent { final_contract }))
+            .is_err());
+
+        let final_contract = MiniBridge::contract(&deal);
+        game.process_game_event(GameEvent::BiddingEnded(BiddingEndedEvent { final_contract }))
+            .unwrap();
+        assert!(matches!(game, GameState::OpeningLead(_)));
+        assert_eq!(game.next_to_play(), Some(final_contract.declarer + 1));
+    }
+
+    fn start_card_play(deal: &Deal<13>) -> GameState {
+        let mut game = GameState::new_from_board(deal.board);
+
+        let bids = ["p", "1NT", "p", "2C", "p", "2S", "p", "4S", "p", "p", "p"];
+
+        for &bid in bids.iter() {
+            let bid_event = BidEvent {
+                player: game.next_to_play().unwrap(),
+                bid: Bid::from_str(bid).unwrap(),
+                alert: false,
+                explanation: None,
+            };
+            game.process_game_event(GameEvent::Bid(bid_event)).unwrap();
+        }
+
+        let final_contract = Contract::from_str("N4S").unwrap();
+        game.process_game_event(GameEvent::BiddingEnded(BiddingEndedEvent { final_contract }))
+            .unwrap();
+
+        let card_event = CardEvent {
+            player: Seat::East,
+            card: Card::from_str("C2").unwrap(),
+        };
+        game.process_game_event(GameEvent::Card(card_event)).unwrap();
+
+        let dummy_event = DummyUncoveredEvent {
+            dummy: *deal.hand_of(Seat::South),
+        };
+        game.process_game_event(GameEvent::DummyUncovered(dummy_event)).unwrap();
+
+        game
+    }
+
+    #[test]
+    fn accepted_claim_ends_card_play() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_card_play(&deal);
+
+        let claim_event = ClaimEvent {
+            player: Seat::South,
+            tricks: 6,
+        };
+        game.process_game_event(GameEvent::Claim(claim_event)).unwrap();
+
+        assert_eq!(game.next_to_play(), Some(Seat::East));
+
+        let card_event = CardEvent {
+            player: Seat::South,
+            card: Card::from_str("C7").unwrap(),
+        };
+        assert_eq!(
+            game.process_game_event(GameEvent::Card(card_event)),
+            Err(BBError::ClaimPending)
+        );
+
+        for player in [Seat::East, Seat::West] {
+            let response_event = ClaimResponseEvent { player, accept: true };
+            game.process_game_event(GameEvent::ClaimResponse(response_event))
+                .unwrap();
+        }
+
+        match &game {
+            GameState::CardPlay(state) => {
+                assert!(state.card_play_has_ended());
+                assert_eq!(
+                    state.calculate_game_result(),
+                    GameResult::Failed {
+                        contract: Contract::from_str("N4S").unwrap(),
+                        undertricks: 4
+                    }
+                );
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn rejected_claim_continues_card_play() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_card_play(&deal);
+
+        let claim_event = ClaimEvent {
+            player: Seat::South,
+            tricks: 13,
+        };
+        game.process_game_event(GameEvent::Claim(claim_event)).unwrap();
+
+        let response_event = ClaimResponseEvent {
+            player: Seat::East,
+            accept: false,
+        };
+        game.process_game_event(GameEvent::ClaimResponse(response_event))
+            .unwrap();
+
+        assert_eq!(game.next_to_play(), Some(Seat::South));
+
+        let card_event = CardEvent {
+            player: Seat::South,
+            card: Card::from_str("C7").unwrap(),
+        };
+        game.process_game_event(GameEvent::Card(card_event)).unwrap();
+    }
+
+    #[test]
+    fn concession_ends_card_play() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_card_play(&deal);
+
+        let concede_event = ConcedeEvent { player: Seat::South };
+        game.process_game_event(GameEvent::Concede(concede_event)).unwrap();
+
+        match &game {
+            GameState::CardPlay(state) => {
+                assert!(state.card_play_has_ended());
+                assert_eq!(
+                    state.calculate_game_result(),
+                    GameResult::Failed {
+                        contract: Contract::from_str("N4S").unwrap(),
+                        undertricks: 10
+                    }
+                );
+            }
+            _ => panic!(),
+        }
+    }
+
+    fn approve_undo(game: &mut GameState, player: Seat, responders: &[Seat]) {
+        let request_event = UndoRequestEvent { player };
+        game.process_game_event(GameEvent::UndoRequest(request_event)).unwrap();
+
+        for &responder in responders {
+            assert_eq!(game.next_to_play(), Some(responder));
+            let response_event = UndoResponseEvent {
+                player: responder,
+                accept: true,
+            };
+            game.process_game_event(GameEvent::UndoResponse(response_event))
+                .unwrap();
+        }
+        assert_eq!(game.approved_undo_request(), Some(request_event));
+
+        let moves = game.moves_to_undo(player).unwrap();
+        game.process_game_event(GameEvent::Undo(UndoEvent { player, moves }))
+            .unwrap();
+    }
+
+    #[test]
+    fn nothing_to_undo() {
+        let deal: Deal<13> = Deal::from_u64_seed(9000);
+        let mut game = GameState::new_from_board(deal.board);
+
+        let request_event = UndoRequestEvent { player: Seat::West };
+        assert_eq!(
+            game.process_game_event(GameEvent::UndoRequest(request_event)),
+            Err(BBError::NothingToUndo)
+        );
+    }
+
+    #[test]
+    fn undo_bids() {
+        let deal: Deal<13> = Deal::from_u64_seed(9000);
+        let mut game = GameState::new_from_board(deal.board);
+
+        for bid in ["p", "1NT", "p", "2C"] {
+            let bid_event = BidEvent {
+                player: game.next_to_play().unwrap(),
+                bid: Bid::from_str(bid).unwrap(),
+                alert: false,
+                explanation: None,
+            };
+            game.process_game_event(GameEvent::Bid(bid_event)).unwrap();
+        }
+
+        assert_eq!(game.moves_to_undo(Seat::North), Ok(3));
+        approve_undo(&mut game, Seat::North, &[Seat::East, Seat::West]);
+
+        match &game {
+            GameState::Bidding(state) => assert_eq!(state.bid_manager.bids(), &[Bid::from_str("p").unwrap()]),
+            _ => panic!(),
+        }
+        assert_eq!(game.next_to_play(), Some(Seat::North));
+    }
+
+    #[test]
+    fn undo_from_opening_lead_into_bidding() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_card_play(&deal);
+
+        // taking back the opening lead only needs declarer's approval
+        approve_undo(&mut game, Seat::East, &[Seat::North]);
+        assert!(matches!(game, GameState::OpeningLead(_)));
+
+        // taking back the final pass reopens the auction
+        approve_undo(&mut game, Seat::East, &[Seat::South, Seat::North]);
+
+        match &game {
+            GameState::Bidding(state) => {
+                assert_eq!(state.bid_manager.bids().len(), 10);
+                assert!(!state.bidding_has_ended());
+            }
+            _ => panic!(),
+        }
+        assert_eq!(game.next_to_play(), Some(Seat::East));
+
+        let bid_event = BidEvent {
+            player: Seat::East,
+            bid: Bid::from_str("X").unwrap(),
+            alert: false,
+            explanation: None,
+        };
+        game.process_game_event(GameEvent::Bid(bid_event)).unwrap();
+    }
+
+    #[test]
+    fn undo_cards_in_card_play() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_card_play(&deal);
+
+        let card_event = CardEvent {
+            player: Seat::South,
+            card: Card::from_str("C7").unwrap(),
+        };
+        game.process_game_event(GameEvent::Card(card_event)).unwrap();
+
+        // declarer takes back dummy's card
+        approve_undo(&mut game, Seat::North, &[Seat::East, Seat::West]);
+
+        match &game {
+            GameState::CardPlay(state) => {
+                assert_eq!(state.trick_manager.count_played_cards(), 1);
+                assert!(state
+                    .hand_of(Seat::South)
+                    .unwrap()
+                    .contains(&Card::from_str("C7").unwrap()));
+            }
+            _ => panic!(),
+        }
+        assert_eq!(game.next_to_play(), Some(Seat::South));
+    }
+
+    #[test]
+    fn rejected_undo_continues_card_play() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_card_play(&deal);
+
+        let request_event = UndoRequestEvent { player: Seat::East };
+        game.process_game_event(GameEvent::UndoRequest(request_event)).unwrap();
+
+        let card_event = CardEvent {
+            player: Seat::South,
+            card: Card::from_str("C7").unwrap(),
+        };
+        assert_eq!(
+            game.process_game_event(GameEvent::Card(card_event)),
+            Err(BBError::UndoPending)
+        );
+
+        let response_event = UndoResponseEvent {
+            player: Seat::North,
+            accept: false,
+        };
+        game.process_game_event(GameEvent::UndoResponse(response_event))
+            .unwrap();
+
+        assert_eq!(game.approved_undo_request(), None);
+        let undo_event = UndoEvent {
+            player: Seat::East,
+            moves: 1,
+        };
+        assert!(game.process_game_event(GameEvent::Undo(undo_event)).is_err());
+
+        game.process_game_event(GameEvent::Card(card_event)).unwrap();
+    }
+
+    fn start_director_game(deal: &Deal<13>) -> GameState {
+        let new_game_event = NewGameEvent {
+            board: deal.board,
+            director: true,
+            mini_bridge: false,
+        };
+        let mut game = GameState::from_new_game_event(new_game_event);
+        for seat in SEAT_ARRAY {
+            let disclose_hand_event = DiscloseHandEvent {
+                seat,
+                hand: *deal.hand_of(seat),
+            };
+            game.process_game_event(GameEvent::DiscloseHand(disclose_hand_event))
+                .unwrap();
+        }
+        game
+    }
+
+    fn process_player_event(game: &mut GameState, event: PlayerEvent) -> Result<(), BBError> {
+        let game_event = match game.irregularity(event) {
+            Some(irregularity) => GameEvent::Irregularity(irregularity),
+            None => GameEvent::from(event),
+        };
+        game.process_game_event(game_event)
+    }
+
+    fn call(game: &mut GameState, player: Seat, bid: &str) -> Result<(), BBError> {
+        let bid = Bid::from_str(bid).unwrap();
+        process_player_event(
+            game,
+            PlayerEvent::Bid(BidEvent {
+                player,
+                bid,
+                alert: false,
+                explanation: None,
+            }),
+        )
+    }
+
+    fn play(game: &mut GameState, player: Seat, card: &str) -> Result<(), BBError> {
+        let card = Card::from_str(card).unwrap();
+        process_player_event(game, PlayerEvent::Card(CardEvent { player, card }))
+    }
+
+    fn respond_to_irregularity(game: &mut GameState, player: Seat, accept: bool) -> Result<(), BBError> {
+        process_player_event(
+            game,
+            PlayerEvent::IrregularityResponse(IrregularityResponseEvent { player, accept }),
+        )
+    }
+
+    #[test]
+    fn alerts_are_only_shown_to_opponents() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_director_game(&deal);
+        let explanation = Explanation::from_str("Hand has 8 to 40 hcp.").unwrap();
+
+        call(&mut game, Seat::West, "p").unwrap();
+        call(&mut game, Seat::North, "1NT").unwrap();
+        call(&mut game, Seat::East, "p").unwrap();
+        let alerted_call = BidEvent {
+            player: Seat::South,
+            bid: Bid::from_str("2C").unwrap(),
+            alert: true,
+            explanation: Some(explanation),
+        };
+        process_player_event(&mut game, PlayerEvent::Bid(alerted_call)).unwrap();
+
+        for (seat, visible) in [
+            (Seat::North, false),
+            (Seat::East, true),
+            (Seat::South, true),
+            (Seat::West, true),
+        ] {
+            let SubjectiveGameStateView::Bidding(view) = SubjectiveGameStateView::new(&game, seat) else {
+                panic!("still bidding")
+            };
+            assert_eq!(view.alerts(), [false, false, false, visible]);
+            assert_eq!(view.explanations()[3], visible.then_some(explanation));
+        }
+    }
+
+    #[test]
+    fn irregularities_are_rejected_without_director() {
+        let deal: Deal<13> = Deal::from_u64_seed(9000);
+        let mut game = GameState::new_from_board(deal.board);
+
+        call(&mut game, Seat::West, "p").unwrap();
+        call(&mut game, Seat::North, "1S").unwrap();
+        assert_eq!(
+            call(&mut game, Seat::East, "1H"),
+            Err(BBError::InvalidBid(Bid::from_str("1H").unwrap()))
+        );
+        assert_eq!(
+            call(&mut game, Seat::South, "2S"),
+            Err(BBError::OutOfTurn(Some(Seat::East)))
+        );
+    }
+
+    #[test]
+    fn director_insufficient_bid_accepted() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_director_game(&deal);
+
+        call(&mut game, Seat::West, "p").unwrap();
+        call(&mut game, Seat::North, "1S").unwrap();
+        call(&mut game, Seat::East, "1H").unwrap();
+
+        assert!(matches!(
+            game.pending_irregularity(),
+            Some(IrregularityEvent::InsufficientBid(_))
+        ));
+        assert_eq!(game.next_to_play(), Some(Seat::South));
+        assert_eq!(call(&mut game, Seat::South, "2S"), Err(BBError::IrregularityPending));
+
+        respond_to_irregularity(&mut game, Seat::South, true).unwrap();
+
+        assert_eq!(game.pending_irregularity(), None);
+        assert_eq!(game.next_to_play(), Some(Seat::South));
+        call(&mut game, Seat::South, "1NT").unwrap();
+    }
+
+    #[test_case("2H", false; "Lowest sufficient bid")]
+    #[test_case("3H", true; "Jump")]
+    #[test_case("2C", true; "Other strain")]
+    #[test_case("p", true; "Pass")]
+    fn director_insufficient_bid_replaced(replacement: &str, partner_must_pass: bool) {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_director_game(&deal);
+
+        call(&mut game, Seat::West, "p").unwrap();
+        call(&mut game, Seat::North, "1S").unwrap();
+        call(&mut game, Seat::East, "1H").unwrap();
+        respond_to_irregularity(&mut game, Seat::South, false).unwrap();
+
+        assert_eq!(game.next_to_play(), Some(Seat::East));
+        call(&mut game, Seat::East, replacement).unwrap();
+        call(&mut game, Seat::South, "4S").unwrap();
+
+        assert_eq!(call(&mut game, Seat::West, "5H").is_err(), partner_must_pass);
+    }
+
+    #[test]
+    fn director_call_out_of_turn_accepted() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_director_game(&deal);
+
+        call(&mut game, Seat::West, "p").unwrap();
+        call(&mut game, Seat::South, "1C").unwrap();
+
+        assert_eq!(game.next_to_play(), Some(Seat::West));
+        respond_to_irregularity(&mut game, Seat::West, true).unwrap();
+
+        match &game {
+            GameState::Bidding(state) => assert_eq!(
+                state.bid_manager.bids(),
+                ["p", "p", "p", "1C"].map(|bid| Bid::from_str(bid).unwrap())
+            ),
+            _ => panic!(),
+        }
+        assert_eq!(game.next_to_play(), Some(Seat::West));
+    }
+
+    #[test]
+    fn director_bid_out_of_turn_rejected() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_director_game(&deal);
+
+        call(&mut game, Seat::West, "p").unwrap();
+        call(&mut game, Seat::South, "1C").unwrap();
+        respond_to_irregularity(&mut game, Seat::West, false).unwrap();
+
+        assert_eq!(game.next_to_play(), Some(Seat::North));
+        assert_eq!(call(&mut game, Seat::North, "1S"), Err(BBError::MustPass));
+        call(&mut game, Seat::North, "p").unwrap();
+        call(&mut game, Seat::East, "p").unwrap();
+        call(&mut game, Seat::South, "1S").unwrap();
+        call(&mut game, Seat::West, "p").unwrap();
+        assert_eq!(call(&mut game, Seat::North, "2S"), Err(BBError::MustPass));
+    }
+
+    #[test]
+    fn director_pass_out_of_turn_rejected() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_director_game(&deal);
+
+        call(&mut game, Seat::West, "p").unwrap();
+        call(&mut game, Seat::East, "p").unwrap();
+        respond_to_irregularity(&mut game, Seat::South, false).unwrap();
+
+        call(&mut game, Seat::North, "1S").unwrap();
+        assert_eq!(call(&mut game, Seat::East, "2H"), Err(BBError::MustPass));
+        call(&mut game, Seat::East, "p").unwrap();
+        call(&mut game, Seat::South, "2S").unwrap();
+        call(&mut game, Seat::West, "p").unwrap();
+        call(&mut game, Seat::North, "p").unwrap();
+        call(&mut game, Seat::East, "3H").unwrap();
+    }
+
+    fn start_director_opening_lead(deal: &Deal<13>) -> GameState {
+        let mut game = start_director_game(deal);
+        for bid in ["p", "1NT", "p", "2C", "p", "2S", "p", "4S", "p", "p", "p"] {
+            let player = game.next_to_play().unwrap();
+            call(&mut game, player, bid).unwrap();
+        }
+        let final_contract = Contract::from_str("N4S").unwrap();
+        game.process_game_event(GameEvent::BiddingEnded(BiddingEndedEvent { final_contract }))
+            .unwrap();
+        game
+    }
+
+    #[test]
+    fn director_opening_lead_out_of_turn_accepted() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_director_opening_lead(&deal);
+
+        play(&mut game, Seat::West, "DJ").unwrap();
+        assert_eq!(game.next_to_play(), Some(Seat::North));
+        respond_to_irregularity(&mut game, Seat::North, true).unwrap();
+
+        assert!(matches!(game, GameState::WaitingForDummy(_)));
+        assert_eq!(game.next_to_play(), Some(Seat::North));
+    }
+
+    #[test_case(LeadPenalty::RequireSuit(Suit::Diamonds), "HK", "D3"; "Require")]
+    #[test_case(LeadPenalty::ForbidSuit(Suit::Diamonds), "D3", "HK"; "Forbid")]
+    fn director_opening_lead_out_of_turn_rejected(penalty: LeadPenalty, restricted: &str, allowed: &str) {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_director_opening_lead(&deal);
+
+        play(&mut game, Seat::West, "DJ").unwrap();
+        respond_to_irregularity(&mut game, Seat::North, false).unwrap();
+
+        assert!(game.lead_penalty_is_due());
+        assert_eq!(game.next_to_play(), Some(Seat::North));
+        let lead_penalty_event = LeadPenaltyEvent {
+            player: Seat::North,
+            penalty,
+        };
+        process_player_event(&mut game, PlayerEvent::LeadPenalty(lead_penalty_event)).unwrap();
+
+        assert_eq!(game.next_to_play(), Some(Seat::East));
+        assert_eq!(
+            play(&mut game, Seat::East, restricted),
+            Err(BBError::LeadRestricted(Suit::Diamonds))
+        );
+        play(&mut game, Seat::East, allowed).unwrap();
+    }
+
+    #[test]
+    fn director_penalty_card_must_be_played() {
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = start_director_opening_lead(&deal);
+
+        play(&mut game, Seat::West, "DJ").unwrap();
+        respond_to_irregularity(&mut game, Seat::North, false).unwrap();
+        let lead_penalty_event = LeadPenaltyEvent {
+            player: Seat::North,
+            penalty: LeadPenalty::PenaltyCardsRemain,
+        };
+        process_player_event(&mut game, PlayerEvent::LeadPenalty(lead_penalty_event)).unwrap();
+
+        play(&mut game, Seat::East, "D3").unwrap();
+        let dummy_event = DummyUncoveredEvent {
+            dummy: *deal.hand_of(Seat::South),
+        };
+        game.process_game_event(GameEvent::DummyUncovered(dummy_event)).unwrap();
+        play(&mut game, Seat::South, "D7").unwrap();
+
+        assert_eq!(
+            play(&mut game, Seat::West, "D2"),
+            Err(BBError::MustPlayPenaltyCard(Card::from_str("DJ").unwrap()))
+        );
+        play(&mut game, Seat::West, "DJ").unwrap();
+    }
+
+    #[test]
+    fn director_result_includes_revokes_unseen_by_the_player() {
+        end_after_unseen_revoke(12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn director_result_without_revoke_transfer_is_rejected() {
+        end_after_unseen_revoke(11);
+    }
+
+    fn end_after_unseen_revoke(declarers_tricks: usize) {
+        // East only sees their own hand and dummy's, so West's revoke shows up with the deal at the end
+        let deal = Deal::from_u64_seed(9000);
+        let mut game = GameState::from_new_game_event(NewGameEvent {
+            board: deal.board,
+            director: true,
+            mini_bridge: false,
+        });
+        let disclose_hand_event = DiscloseHandEvent {
+            seat: Seat::East,
+            hand: *deal.hand_of(Seat::East),
+        };
+        game.process_game_event(GameEvent::DiscloseHand(disclose_hand_event))
+            .unwrap();
+        for bid in ["p", "1NT", "p", "2C", "p", "2S", "p", "4S", "p", "p", "p"] {
+            let player = game.next_to_play().unwrap();
+            call(&mut game, player, bid).unwrap();
+        }
+        let final_contract = Contract::from_str("N4S").unwrap();
+        game.process_game_event(GameEvent::BiddingEnded(BiddingEndedEvent { final_contract }))
+            .unwrap();
+
+        play(&mut game, Seat::East, "C2").unwrap();
+        let dummy_event = DummyUncoveredEvent {
+            dummy: *deal.hand_of(Seat::South),
+        };
+        game.process_game_event(GameEvent::DummyUncovered(dummy_event)).unwrap();
+        for (player, card) in [
+            (Seat::South, "C7"),
+            (Seat::West, "D2"),
+            (Seat::North, "C3"),
+            (Seat::South, "HA"),
+            (Seat::West, "H2"),
+            (Seat::North, "H7"),
+            (Seat::East, "H4"),
+            (Seat::South, "D7"),
+            (Seat::West, "DJ"),
+            (Seat::North, "DQ"),
+            (Seat::East, "DA"),
+        ] {
+            play(&mut game, player, card).unwrap();
+        }
+        let claim_event = ClaimEvent {
+            player: Seat::East,
+            tricks: 1,
+        };
+        game.process_game_event(GameEvent::Claim(claim_event)).unwrap();
+        let response_event = ClaimResponseEvent {
+            player: Seat::North,
+            accept: true,
+        };
+        game.process_game_event(GameEvent::ClaimResponse(response_event))
+            .unwrap();
+
+        let result = GameResult::calculate_game_result(final_contract, declarers_tricks);
+        let score = ScoreCalculator::score_result(result, deal.vulnerable());
+        game.process_game_event(GameEvent::GameEnded(GameEndedEvent { result, deal, score }))
+            .unwrap();
+        assert!(matches!(game, GameState::Ended(_)));
+    }
 }
@@ -2,11 +2,18 @@ use crate::primitives::contract::{Level, State, Strain};
 use crate::primitives::deal::{Seat, Vulnerability};
 use crate::primitives::game_result::GameResult;
 use crate::primitives::Contract;
-use std::ops::{Add, AddAssign, Mul, MulAssign};
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub};
 
 #[derive(PartialOrd, PartialEq, Ord, Eq, Clone, Copy, Debug)]
 pub struct ScorePoints(isize);
 
+impl Display for ScorePoints {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl Add<ScorePoints> for ScorePoints {
     type Output = ScorePoints;
 
@@ -21,6 +28,14 @@ impl AddAssign<ScorePoints> for ScorePoints {
     }
 }
 
+impl Sub<ScorePoints> for ScorePoints {
+    type Output = ScorePoints;
+
+    fn sub(self, rhs: ScorePoints) -> ScorePoints {
+        ScorePoints(self.0 - rhs.0)
+    }
+}
+
 impl Mul<isize> for ScorePoints {
     type Output = ScorePoints;
 
@@ -66,6 +81,23 @@ impl ScoreCalculator {
 
     const OVERTRICK_REDOUBLED_VULNERABLE: ScorePoints = ScorePoints(400);
 
+    const MINI_BRIDGE_GAME_BONUS: ScorePoints = ScorePoints(300);
+    const MINI_BRIDGE_UNDERTRICK: ScorePoints = ScorePoints(-50);
+
+    // lower bounds of the score differences worth 1 to 24 IMPs
+    const IMP_SCALE: [isize; 24] = [
+        20, 50, 90, 130, 170, 220, 270, 320, 370, 430, 500, 600, 750, 900, 1100, 1300, 1500, 1750, 2000, 2250, 2500,
+        3000, 3500, 4000,
+    ];
+
+    pub fn imps(difference: ScorePoints) -> isize {
+        let imps = Self::IMP_SCALE
+            .iter()
+            .take_while(|&&bound| difference.0.abs() >= bound)
+            .count() as isize;
+        imps * difference.0.signum()
+    }
+
     pub fn score_result(result: GameResult, vulnerability: Vulnerability) -> ScorePoints {
         match result {
             GameResult::Failed { contract, undertricks } => Self::score_lose(contract, undertricks, vulnerability),
@@ -74,6 +106,33 @@ impl ScoreCalculator {
         }
     }
 
+    // no vulnerability, doubles or slams: tricks plus 50 for a partscore or 300 for a game, 50 per undertrick
+    pub fn score_mini_bridge_result(result: GameResult) -> ScorePoints {
+        let (contract, score) = match result {
+            GameResult::Failed { contract, undertricks } => (contract, Self::MINI_BRIDGE_UNDERTRICK * undertricks),
+            GameResult::Made { contract, overtricks } => {
+                let contract = Contract {
+                    state: State::Passed,
+                    ..contract
+                };
+                let trick_score = Self::score_bid_tricks(contract);
+                let bonus = match trick_score >= Self::GAME_THRESHOLD {
+                    true => Self::MINI_BRIDGE_GAME_BONUS,
+                    false => Self::PART_SCORE_BONUS,
+                };
+                (
+                    contract,
+                    trick_score + bonus + Self::score_overtricks(contract, overtricks, false),
+                )
+            }
+            GameResult::Unplayed => return Self::NO_SCORE,
+        };
+        match contract.declarer {
+            Seat::East | Seat::West => score * -1_isize,
+            _ => score,
+        }
+    }
+
     fn score_lose(contract: Contract, undertricks: usize, vulnerability: Vulnerability) -> ScorePoints {
         let declarer_is_vulnerable = contract.declarer.is_vulnerable(vulnerability);
 
@@ -235,4 +294,31 @@ mod test {
         let score = ScoreCalculator::score_result(result, vulnerability);
         assert_eq!(score, ScorePoints(expected));
     }
+
+    #[test_case("N1S", 8, 110; "Partscore")]
+    #[test_case("S4H", 10, 420; "Game")]
+    #[test_case("E3NT", 9, -400; "Game for East West")]
+    #[test_case("N5D", 9, -100; "Two down")]
+    #[test_case("N6S", 12, 480; "No slam bonus")]
+    #[test_case("N1SX", 8, 110; "Doubled like undoubled")]
+    #[test_case("W2SX", 6, 100; "Doubled undertricks")]
+    fn score_mini_bridge(contract_string: &str, actual_tricks: usize, expected: isize) {
+        let contract = Contract::from_str(contract_string).unwrap();
+
+        let result = GameResult::calculate_game_result(contract, actual_tricks);
+
+        let score = ScoreCalculator::score_mini_bridge_result(result);
+        assert_eq!(score, ScorePoints(expected));
+    }
+
+    #[test_case(0, 0; "Push")]
+    #[test_case(10, 0; "Overtrick in a part score")]
+    #[test_case(20, 1; "Lower bound")]
+    #[test_case(-50, -2; "Negative")]
+    #[test_case(420, 9; "Game against part score")]
+    #[test_case(-750, -13; "Missed slam")]
+    #[test_case(5000, 24; "Maximum")]
+    fn imps(difference: isize, expected: isize) {
+        assert_eq!(ScoreCalculator::imps(ScorePoints(difference)), expected);
+    }
 }
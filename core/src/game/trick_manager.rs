@@ -1,13 +1,13 @@
+use crate::error::BBError;
 use crate::primitives::deal::Seat;
 use crate::primitives::trick::{ActiveTrick, PlayedTrick};
 use crate::primitives::{Card, Suit};
 use itertools::Itertools;
-use std::iter;
 
 #[derive(Debug, Clone)]
 pub struct TrickManager<const N: usize> {
     played_cards: Vec<Card>,
-    opening_leader: Seat,
+    leaders: Vec<Seat>,
     next_to_play: Seat,
     trumps: Option<Suit>,
     winners: Vec<Seat>,
@@ -17,7 +17,7 @@ impl<const N: usize> TrickManager<N> {
     pub fn new(opening_leader: Seat, trumps: Option<Suit>) -> Self {
         Self {
             played_cards: Vec::with_capacity(4 * N),
-            opening_leader,
+            leaders: vec![opening_leader],
             next_to_play: opening_leader,
             winners: Vec::with_capacity(N),
             trumps,
@@ -51,10 +51,16 @@ impl<const N: usize> TrickManager<N> {
     }
 
     pub fn trick_leader(&self) -> Seat {
-        match self.winners.last() {
-            Some(leader) => *leader,
-            None => self.opening_leader,
+        *self.leaders.last().unwrap()
+    }
+
+    pub fn change_leader(&mut self, leader: Seat) -> Result<(), BBError> {
+        if self.count_cards_in_current_trick() != 0 || self.card_play_has_ended() {
+            return Err(BBError::OutOfTurn(Some(self.next_to_play)));
         }
+        *self.leaders.last_mut().unwrap() = leader;
+        self.next_to_play = leader;
+        Ok(())
     }
 
     pub fn trump_suit(&self) -> Option<Suit> {
@@ -93,6 +99,7 @@ impl<const N: usize> TrickManager<N> {
         // println!("The real winner is {}", winner);
         self.next_to_play = winner;
         self.winners.push(winner);
+        self.leaders.push(winner);
     }
 
     pub fn last_trick_winner(&self) -> Option<Seat> {
@@ -155,7 +162,7 @@ impl<const N: usize> TrickManager<N> {
     }
 
     fn leaders(&self) -> impl Iterator<Item = &Seat> {
-        iter::once(&self.opening_leader).chain(self.winners.iter())
+        self.leaders.iter()
     }
 
     pub fn played_tricks(&self) -> Vec<PlayedTrick> {
@@ -175,6 +182,14 @@ impl<const N: usize> TrickManager<N> {
         &self.played_cards
     }
 
+    /// The player of every played card, in the order the cards were played
+    pub fn players_of_played_cards(&self) -> Vec<Seat> {
+        self.leaders()
+            .zip(self.played_cards.chunks(4))
+            .flat_map(|(&lead, cards)| (0..cards.len()).map(move |offset| lead + offset))
+            .collect_vec()
+    }
+
     /// A card is out of play once it is turned over after a trick
     pub fn out_of_play_cards(&self) -> &[Card] {
         let length = self.played_cards.len();
@@ -199,6 +214,7 @@ impl<const N: usize> TrickManager<N> {
         if !self.played_cards.is_empty() {
             if self.trick_complete() {
                 self.winners.pop();
+                self.leaders.pop();
                 self.next_to_play = self.trick_leader() + 3;
             } else {
                 self.next_to_play = self.next_to_play + 3;
@@ -216,6 +232,7 @@ mod test {
     use itertools::Itertools;
     // use rand::Rng;
     use crate::game::trick_manager::TrickManager;
+    use crate::primitives::trick::Trick;
     use rand::Rng;
     use std::str::FromStr;
 
@@ -343,4 +360,34 @@ mod test {
         assert_eq!(manager.tricks_won_by_axis(East), 1);
         assert_eq!(manager.tricks_won_by_axis(West), 1);
     }
+
+    #[test]
+    fn change_leader() {
+        let mut manager = TrickManager::<13>::new(North, Some(Spades));
+
+        manager.play(Card::from_str("H8").unwrap());
+        manager.play(Card::from_str("H9").unwrap());
+        assert!(manager.change_leader(West).is_err());
+        manager.play(Card::from_str("HA").unwrap());
+        manager.play(Card::from_str("H2").unwrap());
+
+        assert_eq!(manager.next_to_play(), South);
+        manager.change_leader(West).unwrap();
+        assert_eq!(manager.next_to_play(), West);
+
+        manager.play(Card::from_str("C2").unwrap());
+        manager.play(Card::from_str("C5").unwrap());
+        manager.play(Card::from_str("CA").unwrap());
+        manager.play(Card::from_str("C3").unwrap());
+
+        assert_eq!(manager.next_to_play(), East);
+        assert_eq!(manager.tricks_won_by_player(South), 1);
+        assert_eq!(manager.tricks_won_by_player(East), 1);
+        assert_eq!(manager.played_tricks()[1].lead(), West);
+        assert_eq!(manager.players_of_played_cards()[4..], [West, North, East, South]);
+
+        manager.undo();
+        assert_eq!(manager.next_to_play(), South);
+        assert_eq!(manager.tricks_won_by_player(East), 0);
+    }
 }
@@ -0,0 +1,148 @@
+use crate::error::BBError;
+use crate::primitives::deal::Seat;
+use crate::primitives::game_event::{UndoRequestEvent, UndoResponseEvent};
+
+#[derive(Debug, Clone, Default)]
+pub struct UndoManager {
+    request: Option<UndoRequestEvent>,
+    responders: Vec<Seat>,
+    accepted_by: Vec<Seat>,
+}
+
+impl UndoManager {
+    pub fn new() -> Self {
+        UndoManager {
+            request: None,
+            responders: Vec::new(),
+            accepted_by: Vec::new(),
+        }
+    }
+
+    pub fn responders_in_bidding(requester: Seat) -> Vec<Seat> {
+        vec![requester + 1, requester + 3]
+    }
+
+    // dummy never takes part, so a request by the defenders only needs declarer's approval
+    pub fn responders_in_card_play(requester: Seat, declarer: Seat) -> Vec<Seat> {
+        match requester.same_axis(&declarer) {
+            true => vec![declarer + 1, declarer + 3],
+            false => vec![declarer],
+        }
+    }
+
+    // waiting for responses, or approved but not yet carried out
+    pub fn request_is_open(&self) -> bool {
+        self.request.is_some()
+    }
+
+    pub fn pending_request(&self) -> Option<UndoRequestEvent> {
+        match self.next_to_respond() {
+            Some(_) => self.request,
+            None => None,
+        }
+    }
+
+    pub fn approved_request(&self) -> Option<UndoRequestEvent> {
+        match self.next_to_respond() {
+            Some(_) => None,
+            None => self.request,
+        }
+    }
+
+    pub fn next_to_respond(&self) -> Option<Seat> {
+        self.request?;
+        self.responders
+            .iter()
+            .find(|responder| !self.accepted_by.contains(responder))
+            .copied()
+    }
+
+    pub fn process_undo_request_event(
+        &mut self,
+        event: UndoRequestEvent,
+        responders: Vec<Seat>,
+    ) -> Result<(), BBError> {
+        if self.request_is_open() {
+            return Err(BBError::UndoPending);
+        }
+        self.request = Some(event);
+        self.responders = responders;
+        self.accepted_by.clear();
+        Ok(())
+    }
+
+    pub fn process_undo_response_event(&mut self, event: UndoResponseEvent) -> Result<(), BBError> {
+        match self.next_to_respond() {
+            None => Err(BBError::InvalidEvent(format!("{:?}", event))),
+            Some(responder) if responder != event.player => Err(BBError::OutOfTurn(Some(responder))),
+            Some(_) if event.accept => {
+                self.accepted_by.push(event.player);
+                Ok(())
+            }
+            Some(_) => {
+                self.clear();
+                Ok(())
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.request = None;
+        self.responders.clear();
+        self.accepted_by.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::BBError;
+    use crate::game::undo_manager::UndoManager;
+    use crate::primitives::deal::Seat;
+    use crate::primitives::game_event::{UndoRequestEvent, UndoResponseEvent};
+
+    #[test]
+    fn approval() {
+        let mut manager = UndoManager::new();
+        let request = UndoRequestEvent { player: Seat::South };
+        manager
+            .process_undo_request_event(request, vec![Seat::West, Seat::East])
+            .unwrap();
+
+        assert_eq!(manager.pending_request(), Some(request));
+        assert_eq!(manager.next_to_respond(), Some(Seat::West));
+
+        for player in [Seat::West, Seat::East] {
+            manager
+                .process_undo_response_event(UndoResponseEvent { player, accept: true })
+                .unwrap();
+        }
+
+        assert_eq!(manager.pending_request(), None);
+        assert_eq!(manager.approved_request(), Some(request));
+        assert!(manager.request_is_open());
+    }
+
+    #[test]
+    fn rejection() {
+        let mut manager = UndoManager::new();
+        let request = UndoRequestEvent { player: Seat::South };
+        manager
+            .process_undo_request_event(request, vec![Seat::West, Seat::East])
+            .unwrap();
+
+        assert_eq!(
+            manager.process_undo_request_event(request, vec![Seat::West, Seat::East]),
+            Err(BBError::UndoPending)
+        );
+
+        manager
+            .process_undo_response_event(UndoResponseEvent {
+                player: Seat::West,
+                accept: false,
+            })
+            .unwrap();
+
+        assert!(!manager.request_is_open());
+        assert_eq!(manager.approved_request(), None);
+    }
+}
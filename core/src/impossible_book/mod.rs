@@ -1,7 +1,7 @@
 // Re-Implementation of https://bridge.thomasoandrews.com/bridge/impossible/
 
 mod andrews;
-mod helper;
+pub mod helper;
 mod pavlicek;
 
 pub use andrews::*;
@@ -1,34 +1,83 @@
 use crate::engine::bidding_engine::SelectBid;
+use crate::engine::reason::Reason;
 use crate::engine::subjective_game_view::SubjectiveGamePhaseStateView;
+use crate::engine::Move;
+use crate::error::BBError;
 use crate::game::game_phase_states::BiddingState;
 use crate::interactive::cli_presenter::CliPresenter;
 use crate::primitives::bid::Bid;
 use crate::primitives::deal::Seat;
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::player_event::PlayerEvent;
 use std::io::stdin;
+use std::rc::Rc;
 use std::str::FromStr;
 
 pub struct CliBidSelector {
     _seat: Seat,
+    advisor: Option<Rc<dyn SelectBid>>,
+    last_move: Option<(PlayerEvent, Reason)>,
 }
 
 impl CliBidSelector {
     pub fn new(seat: Seat) -> Self {
-        CliBidSelector { _seat: seat }
+        CliBidSelector {
+            _seat: seat,
+            advisor: None,
+            last_move: None,
+        }
+    }
+
+    pub fn with_advisor(mut self, advisor: Rc<dyn SelectBid>) -> Self {
+        self.advisor = Some(advisor);
+        self
+    }
+
+    pub fn process_move_reason(&mut self, event: PlayerEvent, reason: Reason) {
+        self.last_move = Some((event, reason));
     }
 
     pub fn get_bid_from_user(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> Bid {
-        CliPresenter::display_bidding_state_for_user(&state);
+        match self.get_bid_or_undo_from_user(&state, false) {
+            Move::Bid(bid) | Move::AlertedBid(bid, _) => bid,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn get_move_from_user(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> Move {
+        self.get_bid_or_undo_from_user(&state, state.may_request_undo())
+    }
+
+    fn get_bid_or_undo_from_user(&self, state: &SubjectiveGamePhaseStateView<BiddingState>, allow_undo: bool) -> Move {
+        CliPresenter::display_bidding_state_for_user(state);
         CliPresenter::display_starting_hand_for_user(state.my_starting_hand().unwrap());
 
-        println!("What do you want to bid?");
+        match allow_undo {
+            true => println!("What do you want to bid? (type 'undo' to take back your last call)"),
+            false => println!("What do you want to bid?"),
+        }
+        println!("Add a '!' to alert your call, e.g. '2C!'.");
+        if self.advisor.is_some() {
+            println!("Type 'why?' to see why the bots made their last move, and what they would bid and why.");
+        }
 
         let mut user_input;
         let mut user_bid: Bid;
+        let mut alert;
 
         loop {
             user_input = String::new();
             stdin().read_line(&mut user_input).unwrap();
-            user_bid = match Bid::from_str(user_input.trim()) {
+            if allow_undo && user_input.trim() == "undo" {
+                return Move::UndoRequest;
+            }
+            if user_input.trim() == "why?" {
+                self.display_advice(*state);
+                continue;
+            }
+            let trimmed = user_input.trim();
+            alert = trimmed.ends_with('!');
+            user_bid = match Bid::from_str(trimmed.trim_end_matches('!')) {
                 Ok(bid) => bid,
                 _ => {
                     println!("That's not a valid bid!");
@@ -36,14 +85,49 @@ impl CliBidSelector {
                 }
             };
 
-            if state.validate_bid(user_bid).is_ok() {
-                break;
-            } else {
-                println!("That bid is not available anymore!");
+            // the director will be called for an insufficient bid
+            let insufficient_bid = state.director_mode() && !state.must_pass() && state.is_insufficient_bid(user_bid);
+            match state.validate_bid(user_bid) {
+                Ok(()) => break,
+                Err(_) if insufficient_bid => break,
+                Err(BBError::MustPass) => println!("You have to pass!"),
+                Err(_) => println!("That bid is not available anymore!"),
             }
         }
 
-        user_bid
+        match alert {
+            true => Move::AlertedBid(user_bid, Self::get_explanation_from_user()),
+            false => Move::Bid(user_bid),
+        }
+    }
+
+    fn display_advice(&self, state: SubjectiveGamePhaseStateView<BiddingState>) {
+        if let Some((event, reason)) = &self.last_move {
+            CliPresenter::display_move_reason_for_user(*event, reason);
+        }
+        match &self.advisor {
+            Some(advisor) => {
+                let (bid, reason) = advisor.select_bid_with_reason(state);
+                CliPresenter::display_reason_for_user(bid, &reason);
+            }
+            None if self.last_move.is_none() => println!("There is no one to ask!"),
+            None => (),
+        }
+    }
+
+    fn get_explanation_from_user() -> Option<Explanation> {
+        println!("How would you explain your call? (e.g. 'Hand has 15 to 17 hcp.', leave empty to skip)");
+        loop {
+            let mut user_input = String::new();
+            stdin().read_line(&mut user_input).unwrap();
+            if user_input.trim().is_empty() {
+                return None;
+            }
+            match Explanation::from_str(user_input.trim()) {
+                Ok(explanation) => return Some(explanation),
+                Err(_) => println!("I did not understand that explanation!"),
+            }
+        }
     }
 }
 
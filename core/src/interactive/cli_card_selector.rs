@@ -1,23 +1,68 @@
 use crate::engine::card_play_engine::SelectCard;
+use crate::engine::reason::Reason;
 use crate::engine::subjective_game_view::SubjectiveGamePhaseStateView;
+use crate::engine::Move;
 use crate::game::game_phase_states::{CardPlayState, OpeningLeadState};
 use crate::interactive::cli_presenter::CliPresenter;
 use crate::primitives::deal::Seat;
+use crate::primitives::game_event::ClaimEvent;
+use crate::primitives::player_event::PlayerEvent;
 use crate::primitives::Card;
 use std::io::stdin;
+use std::rc::Rc;
 use std::str::FromStr;
 
 #[allow(dead_code)]
 pub struct CliCardSelector {
     seat: Seat,
+    advisor: Option<Rc<dyn SelectCard>>,
+    last_move: Option<(PlayerEvent, Reason)>,
 }
 
 impl CliCardSelector {
     pub fn new(seat: Seat) -> Self {
-        CliCardSelector { seat }
+        CliCardSelector {
+            seat,
+            advisor: None,
+            last_move: None,
+        }
+    }
+
+    pub fn with_advisor(mut self, advisor: Rc<dyn SelectCard>) -> Self {
+        self.advisor = Some(advisor);
+        self
+    }
+
+    pub fn process_move_reason(&mut self, event: PlayerEvent, reason: Reason) {
+        self.last_move = Some((event, reason));
+    }
+
+    fn display_advice(&self, advice: impl FnOnce(&dyn SelectCard) -> (Card, Reason)) {
+        if let Some((event, reason)) = &self.last_move {
+            CliPresenter::display_move_reason_for_user(*event, reason);
+        }
+        match &self.advisor {
+            Some(advisor) => {
+                let (card, reason) = advice(advisor.as_ref());
+                CliPresenter::display_reason_for_user(card, &reason);
+            }
+            None if self.last_move.is_none() => println!("There is no one to ask!"),
+            None => (),
+        }
     }
 
     fn get_card_from_user(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card {
+        match self.get_card_or_undo_from_user(state, false) {
+            Move::Card(card) => card,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn get_move_from_user(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> Move {
+        self.get_card_or_undo_from_user(state, true)
+    }
+
+    fn get_card_or_undo_from_user(&self, state: SubjectiveGamePhaseStateView<CardPlayState>, allow_undo: bool) -> Move {
         CliPresenter::display_dummys_hand_for_user(&state.dummys_remaining_cards(), state.declarer());
         CliPresenter::display_trick_for_user(&state);
         CliPresenter::display_hand_for_user(&state.my_remaining_cards());
@@ -29,7 +74,7 @@ impl CliCardSelector {
         //     println!("You have to play from dummy's hand!");
         // }
 
-        println!("What card do you want to play?");
+        self.print_card_prompt(allow_undo);
 
         let mut user_input;
         let mut user_card: Card;
@@ -37,6 +82,13 @@ impl CliCardSelector {
         loop {
             user_input = String::new();
             stdin().read_line(&mut user_input).unwrap();
+            if allow_undo && user_input.trim() == "undo" {
+                return Move::UndoRequest;
+            }
+            if user_input.trim() == "why?" {
+                self.display_advice(|advisor| advisor.select_card_with_reason(state));
+                continue;
+            }
             user_card = match Card::from_str(user_input.trim()) {
                 Ok(card) => card,
                 _ => {
@@ -52,13 +104,81 @@ impl CliCardSelector {
             }
         }
 
-        user_card
+        Move::Card(user_card)
+    }
+
+    fn print_card_prompt(&self, allow_undo: bool) {
+        match allow_undo {
+            true => println!("What card do you want to play? (type 'undo' to take back your last move)"),
+            false => println!("What card do you want to play?"),
+        }
+        if self.advisor.is_some() {
+            println!("Type 'why?' to see why the bots made their last move, and what they would play and why.");
+        }
+    }
+
+    fn get_claim_from_user(&self, state: &SubjectiveGamePhaseStateView<CardPlayState>) -> Option<usize> {
+        if state.suit_to_follow().is_some() {
+            return None;
+        }
+
+        println!(
+            "Press enter to lead a card, or type 'claim <tricks>' or 'concede' ({} tricks left).",
+            state.tricks_left()
+        );
+
+        loop {
+            let mut user_input = String::new();
+            stdin().read_line(&mut user_input).unwrap();
+            let words = user_input.split_whitespace().collect::<Vec<_>>();
+            match words.as_slice() {
+                [] => return None,
+                ["concede"] => return Some(0),
+                ["claim", tricks] => match tricks.parse::<usize>() {
+                    Ok(tricks) if tricks <= state.tricks_left() => return Some(tricks),
+                    _ => println!("You cannot claim that many tricks!"),
+                },
+                _ => println!("I don't understand!"),
+            }
+        }
+    }
+
+    fn get_claim_response_from_user(&self, claim: ClaimEvent) -> bool {
+        println!(
+            "{} claims {} of the remaining tricks. Do you accept? (y/n)",
+            claim.player, claim.tricks
+        );
+
+        loop {
+            let mut user_input = String::new();
+            stdin().read_line(&mut user_input).unwrap();
+            match user_input.trim() {
+                "y" => return true,
+                "n" => return false,
+                _ => println!("Please answer with y or n!"),
+            }
+        }
     }
 
     fn get_opening_lead_from_user(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Card {
+        match self.get_opening_lead_or_undo_from_user(state, false) {
+            Move::Card(card) => card,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn get_opening_lead_move_from_user(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Move {
+        self.get_opening_lead_or_undo_from_user(state, true)
+    }
+
+    fn get_opening_lead_or_undo_from_user(
+        &self,
+        state: SubjectiveGamePhaseStateView<OpeningLeadState>,
+        allow_undo: bool,
+    ) -> Move {
         CliPresenter::display_hand_for_user(&state.my_remaining_cards());
 
-        println!("What card do you want to play?");
+        self.print_card_prompt(allow_undo);
 
         let mut user_input;
         let mut user_card: Card;
@@ -66,6 +186,13 @@ impl CliCardSelector {
         loop {
             user_input = String::new();
             stdin().read_line(&mut user_input).unwrap();
+            if allow_undo && user_input.trim() == "undo" {
+                return Move::UndoRequest;
+            }
+            if user_input.trim() == "why?" {
+                self.display_advice(|advisor| advisor.select_opening_lead_with_reason(state));
+                continue;
+            }
             user_card = match Card::from_str(user_input.trim()) {
                 Ok(card) => card,
                 _ => {
@@ -81,7 +208,7 @@ impl CliCardSelector {
             }
         }
 
-        user_card
+        Move::Card(user_card)
     }
 }
 
@@ -93,4 +220,12 @@ impl SelectCard for CliCardSelector {
     fn select_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Card {
         self.get_opening_lead_from_user(state)
     }
+
+    fn claim(&self, state: &SubjectiveGamePhaseStateView<CardPlayState>) -> Option<usize> {
+        self.get_claim_from_user(state)
+    }
+
+    fn respond_to_claim(&self, _state: &SubjectiveGamePhaseStateView<CardPlayState>, claim: ClaimEvent) -> bool {
+        self.get_claim_response_from_user(claim)
+    }
 }
@@ -1,7 +1,9 @@
+use crate::actors::card_analyser::{CardAnalysis, Feedback};
 use crate::engine::bidding_engine::SelectBid;
 use crate::engine::card_play_engine::SelectCard;
+use crate::engine::reason::Reason;
 use crate::engine::subjective_game_view::{SubjectiveGamePhaseStateView, SubjectiveGameStateView};
-use crate::engine::SelectMove;
+use crate::engine::{Move, SelectMove};
 use crate::error::BBError;
 
 use crate::game::game_phase_states::{BiddingState, CardPlayState, OpeningLeadState};
@@ -12,31 +14,209 @@ use crate::interactive::cli_presenter::CliPresenter;
 
 use crate::primitives::bid::Bid;
 use crate::primitives::deal::Seat;
-use crate::primitives::game_event::GameEvent;
-use crate::primitives::Card;
+use crate::primitives::game_event::{ClaimEvent, GameEvent, IrregularityEvent, LeadPenalty, UndoRequestEvent};
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::player_event::PlayerEvent;
+use crate::primitives::{Card, Suit};
+use std::cell::RefCell;
+use std::io::stdin;
+use std::rc::Rc;
+use std::str::FromStr;
 
 #[allow(dead_code)]
 pub struct CliMoveSelector {
     seat: Seat,
     bid_selector: CliBidSelector,
     card_selector: CliCardSelector,
+    feedback: Feedback,
+    analyses: Vec<CardAnalysis>,
+    advisor: Option<Rc<RefCell<dyn SelectMove>>>,
 }
 
 impl CliMoveSelector {
     pub fn new(seat: Seat) -> Self {
+        Self::with_feedback(seat, Feedback::default())
+    }
+
+    pub fn with_feedback(seat: Seat, feedback: Feedback) -> Self {
         Self {
             seat,
             bid_selector: CliBidSelector::new(seat),
             card_selector: CliCardSelector::new(seat),
+            feedback,
+            analyses: Vec::new(),
+            advisor: None,
         }
     }
+
+    pub fn with_advisor<T: SelectMove + 'static>(mut self, advisor: T) -> Self {
+        let advisor = Rc::new(RefCell::new(advisor));
+        self.bid_selector = self.bid_selector.with_advisor(Rc::new(Advisor(advisor.clone())));
+        self.card_selector = self.card_selector.with_advisor(Rc::new(Advisor(advisor.clone())));
+        self.advisor = Some(advisor);
+        self
+    }
+}
+
+// the advisor follows the game through the move selector, and advises through the bid and card selectors
+struct Advisor<T>(Rc<RefCell<T>>);
+
+impl<T: SelectMove> SelectBid for Advisor<T> {
+    fn select_bid(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> Bid {
+        self.0.borrow().select_bid(state)
+    }
+
+    fn select_bid_with_reason(&self, state: SubjectiveGamePhaseStateView<BiddingState>) -> (Bid, Reason) {
+        self.0.borrow().select_bid_with_reason(state)
+    }
+
+    fn alert(&self, auction: &[Bid], bid: Bid) -> Option<Explanation> {
+        self.0.borrow().alert(auction, bid)
+    }
+
+    fn interpret(&self, auction: &[Bid], bid: Bid) -> Explanation {
+        self.0.borrow().interpret(auction, bid)
+    }
+}
+
+impl<T: SelectMove> SelectCard for Advisor<T> {
+    fn select_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card {
+        self.0.borrow().select_card(state)
+    }
+
+    fn select_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Card {
+        self.0.borrow().select_opening_lead(state)
+    }
+
+    fn select_card_with_reason(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> (Card, Reason) {
+        self.0.borrow().select_card_with_reason(state)
+    }
+
+    fn select_opening_lead_with_reason(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> (Card, Reason) {
+        self.0.borrow().select_opening_lead_with_reason(state)
+    }
 }
 
 impl SelectMove for CliMoveSelector {
-    fn process_game_event(&mut self, event: GameEvent, _game_state: SubjectiveGameStateView) -> Result<(), BBError> {
+    fn select_move(&self, game: SubjectiveGameStateView) -> Result<Move, BBError> {
+        if let Some(request) = game.pending_undo_request() {
+            return Ok(Move::UndoResponse(self.respond_to_undo_request(&game, request)));
+        }
+        if let Some(irregularity) = game.pending_irregularity() {
+            return Ok(Move::IrregularityResponse(
+                self.respond_to_irregularity(&game, irregularity),
+            ));
+        }
+        if game.lead_penalty_is_due() {
+            return Ok(Move::LeadPenalty(self.choose_lead_penalty(&game)));
+        }
+        match game {
+            SubjectiveGameStateView::Bidding(state) => Ok(self.bid_selector.get_move_from_user(state)),
+            SubjectiveGameStateView::OpeningLead(state) => {
+                Ok(self.card_selector.get_opening_lead_move_from_user(state))
+            }
+            SubjectiveGameStateView::CardPlay(state) => match state.pending_claim() {
+                Some(claim) => Ok(Move::ClaimResponse(self.respond_to_claim(&state, claim))),
+                None if state.may_claim() => match self.claim(&state) {
+                    Some(0) => Ok(Move::Concede),
+                    Some(tricks) => Ok(Move::Claim(tricks)),
+                    None => Ok(self.card_selector.get_move_from_user(state)),
+                },
+                None => Ok(self.card_selector.get_move_from_user(state)),
+            },
+            SubjectiveGameStateView::WaitingForDummy(_) => Err(BBError::OutOfTurn(None)),
+            SubjectiveGameStateView::Ended(_) => Err(BBError::GameHasEnded),
+        }
+    }
+
+    fn respond_to_undo_request(&self, _game: &SubjectiveGameStateView, request: UndoRequestEvent) -> bool {
+        println!(
+            "{} would like to take back their last call or card. Do you agree? (y/n)",
+            request.player
+        );
+
+        loop {
+            let mut user_input = String::new();
+            stdin().read_line(&mut user_input).unwrap();
+            match user_input.trim() {
+                "y" => return true,
+                "n" => return false,
+                _ => println!("Please answer with y or n!"),
+            }
+        }
+    }
+
+    fn respond_to_irregularity(&self, _game: &SubjectiveGameStateView, irregularity: IrregularityEvent) -> bool {
+        match irregularity {
+            IrregularityEvent::InsufficientBid(event) => {
+                println!("{} made an insufficient bid of {}.", event.player, event.bid)
+            }
+            IrregularityEvent::CallOutOfTurn(event) => println!("{} called {} out of turn.", event.player, event.bid),
+            IrregularityEvent::LeadOutOfTurn(event) | IrregularityEvent::PlayOutOfTurn(event) => {
+                println!("{} led {} out of turn.", event.player, event.card)
+            }
+        }
+        println!("Do you accept it? (y/n)");
+
+        loop {
+            let mut user_input = String::new();
+            stdin().read_line(&mut user_input).unwrap();
+            match user_input.trim() {
+                "y" => return true,
+                "n" => return false,
+                _ => println!("Please answer with y or n!"),
+            }
+        }
+    }
+
+    fn choose_lead_penalty(&self, _game: &SubjectiveGameStateView) -> LeadPenalty {
+        println!("A defender has a major penalty card and partner is on lead.");
+        println!("Type 'require <suit>' or 'forbid <suit>' to restrict the lead, or press enter to leave the penalty cards on the table.");
+
+        loop {
+            let mut user_input = String::new();
+            stdin().read_line(&mut user_input).unwrap();
+            let words = user_input.split_whitespace().collect::<Vec<_>>();
+            match words.as_slice() {
+                [] => return LeadPenalty::PenaltyCardsRemain,
+                ["require", suit] => match Suit::from_str(suit) {
+                    Ok(suit) => return LeadPenalty::RequireSuit(suit),
+                    Err(_) => println!("That's not a suit!"),
+                },
+                ["forbid", suit] => match Suit::from_str(suit) {
+                    Ok(suit) => return LeadPenalty::ForbidSuit(suit),
+                    Err(_) => println!("That's not a suit!"),
+                },
+                _ => println!("I don't understand!"),
+            }
+        }
+    }
+
+    fn process_game_event(&mut self, event: GameEvent, game_state: SubjectiveGameStateView) -> Result<(), BBError> {
+        if let Some(advisor) = &self.advisor {
+            advisor.borrow_mut().process_game_event(event, game_state)?;
+        }
         CliPresenter::print_game_event_to_console(event);
+        if let GameEvent::GameEnded(_) = event {
+            if self.feedback == Feedback::EndOfHand {
+                CliPresenter::display_card_analyses_for_user(&self.analyses);
+            }
+            self.analyses.clear();
+        }
         Ok(())
     }
+
+    fn process_card_analysis(&mut self, analysis: CardAnalysis) {
+        if self.feedback == Feedback::EveryCard {
+            CliPresenter::display_card_analysis_for_user(&analysis);
+        }
+        self.analyses.push(analysis);
+    }
+
+    fn process_move_reason(&mut self, event: PlayerEvent, reason: Reason) {
+        self.bid_selector.process_move_reason(event, reason.clone());
+        self.card_selector.process_move_reason(event, reason);
+    }
 }
 
 impl SelectBid for CliMoveSelector {
@@ -53,4 +233,12 @@ impl SelectCard for CliMoveSelector {
     fn select_opening_lead(&self, game_state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Card {
         self.card_selector.select_opening_lead(game_state)
     }
+
+    fn claim(&self, game_state: &SubjectiveGamePhaseStateView<CardPlayState>) -> Option<usize> {
+        self.card_selector.claim(game_state)
+    }
+
+    fn respond_to_claim(&self, game_state: &SubjectiveGamePhaseStateView<CardPlayState>, claim: ClaimEvent) -> bool {
+        self.card_selector.respond_to_claim(game_state, claim)
+    }
 }
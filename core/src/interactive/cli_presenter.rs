@@ -1,13 +1,19 @@
+use crate::actors::card_analyser::CardAnalysis;
+use crate::engine::reason::Reason;
 use crate::engine::subjective_game_view::{SubjectiveGamePhaseStateView, SubjectiveSeat};
 use crate::game::game_phase_states::{BiddingState, CardPlayState};
 use itertools::Itertools;
 
 use crate::primitives::card::suit::SUIT_ARRAY;
 use crate::primitives::game_event::{
-    BidEvent, BiddingEndedEvent, CardEvent, DiscloseHandEvent, DummyUncoveredEvent, GameEndedEvent, GameEvent,
-    NewGameEvent,
+    BidEvent, BiddingEndedEvent, CardEvent, ClaimEvent, ClaimResponseEvent, ClaimRulingEvent, ConcedeEvent,
+    DiscloseHandEvent, DummyUncoveredEvent, ExposedCardEvent, GameEndedEvent, GameEvent, IrregularityEvent,
+    IrregularityResponseEvent, LeadPenalty, LeadPenaltyEvent, NewGameEvent, UndoEvent, UndoRequestEvent,
+    UndoResponseEvent,
 };
+use crate::primitives::player_event::PlayerEvent;
 use crate::primitives::{Card, Hand};
+use std::fmt::Display;
 
 pub struct CliPresenter {}
 
@@ -20,6 +26,17 @@ impl CliPresenter {
             GameEvent::BiddingEnded(mtcp_event) => Self::print_move_to_card_play_event_to_console(mtcp_event),
             GameEvent::Card(c_event) => Self::print_card_event_to_console(c_event),
             GameEvent::DummyUncovered(du_event) => Self::print_dummy_uncovered_event_to_console(du_event),
+            GameEvent::Claim(c_event) => Self::print_claim_event_to_console(c_event),
+            GameEvent::Concede(c_event) => Self::print_concede_event_to_console(c_event),
+            GameEvent::ClaimResponse(cr_event) => Self::print_claim_response_event_to_console(cr_event),
+            GameEvent::ClaimRuling(cr_event) => Self::print_claim_ruling_event_to_console(cr_event),
+            GameEvent::UndoRequest(ur_event) => Self::print_undo_request_event_to_console(ur_event),
+            GameEvent::UndoResponse(ur_event) => Self::print_undo_response_event_to_console(ur_event),
+            GameEvent::Undo(u_event) => Self::print_undo_event_to_console(u_event),
+            GameEvent::Irregularity(i_event) => Self::print_irregularity_event_to_console(i_event),
+            GameEvent::IrregularityResponse(ir_event) => Self::print_irregularity_response_event_to_console(ir_event),
+            GameEvent::ExposedCard(ec_event) => Self::print_exposed_card_event_to_console(ec_event),
+            GameEvent::LeadPenalty(lp_event) => Self::print_lead_penalty_event_to_console(lp_event),
             GameEvent::GameEnded(ge_event) => Self::print_game_ended_event_to_console(ge_event),
         }
     }
@@ -43,7 +60,11 @@ impl CliPresenter {
     }
 
     fn print_bid_event_to_console(event: BidEvent) {
-        println!("{} bid {}", event.player, event.bid)
+        match (event.alert, event.explanation) {
+            (true, Some(explanation)) => println!("{} bid {}! ({})", event.player, event.bid, explanation),
+            (true, None) => println!("{} bid {}!", event.player, event.bid),
+            (false, _) => println!("{} bid {}", event.player, event.bid),
+        }
     }
 
     fn print_move_to_card_play_event_to_console(event: BiddingEndedEvent) {
@@ -62,6 +83,88 @@ impl CliPresenter {
         println!("{} played {}", event.player, event.card)
     }
 
+    fn print_claim_event_to_console(event: ClaimEvent) {
+        println!("{} claims {} of the remaining tricks", event.player, event.tricks)
+    }
+
+    fn print_concede_event_to_console(event: ConcedeEvent) {
+        println!("{} concedes the remaining tricks", event.player)
+    }
+
+    fn print_claim_response_event_to_console(event: ClaimResponseEvent) {
+        match event.accept {
+            true => println!("{} accepts the claim", event.player),
+            false => println!("{} rejects the claim, play continues", event.player),
+        }
+    }
+
+    fn print_claim_ruling_event_to_console(event: ClaimRulingEvent) {
+        match event.valid {
+            true => println!(
+                "The claim of {} tricks by {} is valid",
+                event.claim.tricks, event.claim.player
+            ),
+            false => println!(
+                "The claim of {} tricks by {} is not valid, play continues",
+                event.claim.tricks, event.claim.player
+            ),
+        }
+    }
+
+    fn print_undo_request_event_to_console(event: UndoRequestEvent) {
+        println!("{} asks to take back their last call or card", event.player)
+    }
+
+    fn print_undo_response_event_to_console(event: UndoResponseEvent) {
+        match event.accept {
+            true => println!("{} agrees to the takeback", event.player),
+            false => println!("{} refuses the takeback", event.player),
+        }
+    }
+
+    fn print_undo_event_to_console(event: UndoEvent) {
+        println!("{} moves have been taken back for {}", event.moves, event.player)
+    }
+
+    fn print_irregularity_event_to_console(event: IrregularityEvent) {
+        match event {
+            IrregularityEvent::InsufficientBid(bid_event) => {
+                println!(
+                    "Director! {} made an insufficient bid of {}",
+                    bid_event.player, bid_event.bid
+                )
+            }
+            IrregularityEvent::CallOutOfTurn(bid_event) => {
+                println!("Director! {} called {} out of turn", bid_event.player, bid_event.bid)
+            }
+            IrregularityEvent::LeadOutOfTurn(card_event) => {
+                println!("Director! {} led {} out of turn", card_event.player, card_event.card)
+            }
+            IrregularityEvent::PlayOutOfTurn(card_event) => {
+                println!("Director! {} played {} out of turn", card_event.player, card_event.card)
+            }
+        }
+    }
+
+    fn print_irregularity_response_event_to_console(event: IrregularityResponseEvent) {
+        match event.accept {
+            true => println!("{} accepts the irregularity", event.player),
+            false => println!("{} does not accept the irregularity", event.player),
+        }
+    }
+
+    fn print_exposed_card_event_to_console(event: ExposedCardEvent) {
+        println!("{} exposed {}", event.player, event.card)
+    }
+
+    fn print_lead_penalty_event_to_console(event: LeadPenaltyEvent) {
+        match event.penalty {
+            LeadPenalty::RequireSuit(suit) => println!("{} requires a lead in {}", event.player, suit),
+            LeadPenalty::ForbidSuit(suit) => println!("{} forbids a lead in {}", event.player, suit),
+            LeadPenalty::PenaltyCardsRemain => println!("{} lets the penalty cards remain", event.player),
+        }
+    }
+
     fn print_game_ended_event_to_console(event: GameEndedEvent) {
         println!("The game ended");
         println!("Result is {:?}", event.result);
@@ -90,8 +193,12 @@ impl CliPresenter {
             print!("{}", pad);
         }
 
-        for bid in data.bids() {
-            let bid_str = format!("{}", bid);
+        let alerts = data.alerts();
+        for (bid, alert) in data.bids().into_iter().zip(alerts) {
+            let bid_str = match alert {
+                true => format!("{}!", bid),
+                false => format!("{}", bid),
+            };
             print!("{:<5}", bid_str);
             x += 1;
             if x % 4 == 0 {
@@ -101,6 +208,70 @@ impl CliPresenter {
         if x % 4 != 0 {
             println!();
         }
+
+        for (bid, explanation) in data.bids().into_iter().zip(data.explanations()) {
+            if let Some(explanation) = explanation {
+                println!("{}!: {}", bid, explanation);
+            }
+        }
+    }
+
+    pub fn display_card_analysis_for_user(analysis: &CardAnalysis) {
+        let best_cards = analysis.best_cards.iter().join(", ");
+        match analysis.tricks_lost {
+            0 => println!("{} was one of the best cards double dummy.", analysis.card),
+            1 => println!(
+                "{} cost a trick double dummy, better was {}.",
+                analysis.card, best_cards
+            ),
+            n => println!(
+                "{} cost {} tricks double dummy, better was {}.",
+                analysis.card, n, best_cards
+            ),
+        }
+    }
+
+    pub fn display_card_analyses_for_user(analyses: &[CardAnalysis]) {
+        let mistakes = analyses
+            .iter()
+            .filter(|analysis| analysis.tricks_lost > 0)
+            .collect_vec();
+        if mistakes.is_empty() {
+            println!("Your play was perfect double dummy.");
+            return;
+        }
+        let tricks_lost: usize = mistakes.iter().map(|analysis| analysis.tricks_lost).sum();
+        println!("Your play cost {} tricks double dummy:", tricks_lost);
+        for analysis in mistakes {
+            println!(
+                "  trick {}: {} by {} cost {}, better was {}",
+                analysis.trick_number,
+                analysis.card,
+                analysis.player,
+                analysis.tricks_lost,
+                analysis.best_cards.iter().join(", ")
+            );
+        }
+    }
+
+    pub fn display_reason_for_user(suggestion: impl Display, reason: &Reason) {
+        println!("The bots would choose {}.", suggestion);
+        match reason == &Reason::default() {
+            true => println!("They can't tell why."),
+            false => print!("{}", reason),
+        }
+    }
+
+    pub fn display_move_reason_for_user(event: PlayerEvent, reason: &Reason) {
+        match event {
+            PlayerEvent::Bid(bid_event) => println!("{} bid {}.", bid_event.player, bid_event.bid),
+            PlayerEvent::Card(card_event) => println!("{} played {}.", card_event.player, card_event.card),
+            _ => return,
+        }
+        match reason == &Reason::default() {
+            true => println!("They can't tell why."),
+            false => print!("{}", reason),
+        }
     }
 
     pub fn display_hand_for_user(cards: &[Card]) {
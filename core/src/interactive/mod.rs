@@ -1,4 +1,4 @@
-mod cli_bid_selector;
+pub mod cli_bid_selector;
 mod cli_card_selector;
 pub mod cli_move_selector;
 mod cli_presenter;
@@ -0,0 +1,247 @@
+use crate::error::BBError;
+use crate::game::bid_manager::BidManager;
+use crate::interchange::{board_for, contract_bid_in_letters, replay, split_alert};
+use crate::primitives::bid::{AuxiliaryBid, Bid, ContractBid};
+use crate::primitives::bid_line::BidLine;
+use crate::primitives::card::Rank;
+use crate::primitives::deal::{Deck, Seat, Vulnerability};
+use crate::primitives::game_event::GameEvent;
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::{Card, Deal, Hand, Suit};
+use itertools::Itertools;
+use std::str::FromStr;
+
+pub fn write_auction(bid_line: &BidLine) -> String {
+    let mut lin = String::new();
+    for ((bid, alert), explanation) in bid_line
+        .bids()
+        .iter()
+        .zip(bid_line.alerts())
+        .zip(bid_line.explanations())
+    {
+        lin.push_str("mb|");
+        lin.push_str(&call_in_letters(*bid));
+        if *alert {
+            lin.push('!');
+        }
+        lin.push('|');
+        if let Some(explanation) = explanation {
+            lin.push_str(&format!("an|{}|", explanation));
+        }
+    }
+    lin
+}
+
+// explanations that can't be read as `HandInfo`s are dropped, the alert stays
+pub fn read_auction(dealer: Seat, lin: &str) -> Result<BidLine, BBError> {
+    let mut calls: Vec<(Bid, bool, Option<Explanation>)> = Vec::new();
+
+    let mut fields = lin.trim().split('|');
+    while let Some(tag) = fields.next() {
+        let value = fields.next().unwrap_or_default();
+        match tag.trim().to_lowercase().as_str() {
+            "mb" => {
+                let (call, alert) = split_alert(value);
+                calls.push((parse_call(call)?, alert, None));
+            }
+            "an" => {
+                if let Some(call) = calls.last_mut() {
+                    call.2 = Explanation::from_str(value).ok();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let mut bid_manager = BidManager::new(dealer);
+    for (bid, alert, explanation) in calls {
+        bid_manager.bid_with_alert(bid, alert, explanation)?;
+    }
+    Ok(bid_manager.bid_line())
+}
+
+// if the play stops early, declarer's side claims the tricks still missing to `mc`
+pub fn read_game(lin: &str) -> Result<Vec<GameEvent>, BBError> {
+    let mut deal = None;
+    let mut vulnerability = Vulnerability::None;
+    let mut number = None;
+    let mut cards = Vec::new();
+    let mut declarers_tricks = None;
+
+    let mut fields = lin.trim().split('|');
+    while let Some(tag) = fields.next() {
+        let value = fields.next().unwrap_or_default().trim();
+        match tag.trim().to_lowercase().as_str() {
+            "md" => deal = Some(read_deal(value)?),
+            "sv" => vulnerability = read_vulnerability(value)?,
+            "ah" => number = value.trim_start_matches("Board").trim().parse().ok(),
+            "pc" => cards.push(Card::from_str(value)?),
+            "mc" => declarers_tricks = value.parse().ok(),
+            _ => (),
+        }
+    }
+
+    let (dealer, hands) = deal.ok_or(BBError::ParseError(lin.into(), "no deal found"))?;
+    let bid_line = read_auction(dealer, lin)?;
+    let deal = Deal {
+        board: board_for(number, dealer, vulnerability),
+        hands,
+    };
+    replay(deal, &bid_line, &cards, declarers_tricks)
+}
+
+// the dealer (1 is South, then clockwise), then the hands of South, West, North and East; East's may be left out
+fn read_deal(value: &str) -> Result<(Seat, [Hand<13>; 4]), BBError> {
+    let mut chars = value.chars();
+    let dealer = match chars.next() {
+        Some('1') => Seat::South,
+        Some('2') => Seat::West,
+        Some('3') => Seat::North,
+        Some('4') => Seat::East,
+        _ => return Err(BBError::ParseError(value.into(), "unknown dealer")),
+    };
+
+    let mut hands: Vec<Vec<Card>> = Vec::new();
+    for hand in chars.as_str().split(',').map(str::trim).filter(|hand| !hand.is_empty()) {
+        let mut cards = Vec::new();
+        let mut suit = None;
+        for char in hand.chars() {
+            match Suit::from_char(char) {
+                Ok(new_suit) => suit = Some(new_suit),
+                Err(_) => {
+                    let suit = suit.ok_or(BBError::ParseError(hand.into(), "cards before the first suit"))?;
+                    cards.push(Card {
+                        suit,
+                        rank: Rank::from_char(char)?,
+                    });
+                }
+            }
+        }
+        hands.push(cards);
+    }
+    if hands.len() == 3 {
+        let remaining = Deck::<13>::new()
+            .cards()
+            .iter()
+            .filter(|card| !hands.iter().flatten().contains(card))
+            .copied()
+            .collect();
+        hands.push(remaining);
+    }
+    if hands.len() != 4 {
+        return Err(BBError::ParseError(value.into(), "deal needs at least three hands"));
+    }
+
+    let [south, west, north, east] = [0, 1, 2, 3].map(|index| Hand::from_cards(&hands[index]));
+    Ok((dealer, [north?, east?, south?, west?]))
+}
+
+fn read_vulnerability(value: &str) -> Result<Vulnerability, BBError> {
+    match value.to_lowercase().as_str() {
+        "o" | "0" | "-" => Ok(Vulnerability::None),
+        "n" => Ok(Vulnerability::NorthSouth),
+        "e" => Ok(Vulnerability::EastWest),
+        "b" => Ok(Vulnerability::All),
+        _ => Err(BBError::ParseError(value.into(), "unknown vulnerability")),
+    }
+}
+
+fn call_in_letters(bid: Bid) -> String {
+    match bid {
+        Bid::Contract(bid) => contract_bid_in_letters(bid, "N"),
+        Bid::Auxiliary(AuxiliaryBid::Pass) => "p".into(),
+        Bid::Auxiliary(AuxiliaryBid::Double) => "d".into(),
+        Bid::Auxiliary(AuxiliaryBid::Redouble) => "r".into(),
+    }
+}
+
+fn parse_call(call: &str) -> Result<Bid, BBError> {
+    match call.to_lowercase().as_str() {
+        "p" => Ok(Bid::Auxiliary(AuxiliaryBid::Pass)),
+        "d" => Ok(Bid::Auxiliary(AuxiliaryBid::Double)),
+        "r" => Ok(Bid::Auxiliary(AuxiliaryBid::Redouble)),
+        contract => {
+            let contract = match contract.strip_suffix('n') {
+                Some(level) => format!("{}NT", level),
+                None => contract.to_string(),
+            };
+            ContractBid::from_str(&contract)
+                .map(Bid::Contract)
+                .map_err(|_| BBError::UnknownBid(call.into()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::primitives::game_result::GameResult;
+    use crate::primitives::Contract;
+    use test_case::test_case;
+
+    #[test_case("p", "Pass"; "Pass")]
+    #[test_case("D", "X"; "Double")]
+    #[test_case("r", "XX"; "Redouble")]
+    #[test_case("1N", "1NT"; "No Trump")]
+    #[test_case("3nt", "3NT"; "Spelled out No Trump")]
+    #[test_case("2c", "2C"; "Clubs")]
+    fn parse_call(input: &str, expected: &str) {
+        assert_eq!(super::parse_call(input).unwrap(), Bid::from_str(expected).unwrap());
+    }
+
+    #[test]
+    fn read_auction() {
+        let lin = "pn|a,b,c,d|md|2S...|mb|p|mb|1N|an|Hand is balanced. Hand has 15 to 17 hcp.|mb|p|mb|2C!|an|Stayman|mb|p|mb|2S|mb|p|mb|4S|mb|p|mb|p|mb|p|pg||";
+        let bid_line = super::read_auction(Seat::West, lin).unwrap();
+
+        assert_eq!(bid_line.bids().len(), 11);
+        assert_eq!(bid_line.bids()[3], Bid::from_str("2C").unwrap());
+        assert_eq!(bid_line.alerts().iter().filter(|&&alert| alert).count(), 1);
+        assert!(bid_line.alerts()[3]);
+        assert_eq!(
+            bid_line.explanations()[1],
+            Some(Explanation::from_str("Hand is balanced. Hand has 15 to 17 hcp.").unwrap())
+        );
+        // free text can't be represented, but the alert is kept
+        assert_eq!(bid_line.explanations()[3], None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut bid_line = BidLine::new();
+        bid_line.bid(Bid::from_str("1NT").unwrap());
+        bid_line.bid_with_alert(
+            Bid::from_str("X").unwrap(),
+            true,
+            Some(Explanation::from_str("Hand has 15 to 17 hcp.").unwrap()),
+        );
+        bid_line.bid(Bid::from_str("XX").unwrap());
+
+        let lin = write_auction(&bid_line);
+        assert_eq!(lin, "mb|1N|mb|d!|an|Hand has 15 to 17 hcp.|mb|r|");
+        assert_eq!(super::read_auction(Seat::North, &lin).unwrap(), bid_line);
+    }
+
+    #[test]
+    fn unknown_call() {
+        assert!(super::read_auction(Seat::North, "mb|8C|").is_err());
+    }
+
+    #[test]
+    fn read_game() {
+        let lin = "md|3DAKQJT98765432,CAKQJT98765432,SAKQJT98765432,|sv|n|ah|Board 5|mb|4S|mb|p|mb|p|mb|p|\
+                   pc|HA|pc|D2|pc|C2|pc|S2|mc|12|";
+        let history = super::read_game(lin).unwrap();
+
+        let Some(GameEvent::GameEnded(game_ended)) = history.last() else {
+            panic!("game has not ended")
+        };
+        assert_eq!(game_ended.deal.board.dealer(), Seat::North);
+        assert_eq!(game_ended.deal.board.vulnerability(), Vulnerability::NorthSouth);
+        assert_eq!(game_ended.deal.hands[1].length_in(Suit::Hearts), 13);
+        assert_eq!(
+            game_ended.result,
+            GameResult::calculate_game_result(Contract::from_str("N4S").unwrap(), 12)
+        );
+    }
+}
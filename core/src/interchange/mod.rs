@@ -0,0 +1,100 @@
+use crate::actors::game_manager::GameManager;
+use crate::error::BBError;
+use crate::game::trick_manager::TrickManager;
+use crate::primitives::bid::ContractBid;
+use crate::primitives::bid_line::BidLine;
+use crate::primitives::contract::Strain;
+use crate::primitives::deal::{Board, Seat, Vulnerability};
+use crate::primitives::game_event::GameEvent;
+use crate::primitives::player_event::{BidEvent, CardEvent, ClaimEvent, ClaimResponseEvent, PlayerEvent};
+use crate::primitives::{Card, Deal, Suit};
+
+pub mod lin;
+pub mod pbn;
+
+fn contract_bid_in_letters(bid: ContractBid, no_trump: &str) -> String {
+    let strain = match bid.strain {
+        Strain::Trump(Suit::Clubs) => "C",
+        Strain::Trump(Suit::Diamonds) => "D",
+        Strain::Trump(Suit::Hearts) => "H",
+        Strain::Trump(Suit::Spades) => "S",
+        Strain::NoTrump => no_trump,
+    };
+    format!("{}{}", bid.level, strain)
+}
+
+fn split_alert(call: &str) -> (&str, bool) {
+    match call.strip_suffix('!') {
+        Some(call) => (call, true),
+        None => (call, false),
+    }
+}
+
+// the board with the given number if it fits, otherwise the first board with this dealer and vulnerability
+fn board_for(number: Option<usize>, dealer: Seat, vulnerability: Vulnerability) -> Board {
+    let matches = |board: &Board| board.dealer() == dealer && board.vulnerability() == vulnerability;
+    number
+        .map(Board::from_number)
+        .filter(matches)
+        .into_iter()
+        .chain((1..=16).map(Board::from_number))
+        .find(matches)
+        .unwrap_or(Board::from_number(1))
+}
+
+// replayed at a `GameManager`, so the history looks like that of a game played at a `Table`;
+// if the play stopped early, declarer's side gets `declarers_tricks` by an accepted claim
+fn replay(
+    deal: Deal<13>,
+    bid_line: &BidLine,
+    cards: &[Card],
+    declarers_tricks: Option<usize>,
+) -> Result<Vec<GameEvent>, BBError> {
+    let mut manager = GameManager::new_from_deal(deal);
+    manager.start_game()?;
+
+    for ((&bid, &alert), &explanation) in bid_line
+        .bids()
+        .iter()
+        .zip(bid_line.alerts())
+        .zip(bid_line.explanations())
+    {
+        let player = manager.next_to_play().ok_or(BBError::GameHasEnded)?;
+        manager.process_player_event(PlayerEvent::Bid(BidEvent {
+            player,
+            bid,
+            alert,
+            explanation,
+        }))?;
+    }
+
+    for &card in cards {
+        let player = manager.next_to_play().ok_or(BBError::GameHasEnded)?;
+        manager.process_player_event(PlayerEvent::Card(CardEvent { player, card }))?;
+    }
+
+    let contract = manager.history().iter().find_map(|event| match event {
+        GameEvent::BiddingEnded(event) => Some(event.final_contract),
+        _ => None,
+    });
+    if let (Some(contract), Some(player), Some(declarers_tricks)) = (contract, manager.next_to_play(), declarers_tricks)
+    {
+        let mut trick_manager = TrickManager::<13>::new(contract.declarer + 1, contract.trump_suit());
+        cards.iter().for_each(|&card| trick_manager.play(card));
+        let remaining = declarers_tricks
+            .checked_sub(trick_manager.tricks_won_by_axis(contract.declarer))
+            .filter(|&remaining| remaining <= trick_manager.tricks_left())
+            .ok_or(BBError::InvalidClaim(declarers_tricks))?;
+        let tricks = match player.same_axis(&contract.declarer) {
+            true => remaining,
+            false => trick_manager.tricks_left() - remaining,
+        };
+        manager.process_player_event(PlayerEvent::Claim(ClaimEvent { player, tricks }))?;
+        while manager.pending_claim().is_some() {
+            let player = manager.next_to_play().ok_or(BBError::GameHasEnded)?;
+            manager.process_player_event(PlayerEvent::ClaimResponse(ClaimResponseEvent { player, accept: true }))?;
+        }
+    }
+
+    Ok(manager.history())
+}
@@ -0,0 +1,314 @@
+use crate::error::BBError;
+use crate::game::bid_manager::BidManager;
+use crate::game::trick_manager::TrickManager;
+use crate::interchange::{board_for, contract_bid_in_letters, replay, split_alert};
+use crate::primitives::bid::{AuxiliaryBid, Bid};
+use crate::primitives::bid_line::BidLine;
+use crate::primitives::deal::{Seat, Vulnerability};
+use crate::primitives::game_event::GameEvent;
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::{Card, Deal, Hand, Suit};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+pub fn write_auction(dealer: Seat, bid_line: &BidLine) -> String {
+    let mut notes = Vec::new();
+    let mut calls = Vec::new();
+
+    for ((bid, alert), explanation) in bid_line
+        .bids()
+        .iter()
+        .zip(bid_line.alerts())
+        .zip(bid_line.explanations())
+    {
+        let mut call = call_in_letters(*bid);
+        if *alert {
+            call.push('!');
+        }
+        if let Some(explanation) = explanation {
+            notes.push(explanation);
+            call.push_str(&format!(" ={}=", notes.len()));
+        }
+        calls.push(call);
+    }
+
+    let mut pbn = format!("[Auction \"{}\"]\n", dealer);
+    for line in calls.chunks(4) {
+        pbn.push_str(&line.join(" "));
+        pbn.push('\n');
+    }
+    for (index, note) in notes.iter().enumerate() {
+        pbn.push_str(&format!("[Note \"{}:{}\"]\n", index + 1, note));
+    }
+    pbn
+}
+
+// notes that can't be read as `HandInfo`s are dropped, the alert stays
+pub fn read_auction(pbn: &str) -> Result<(Seat, BidLine), BBError> {
+    let mut dealer = None;
+    let mut in_auction = false;
+    let mut calls: Vec<(Bid, bool, Option<usize>)> = Vec::new();
+    let mut all_pass = false;
+    let mut notes = HashMap::new();
+
+    for line in pbn.lines().map(str::trim) {
+        if let Some((tag, value)) = parse_tag(line) {
+            in_auction = tag == "Auction";
+            match tag {
+                "Auction" => {
+                    dealer =
+                        Some(Seat::from_str(value).map_err(|_| BBError::ParseError(value.into(), "unknown dealer"))?)
+                }
+                "Note" => {
+                    if let Some((number, text)) = value.split_once(':') {
+                        if let Ok(number) = number.parse::<usize>() {
+                            notes.insert(number, text);
+                        }
+                    }
+                }
+                _ => (),
+            }
+            continue;
+        }
+        if !in_auction {
+            continue;
+        }
+        for token in line.split_whitespace() {
+            match token {
+                "AP" => all_pass = true,
+                "*" | "-" | "+" => (),
+                note if note.len() > 2 && note.starts_with('=') && note.ends_with('=') => {
+                    if let Some(call) = calls.last_mut() {
+                        call.2 = note[1..note.len() - 1].parse().ok();
+                    }
+                }
+                call => {
+                    let (call, alert) = split_alert(call);
+                    let bid = Bid::from_str(call)?;
+                    calls.push((bid, alert, None));
+                }
+            }
+        }
+    }
+
+    let dealer = dealer.ok_or(BBError::ParseError(pbn.into(), "no auction found"))?;
+    let mut bid_manager = BidManager::new(dealer);
+    for (bid, alert, note) in calls {
+        let explanation = note
+            .and_then(|number| notes.get(&number))
+            .and_then(|text| Explanation::from_str(text).ok());
+        bid_manager.bid_with_alert(bid, alert, explanation)?;
+    }
+    while all_pass && !bid_manager.bidding_has_ended() {
+        bid_manager.bid(Bid::Auxiliary(AuxiliaryBid::Pass))?;
+    }
+    Ok((dealer, bid_manager.bid_line()))
+}
+
+// if the play stops early, declarer's side claims the tricks still missing to the `Result`
+pub fn read_game(pbn: &str) -> Result<Vec<GameEvent>, BBError> {
+    let (dealer, bid_line) = read_auction(pbn)?;
+    let mut hands = None;
+    let mut vulnerability = Vulnerability::None;
+    let mut number = None;
+    let mut in_play = false;
+    let mut tricks: Vec<Vec<&str>> = Vec::new();
+    let mut declarers_tricks = None;
+
+    for line in pbn.lines().map(str::trim) {
+        if let Some((tag, value)) = parse_tag(line) {
+            in_play = tag == "Play";
+            match tag {
+                "Deal" => hands = Some(read_deal(value)?),
+                "Vulnerable" => vulnerability = read_vulnerability(value)?,
+                "Board" => number = value.parse().ok(),
+                "Result" => declarers_tricks = value.parse().ok(),
+                _ => (),
+            }
+            continue;
+        }
+        if in_play {
+            let trick = line.split_whitespace().take_while(|&card| card != "*").collect_vec();
+            in_play = trick.len() == 4;
+            if !trick.is_empty() {
+                tricks.push(trick);
+            }
+        }
+    }
+
+    let hands = hands.ok_or(BBError::ParseError(pbn.into(), "no deal found"))?;
+    let deal = Deal {
+        board: board_for(number, dealer, vulnerability),
+        hands,
+    };
+    let cards = match BidManager::from_bid_line(dealer, bid_line.clone()).implied_contract() {
+        Some(contract) => cards_in_play_order(&tricks, contract.declarer + 1, contract.trump_suit())?,
+        None => Vec::new(),
+    };
+    replay(deal, &bid_line, &cards, declarers_tricks)
+}
+
+// each row of the play section lists a trick in seat order, starting with the opening leader
+fn cards_in_play_order(tricks: &[Vec<&str>], opening_leader: Seat, trumps: Option<Suit>) -> Result<Vec<Card>, BBError> {
+    let mut trick_manager = TrickManager::<13>::new(opening_leader, trumps);
+    for trick in tricks {
+        for _ in 0..4 {
+            let column = (trick_manager.next_to_play() as usize + 4 - opening_leader as usize) % 4;
+            match trick.get(column) {
+                None | Some(&"-") => return Ok(trick_manager.played_cards().to_vec()),
+                Some(card) => trick_manager.play(Card::from_str(card)?),
+            }
+        }
+    }
+    Ok(trick_manager.played_cards().to_vec())
+}
+
+// hands clockwise from the given seat, suits in the order spades, hearts, diamonds and clubs
+fn read_deal(value: &str) -> Result<[Hand<13>; 4], BBError> {
+    let (first, hands) = value
+        .split_once(':')
+        .ok_or(BBError::ParseError(value.into(), "missing first seat of deal"))?;
+    let first = Seat::from_str(first).map_err(|_| BBError::ParseError(value.into(), "unknown first seat"))?;
+    let hands: Vec<&str> = hands.split_whitespace().collect();
+    if hands.len() != 4 {
+        return Err(BBError::ParseError(value.into(), "deal needs four hands"));
+    }
+
+    let mut clockwise = Vec::new();
+    for hand in hands {
+        let mut cards = Vec::new();
+        for (suit, ranks) in ["S", "H", "D", "C"].iter().zip(hand.split('.')) {
+            for rank in ranks.chars().filter(|&rank| rank != '-') {
+                cards.push(Card::from_str(&format!("{}{}", suit, rank))?);
+            }
+        }
+        clockwise.push(Hand::from_cards(&cards)?);
+    }
+    Ok(std::array::from_fn(|seat| clockwise[(seat + 4 - first as usize) % 4]))
+}
+
+fn read_vulnerability(value: &str) -> Result<Vulnerability, BBError> {
+    match value {
+        "None" | "Love" | "-" => Ok(Vulnerability::None),
+        "NS" => Ok(Vulnerability::NorthSouth),
+        "EW" => Ok(Vulnerability::EastWest),
+        "All" | "Both" => Ok(Vulnerability::All),
+        _ => Err(BBError::ParseError(value.into(), "unknown vulnerability")),
+    }
+}
+
+fn call_in_letters(bid: Bid) -> String {
+    match bid {
+        Bid::Contract(bid) => contract_bid_in_letters(bid, "NT"),
+        Bid::Auxiliary(bid) => format!("{}", bid),
+    }
+}
+
+fn parse_tag(line: &str) -> Option<(&str, &str)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (tag, value) = inner.split_once(' ')?;
+    Some((tag, value.trim().trim_matches('"')))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::primitives::game_result::GameResult;
+    use crate::primitives::Contract;
+
+    #[test]
+    fn read_auction() {
+        let pbn = "[Event \"\"]
+[Dealer \"W\"]
+[Auction \"W\"]
+Pass 1NT =1= Pass 2C!
+Pass 2S Pass 4S
+AP
+[Play \"N\"]
+[Note \"1:Hand is balanced. Hand has 15 to 17 hcp.\"]";
+        let (dealer, bid_line) = super::read_auction(pbn).unwrap();
+
+        assert_eq!(dealer, Seat::West);
+        assert_eq!(bid_line.bids().len(), 11);
+        assert!(bid_line.alerts()[3]);
+        assert!(!bid_line.alerts()[1]);
+        assert_eq!(
+            bid_line.explanations()[1],
+            Some(Explanation::from_str("Hand is balanced. Hand has 15 to 17 hcp.").unwrap())
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut bid_line = BidLine::new();
+        bid_line.bid(Bid::from_str("Pass").unwrap());
+        bid_line.bid_with_alert(
+            Bid::from_str("1NT").unwrap(),
+            true,
+            Some(Explanation::from_str("Hand has 15 to 17 hcp.").unwrap()),
+        );
+        for call in ["Pass", "2C", "Pass", "2S", "Pass", "4S", "Pass", "Pass", "Pass"] {
+            bid_line.bid(Bid::from_str(call).unwrap());
+        }
+
+        let pbn = write_auction(Seat::West, &bid_line);
+        assert_eq!(
+            pbn,
+            "[Auction \"W\"]\nPass 1NT! =1= Pass 2C\nPass 2S Pass 4S\nPass Pass Pass\n[Note \"1:Hand has 15 to 17 hcp.\"]\n"
+        );
+        assert_eq!(super::read_auction(&pbn).unwrap(), (Seat::West, bid_line));
+    }
+
+    #[test]
+    fn invalid_auction() {
+        assert!(super::read_auction("[Auction \"N\"]\n1NT 1C").is_err());
+    }
+
+    #[test]
+    fn read_game() {
+        let pbn = "[Board \"1\"]
+[Dealer \"N\"]
+[Vulnerable \"None\"]
+[Deal \"N:AKQJT98765432... .AKQJT98765432.. ..AKQJT98765432. ...AKQJT98765432\"]
+[Auction \"N\"]
+4S Pass Pass Pass
+[Play \"E\"]
+HA D2 C2 S2
+H2 D3 C3 S3
+*
+[Result \"13\"]";
+        let history = super::read_game(pbn).unwrap();
+
+        let Some(GameEvent::GameEnded(game_ended)) = history.last() else {
+            panic!("game has not ended")
+        };
+        assert_eq!(game_ended.deal.board.number(), 1);
+        assert_eq!(game_ended.deal.hands[1].length_in(Suit::Hearts), 13);
+        assert_eq!(
+            game_ended.result,
+            GameResult::calculate_game_result(Contract::from_str("N4S").unwrap(), 13)
+        );
+        let cards = history
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::Card(card_event) => Some((card_event.player, card_event.card)),
+                _ => None,
+            })
+            .collect_vec();
+        assert_eq!(cards.len(), 8);
+        assert_eq!(cards[3], (Seat::North, Card::from_str("S2").unwrap()));
+        assert_eq!(cards[4], (Seat::North, Card::from_str("S3").unwrap()));
+    }
+
+    #[test]
+    fn impossible_result() {
+        let pbn = "[Deal \"N:AKQJT98765432... .AKQJT98765432.. ..AKQJT98765432. ...AKQJT98765432\"]
+[Auction \"N\"]
+4S Pass Pass Pass
+[Play \"E\"]
+HA D2 C2 S2
+[Result \"0\"]";
+        assert!(super::read_game(pbn).is_err());
+    }
+}
@@ -4,4 +4,5 @@ pub mod error;
 pub mod game;
 pub mod impossible_book;
 pub mod interactive;
+pub mod interchange;
 pub mod primitives;
@@ -5,11 +5,14 @@ use itertools::Itertools;
 use crate::error::BBError;
 use crate::game::bid_manager::BidManager;
 use crate::primitives::deal::Seat;
+use crate::primitives::hand_info::explanation::Explanation;
 use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BidLine {
     bids: Vec<Bid>,
+    alerts: Vec<bool>,
+    explanations: Vec<Option<Explanation>>,
 }
 
 impl Display for BidLine {
@@ -26,12 +29,35 @@ impl BidLine {
     }
 
     pub fn bid(&mut self, bid: Bid) {
-        self.bids.push(bid)
+        self.bid_with_alert(bid, false, None)
+    }
+
+    pub fn bid_with_alert(&mut self, bid: Bid, alert: bool, explanation: Option<Explanation>) {
+        self.bids.push(bid);
+        self.alerts.push(alert);
+        self.explanations.push(explanation);
+    }
+
+    pub fn alerts(&self) -> &[bool] {
+        &self.alerts
+    }
+
+    pub fn explanations(&self) -> &[Option<Explanation>] {
+        &self.explanations
+    }
+
+    pub fn undo(&mut self) -> Option<Bid> {
+        self.alerts.pop();
+        self.explanations.pop();
+        self.bids.pop()
     }
 
     pub fn new() -> Self {
-        let bids = vec![];
-        BidLine { bids }
+        BidLine {
+            bids: vec![],
+            alerts: vec![],
+            explanations: vec![],
+        }
     }
 
     pub fn len(&self) -> usize {
@@ -86,8 +112,16 @@ mod test {
     #[test_case("", &[]; "unbid")]
     fn from_str(input: &str, expect_line: &[&str]) {
         let input_line = BidLine::from_str(input).unwrap();
-        let expected = expect_line.iter().map(|x| Bid::from_str(x).unwrap()).collect();
-        assert_eq!(input_line, BidLine { bids: expected });
+        let expected: Vec<Bid> = expect_line.iter().map(|x| Bid::from_str(x).unwrap()).collect();
+        let calls = expected.len();
+        assert_eq!(
+            input_line,
+            BidLine {
+                bids: expected,
+                alerts: vec![false; calls],
+                explanations: vec![None; calls],
+            }
+        );
     }
 
     #[test_case("P-1NT", "Pass-1NT"; "Pass then 1NT")]
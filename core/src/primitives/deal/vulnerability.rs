@@ -1,6 +1,6 @@
 use crate::primitives::deal::Seat;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Vulnerability {
     None,
     NorthSouth,
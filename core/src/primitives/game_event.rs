@@ -4,28 +4,44 @@ use crate::primitives::game_result::GameResult;
 use crate::primitives::player_event::PlayerEvent;
 use crate::primitives::{Contract, Deal, Hand};
 
-pub use crate::primitives::player_event::{BidEvent, CardEvent};
+pub use crate::primitives::player_event::{
+    BidEvent, CardEvent, ClaimEvent, ClaimResponseEvent, ConcedeEvent, ExposedCardEvent, IrregularityResponseEvent,
+    LeadPenalty, LeadPenaltyEvent, UndoRequestEvent, UndoResponseEvent,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum GameEvent {
+pub enum GameEvent<const N: usize = 13> {
     NewGame(NewGameEvent),
-    DiscloseHand(DiscloseHandEvent),
+    DiscloseHand(DiscloseHandEvent<N>),
     Bid(BidEvent),
     BiddingEnded(BiddingEndedEvent),
     Card(CardEvent),
-    DummyUncovered(DummyUncoveredEvent),
-    GameEnded(GameEndedEvent),
+    DummyUncovered(DummyUncoveredEvent<N>),
+    Claim(ClaimEvent),
+    Concede(ConcedeEvent),
+    ClaimResponse(ClaimResponseEvent),
+    ClaimRuling(ClaimRulingEvent),
+    UndoRequest(UndoRequestEvent),
+    UndoResponse(UndoResponseEvent),
+    Undo(UndoEvent),
+    Irregularity(IrregularityEvent),
+    IrregularityResponse(IrregularityResponseEvent),
+    ExposedCard(ExposedCardEvent),
+    LeadPenalty(LeadPenaltyEvent),
+    GameEnded(GameEndedEvent<N>),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct NewGameEvent {
     pub board: Board,
+    pub director: bool,
+    pub mini_bridge: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct DiscloseHandEvent {
+pub struct DiscloseHandEvent<const N: usize = 13> {
     pub seat: Seat,
-    pub hand: Hand<13>,
+    pub hand: Hand<N>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,22 +50,59 @@ pub struct BiddingEndedEvent {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct DummyUncoveredEvent {
-    pub dummy: Hand<13>,
+pub struct DummyUncoveredEvent<const N: usize = 13> {
+    pub dummy: Hand<N>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct GameEndedEvent {
-    pub deal: Deal<13>,
+pub struct ClaimRulingEvent {
+    pub claim: ClaimEvent,
+    pub valid: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UndoEvent {
+    pub player: Seat,
+    pub moves: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IrregularityEvent {
+    InsufficientBid(BidEvent),
+    CallOutOfTurn(BidEvent),
+    LeadOutOfTurn(CardEvent),
+    PlayOutOfTurn(CardEvent),
+}
+
+impl IrregularityEvent {
+    pub fn offender(&self) -> Seat {
+        match self {
+            IrregularityEvent::InsufficientBid(event) | IrregularityEvent::CallOutOfTurn(event) => event.player,
+            IrregularityEvent::LeadOutOfTurn(event) | IrregularityEvent::PlayOutOfTurn(event) => event.player,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameEndedEvent<const N: usize = 13> {
+    pub deal: Deal<N>,
     pub result: GameResult,
     pub score: ScorePoints,
 }
 
-impl From<PlayerEvent> for GameEvent {
+impl<const N: usize> From<PlayerEvent> for GameEvent<N> {
     fn from(player_event: PlayerEvent) -> Self {
         match player_event {
             PlayerEvent::Bid(event) => GameEvent::Bid(event),
             PlayerEvent::Card(event) => GameEvent::Card(event),
+            PlayerEvent::Claim(event) => GameEvent::Claim(event),
+            PlayerEvent::Concede(event) => GameEvent::Concede(event),
+            PlayerEvent::ClaimResponse(event) => GameEvent::ClaimResponse(event),
+            PlayerEvent::UndoRequest(event) => GameEvent::UndoRequest(event),
+            PlayerEvent::UndoResponse(event) => GameEvent::UndoResponse(event),
+            PlayerEvent::IrregularityResponse(event) => GameEvent::IrregularityResponse(event),
+            PlayerEvent::ExposedCard(event) => GameEvent::ExposedCard(event),
+            PlayerEvent::LeadPenalty(event) => GameEvent::LeadPenalty(event),
         }
     }
 }
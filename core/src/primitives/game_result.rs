@@ -1,5 +1,6 @@
 use crate::primitives::Contract;
 use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum GameResult {
@@ -18,7 +19,12 @@ impl GameResult {
     }
 
     pub fn calculate_game_result(contract: Contract, actual_tricks: usize) -> GameResult {
-        let exp = contract.expected_tricks();
+        Self::calculate_reduced_game_result(contract, actual_tricks, 13)
+    }
+
+    // with fewer than 13 tricks to play, the tricks needed shrink in proportion
+    pub fn calculate_reduced_game_result(contract: Contract, actual_tricks: usize, total_tricks: usize) -> GameResult {
+        let exp = (contract.expected_tricks() * total_tricks).div_ceil(13);
         let act = actual_tricks;
         match exp.cmp(&act) {
             Ordering::Equal => GameResult::Made {
@@ -36,3 +42,17 @@ impl GameResult {
         }
     }
 }
+
+impl Display for GameResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameResult::Made {
+                contract,
+                overtricks: 0,
+            } => write!(f, "{} =", contract),
+            GameResult::Made { contract, overtricks } => write!(f, "{} +{}", contract, overtricks),
+            GameResult::Failed { contract, undertricks } => write!(f, "{} -{}", contract, undertricks),
+            GameResult::Unplayed => write!(f, "passed out"),
+        }
+    }
+}
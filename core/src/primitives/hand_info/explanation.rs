@@ -0,0 +1,196 @@
+use crate::error::BBError;
+use crate::primitives::card::suit::SUIT_ARRAY;
+use crate::primitives::deal::hand::HandType;
+use crate::primitives::hand_info::keycards::Keycards;
+use crate::primitives::hand_info::ranges::{HcpRange, LengthRange, PointRange};
+use crate::primitives::hand_info::suit_quality::SuitQuality;
+use crate::primitives::hand_info::HandInfo;
+use crate::primitives::Suit;
+use itertools::Itertools;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Explanation {
+    pub hand_type: Option<HandType>,
+    pub hcp: Option<(u8, u8)>,
+    pub total_points: Option<(u8, u8)>,
+    pub suit_lengths: [Option<(u8, u8)>; 4],
+    pub suit_qualities: [Option<SuitQuality>; 4],
+    pub keycards: Option<Keycards>,
+}
+
+impl Explanation {
+    pub fn from_hand_info(hand_info: &[HandInfo]) -> Self {
+        let mut explanation = Explanation::default();
+        for info in hand_info {
+            match info {
+                HandInfo::HandType(hand_type) => explanation.hand_type = Some(*hand_type),
+                HandInfo::SuitLength(suit, LengthRange(range)) => {
+                    explanation.suit_lengths[*suit as usize] = Some((*range.start() as u8, *range.end() as u8))
+                }
+                HandInfo::SuitQuality(suit, quality) => explanation.suit_qualities[*suit as usize] = Some(*quality),
+                HandInfo::Hcp(HcpRange(range)) => {
+                    explanation.hcp = Some((range.start().round() as u8, range.end().round() as u8))
+                }
+                HandInfo::TotalPoints(PointRange(range)) => {
+                    explanation.total_points = Some((range.start().round() as u8, range.end().round() as u8))
+                }
+                HandInfo::Keycards(keycards) => explanation.keycards = Some(*keycards),
+            }
+        }
+        explanation
+    }
+
+    pub fn hand_info(&self) -> Vec<HandInfo> {
+        let mut hand_info = Vec::new();
+        if let Some(hand_type) = self.hand_type {
+            hand_info.push(HandInfo::HandType(hand_type));
+        }
+        for suit in SUIT_ARRAY.iter().rev() {
+            if let Some((min, max)) = self.suit_lengths[*suit as usize] {
+                hand_info.push(HandInfo::SuitLength(*suit, LengthRange(min as usize..=max as usize)));
+            }
+            if let Some(quality) = self.suit_qualities[*suit as usize] {
+                hand_info.push(HandInfo::SuitQuality(*suit, quality));
+            }
+        }
+        if let Some((min, max)) = self.hcp {
+            hand_info.push(HandInfo::Hcp(HcpRange(min as f64..=max as f64)));
+        }
+        if let Some((min, max)) = self.total_points {
+            hand_info.push(HandInfo::TotalPoints(PointRange(min as f64..=max as f64)));
+        }
+        if let Some(keycards) = self.keycards {
+            hand_info.push(HandInfo::Keycards(keycards));
+        }
+        hand_info
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Explanation::default()
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.hand_info().iter().join(" "))
+    }
+}
+
+impl FromStr for Explanation {
+    type Err = BBError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut explanation = Explanation::default();
+        for sentence in s
+            .split_terminator('.')
+            .map(str::trim)
+            .filter(|sentence| !sentence.is_empty())
+        {
+            let unknown = || BBError::ParseError(sentence.into(), "unknown hand information");
+            if let Some(hand_type) = sentence.strip_prefix("Hand is ") {
+                explanation.hand_type = Some(parse_hand_type(hand_type).ok_or_else(unknown)?);
+            } else if let Some(rest) = sentence.strip_prefix("Hand's ") {
+                let (suit, quality) = rest.split_once(" are ").ok_or_else(unknown)?;
+                let suit = Suit::from_str(suit)?;
+                let quality = SuitQuality::from_str(quality).map_err(|_| unknown())?;
+                explanation.suit_qualities[suit as usize] = Some(quality);
+            } else if let Some(rest) = sentence.strip_prefix("Hand has ").filter(|rest| !rest.contains(" to ")) {
+                explanation.keycards = Some(Keycards::from_str(rest).map_err(|_| unknown())?);
+            } else if let Some(rest) = sentence.strip_prefix("Hand has ") {
+                let (min, rest) = rest.split_once(" to ").ok_or_else(unknown)?;
+                let (max, unit) = rest.split_once(' ').ok_or_else(unknown)?;
+                let range = (
+                    min.parse::<u8>().map_err(|_| unknown())?,
+                    max.parse::<u8>().map_err(|_| unknown())?,
+                );
+                match unit {
+                    "hcp" => explanation.hcp = Some(range),
+                    "points" => explanation.total_points = Some(range),
+                    _ => {
+                        let suit = unit.strip_prefix("cards in ").ok_or_else(unknown)?;
+                        explanation.suit_lengths[Suit::from_str(suit)? as usize] = Some(range);
+                    }
+                }
+            } else {
+                return Err(unknown());
+            }
+        }
+        Ok(explanation)
+    }
+}
+
+fn parse_hand_type(s: &str) -> Option<HandType> {
+    let suits = |list: &str| {
+        list.split([',', ' '])
+            .filter(|word| !word.is_empty() && *word != "and")
+            .map(Suit::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .ok()
+    };
+    match s.split_once(": ") {
+        Some(("single-suited", list)) => match suits(list)?[..] {
+            [s1] => Some(HandType::SingleSuited(s1)),
+            _ => None,
+        },
+        Some(("two-suited", list)) => match suits(list)?[..] {
+            [s1, s2] => Some(HandType::TwoSuited(s1, s2)),
+            _ => None,
+        },
+        Some(("three-suited", list)) => match suits(list)?[..] {
+            [s1, s2, s3] => Some(HandType::ThreeSuited(s1, s2, s3)),
+            _ => None,
+        },
+        Some(_) => None,
+        None if s == "balanced" => Some(HandType::Balanced(None)),
+        None => {
+            let suit = s.strip_prefix("balanced with 5 cards in ")?;
+            Some(HandType::Balanced(Some(Suit::from_str(suit).ok()?)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Explanation;
+    use crate::primitives::deal::hand::HandType;
+    use crate::primitives::hand_info::keycards::Keycards;
+    use crate::primitives::hand_info::ranges::{HcpRange, LengthRange};
+    use crate::primitives::hand_info::suit_quality::SuitQuality;
+    use crate::primitives::hand_info::HandInfo;
+    use crate::primitives::Suit::*;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    #[test]
+    fn from_hand_info() {
+        let explanation = Explanation::from_hand_info(&[
+            HandInfo::Hcp(HcpRange(15.0..=17.0)),
+            HandInfo::HandType(HandType::Balanced(None)),
+        ]);
+        assert_eq!(explanation.hcp, Some((15, 17)));
+        assert_eq!(explanation.hand_type, Some(HandType::Balanced(None)));
+        assert_eq!(format!("{}", explanation), "Hand is balanced. Hand has 15 to 17 hcp.");
+    }
+
+    #[test_case(&[HandInfo::Hcp(HcpRange(15.0..=17.0)), HandInfo::HandType(HandType::Balanced(None))]; "1NT")]
+    #[test_case(&[HandInfo::SuitLength(Hearts, LengthRange(6..=6)), HandInfo::SuitQuality(Hearts, SuitQuality::Good)]; "Weak Two")]
+    #[test_case(&[HandInfo::HandType(HandType::ThreeSuited(Spades, Hearts, Diamonds))]; "Three-suited")]
+    #[test_case(&[HandInfo::HandType(HandType::TwoSuited(Spades, Clubs))]; "Two-suited")]
+    #[test_case(&[HandInfo::HandType(HandType::SingleSuited(Diamonds))]; "Single-suited")]
+    #[test_case(&[HandInfo::HandType(HandType::Balanced(Some(Spades)))]; "Balanced with five")]
+    #[test_case(&[HandInfo::Keycards(Keycards::aces((0, 4)))]; "Gerber answer")]
+    #[test_case(&[HandInfo::Keycards(Keycards::for_trump_suit(Hearts, (2, 2), Some(true)))]; "Keycard answer")]
+    #[test_case(&[]; "Empty")]
+    fn round_trip(hand_info: &[HandInfo]) {
+        let explanation = Explanation::from_hand_info(hand_info);
+        let text = format!("{}", explanation);
+        assert_eq!(Explanation::from_str(&text).unwrap(), explanation);
+    }
+
+    #[test]
+    fn free_text_is_not_an_explanation() {
+        assert!(Explanation::from_str("Stayman").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::error::BBError;
+use crate::primitives::Suit;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// step answers don't tell the counts apart, so two counts may be shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keycards {
+    pub trump_suit: Option<Suit>,
+    pub counts: (u8, u8),
+    pub trump_queen: Option<bool>,
+}
+
+impl Keycards {
+    pub fn aces(counts: (u8, u8)) -> Self {
+        Keycards {
+            trump_suit: None,
+            counts,
+            trump_queen: None,
+        }
+    }
+
+    pub fn for_trump_suit(trump_suit: Suit, counts: (u8, u8), trump_queen: Option<bool>) -> Self {
+        Keycards {
+            trump_suit: Some(trump_suit),
+            counts,
+            trump_queen,
+        }
+    }
+
+    pub fn allows(&self, count: u8) -> bool {
+        self.counts.0 == count || self.counts.1 == count
+    }
+}
+
+impl Display for Keycards {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (first, second) = self.counts;
+        match first == second {
+            true => write!(f, "{}", first)?,
+            false => write!(f, "{} or {}", first, second)?,
+        }
+        let unit = match self.trump_suit {
+            None => "ace",
+            Some(_) => "keycard",
+        };
+        let plural = if first == 1 && second == 1 { "" } else { "s" };
+        write!(f, " {}{}", unit, plural)?;
+        if let Some(suit) = self.trump_suit {
+            write!(f, " for {}", suit)?;
+        }
+        match self.trump_queen {
+            Some(true) => write!(f, " with the queen"),
+            Some(false) => write!(f, " without the queen"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Keycards {
+    type Err = BBError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || BBError::ParseError(s.into(), "invalid keycards");
+        let (rest, trump_queen) = match (s.strip_suffix(" with the queen"), s.strip_suffix(" without the queen")) {
+            (Some(rest), _) => (rest, Some(true)),
+            (_, Some(rest)) => (rest, Some(false)),
+            _ => (s, None),
+        };
+        let (rest, trump_suit) = match rest.split_once(" for ") {
+            Some((rest, suit)) => (rest, Some(Suit::from_str(suit)?)),
+            None => (rest, None),
+        };
+        let (counts, unit) = rest.rsplit_once(' ').ok_or_else(invalid)?;
+        match (unit, trump_suit) {
+            ("ace" | "aces", None) | ("keycard" | "keycards", Some(_)) => (),
+            _ => return Err(invalid()),
+        }
+        let count = |count: &str| count.parse::<u8>().map_err(|_| invalid());
+        let counts = match counts.split_once(" or ") {
+            Some((first, second)) => (count(first)?, count(second)?),
+            None => (count(counts)?, count(counts)?),
+        };
+        Ok(Keycards {
+            trump_suit,
+            counts,
+            trump_queen,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Keycards;
+    use crate::primitives::Suit::*;
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    #[test_case(Keycards::aces((0, 4)), "0 or 4 aces"; "Aces")]
+    #[test_case(Keycards::aces((1, 1)), "1 ace"; "One ace")]
+    #[test_case(Keycards::for_trump_suit(Spades, (1, 4), None), "1 or 4 keycards for ♠"; "Keycards")]
+    #[test_case(Keycards::for_trump_suit(Hearts, (2, 2), Some(true)), "2 keycards for ♥ with the queen"; "With the queen")]
+    #[test_case(Keycards::for_trump_suit(Hearts, (2, 2), Some(false)), "2 keycards for ♥ without the queen"; "Without the queen")]
+    fn round_trip(keycards: Keycards, expected: &str) {
+        assert_eq!(format!("{}", keycards), expected);
+        assert_eq!(Keycards::from_str(expected).unwrap(), keycards);
+    }
+
+    #[test_case("2 keycards"; "Keycards without a trump suit")]
+    #[test_case("2 aces for ♠"; "Aces for a trump suit")]
+    #[test_case("two aces"; "Count in words")]
+    fn invalid(input: &str) {
+        assert!(Keycards::from_str(input).is_err());
+    }
+
+    #[test]
+    fn allows() {
+        let keycards = Keycards::for_trump_suit(Spades, (0, 3), None);
+        assert!(keycards.allows(3));
+        assert!(!keycards.allows(1));
+    }
+}
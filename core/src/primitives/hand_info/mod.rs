@@ -1,7 +1,10 @@
+pub mod explanation;
+pub mod keycards;
 pub mod ranges;
 pub mod suit_quality;
 
 use crate::primitives::deal::hand::HandType;
+use crate::primitives::hand_info::keycards::Keycards;
 use crate::primitives::hand_info::ranges::{HcpRange, LengthRange, PointRange};
 use crate::primitives::hand_info::suit_quality::SuitQuality;
 use crate::primitives::Suit;
@@ -13,6 +16,7 @@ pub enum HandInfo {
     SuitQuality(Suit, SuitQuality),
     Hcp(HcpRange),
     TotalPoints(PointRange),
+    Keycards(Keycards),
 }
 
 impl std::fmt::Display for HandInfo {
@@ -23,12 +27,14 @@ impl std::fmt::Display for HandInfo {
             HandInfo::SuitQuality(suit, quality) => write!(f, "Hand's {} are {}.", suit, quality),
             HandInfo::Hcp(range) => write!(f, "Hand has {}.", range),
             HandInfo::TotalPoints(range) => write!(f, "Hand has {}.", range),
+            HandInfo::Keycards(keycards) => write!(f, "Hand has {}.", keycards),
         }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::keycards::Keycards;
     use super::ranges::HcpRange;
     use super::ranges::LengthRange;
     use super::ranges::PointRange;
@@ -47,6 +53,7 @@ mod test {
     #[test_case(HandInfo::TotalPoints(PointRange(15.0..=19.0)), "Hand has 15 to 19 points."; "15 to 19 total points")]
     #[test_case(HandInfo::SuitQuality(Spades, SuitQuality::Standing), "Hand's ♠ are standing."; "Standing Spades-suit")]
     #[test_case(HandInfo::SuitQuality(Hearts, SuitQuality::AlmostStanding), "Hand's ♥ are almost standing."; "Almost standing hearts-suit")]
+    #[test_case(HandInfo::Keycards(Keycards::for_trump_suit(Spades, (1, 4), None)), "Hand has 1 or 4 keycards for ♠."; "Keycards")]
     fn display(input: HandInfo, expected: &str) {
         let string = format!("{}", input);
         assert_eq!(string, expected);
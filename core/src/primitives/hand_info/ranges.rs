@@ -1,10 +1,15 @@
 use std::fmt::Display;
 use std::ops::RangeInclusive;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LengthRange(pub RangeInclusive<usize>);
+#[derive(Debug, Clone, PartialEq)]
 pub struct HcpRange(pub RangeInclusive<f64>);
+#[derive(Debug, Clone, PartialEq)]
 pub struct PointRange(pub RangeInclusive<f64>);
+#[derive(Debug, Clone, PartialEq)]
 pub struct WinnerRange(pub RangeInclusive<f64>);
+#[derive(Debug, Clone, PartialEq)]
 pub struct LoserRange(pub RangeInclusive<f64>);
 
 impl Display for LengthRange {
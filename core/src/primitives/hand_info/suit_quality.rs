@@ -1,6 +1,6 @@
-use strum::Display;
+use strum::{Display, EnumString};
 
-#[derive(Debug, Display, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Display, EnumString, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum SuitQuality {
     #[strum(serialize = "weak")]
     Weak,
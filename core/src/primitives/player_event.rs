@@ -1,19 +1,83 @@
 use crate::primitives::bid::Bid;
 use crate::primitives::deal::Seat;
-use crate::primitives::Card;
+use crate::primitives::hand_info::explanation::Explanation;
+use crate::primitives::{Card, Suit};
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum PlayerEvent {
     Bid(BidEvent),
     Card(CardEvent),
+    Claim(ClaimEvent),
+    Concede(ConcedeEvent),
+    ClaimResponse(ClaimResponseEvent),
+    UndoRequest(UndoRequestEvent),
+    UndoResponse(UndoResponseEvent),
+    IrregularityResponse(IrregularityResponseEvent),
+    ExposedCard(ExposedCardEvent),
+    LeadPenalty(LeadPenaltyEvent),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct BidEvent {
     pub player: Seat,
     pub bid: Bid,
+    pub alert: bool,
+    pub explanation: Option<Explanation>,
 }
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct CardEvent {
     pub player: Seat,
     pub card: Card,
 }
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ClaimEvent {
+    pub player: Seat,
+    pub tricks: usize,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ConcedeEvent {
+    pub player: Seat,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ClaimResponseEvent {
+    pub player: Seat,
+    pub accept: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UndoRequestEvent {
+    pub player: Seat,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UndoResponseEvent {
+    pub player: Seat,
+    pub accept: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IrregularityResponseEvent {
+    pub player: Seat,
+    pub accept: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ExposedCardEvent {
+    pub player: Seat,
+    pub card: Card,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LeadPenaltyEvent {
+    pub player: Seat,
+    pub penalty: LeadPenalty,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LeadPenalty {
+    RequireSuit(Suit),
+    ForbidSuit(Suit),
+    PenaltyCardsRemain,
+}
@@ -0,0 +1,93 @@
+use crate::DoubleDummyRunner;
+use bridge_buddy_core::actors::card_analyser::{AnalyseCard, CardAnalysis};
+use bridge_buddy_core::primitives::deal::Seat;
+use bridge_buddy_core::primitives::{Card, Contract, Deal};
+use itertools::Itertools;
+
+#[derive(Default)]
+pub struct DoubleDummyCardAnalyser {}
+
+impl DoubleDummyCardAnalyser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl<const N: usize> AnalyseCard<N> for DoubleDummyCardAnalyser {
+    fn analyse_card(
+        &mut self,
+        deal: &Deal<N>,
+        contract: Contract,
+        played_cards: &[Card],
+        player: Seat,
+        card: Card,
+    ) -> Option<CardAnalysis> {
+        let mut runner = DoubleDummyRunner::default();
+        let options = runner
+            .solve_card_options(*deal, contract.strain, contract.declarer + 1, played_cards)
+            .ok()?;
+        let best = options.iter().map(|&(_, tricks)| tricks).max()?;
+        let (_, tricks) = options.iter().find(|&&(option, _)| option == card)?;
+        let best_cards = options
+            .iter()
+            .filter(|&&(_, tricks)| tricks == best)
+            .map(|&(card, _)| card)
+            .sorted()
+            .rev()
+            .collect();
+        Some(CardAnalysis {
+            player,
+            card,
+            trick_number: played_cards.len() / 4 + 1,
+            tricks_lost: best - tricks,
+            best_cards,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DoubleDummyCardAnalyser;
+    use crate::DoubleDummyRunner;
+    use bridge_buddy_core::actors::card_analyser::AnalyseCard;
+    use bridge_buddy_core::primitives::deal::Seat;
+    use bridge_buddy_core::primitives::{Card, Contract, Deal};
+    use std::str::FromStr;
+
+    const PLAYED_CARDS: [&str; 40] = [
+        "C2", "C7", "CK", "C3", "CJ", "S6", "C4", "C8", "D4", "D6", "D7", "DJ", "C6", "S9", "C5", "C9", "D5", "D8",
+        "D9", "D2", "CA", "S2", "ST", "CT", "DT", "D3", "DK", "H2", "H5", "H3", "H7", "H4", "DQ", "DA", "H6", "S3",
+        "S4", "SQ", "H8", "S8",
+    ];
+
+    #[test]
+    fn last_three_tricks() {
+        let deal: Deal<13> = Deal::from_u64_seed(9000);
+        let contract = Contract::from_str("N4S").unwrap();
+        let played_cards = PLAYED_CARDS.map(|card| Card::from_str(card).unwrap());
+        let mut runner = DoubleDummyRunner::default();
+        let options = runner
+            .solve_card_options(deal, contract.strain, contract.declarer + 1, &played_cards)
+            .unwrap();
+        let best = options.iter().map(|&(_, tricks)| tricks).max().unwrap();
+
+        for (card, tricks) in options {
+            let analysis = DoubleDummyCardAnalyser::new()
+                .analyse_card(&deal, contract, &played_cards, Seat::North, card)
+                .unwrap();
+            assert_eq!(analysis.trick_number, 11);
+            assert_eq!(analysis.tricks_lost, best - tricks);
+            assert_eq!(analysis.best_cards.contains(&card), tricks == best);
+        }
+    }
+
+    #[test]
+    fn card_already_played() {
+        let deal: Deal<13> = Deal::from_u64_seed(9000);
+        let contract = Contract::from_str("N4S").unwrap();
+        let played_cards = PLAYED_CARDS.map(|card| Card::from_str(card).unwrap());
+        let analysis =
+            DoubleDummyCardAnalyser::new().analyse_card(&deal, contract, &played_cards, Seat::North, played_cards[0]);
+        assert_eq!(analysis, None);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::DoubleDummyRunner;
+use bridge_buddy_core::actors::claim_verifier::VerifyClaim;
+use bridge_buddy_core::game::game_phase_states::CardPlayState;
+use bridge_buddy_core::primitives::game_event::ClaimEvent;
+use bridge_buddy_core::primitives::Deal;
+
+#[derive(Default)]
+pub struct DoubleDummyClaimVerifier {}
+
+impl DoubleDummyClaimVerifier {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl<const N: usize> VerifyClaim<N> for DoubleDummyClaimVerifier {
+    fn verify_claim(&mut self, deal: &Deal<N>, state: &CardPlayState<N>, claim: ClaimEvent) -> bool {
+        let trick_manager = &state.trick_manager;
+        let mut runner = DoubleDummyRunner::default();
+        let tricks_for_next_to_play = runner.solve_position(
+            *deal,
+            state.contract.strain,
+            state.declarer() + 1,
+            trick_manager.played_cards(),
+        );
+        match tricks_for_next_to_play {
+            Ok(tricks) => {
+                let tricks_for_claimer = match claim.player.same_axis(&trick_manager.next_to_play()) {
+                    true => tricks,
+                    false => trick_manager.tricks_left() - tricks,
+                };
+                tricks_for_claimer >= claim.tricks
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DoubleDummyClaimVerifier;
+    use crate::DoubleDummyRunner;
+    use bridge_buddy_core::actors::game_manager::GameManager;
+    use bridge_buddy_core::primitives::bid::Bid;
+    use bridge_buddy_core::primitives::contract::Strain;
+    use bridge_buddy_core::primitives::deal::Seat;
+    use bridge_buddy_core::primitives::game_event::{ClaimEvent, GameEvent};
+    use bridge_buddy_core::primitives::player_event::{BidEvent, CardEvent, PlayerEvent};
+    use bridge_buddy_core::primitives::{Card, Deal, Suit};
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    fn manager_with_three_tricks_left(deal: Deal<13>) -> GameManager {
+        let mut manager = GameManager::new_from_deal(deal);
+        manager.start_game().unwrap();
+
+        let bids = ["p", "1NT", "p", "2C", "p", "2S", "p", "4S", "p", "p", "p"];
+        for bid in bids {
+            let event = PlayerEvent::Bid(BidEvent {
+                player: manager.next_to_play().unwrap(),
+                bid: Bid::from_str(bid).unwrap(),
+                alert: false,
+                explanation: None,
+            });
+            manager.process_player_event(event).unwrap();
+        }
+
+        let cards = [
+            "C2", "C7", "CK", "C3", "CJ", "S6", "C4", "C8", "D4", "D6", "D7", "DJ", "C6", "S9", "C5", "C9", "D5", "D8",
+            "D9", "D2", "CA", "S2", "ST", "CT", "DT", "D3", "DK", "H2", "H5", "H3", "H7", "H4", "DQ", "DA", "H6", "S3",
+            "S4", "SQ", "H8", "S8",
+        ];
+        for card in cards {
+            let event = PlayerEvent::Card(CardEvent {
+                player: manager.next_to_play().unwrap(),
+                card: Card::from_str(card).unwrap(),
+            });
+            manager.process_player_event(event).unwrap();
+        }
+        manager
+    }
+
+    #[test_case(0, true; "Makeable")]
+    #[test_case(1, false; "One too many")]
+    fn verify_claim(overclaim: usize, valid: bool) {
+        let deal = Deal::from_u64_seed(9000);
+        let mut manager = manager_with_three_tricks_left(deal);
+
+        let played_cards = manager
+            .history()
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::Card(card_event) => Some(card_event.card),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let claimers_tricks = DoubleDummyRunner::default()
+            .solve_position(deal, Strain::Trump(Suit::Spades), Seat::East, &played_cards)
+            .unwrap();
+
+        let claim = ClaimEvent {
+            player: manager.next_to_play().unwrap(),
+            tricks: claimers_tricks + overclaim,
+        };
+        manager.process_player_event(PlayerEvent::Claim(claim)).unwrap();
+        assert_eq!(manager.pending_claim(), Some(claim));
+
+        manager
+            .verify_pending_claim(&mut DoubleDummyClaimVerifier::new())
+            .unwrap();
+
+        assert_eq!(manager.pending_claim(), None);
+        let game_ended = manager
+            .history()
+            .iter()
+            .any(|event| matches!(event, GameEvent::GameEnded(_)));
+        assert_eq!(game_ended, valid);
+    }
+
+    #[test_case(0, true; "Makeable")]
+    #[test_case(1, false; "One too many")]
+    fn verify_claim_with_three_cards_per_hand(overclaim: usize, valid: bool) {
+        let deal: Deal<3> = Deal::from_u64_seed(9000);
+        let mut manager = GameManager::new_from_deal(deal);
+        manager.start_game().unwrap();
+        for bid in ["1NT", "p", "p", "p"] {
+            let event = PlayerEvent::Bid(BidEvent {
+                player: manager.next_to_play().unwrap(),
+                bid: Bid::from_str(bid).unwrap(),
+                alert: false,
+                explanation: None,
+            });
+            manager.process_player_event(event).unwrap();
+        }
+        let leader = manager.next_to_play().unwrap();
+        let lead = *deal.hand_of(leader).cards().next().unwrap();
+        let event = PlayerEvent::Card(CardEvent {
+            player: leader,
+            card: lead,
+        });
+        manager.process_player_event(event).unwrap();
+
+        let claimers_tricks = DoubleDummyRunner::default()
+            .solve_position(deal, Strain::NoTrump, leader, &[lead])
+            .unwrap();
+        let claim = ClaimEvent {
+            player: manager.next_to_play().unwrap(),
+            tricks: claimers_tricks + overclaim,
+        };
+        manager.process_player_event(PlayerEvent::Claim(claim)).unwrap();
+        manager
+            .verify_pending_claim(&mut DoubleDummyClaimVerifier::new())
+            .unwrap();
+
+        let game_ended = manager
+            .history()
+            .iter()
+            .any(|event| matches!(event, GameEvent::GameEnded(_)));
+        assert_eq!(game_ended, valid);
+    }
+}
@@ -1,7 +1,10 @@
-use bridge_buddy_core::primitives::contract::Strain;
+use bridge_buddy_core::game::scoring::{ScoreCalculator, ScorePoints};
+use bridge_buddy_core::primitives::contract::strain::STRAIN_ARRAY;
+use bridge_buddy_core::primitives::contract::{Level, State, Strain};
 use bridge_buddy_core::primitives::deal::seat::SEAT_ARRAY;
-use bridge_buddy_core::primitives::deal::Seat;
-use bridge_buddy_core::primitives::Suit;
+use bridge_buddy_core::primitives::deal::{Seat, Vulnerability};
+use bridge_buddy_core::primitives::game_result::GameResult;
+use bridge_buddy_core::primitives::{Contract, Suit};
 use std::fmt::{Display, Formatter};
 
 pub struct DoubleDummyResult {
@@ -52,6 +55,50 @@ impl DoubleDummyResult {
 
         self.max_tricks[index] = tricks
     }
+
+    // from the point of view of `seat`'s partnership
+    pub fn score_for(&self, contract: Contract, seat: Seat, vulnerability: Vulnerability) -> ScorePoints {
+        let tricks = self.get_tricks_for_declarer_in_strain(contract.declarer, contract.strain);
+        let result = GameResult::calculate_game_result(contract, tricks);
+        let score = ScoreCalculator::score_result(result, vulnerability);
+        match seat {
+            Seat::North | Seat::South => score,
+            Seat::East | Seat::West => score * -1_isize,
+        }
+    }
+
+    // undoubled, ignoring the opponents
+    pub fn best_contract_for(&self, seat: Seat, vulnerability: Vulnerability) -> Option<(Contract, ScorePoints)> {
+        [seat, seat.partner()]
+            .into_iter()
+            .flat_map(|declarer| STRAIN_ARRAY.map(|strain| (declarer, strain)))
+            .filter_map(|(declarer, strain)| {
+                let tricks = self.get_tricks_for_declarer_in_strain(declarer, strain);
+                let level = Self::highest_level(tricks)?;
+                let contract = Contract {
+                    level,
+                    strain,
+                    state: State::Passed,
+                    declarer,
+                };
+                Some((contract, self.score_for(contract, seat, vulnerability)))
+            })
+            .max_by_key(|(_, score)| *score)
+    }
+
+    fn highest_level(tricks: usize) -> Option<Level> {
+        let mut level = Level::One;
+        if tricks < level.expected_tricks() {
+            return None;
+        }
+        while let Ok(next) = level.next() {
+            if tricks < next.expected_tricks() {
+                break;
+            }
+            level = next;
+        }
+        Some(level)
+    }
 }
 
 impl Default for DoubleDummyResult {
@@ -78,6 +125,9 @@ impl Display for DoubleDummyResult {
 #[cfg(test)]
 mod test {
     use super::DoubleDummyResult;
+    use bridge_buddy_core::primitives::deal::{Seat, Vulnerability};
+    use bridge_buddy_core::primitives::Contract;
+    use std::str::FromStr;
     use test_case::test_case;
 
     #[test_case([0,1,2,3,4,1,2,3,4,5,2,3,4,5,6,3,4,5,6,7], "  NT ♠S ♥H ♦D ♣C\nN  4  3  2  1  0 \nE  5  4  3  2  1 \nS  6  5  4  3  2 \nW  7  6  5  4  3 \n")]
@@ -86,4 +136,20 @@ mod test {
         let str = format!("{}", ddr);
         assert_eq!(str, expected)
     }
+
+    #[test_case(Seat::North, Vulnerability::None, Some(("S3NT", 400)); "Game in NT")]
+    #[test_case(Seat::East, Vulnerability::All, Some(("W1NT", 90)); "Only a part-score")]
+    #[test_case(Seat::East, Vulnerability::None, None; "Nothing makes")]
+    fn best_contract_for(seat: Seat, vulnerability: Vulnerability, expected: Option<(&str, isize)>) {
+        let max_tricks = match expected {
+            None => [0; 20],
+            Some(_) => [8, 6, 6, 6, 8, 6, 6, 6, 6, 6, 8, 7, 7, 7, 9, 6, 6, 6, 6, 7],
+        };
+        let ddr = DoubleDummyResult { max_tricks };
+        let best = ddr
+            .best_contract_for(seat, vulnerability)
+            .map(|(contract, score)| (contract, format!("{}", score)));
+        let expected = expected.map(|(contract, score)| (Contract::from_str(contract).unwrap(), format!("{}", score)));
+        assert_eq!(best, expected);
+    }
 }
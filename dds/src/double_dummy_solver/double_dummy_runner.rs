@@ -1,16 +1,18 @@
 use crate::dds_config::DdsConfig;
 use crate::double_dummy_solver::dds_statistics::DdsStatistics;
 use crate::move_generator::MoveGenerator;
+use crate::state::virtual_card::VirtualCard;
 use crate::state::VirtualState;
 use crate::transposition_table::{TTKey, TranspositionTable};
 use crate::trick_estimations::{
     losing_tricks_for_leader, quick_tricks_for_leader, quick_tricks_for_second_hand, EstimationState,
 };
 use bridge_buddy_core::engine::hand_evaluation::ForumDPlus2015Evaluator;
+use bridge_buddy_core::error::BBError;
 use bridge_buddy_core::primitives::contract::Strain;
 use bridge_buddy_core::primitives::deal::seat::SEAT_ARRAY;
 use bridge_buddy_core::primitives::deal::Seat;
-use bridge_buddy_core::primitives::Deal;
+use bridge_buddy_core::primitives::{Card, Deal, Suit};
 
 use std::cmp::min;
 
@@ -82,6 +84,121 @@ impl DoubleDummyRunner {
         at_least
     }
 
+    // tricks left (including the current one) for the side next to play
+    pub fn solve_position<const N: usize>(
+        &mut self,
+        deal: Deal<N>,
+        strain: Strain,
+        opening_leader: Seat,
+        played_cards: &[Card],
+    ) -> Result<usize, BBError> {
+        let mut state = Self::replay(deal, strain, opening_leader, played_cards)?;
+        let player = state.next_to_play();
+        let total = self.solve_state(&mut state);
+        Ok(total - state.tricks_won_by_axis(player))
+    }
+
+    // tricks left (including the current one) for the player's side after each card
+    pub fn solve_card_options<const N: usize>(
+        &mut self,
+        deal: Deal<N>,
+        strain: Strain,
+        opening_leader: Seat,
+        played_cards: &[Card],
+    ) -> Result<Vec<(Card, usize)>, BBError> {
+        let mut state = Self::replay(deal, strain, opening_leader, played_cards)?;
+        let player = state.next_to_play();
+        let tricks_won = state.tricks_won_by_axis(player);
+
+        let mut options = Vec::new();
+        let mut solved: Vec<(VirtualCard, usize)> = Vec::new();
+        for card in state.valid_absolute_moves() {
+            let virtual_card = state.absolute_to_virtual(&card);
+            // touching cards of the same hand are equivalent
+            let equivalent = virtual_card.and_then(|virtual_card| {
+                solved
+                    .iter()
+                    .find(|(other, _)| other.suit == virtual_card.suit && other.rank.touches(&virtual_card.rank))
+                    .map(|&(_, total)| total)
+            });
+            let total = match equivalent {
+                Some(total) => total,
+                None => {
+                    state.play_absolute(&card)?;
+                    let total = match state.next_to_play().same_axis(&player) {
+                        true => self.solve_state(&mut state),
+                        false => N - self.solve_state(&mut state),
+                    };
+                    state.undo();
+                    total
+                }
+            };
+            if let Some(virtual_card) = virtual_card {
+                solved.push((virtual_card, total));
+            }
+            options.push((card, total - tricks_won));
+        }
+        Ok(options)
+    }
+
+    fn replay<const N: usize>(
+        deal: Deal<N>,
+        strain: Strain,
+        opening_leader: Seat,
+        played_cards: &[Card],
+    ) -> Result<VirtualState<N>, BBError> {
+        let mut state = VirtualState::new(deal.hands, opening_leader, Self::trumps(strain));
+        for card in played_cards {
+            state.play_absolute(card)?;
+        }
+        Ok(state)
+    }
+
+    fn trumps(strain: Strain) -> Option<Suit> {
+        match strain {
+            Strain::Trump(suit) => Some(suit),
+            _ => None,
+        }
+    }
+
+    // includes the tricks already won
+    fn solve_state<const N: usize>(&mut self, state: &mut VirtualState<N>) -> usize {
+        let player = state.next_to_play();
+
+        if state.tricks_left() == 0 {
+            return state.tricks_won_by_axis(player);
+        }
+
+        if state.is_last_trick() && !state.player_is_leading() {
+            // only forced cards are left
+            let cards_to_play = 4 - state.count_cards_in_current_trick();
+            for _ in 0..cards_to_play {
+                let card = state.valid_absolute_moves()[0];
+                state.play_absolute(&card).unwrap();
+            }
+            let total = state.tricks_won_by_axis(player);
+            for _ in 0..cards_to_play {
+                state.undo();
+            }
+            return total;
+        }
+
+        let mut at_least = state.tricks_won_by_axis(player);
+        let mut at_most = at_least + state.tricks_left();
+
+        while at_least < at_most {
+            let estimate = (at_least + at_most).div_ceil(2);
+            let score = self.score_node(state, estimate);
+            if score >= estimate {
+                at_least = score;
+            } else {
+                at_most = score;
+            }
+        }
+        self.current_node_tt_key = None;
+        at_least
+    }
+
     fn get_initial_estimate<const N: usize>(deal: Deal<N>, strain: Strain, opening_leader: Seat) -> usize {
         let my_hand = deal.hand_of(opening_leader);
         let partners_hand = deal.hand_of(opening_leader + 2);
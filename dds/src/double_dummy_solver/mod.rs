@@ -153,6 +153,55 @@ mod test {
         assert_eq!(dds_result.max_tricks, expected);
     }
 
+    #[test_case( 30u64, Strain::NoTrump; "NoTrump A")]
+    #[test_case( 31u64, Strain::NoTrump; "NoTrump B")]
+    #[test_case( 32u64, Strain::Trump(Suit::Spades); "Spades A")]
+    #[test_case( 33u64, Strain::Trump(Suit::Hearts); "Hearts A")]
+    fn solve_position_along_best_line(seed: u64, strain: Strain) {
+        let deal: Deal<5> = Deal::from_u64_seed(seed);
+        let opening_leader = Seat::West;
+
+        let mut runner = DoubleDummyRunner::default();
+        let defenders_tricks = runner.solve_initial_position(deal, strain, opening_leader);
+
+        let mut runner = DoubleDummyRunner::default();
+        let mut played_cards = Vec::new();
+        assert_eq!(
+            runner
+                .solve_position(deal, strain, opening_leader, &played_cards)
+                .unwrap(),
+            defenders_tricks
+        );
+
+        for _ in 0..20 {
+            let remaining = runner
+                .solve_position(deal, strain, opening_leader, &played_cards)
+                .unwrap();
+            let options = runner
+                .solve_card_options(deal, strain, opening_leader, &played_cards)
+                .unwrap();
+            let (best_card, best_tricks) = options.into_iter().max_by_key(|(_, tricks)| *tricks).unwrap();
+            assert_eq!(best_tricks, remaining);
+            played_cards.push(best_card);
+        }
+    }
+
+    #[test_case( 34u64, Strain::NoTrump; "NoTrump")]
+    #[test_case( 35u64, Strain::Trump(Suit::Clubs); "Clubs")]
+    fn card_options_match_positions(seed: u64, strain: Strain) {
+        let deal: Deal<5> = Deal::from_u64_seed(seed);
+        let opening_leader = Seat::West;
+
+        let mut runner = DoubleDummyRunner::default();
+        let options = runner.solve_card_options(deal, strain, opening_leader, &[]).unwrap();
+        assert_eq!(options.len(), 5);
+        for (card, tricks) in options {
+            // after the lead, it's the opponents' turn
+            let opponents_tricks = runner.solve_position(deal, strain, opening_leader, &[card]).unwrap();
+            assert_eq!(tricks, 5 - opponents_tricks);
+        }
+    }
+
     #[ignore]
     #[test]
     fn node_count_trumps() {
@@ -1,13 +1,23 @@
 extern crate core;
 
+mod card_analyser;
 pub mod card_manager;
+mod claim_verifier;
 mod dds_config;
+mod monte_carlo_card_play_engine;
 mod move_generator;
+mod post_mortem;
 mod state;
+mod suit_combination;
 mod transposition_table;
 
 mod double_dummy_solver;
 mod trick_estimations;
 
+pub use card_analyser::DoubleDummyCardAnalyser;
+pub use claim_verifier::DoubleDummyClaimVerifier;
 pub use double_dummy_solver::double_dummy_runner::DoubleDummyRunner;
 pub use double_dummy_solver::DoubleDummySolver;
+pub use monte_carlo_card_play_engine::MonteCarloCardPlayEngine;
+pub use post_mortem::{CostlyCard, PostMortem};
+pub use suit_combination::{Split, SuitCombination, SuitPlay};
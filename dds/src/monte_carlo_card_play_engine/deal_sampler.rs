@@ -0,0 +1,230 @@
+use bridge_buddy_core::engine::card_play_engine::signals::Signal;
+use bridge_buddy_core::engine::engine_state::HandDescription;
+use bridge_buddy_core::primitives::card::rank::RANK_ARRAY;
+use bridge_buddy_core::primitives::card::suit::SUIT_ARRAY;
+use bridge_buddy_core::primitives::deal::seat::SEAT_ARRAY;
+use bridge_buddy_core::primitives::deal::{Hand, Seat};
+use bridge_buddy_core::primitives::{Card, Deal, Suit};
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// deals the unseen cards to the hidden hands so that they agree with everything known
+#[derive(Debug, Clone)]
+pub struct DealSampler {
+    known_hands: [Option<Hand<13>>; 4],
+    played_cards: [Vec<Card>; 4],
+    voids: [[bool; 4]; 4],
+    descriptions: [Option<HandDescription>; 4],
+    signals: [Vec<Signal>; 4],
+}
+
+impl DealSampler {
+    pub fn new() -> Self {
+        Self {
+            known_hands: [None; 4],
+            played_cards: Default::default(),
+            voids: [[false; 4]; 4],
+            descriptions: Default::default(),
+            signals: Default::default(),
+        }
+    }
+
+    pub fn add_known_hand(&mut self, seat: Seat, hand: Hand<13>) {
+        self.known_hands[seat as usize] = Some(hand);
+    }
+
+    // not following suit shows a void
+    pub fn add_played_card(&mut self, seat: Seat, card: Card, suit_led: Option<Suit>) {
+        self.played_cards[seat as usize].push(card);
+        if let Some(suit) = suit_led {
+            if card.suit != suit {
+                self.voids[seat as usize][suit as usize] = true;
+            }
+        }
+    }
+
+    pub fn add_description(&mut self, seat: Seat, description: HandDescription) {
+        self.descriptions[seat as usize] = Some(description);
+    }
+
+    // e.g. when no deal agrees with the auction after a misbid
+    pub fn forget_descriptions(&mut self) {
+        self.descriptions = Default::default();
+    }
+
+    pub fn add_signal(&mut self, seat: Seat, signal: Signal) {
+        self.signals[seat as usize].push(signal);
+    }
+
+    // e.g. when a spot card was misread and no deal agrees with the signals
+    pub fn forget_signals(&mut self) {
+        self.signals = Default::default();
+    }
+
+    pub fn hidden_seats(&self) -> Vec<Seat> {
+        SEAT_ARRAY
+            .into_iter()
+            .filter(|&seat| self.known_hands[seat as usize].is_none())
+            .collect()
+    }
+
+    // `None` if the random choices contradicted the auction or the signals
+    pub fn sample(&self, rng: &mut impl Rng) -> Option<Deal<13>> {
+        let hidden_seats = self.hidden_seats();
+        let mut missing = SEAT_ARRAY.map(|seat| match self.known_hands[seat as usize] {
+            Some(_) => 0,
+            None => 13 - self.played_cards[seat as usize].len(),
+        });
+        let mut cards = self.unseen_cards();
+        cards.shuffle(rng);
+        // cards only one of the hidden hands can hold are dealt first
+        cards.sort_by_key(|card| {
+            hidden_seats
+                .iter()
+                .filter(|&&seat| !self.voids[seat as usize][card.suit as usize])
+                .count()
+        });
+
+        let mut hands = self.played_cards.clone();
+        for card in cards {
+            let holders = hidden_seats
+                .iter()
+                .copied()
+                .filter(|&seat| missing[seat as usize] > 0 && !self.voids[seat as usize][card.suit as usize])
+                .collect_vec();
+            let total: usize = holders.iter().map(|&seat| missing[seat as usize]).sum();
+            if total == 0 {
+                return None;
+            }
+            // the more cards a hand is missing, the likelier it holds this one
+            let mut pick = rng.gen_range(0..total);
+            for seat in holders {
+                if pick < missing[seat as usize] {
+                    hands[seat as usize].push(card);
+                    missing[seat as usize] -= 1;
+                    break;
+                }
+                pick -= missing[seat as usize];
+            }
+        }
+
+        let starting_hands: [Hand<13>; 4] = SEAT_ARRAY
+            .into_iter()
+            .map(|seat| match self.known_hands[seat as usize] {
+                Some(hand) => Some(hand),
+                None => Hand::from_cards(&hands[seat as usize]).ok(),
+            })
+            .collect::<Option<Vec<_>>>()?
+            .try_into()
+            .ok()?;
+        let fits_description = |seat: Seat| {
+            let hand = &starting_hands[seat as usize];
+            let fits_auction = match &self.descriptions[seat as usize] {
+                Some(description) => description.admits(hand),
+                None => true,
+            };
+            fits_auction && self.signals[seat as usize].iter().all(|signal| signal.admits(hand))
+        };
+        match hidden_seats.into_iter().all(fits_description) {
+            true => Some(Deal::from_hands(starting_hands)),
+            false => None,
+        }
+    }
+
+    fn unseen_cards(&self) -> Vec<Card> {
+        SUIT_ARRAY
+            .iter()
+            .cartesian_product(RANK_ARRAY.iter())
+            .map(|(&suit, &rank)| Card { suit, rank })
+            .filter(|card| {
+                !self.known_hands.iter().flatten().any(|hand| hand.contains(card))
+                    && !self.played_cards.iter().flatten().any(|played| played == card)
+            })
+            .collect()
+    }
+}
+
+impl Default for DealSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DealSampler;
+    use bridge_buddy_core::engine::card_play_engine::signals::Signal;
+    use bridge_buddy_core::engine::engine_state::HandDescription;
+    use bridge_buddy_core::engine::hand_evaluation::ForumDPlus2015Evaluator;
+    use bridge_buddy_core::primitives::deal::{Hand, Seat};
+    use bridge_buddy_core::primitives::hand_info::explanation::Explanation;
+    use bridge_buddy_core::primitives::{Card, Suit};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::str::FromStr;
+
+    fn card(card: &str) -> Card {
+        Card::from_str(card).unwrap()
+    }
+
+    #[test]
+    fn sample_agrees_with_play() {
+        let mut sampler = DealSampler::new();
+        let south = Hand::from_str("S:AKQ2,H:AK2,D:AK2,C:AK2").unwrap();
+        let north = Hand::from_str("S:543,H:543,D:543,C:6543").unwrap();
+        sampler.add_known_hand(Seat::South, south);
+        sampler.add_known_hand(Seat::North, north);
+        sampler.add_played_card(Seat::West, card("H7"), None);
+        sampler.add_played_card(Seat::North, card("H3"), Some(Suit::Hearts));
+        sampler.add_played_card(Seat::East, card("C7"), Some(Suit::Hearts));
+        sampler.add_played_card(Seat::South, card("HA"), Some(Suit::Hearts));
+
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for _ in 0..20 {
+            let deal = sampler.sample(&mut rng).unwrap();
+            assert_eq!(*deal.hand_of(Seat::South), south);
+            assert_eq!(*deal.hand_of(Seat::North), north);
+            assert!(deal.hand_of(Seat::West).contains(&card("H7")));
+            assert!(deal.hand_of(Seat::East).contains(&card("C7")));
+            assert_eq!(deal.hand_of(Seat::East).length_in(Suit::Hearts), 0);
+            assert_eq!(deal.hand_of(Seat::West).length_in(Suit::Hearts), 7);
+        }
+    }
+
+    #[test]
+    fn sample_agrees_with_auction() {
+        let mut sampler = DealSampler::new();
+        sampler.add_known_hand(Seat::South, Hand::from_str("S:AKQ2,H:AK2,D:32,C:5432").unwrap());
+        let mut description = HandDescription::unknown();
+        description.narrow(&Explanation::from_str("Hand has 6 to 13 cards in ♥. Hand has 5 to 10 hcp.").unwrap());
+        sampler.add_description(Seat::West, description);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let deals = (0..1000).filter_map(|_| sampler.sample(&mut rng)).collect::<Vec<_>>();
+        assert!(!deals.is_empty());
+        for deal in deals {
+            let west = deal.hand_of(Seat::West);
+            assert!(west.length_in(Suit::Hearts) >= 6);
+            assert!((5.0..=10.0).contains(&ForumDPlus2015Evaluator::hcp(west)));
+        }
+    }
+
+    #[test]
+    fn sample_agrees_with_signals() {
+        let mut sampler = DealSampler::new();
+        sampler.add_known_hand(Seat::South, Hand::from_str("S:AKQ2,H:AK2,D:32,C:5432").unwrap());
+        sampler.add_known_hand(Seat::North, Hand::from_str("S:543,H:543,D:7654,C:876").unwrap());
+        sampler.add_signal(Seat::West, Signal::EvenCount(Suit::Diamonds));
+        sampler.add_signal(Seat::West, Signal::Encouraging(Suit::Hearts));
+
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let deals = (0..100).filter_map(|_| sampler.sample(&mut rng)).collect::<Vec<_>>();
+        assert!(!deals.is_empty());
+        for deal in deals {
+            let west = deal.hand_of(Seat::West);
+            assert!(west.length_in(Suit::Diamonds).is_multiple_of(2));
+            assert!(west.contains(&card("HQ")));
+        }
+    }
+}
@@ -0,0 +1,590 @@
+mod deal_sampler;
+
+use deal_sampler::DealSampler;
+
+use crate::DoubleDummyRunner;
+use bridge_buddy_core::engine::bidding_engine::SelectBid;
+use bridge_buddy_core::engine::card_play_engine::opening_leads::OpeningLeads;
+use bridge_buddy_core::engine::card_play_engine::signals::Signals;
+use bridge_buddy_core::engine::card_play_engine::SelectCard;
+use bridge_buddy_core::engine::engine_state::EngineState;
+use bridge_buddy_core::engine::reason::Reason;
+use bridge_buddy_core::engine::subjective_game_view::subjectiviser::Subjectiviser;
+use bridge_buddy_core::engine::subjective_game_view::{SubjectiveGamePhaseStateView, SubjectiveSeat};
+use bridge_buddy_core::game::game_phase_states::{CardPlayState, OpeningLeadState};
+use bridge_buddy_core::game::scoring::{ScoreCalculator, ScorePoints};
+use bridge_buddy_core::game::trick_manager::TrickManager;
+use bridge_buddy_core::primitives::bid::Bid;
+use bridge_buddy_core::primitives::contract::Strain;
+use bridge_buddy_core::primitives::deal::axis::Axis;
+use bridge_buddy_core::primitives::deal::{Seat, Vulnerability};
+use bridge_buddy_core::primitives::game_event::ClaimEvent;
+use bridge_buddy_core::primitives::game_result::GameResult;
+use bridge_buddy_core::primitives::hand_info::explanation::Explanation;
+use bridge_buddy_core::primitives::{Card, Contract, Deal, Suit};
+use itertools::Itertools;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+use std::cell::RefCell;
+
+// plays the card with the best average over sampled deals, each solved double dummy
+pub struct MonteCarloCardPlayEngine {
+    seat: Seat,
+    samples: usize,
+    rng: RefCell<ChaCha8Rng>,
+    interpreter: Option<Box<dyn SelectBid>>,
+    opening_leads: Option<OpeningLeads>,
+    signals: Signals,
+}
+
+impl MonteCarloCardPlayEngine {
+    pub const DEFAULT_SAMPLES: usize = 20;
+    // deals tried per sample before giving up on the auction, which might have been a misbid
+    const ATTEMPTS_PER_SAMPLE: usize = 200;
+    // solving every sample for a claim before each lead only pays off near the end
+    const CLAIM_TRICKS_LEFT: usize = 3;
+
+    pub fn new(seat: Seat) -> Self {
+        Self {
+            seat,
+            samples: Self::DEFAULT_SAMPLES,
+            rng: RefCell::new(ChaCha8Rng::from_entropy()),
+            interpreter: None,
+            opening_leads: None,
+            signals: Signals::default(),
+        }
+    }
+
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = RefCell::new(ChaCha8Rng::seed_from_u64(seed));
+        self
+    }
+
+    // without an interpreter, only the explanations of alerted calls are taken into account
+    pub fn with_interpreter<T: SelectBid + 'static>(mut self, interpreter: T) -> Self {
+        self.interpreter = Some(Box::new(interpreter));
+        self
+    }
+
+    pub fn with_opening_leads(mut self, opening_leads: OpeningLeads) -> Self {
+        self.opening_leads = Some(opening_leads);
+        self
+    }
+
+    pub fn with_signals(mut self, signals: Signals) -> Self {
+        self.signals = signals;
+        self
+    }
+
+    fn pick_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> (Card, Reason) {
+        let legal_cards = state.legal_cards();
+        let contract = state.contract().unwrap();
+        if let Some(opening_leads) = &self.opening_leads {
+            let dealer = Subjectiviser::new(self.seat).objective_seat(state.dealer());
+            let lead = opening_leads.find_lead(&state.my_starting_hand().unwrap(), contract, dealer, &state.bids());
+            return (
+                lead,
+                Reason::from_rule("Textbook opening lead against the contract and auction"),
+            );
+        }
+        let mut sampler = DealSampler::new();
+        sampler.add_known_hand(self.seat, state.my_starting_hand().unwrap());
+        self.add_descriptions(&mut sampler, state.dealer(), &state.bids(), &state.explanations());
+
+        let trick_manager = TrickManager::new(self.seat, trumps(contract.strain));
+        let vulnerability = Subjectiviser::new(self.seat).objective_vulnerability(state.vulnerability());
+        let (scores, samples) = self.score_cards(&sampler, &trick_manager, contract, vulnerability, &legal_cards);
+        (best_cards(&scores)[0], sample_reason(&scores, samples))
+    }
+
+    fn pick_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> (Card, Reason) {
+        let legal_cards = state.legal_cards(state.next_to_play());
+        if legal_cards.len() == 1 {
+            return (legal_cards[0], Reason::from_rule("Only legal card"));
+        }
+        let contract = state.contract().unwrap();
+        let (sampler, trick_manager) = self.card_play_sampler(&state, contract);
+        let vulnerability = Subjectiviser::new(self.seat).objective_vulnerability(state.vulnerability());
+        let (scores, samples) = self.score_cards(&sampler, &trick_manager, contract, vulnerability, &legal_cards);
+        let best_cards = best_cards(&scores);
+        let reason = sample_reason(&scores, samples);
+        let signal = self.signals.choose_card(
+            &best_cards,
+            &state.my_starting_hand().unwrap(),
+            &state.dummys_starting_hand().unwrap(),
+            contract,
+            &state.played_cards(),
+        );
+        match signal {
+            Some(card) => (
+                card,
+                reason.with_detail("Signals to partner with one of the equally good cards.".into()),
+            ),
+            None => (best_cards[0], reason),
+        }
+    }
+
+    fn try_claim(&self, state: &SubjectiveGamePhaseStateView<CardPlayState>) -> Option<usize> {
+        if state.suit_to_follow().is_some() {
+            return None;
+        }
+        if state.tricks_left() > Self::CLAIM_TRICKS_LEFT {
+            return None;
+        }
+        let contract = state.contract()?;
+        let (sampler, trick_manager) = self.card_play_sampler(state, contract);
+        let tricks_left = trick_manager.tricks_left();
+        let claimer = trick_manager.next_to_play();
+        let makes_claim = |tricks: usize| tricks == tricks_left;
+        self.claim_holds(&sampler, &trick_manager, contract, claimer, makes_claim)
+            .then_some(tricks_left)
+    }
+
+    fn check_claim(&self, state: &SubjectiveGamePhaseStateView<CardPlayState>, claim: ClaimEvent) -> bool {
+        let Some(contract) = state.contract() else {
+            return false;
+        };
+        let (sampler, trick_manager) = self.card_play_sampler(state, contract);
+        let makes_claim = |tricks: usize| tricks >= claim.tricks;
+        self.claim_holds(&sampler, &trick_manager, contract, claim.player, makes_claim)
+    }
+
+    fn claim_holds(
+        &self,
+        sampler: &DealSampler,
+        trick_manager: &TrickManager<13>,
+        contract: Contract,
+        claimer: Seat,
+        makes_claim: impl Fn(usize) -> bool + Sync,
+    ) -> bool {
+        let deals = self.sample_deals(sampler);
+        let played_cards = trick_manager.played_cards();
+        let next_to_play = trick_manager.next_to_play();
+        let tricks_left = trick_manager.tricks_left();
+        deals.par_iter().all(|&deal| {
+            let mut runner = DoubleDummyRunner::default();
+            match runner.solve_position(deal, contract.strain, contract.declarer + 1, played_cards) {
+                Ok(tricks) if claimer.same_axis(&next_to_play) => makes_claim(tricks),
+                Ok(tricks) => makes_claim(tricks_left - tricks),
+                Err(_) => false,
+            }
+        })
+    }
+
+    fn card_play_sampler(
+        &self,
+        state: &SubjectiveGamePhaseStateView<CardPlayState>,
+        contract: Contract,
+    ) -> (DealSampler, TrickManager<13>) {
+        let mut sampler = DealSampler::new();
+        sampler.add_known_hand(self.seat, state.my_starting_hand().unwrap());
+        sampler.add_known_hand(contract.declarer.partner(), state.dummys_starting_hand().unwrap());
+        let my_hand = state.my_starting_hand().unwrap();
+        let dummy = state.dummys_starting_hand().unwrap();
+        let played_cards = state.played_cards();
+        let seen = my_hand
+            .cards()
+            .chain(dummy.cards())
+            .chain(&played_cards)
+            .copied()
+            .collect::<Vec<_>>();
+        let mut trick_manager = TrickManager::new(contract.declarer + 1, trumps(contract.strain));
+        for (index, &card) in played_cards.iter().enumerate() {
+            let player = trick_manager.next_to_play();
+            sampler.add_played_card(player, card, trick_manager.suit_to_follow());
+            if player == self.seat.partner() {
+                for signal in self.signals.read(&played_cards[..=index], contract, &dummy, &seen) {
+                    sampler.add_signal(player, signal);
+                }
+            }
+            trick_manager.play(card);
+        }
+        self.add_descriptions(&mut sampler, state.dealer(), &state.bids(), &state.explanations());
+        (sampler, trick_manager)
+    }
+
+    fn add_descriptions(
+        &self,
+        sampler: &mut DealSampler,
+        dealer: SubjectiveSeat,
+        bids: &[Bid],
+        explanations: &[Option<Explanation>],
+    ) {
+        let dealer = Subjectiviser::new(self.seat).objective_seat(dealer);
+        let mut engine_state = EngineState::new(self.seat);
+        for (index, &bid) in bids.iter().enumerate() {
+            let explanation = match (explanations[index], &self.interpreter) {
+                (Some(explanation), _) => explanation,
+                (None, Some(interpreter)) => interpreter.interpret(&bids[..index], bid),
+                (None, None) => continue,
+            };
+            engine_state.process_call(dealer + index, explanation);
+        }
+        for seat in sampler.hidden_seats() {
+            let seat_for_me = Subjectiviser::new(self.seat).subjective_seat(seat);
+            sampler.add_description(seat, engine_state.hand_description(seat_for_me).clone());
+        }
+    }
+
+    // drops the signals and then the auction if they can't be satisfied
+    fn sample_deals(&self, sampler: &DealSampler) -> Vec<Deal<13>> {
+        let mut rng = self.rng.borrow_mut();
+        let mut sample = |sampler: &DealSampler| {
+            (0..self.samples * Self::ATTEMPTS_PER_SAMPLE)
+                .filter_map(|_| sampler.sample(&mut *rng))
+                .take(self.samples)
+                .collect::<Vec<_>>()
+        };
+        let deals = sample(sampler);
+        if !deals.is_empty() {
+            return deals;
+        }
+        let mut sampler = sampler.clone();
+        sampler.forget_signals();
+        let deals = sample(&sampler);
+        if !deals.is_empty() {
+            return deals;
+        }
+        sampler.forget_descriptions();
+        sample(&sampler)
+    }
+
+    // from low to high, with the number of solved samples
+    fn score_cards(
+        &self,
+        sampler: &DealSampler,
+        trick_manager: &TrickManager<13>,
+        contract: Contract,
+        vulnerability: Vulnerability,
+        legal_cards: &[Card],
+    ) -> (Vec<CardScore>, usize) {
+        let deals = self.sample_deals(sampler);
+        let played_cards = trick_manager.played_cards();
+        let player = trick_manager.next_to_play();
+        let tricks_won = trick_manager.tricks_won_by_axis(player);
+
+        let options = deals
+            .par_iter()
+            .filter_map(|&deal| {
+                let mut runner = DoubleDummyRunner::default();
+                runner
+                    .solve_card_options(deal, contract.strain, contract.declarer + 1, played_cards)
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+
+        let mut cards = legal_cards.to_vec();
+        cards.sort_by_key(|card| card.rank);
+        let scores = cards
+            .into_iter()
+            .map(|card| {
+                let declarers_tricks = options
+                    .iter()
+                    .flatten()
+                    .filter(|(option, _)| *option == card)
+                    .map(|&(_, tricks)| match player.same_axis(&contract.declarer) {
+                        true => tricks_won + tricks,
+                        false => 13 - tricks_won - tricks,
+                    })
+                    .collect::<Vec<_>>();
+                CardScore {
+                    card,
+                    total: declarers_tricks
+                        .iter()
+                        .map(|&tricks| self.score_for_me(contract, tricks, vulnerability))
+                        .fold(ScoreCalculator::NO_SCORE, |total, score| total + score),
+                    declarers_tricks: match declarers_tricks.len() {
+                        0 => 0.0,
+                        n => declarers_tricks.iter().sum::<usize>() as f64 / n as f64,
+                    },
+                }
+            })
+            .collect();
+        (scores, options.len())
+    }
+
+    fn score_for_me(&self, contract: Contract, declarers_tricks: usize, vulnerability: Vulnerability) -> ScorePoints {
+        let result = GameResult::calculate_game_result(contract, declarers_tricks);
+        let score = ScoreCalculator::score_result(result, vulnerability);
+        match Axis::from(self.seat) {
+            Axis::NorthSouth => score,
+            Axis::EastWest => score * -1_isize,
+        }
+    }
+}
+
+struct CardScore {
+    card: Card,
+    total: ScorePoints,
+    declarers_tricks: f64,
+}
+
+fn best_cards(scores: &[CardScore]) -> Vec<Card> {
+    let best = scores.iter().map(|score| score.total).max().unwrap();
+    scores
+        .iter()
+        .filter(|score| score.total == best)
+        .map(|score| score.card)
+        .collect()
+}
+
+fn sample_reason(scores: &[CardScore], samples: usize) -> Reason {
+    let rule = format!("Best total score over {} double dummy samples", samples);
+    scores
+        .iter()
+        .sorted_by(|score, other| other.total.cmp(&score.total))
+        .fold(Reason::from_rule(&rule), |reason, score| {
+            reason.with_detail(format!(
+                "{}: {:.1} tricks for declarer on average, {} points in total",
+                score.card, score.declarers_tricks, score.total
+            ))
+        })
+}
+
+fn trumps(strain: Strain) -> Option<Suit> {
+    match strain {
+        Strain::Trump(suit) => Some(suit),
+        Strain::NoTrump => None,
+    }
+}
+
+impl SelectCard for MonteCarloCardPlayEngine {
+    fn select_card(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> Card {
+        self.pick_card(state).0
+    }
+
+    fn select_opening_lead(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> Card {
+        self.pick_opening_lead(state).0
+    }
+
+    fn select_card_with_reason(&self, state: SubjectiveGamePhaseStateView<CardPlayState>) -> (Card, Reason) {
+        self.pick_card(state)
+    }
+
+    fn select_opening_lead_with_reason(&self, state: SubjectiveGamePhaseStateView<OpeningLeadState>) -> (Card, Reason) {
+        self.pick_opening_lead(state)
+    }
+
+    fn claim(&self, state: &SubjectiveGamePhaseStateView<CardPlayState>) -> Option<usize> {
+        self.try_claim(state)
+    }
+
+    fn respond_to_claim(&self, state: &SubjectiveGamePhaseStateView<CardPlayState>, claim: ClaimEvent) -> bool {
+        self.check_claim(state, claim)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MonteCarloCardPlayEngine;
+    use crate::DoubleDummyRunner;
+    use bridge_buddy_core::actors::game_manager::GameManager;
+    use bridge_buddy_core::engine::bidding_engine::forum_d_plus_2015_bidding_engine::ForumDPlus2015BiddingEngine;
+    use bridge_buddy_core::engine::card_play_engine::opening_leads::OpeningLeads;
+    use bridge_buddy_core::engine::card_play_engine::SelectCard;
+    use bridge_buddy_core::engine::subjective_game_view::SubjectiveGameStateView;
+    use bridge_buddy_core::game::GameState;
+    use bridge_buddy_core::primitives::bid::Bid;
+    use bridge_buddy_core::primitives::contract::Strain;
+    use bridge_buddy_core::primitives::deal::Seat;
+    use bridge_buddy_core::primitives::game_event::GameEvent;
+    use bridge_buddy_core::primitives::player_event::{BidEvent, CardEvent, PlayerEvent};
+    use bridge_buddy_core::primitives::{Card, Deal, Suit};
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    const CARDS: [&str; 40] = [
+        "C2", "C7", "CK", "C3", "CJ", "S6", "C4", "C8", "D4", "D6", "D7", "DJ", "C6", "S9", "C5", "C9", "D5", "D8",
+        "D9", "D2", "CA", "S2", "ST", "CT", "DT", "D3", "DK", "H2", "H5", "H3", "H7", "H4", "DQ", "DA", "H6", "S3",
+        "S4", "SQ", "H8", "S8",
+    ];
+
+    fn manager_after(cards: usize) -> GameManager {
+        let mut manager = GameManager::new_from_deal(Deal::from_u64_seed(9000));
+        manager.start_game().unwrap();
+        for bid in ["p", "1NT", "p", "2C", "p", "2S", "p", "4S", "p", "p", "p"] {
+            let event = PlayerEvent::Bid(BidEvent {
+                player: manager.next_to_play().unwrap(),
+                bid: Bid::from_str(bid).unwrap(),
+                alert: false,
+                explanation: None,
+            });
+            manager.process_player_event(event).unwrap();
+        }
+        for card in &CARDS[..cards] {
+            play(&mut manager, Card::from_str(card).unwrap());
+        }
+        manager
+    }
+
+    fn play(manager: &mut GameManager, card: Card) {
+        let event = PlayerEvent::Card(CardEvent {
+            player: manager.next_to_play().unwrap(),
+            card,
+        });
+        manager.process_player_event(event).unwrap();
+    }
+
+    fn game_state(manager: &GameManager) -> GameState {
+        let history = manager.history();
+        let GameEvent::NewGame(new_game) = history[0] else {
+            panic!("game has not started")
+        };
+        let mut game = GameState::from_new_game_event(new_game);
+        for event in &history[1..] {
+            game.process_game_event(*event).unwrap();
+        }
+        game
+    }
+
+    // declarer chooses dummy's cards
+    fn chooser(manager: &GameManager) -> Seat {
+        let next = manager.next_to_play().unwrap();
+        match manager.dummy() == Some(next) {
+            true => next.partner(),
+            false => next,
+        }
+    }
+
+    fn select_card(manager: &GameManager, engine: &MonteCarloCardPlayEngine, seat: Seat) -> Card {
+        let game = game_state(manager);
+        let SubjectiveGameStateView::CardPlay(view) = SubjectiveGameStateView::new(&game, seat) else {
+            panic!("not in card play")
+        };
+        engine.select_card(view)
+    }
+
+    fn played_cards(manager: &GameManager) -> Vec<Card> {
+        manager
+            .history()
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::Card(card_event) => Some(card_event.card),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test_case(36; "Four tricks left")]
+    #[test_case(38; "During the trick")]
+    #[test_case(40; "Three tricks left")]
+    fn plays_a_double_dummy_best_card(cards: usize) {
+        let manager = manager_after(cards);
+        let seat = chooser(&manager);
+        let engine = MonteCarloCardPlayEngine::new(seat).with_samples(8).with_seed(1);
+        let card = select_card(&manager, &engine, seat);
+
+        let options = DoubleDummyRunner::default()
+            .solve_card_options(
+                Deal::<13>::from_u64_seed(9000),
+                Strain::Trump(Suit::Spades),
+                Seat::East,
+                &played_cards(&manager),
+            )
+            .unwrap();
+        let best = options.iter().map(|(_, tricks)| *tricks).max().unwrap();
+        assert!(options.contains(&(card, best)));
+    }
+
+    #[test]
+    fn same_seed_plays_the_same_card() {
+        let manager = manager_after(32);
+        let seat = chooser(&manager);
+        let first = MonteCarloCardPlayEngine::new(seat).with_samples(4).with_seed(7);
+        let second = MonteCarloCardPlayEngine::new(seat).with_samples(4).with_seed(7);
+        assert_eq!(
+            select_card(&manager, &first, seat),
+            select_card(&manager, &second, seat)
+        );
+    }
+
+    #[test]
+    fn explains_its_card() {
+        let manager = manager_after(40);
+        let seat = chooser(&manager);
+        let engine = || MonteCarloCardPlayEngine::new(seat).with_samples(4).with_seed(7);
+        let game = game_state(&manager);
+        let SubjectiveGameStateView::CardPlay(view) = SubjectiveGameStateView::new(&game, seat) else {
+            panic!("not in card play")
+        };
+        let (card, reason) = engine().select_card_with_reason(view);
+
+        assert_eq!(card, engine().select_card(view));
+        assert_eq!(reason.rule, "Best total score over 4 double dummy samples");
+        assert_eq!(reason.details.len(), view.legal_cards(view.next_to_play()).len());
+        assert!(reason
+            .details
+            .iter()
+            .any(|detail| detail.starts_with(&card.to_string())));
+    }
+
+    #[test]
+    fn claims_only_near_the_end() {
+        let manager = manager_after(32);
+        let seat = chooser(&manager);
+        let engine = MonteCarloCardPlayEngine::new(seat).with_samples(4).with_seed(7);
+        let game = game_state(&manager);
+        let SubjectiveGameStateView::CardPlay(view) = SubjectiveGameStateView::new(&game, seat) else {
+            panic!("not in card play")
+        };
+        assert_eq!(view.tricks_left(), 5);
+        assert_eq!(engine.claim(&view), None);
+    }
+
+    #[test]
+    fn plays_to_the_end() {
+        let mut manager = manager_after(32);
+        while !manager
+            .history()
+            .iter()
+            .any(|event| matches!(event, GameEvent::GameEnded(_)))
+        {
+            let seat = chooser(&manager);
+            let engine = MonteCarloCardPlayEngine::new(seat)
+                .with_samples(2)
+                .with_seed(3)
+                .with_interpreter(ForumDPlus2015BiddingEngine::new());
+            let card = select_card(&manager, &engine, seat);
+            play(&mut manager, card);
+        }
+        assert_eq!(played_cards(&manager).len(), 52);
+    }
+
+    #[test]
+    fn leads_a_legal_card() {
+        let manager = manager_after(0);
+        let seat = manager.next_to_play().unwrap();
+        let game = game_state(&manager);
+        let SubjectiveGameStateView::OpeningLead(view) = SubjectiveGameStateView::new(&game, seat) else {
+            panic!("not on opening lead")
+        };
+        let legal_cards = view.legal_cards();
+        let engine = MonteCarloCardPlayEngine::new(seat)
+            .with_samples(1)
+            .with_seed(1)
+            .with_opening_leads(OpeningLeads::default());
+        let (lead, reason) = engine.select_opening_lead_with_reason(view);
+        assert!(legal_cards.contains(&lead));
+        assert_eq!(reason.rule, "Textbook opening lead against the contract and auction");
+    }
+
+    #[ignore = "solves the full deal for every card, takes minutes in a debug build"]
+    #[test]
+    fn samples_a_legal_opening_lead() {
+        let manager = manager_after(0);
+        let seat = manager.next_to_play().unwrap();
+        let game = game_state(&manager);
+        let SubjectiveGameStateView::OpeningLead(view) = SubjectiveGameStateView::new(&game, seat) else {
+            panic!("not on opening lead")
+        };
+        let legal_cards = view.legal_cards();
+        let engine = MonteCarloCardPlayEngine::new(seat).with_samples(1).with_seed(1);
+        assert!(legal_cards.contains(&engine.select_opening_lead(view)));
+    }
+}
@@ -0,0 +1,269 @@
+use crate::DoubleDummyRunner;
+use bridge_buddy_core::error::BBError;
+use bridge_buddy_core::game::trick_manager::TrickManager;
+use bridge_buddy_core::game::GameState;
+use bridge_buddy_core::primitives::deal::Seat;
+use bridge_buddy_core::primitives::game_event::GameEvent;
+use bridge_buddy_core::primitives::game_result::GameResult;
+use bridge_buddy_core::primitives::{Card, Contract, Deal};
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CostlyCard {
+    pub trick_number: usize,
+    pub player: Seat,
+    pub card: Card,
+    // declarer plays dummy's cards, so is responsible for them
+    pub responsible: Seat,
+    pub tricks_lost: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostMortem {
+    pub contract: Contract,
+    pub cards: Vec<(Seat, Card)>,
+    // declarer's double dummy tricks from each position on, counting the tricks already won
+    pub values: Vec<usize>,
+    // including claimed tricks
+    pub actual_tricks: usize,
+}
+
+impl PostMortem {
+    pub fn from_history(history: &[GameEvent]) -> Result<Self, BBError> {
+        let mut game = match history.first() {
+            Some(GameEvent::NewGame(event)) => GameState::from_new_game_event(*event),
+            _ => return Err(BBError::GameHasNotStarted),
+        };
+        let mut played_cards = Vec::new();
+        let mut ended = None;
+        for event in &history[1..] {
+            game.process_game_event(*event)?;
+            match &game {
+                GameState::OpeningLead(state) => played_cards = state.trick_manager.played_cards().to_vec(),
+                GameState::CardPlay(state) => played_cards = state.trick_manager.played_cards().to_vec(),
+                _ => (),
+            }
+            if let GameEvent::GameEnded(event) = event {
+                ended = Some(*event);
+            }
+        }
+
+        let ended = ended.ok_or(BBError::InsufficientInfo)?;
+        let contract = ended.result.played_contract().ok_or(BBError::InsufficientInfo)?;
+        let actual_tricks = match ended.result {
+            GameResult::Made { overtricks, .. } => contract.expected_tricks() + overtricks,
+            GameResult::Failed { undertricks, .. } => contract.expected_tricks() - undertricks,
+            GameResult::Unplayed => 0,
+        };
+        Self::new(ended.deal, contract, &played_cards, actual_tricks)
+    }
+
+    pub fn new(
+        deal: Deal<13>,
+        contract: Contract,
+        played_cards: &[Card],
+        actual_tricks: usize,
+    ) -> Result<Self, BBError> {
+        let declarer = contract.declarer;
+        let opening_leader = declarer + 1;
+        let mut runner = DoubleDummyRunner::default();
+        let mut trick_manager = TrickManager::<13>::new(opening_leader, contract.trump_suit());
+        let mut cards = Vec::new();
+        let mut values = Vec::new();
+
+        for index in 0..=played_cards.len() {
+            let remaining = runner.solve_position(deal, contract.strain, opening_leader, &played_cards[..index])?;
+            let remaining = match trick_manager.next_to_play().same_axis(&declarer) {
+                true => remaining,
+                false => trick_manager.tricks_left() - remaining,
+            };
+            values.push(trick_manager.tricks_won_by_axis(declarer) + remaining);
+
+            if let Some(&card) = played_cards.get(index) {
+                cards.push((trick_manager.next_to_play(), card));
+                trick_manager.play(card);
+            }
+        }
+
+        Ok(Self {
+            contract,
+            cards,
+            values,
+            actual_tricks,
+        })
+    }
+
+    pub fn double_dummy_tricks(&self) -> usize {
+        self.values[0]
+    }
+
+    pub fn trick_values(&self) -> Vec<usize> {
+        self.values
+            .iter()
+            .take(self.cards.len().div_ceil(4) * 4)
+            .step_by(4)
+            .copied()
+            .collect_vec()
+    }
+
+    pub fn costly_cards(&self) -> Vec<CostlyCard> {
+        let declarer = self.contract.declarer;
+        self.cards
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| self.values[index] != self.values[index + 1])
+            .map(|(index, &(player, card))| CostlyCard {
+                trick_number: index / 4 + 1,
+                player,
+                card,
+                responsible: match player == declarer.partner() {
+                    true => declarer,
+                    false => player,
+                },
+                tricks_lost: self.values[index].abs_diff(self.values[index + 1]),
+            })
+            .collect_vec()
+    }
+}
+
+impl Display for PostMortem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let declarer = self.contract.declarer;
+        writeln!(
+            f,
+            "{}: declarer took {} tricks, double dummy par is {}.",
+            self.contract,
+            self.actual_tricks,
+            self.double_dummy_tricks()
+        )?;
+        for (trick, value) in self.trick_values().iter().enumerate() {
+            writeln!(
+                f,
+                "Trick {:>2}: {:>2} tricks for declarer double dummy",
+                trick + 1,
+                value
+            )?;
+        }
+        for costly in self.costly_cards() {
+            let dummy = match costly.player == declarer.partner() {
+                true => " from dummy",
+                false => "",
+            };
+            writeln!(
+                f,
+                "Trick {:>2}: {} lost {} trick(s) with {}{}",
+                costly.trick_number, costly.responsible, costly.tricks_lost, costly.card, dummy
+            )?;
+        }
+        let last = *self.values.last().unwrap();
+        if last != self.actual_tricks {
+            writeln!(
+                f,
+                "After the claim declarer has {} tricks, {} double dummy.",
+                self.actual_tricks, last
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CostlyCard, PostMortem};
+    use bridge_buddy_core::interchange::pbn;
+    use bridge_buddy_core::primitives::deal::Seat;
+    use bridge_buddy_core::primitives::{Card, Contract};
+    use std::str::FromStr;
+
+    const PBN: &str = "[Dealer \"N\"]
+[Deal \"N:AKQJT98765432... .AKQJT98765432.. ..AKQJT98765432. ...AKQJT98765432\"]
+[Auction \"N\"]
+4S Pass Pass Pass
+[Play \"E\"]
+HA D2 C2 S2
+H2 D3 C3 S3
+*
+[Result \"13\"]";
+
+    fn post_mortem(cards: [(Seat, &str); 4], values: Vec<usize>, actual_tricks: usize) -> PostMortem {
+        PostMortem {
+            contract: Contract::from_str("N4S").unwrap(),
+            cards: cards
+                .iter()
+                .map(|&(player, card)| (player, Card::from_str(card).unwrap()))
+                .collect(),
+            values,
+            actual_tricks,
+        }
+    }
+
+    #[test]
+    fn imported_game() {
+        let history = pbn::read_game(PBN).unwrap();
+        let post_mortem = PostMortem::from_history(&history).unwrap();
+
+        assert_eq!(post_mortem.double_dummy_tricks(), 13);
+        assert_eq!(post_mortem.values, vec![13; 9]);
+        assert_eq!(post_mortem.trick_values(), vec![13, 13]);
+        assert_eq!(post_mortem.cards[3], (Seat::North, Card::from_str("S2").unwrap()));
+        assert_eq!(post_mortem.actual_tricks, 13);
+        assert!(post_mortem.costly_cards().is_empty());
+    }
+
+    #[test]
+    fn costly_cards() {
+        let post_mortem = post_mortem(
+            [
+                (Seat::East, "HA"),
+                (Seat::South, "D2"),
+                (Seat::West, "C2"),
+                (Seat::North, "S2"),
+            ],
+            vec![10, 10, 9, 10, 10],
+            10,
+        );
+
+        assert_eq!(
+            post_mortem.costly_cards(),
+            vec![
+                CostlyCard {
+                    trick_number: 1,
+                    player: Seat::South,
+                    card: Card::from_str("D2").unwrap(),
+                    responsible: Seat::North,
+                    tricks_lost: 1,
+                },
+                CostlyCard {
+                    trick_number: 1,
+                    player: Seat::West,
+                    card: Card::from_str("C2").unwrap(),
+                    responsible: Seat::West,
+                    tricks_lost: 1,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let post_mortem = post_mortem(
+            [
+                (Seat::East, "HA"),
+                (Seat::South, "D2"),
+                (Seat::West, "C2"),
+                (Seat::North, "S2"),
+            ],
+            vec![10, 10, 9, 9, 9],
+            8,
+        );
+
+        assert_eq!(
+            format!("{}", post_mortem),
+            "4♠ by N: declarer took 8 tricks, double dummy par is 10.\n\
+             Trick  1: 10 tricks for declarer double dummy\n\
+             Trick  1: N lost 1 trick(s) with ♦2 from dummy\n\
+             After the claim declarer has 8 tricks, 9 double dummy.\n"
+        );
+    }
+}
@@ -72,6 +72,26 @@ impl<const N: usize> VirtualState<N> {
         }
     }
 
+    pub fn play_absolute(&mut self, card: &Card) -> Result<(), BBError> {
+        if !self.valid_absolute_moves().contains(card) {
+            return Err(BBError::InvalidCard(*card));
+        }
+        match self.absolute_to_virtual(card) {
+            Some(virtual_card) => self.play(&virtual_card),
+            None => Err(BBError::InvalidCard(*card)),
+        }
+    }
+
+    pub fn valid_absolute_moves(&self) -> Vec<Card> {
+        let player = self.next_to_play();
+        match self.suit_to_follow() {
+            Some(suit) if !self.game.player_is_void_in(suit, player) => {
+                self.game.remaining_cards_of_player_in_suit(player, suit).collect()
+            }
+            _ => self.game.remaining_cards_of(player).collect(),
+        }
+    }
+
     fn update_virtualizer(&mut self) {
         self.virtualizer = Virtualizer::new(self.game.out_of_play_cards().clone());
     }
@@ -165,7 +185,7 @@ impl<const N: usize> VirtualState<N> {
         }
     }
 
-    fn absolute_to_virtual(&self, card: &Card) -> Option<VirtualCard> {
+    pub fn absolute_to_virtual(&self, card: &Card) -> Option<VirtualCard> {
         self.virtualizer.absolute_to_virtual_card(card)
     }
 
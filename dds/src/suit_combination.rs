@@ -0,0 +1,607 @@
+use crate::card_manager::suit_field::SuitField;
+use bridge_buddy_core::error::BBError;
+use bridge_buddy_core::impossible_book::helper::choose;
+use bridge_buddy_core::primitives::card::Rank;
+use bridge_buddy_core::primitives::deal::Seat;
+use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+
+// South is declarer and North dummy. The suit is played on its own: declarer may lead from either hand,
+// the defenders never lead it and play as if they could see all cards, declarer only sees the played cards.
+// Declarer's choices are found by vector minimaxing over the splits, exact for all but a few rare positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuitCombination {
+    north: SuitField,
+    south: SuitField,
+    known: [SuitField; 4],
+}
+
+// probability before any card has been played
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Split {
+    pub west: SuitField,
+    pub east: SuitField,
+    pub probability: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuitPlay {
+    pub tricks: usize,
+    pub probability: f64,
+    pub lead: Option<(Seat, Rank)>,
+    // second hand's cards to the first trick (`None` for showing out), each with declarer's card from the other hand
+    pub responses: Vec<(Option<Rank>, Option<Rank>)>,
+    pub successful_splits: Vec<Split>,
+}
+
+impl SuitCombination {
+    pub fn new(north: SuitField, south: SuitField) -> Self {
+        Self {
+            north,
+            south,
+            known: [SuitField::empty(); 4],
+        }
+    }
+
+    pub fn from_holdings(north: &str, south: &str) -> Result<Self, BBError> {
+        let (north, south) = (read_holding(north)?, read_holding(south)?);
+        match north.union(&south).count_cards() == north.count_cards() + south.count_cards() {
+            true => Ok(Self::new(north, south)),
+            false => Err(BBError::ParseError(
+                format!("{} {}", holding_text(&north), holding_text(&south)),
+                "both hands hold the same card",
+            )),
+        }
+    }
+
+    pub fn with_known_card(mut self, seat: Seat, rank: Rank) -> Self {
+        self.known[seat as usize].add_rank(rank);
+        self
+    }
+
+    pub fn max_tricks(&self) -> usize {
+        self.north.count_cards().max(self.south.count_cards())
+    }
+
+    // each defender holds 13 cards, so a long holding is less likely than a short one
+    pub fn splits(&self) -> Vec<Split> {
+        let outstanding = SuitField::ALL_RANKS & !u16::from(self.north.union(&self.south));
+        let count = outstanding.count_ones() as u8;
+        let [_, east_known, _, west_known] = self.known.map(u16::from);
+
+        let mut splits = Vec::new();
+        let mut west = outstanding;
+        loop {
+            let east = outstanding & !west;
+            if west & west_known == west_known && east & east_known == east_known {
+                let length = west.count_ones() as u8;
+                splits.push(Split {
+                    west: SuitField::from_u16(west),
+                    east: SuitField::from_u16(east),
+                    probability: choose(26 - count, 13 - length) as f64,
+                });
+            }
+            match west {
+                0 => break,
+                _ => west = (west - 1) & outstanding,
+            }
+        }
+        let total = splits.iter().map(|split| split.probability).sum::<f64>();
+        for split in &mut splits {
+            split.probability /= total;
+        }
+        splits
+    }
+
+    pub fn best_play(&self, tricks: usize) -> SuitPlay {
+        let splits = self.splits();
+        let mut search = Search::new(&splits, tricks, self.north.union(&self.south).into());
+        let start = Position {
+            north: self.north.into(),
+            south: self.south.into(),
+            played: [0; 2],
+            void: [false; 2],
+            won: 0,
+        };
+        let leads = search.leads(start);
+        let Some(((leader, lead), values)) = leads
+            .into_iter()
+            .max_by(|(_, values), (_, other)| search.compare(&start, values, other))
+        else {
+            return SuitPlay::impossible(tricks);
+        };
+        let successful_splits = splits
+            .iter()
+            .zip(&values)
+            .filter(|&(_, &taken)| taken as usize >= tricks)
+            .map(|(split, _)| *split)
+            .collect_vec();
+        if successful_splits.is_empty() {
+            return SuitPlay::impossible(tricks);
+        }
+
+        let trick = Trick::led(leader, lead);
+        let position = start.without(leader, lead);
+        let responses = search
+            .defenders_cards(&position, trick.next_player())
+            .into_iter()
+            .map(|card| {
+                let (position, trick) = (position.after(trick.next_player(), card), trick.with(card));
+                let third_hand = search
+                    .declarers_options(&position, &trick)
+                    .into_iter()
+                    .max_by(|(_, values), (_, other)| search.compare(&position, values, other))
+                    .and_then(|(card, _)| card);
+                (card, third_hand)
+            })
+            .collect_vec();
+        SuitPlay {
+            tricks,
+            probability: successful_splits.iter().map(|split| split.probability).sum(),
+            lead: Some((leader, lead)),
+            responses,
+            successful_splits,
+        }
+    }
+
+    pub fn table(&self) -> Vec<SuitPlay> {
+        (1..=self.max_tricks()).map(|tricks| self.best_play(tricks)).collect()
+    }
+}
+
+impl SuitPlay {
+    fn impossible(tricks: usize) -> Self {
+        SuitPlay {
+            tricks,
+            probability: 0.0,
+            lead: None,
+            responses: Vec::new(),
+            successful_splits: Vec::new(),
+        }
+    }
+}
+
+impl Display for SuitCombination {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} opposite {}",
+            holding_text(&self.north),
+            holding_text(&self.south)
+        )
+    }
+}
+
+impl Display for Split {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "W {} / E {} ({:.2}%)",
+            holding_text(&self.west),
+            holding_text(&self.east),
+            self.probability * 100.0
+        )
+    }
+}
+
+impl Display for SuitPlay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} trick(s): {:.2}%", self.tricks, self.probability * 100.0)?;
+        let Some((leader, lead)) = self.lead else {
+            return Ok(());
+        };
+        writeln!(f, "  Lead the {} from {}.", lead, leader)?;
+        let groups = self
+            .responses
+            .iter()
+            .group_by(|(second_hand, third_hand)| (second_hand.is_some(), *third_hand));
+        for ((follows, third_hand), group) in &groups {
+            let second_hand = match follows {
+                true => {
+                    let cards = group
+                        .filter_map(|(card, _)| card.map(|card| card.to_string()))
+                        .collect_vec();
+                    match cards.split_last() {
+                        Some((last, [])) => format!("If {} plays the {}", leader + 1, last),
+                        Some((last, others)) => {
+                            format!("If {} plays the {} or {}", leader + 1, others.join(", "), last)
+                        }
+                        None => unreachable!(),
+                    }
+                }
+                false => format!("If {} shows out", leader + 1),
+            };
+            match third_hand {
+                Some(card) => writeln!(f, "  {}, play the {} from {}.", second_hand, card, leader + 2)?,
+                None => writeln!(f, "  {}, {} discards.", second_hand, leader + 2)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_holding(text: &str) -> Result<SuitField, BBError> {
+    let mut holding = SuitField::empty();
+    for char in text.trim().replace("10", "T").chars().filter(|&char| char != '-') {
+        let rank = Rank::from_char(char)?;
+        if holding.contains_rank(&rank) {
+            return Err(BBError::ParseError(text.into(), "a card appears twice"));
+        }
+        holding.add_rank(rank);
+    }
+    Ok(holding)
+}
+
+fn holding_text(holding: &SuitField) -> String {
+    match holding.is_void() {
+        true => "-".into(),
+        false => holding.iter().rev().join(""),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Position {
+    north: u16,
+    south: u16,
+    played: [u16; 2],
+    void: [bool; 2],
+    won: u8,
+}
+
+impl Position {
+    fn hand(&self, seat: Seat) -> u16 {
+        match seat {
+            Seat::North => self.north,
+            Seat::South => self.south,
+            _ => unreachable!(),
+        }
+    }
+
+    fn without(mut self, seat: Seat, rank: Rank) -> Self {
+        let card = SuitField::u16_from_rank(rank);
+        match seat {
+            Seat::North => self.north &= !card,
+            Seat::South => self.south &= !card,
+            _ => unreachable!(),
+        }
+        self
+    }
+
+    // `None` means a discard
+    fn after(self, seat: Seat, card: Option<Rank>) -> Self {
+        match (seat, card) {
+            (Seat::North | Seat::South, Some(rank)) => self.without(seat, rank),
+            (Seat::North | Seat::South, None) => self,
+            (_, Some(rank)) => {
+                let mut position = self;
+                position.played[defender(seat)] |= SuitField::u16_from_rank(rank);
+                position
+            }
+            (_, None) => {
+                let mut position = self;
+                position.void[defender(seat)] = true;
+                position
+            }
+        }
+    }
+
+    fn allows(&self, split: &Split) -> bool {
+        [u16::from(split.west), u16::from(split.east)]
+            .iter()
+            .enumerate()
+            .all(|(index, &holding)| {
+                holding & self.played[index] == self.played[index]
+                    && (!self.void[index] || holding & !self.played[index] == 0)
+            })
+    }
+
+    fn unplayed(&self) -> u16 {
+        SuitField::ALL_RANKS & !(self.played[0] | self.played[1])
+    }
+}
+
+fn defender(seat: Seat) -> usize {
+    match seat {
+        Seat::West => 0,
+        Seat::East => 1,
+        _ => unreachable!(),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Trick {
+    leader: Seat,
+    cards: [Option<Rank>; 4],
+    played: usize,
+}
+
+impl Trick {
+    fn led(leader: Seat, rank: Rank) -> Self {
+        Trick {
+            leader,
+            cards: [Some(rank), None, None, None],
+            played: 1,
+        }
+    }
+
+    fn next_player(&self) -> Seat {
+        self.leader + self.played
+    }
+
+    fn with(mut self, card: Option<Rank>) -> Self {
+        self.cards[self.played] = card;
+        self.played += 1;
+        self
+    }
+
+    fn ranks(&self) -> u16 {
+        self.cards
+            .iter()
+            .flatten()
+            .fold(0, |ranks, &rank| ranks | SuitField::u16_from_rank(rank))
+    }
+
+    fn won_by_declarer(&self) -> bool {
+        let (winner, _) = self
+            .cards
+            .iter()
+            .enumerate()
+            .filter_map(|(index, card)| card.map(|card| (index, card)))
+            .max_by_key(|&(_, card)| card)
+            .unwrap();
+        winner % 2 == 0
+    }
+}
+
+// every node has the tricks declarer takes from there on for each split
+struct Search<'a> {
+    splits: &'a [Split],
+    tricks: usize,
+    declarers_cards: u16,
+    memo: HashMap<Position, Vec<u8>>,
+}
+
+impl<'a> Search<'a> {
+    fn new(splits: &'a [Split], tricks: usize, declarers_cards: u16) -> Self {
+        Self {
+            splits,
+            tricks,
+            declarers_cards,
+            memo: HashMap::new(),
+        }
+    }
+
+    // by the chance to take the tricks needed, then by the tricks expected, over the splits agreeing with `position`
+    fn compare(&self, position: &Position, values: &[u8], other: &[u8]) -> Ordering {
+        let rate = |values: &[u8]| {
+            let needed = self.tricks.saturating_sub(position.won as usize);
+            self.splits
+                .iter()
+                .zip(values)
+                .filter(|(split, _)| position.allows(split))
+                .fold((0.0, 0.0), |(success, expected), (split, &taken)| {
+                    let made = match taken as usize >= needed {
+                        true => split.probability,
+                        false => 0.0,
+                    };
+                    (success + made, expected + split.probability * taken as f64)
+                })
+        };
+        let (success, expected) = rate(values);
+        let (other_success, other_expected) = rate(other);
+        (success, expected)
+            .partial_cmp(&(other_success, other_expected))
+            .unwrap()
+    }
+
+    fn tricks_from(&mut self, position: Position) -> Vec<u8> {
+        if let Some(values) = self.memo.get(&position) {
+            return values.clone();
+        }
+        let values = self
+            .leads(position)
+            .into_iter()
+            .map(|(_, values)| values)
+            .max_by(|values, other| self.compare(&position, values, other))
+            .unwrap_or_else(|| vec![0; self.splits.len()]);
+        self.memo.insert(position, values.clone());
+        values
+    }
+
+    fn leads(&mut self, position: Position) -> Vec<((Seat, Rank), Vec<u8>)> {
+        let mut leads = Vec::new();
+        for leader in [Seat::South, Seat::North] {
+            for rank in self.sensible_cards(&position, leader, None) {
+                let values = self.continue_trick(position.without(leader, rank), Trick::led(leader, rank));
+                leads.push(((leader, rank), values));
+            }
+        }
+        leads
+    }
+
+    fn continue_trick(&mut self, position: Position, trick: Trick) -> Vec<u8> {
+        if trick.played == 4 {
+            let won = trick.won_by_declarer();
+            let mut values = self.tricks_from(Position {
+                won: position.won + won as u8,
+                ..position
+            });
+            if won {
+                values.iter_mut().for_each(|taken| *taken += 1);
+            }
+            return values;
+        }
+        match trick.next_player() {
+            Seat::North | Seat::South => self
+                .declarers_options(&position, &trick)
+                .into_iter()
+                .map(|(_, values)| values)
+                .max_by(|values, other| self.compare(&position, values, other))
+                .unwrap(),
+            defender => self.defend(position, trick, defender),
+        }
+    }
+
+    fn declarers_options(&mut self, position: &Position, trick: &Trick) -> Vec<(Option<Rank>, Vec<u8>)> {
+        let seat = trick.next_player();
+        let cards = match position.hand(seat) {
+            0 => vec![None],
+            _ => self
+                .sensible_cards(position, seat, Some(trick))
+                .into_iter()
+                .map(Some)
+                .collect(),
+        };
+        cards
+            .into_iter()
+            .map(|card| (card, self.continue_trick(position.after(seat, card), trick.with(card))))
+            .collect()
+    }
+
+    // each split gets the worst result of the cards the defender holds in it
+    fn defend(&mut self, position: Position, trick: Trick, defender: Seat) -> Vec<u8> {
+        let mut children = BTreeMap::new();
+        for card in self.defenders_cards(&position, defender) {
+            let values = self.continue_trick(position.after(defender, card), trick.with(card));
+            children.insert(card, values);
+        }
+        self.splits
+            .iter()
+            .enumerate()
+            .map(|(index, split)| match position.allows(split) {
+                true => legal_cards(&position, split, defender)
+                    .iter()
+                    .map(|card| children[card][index])
+                    .min()
+                    .unwrap(),
+                false => 0,
+            })
+            .collect()
+    }
+
+    fn defenders_cards(&self, position: &Position, defender: Seat) -> Vec<Option<Rank>> {
+        self.splits
+            .iter()
+            .filter(|split| position.allows(split))
+            .flat_map(|split| legal_cards(position, split, defender))
+            .unique()
+            .sorted()
+            .collect()
+    }
+
+    // only the lowest of cards that are equals because every card between them is in the same hand or played
+    fn sensible_cards(&self, position: &Position, seat: Seat, trick: Option<&Trick>) -> Vec<Rank> {
+        let hand = position.hand(seat);
+        let defenders = position.unplayed() & !self.declarers_cards;
+        let current_trick = trick.map(Trick::ranks).unwrap_or(0);
+        let others = (defenders | current_trick | position.north | position.south) & !hand;
+        SuitField::from_u16(hand)
+            .into_iter()
+            .fold(Vec::<Rank>::new(), |mut cards, rank| {
+                let separated = match cards.last() {
+                    Some(&lower) => {
+                        let between = SuitField::u16_from_rank(rank) - 2 * SuitField::u16_from_rank(lower);
+                        others & between != 0
+                    }
+                    None => true,
+                };
+                if separated {
+                    cards.push(rank);
+                }
+                cards
+            })
+    }
+}
+
+fn legal_cards(position: &Position, split: &Split, defender: Seat) -> Vec<Option<Rank>> {
+    let index = self::defender(defender);
+    let holding = [u16::from(split.west), u16::from(split.east)][index] & !position.played[index];
+    match holding {
+        0 => vec![None],
+        _ => SuitField::from_u16(holding).into_iter().map(Some).collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SuitCombination;
+    use bridge_buddy_core::primitives::card::Rank;
+    use bridge_buddy_core::primitives::deal::Seat;
+    use test_case::test_case;
+
+    #[test_case("AQ", "32", 2, 0.5; "Finesse")]
+    #[test_case("AKQ32", "654", 5, 0.6783; "Drop")]
+    #[test_case("AKQ32", "654", 4, 0.9609; "Unless five nil")]
+    #[test_case("AK32", "654", 3, 0.3553; "Three three")]
+    #[test_case("AKJ", "432", 3, 0.5048; "Finesse against the queen")]
+    #[test_case("AKJ432", "765", 6, 0.5313; "Eight ever, nine never")]
+    #[test_case("AQ432", "765", 4, 0.3391; "Finesse and a good split"
+    )]
+    #[test_case("AQ432", "765", 5, 0.0; "More tricks than top cards and length")]
+    #[test_case("-", "-", 1, 0.0; "Void")]
+    fn best_play(north: &str, south: &str, tricks: usize, expected: f64) {
+        let play = SuitCombination::from_holdings(north, south).unwrap().best_play(tricks);
+        assert!(
+            (play.probability - expected).abs() < 1e-4,
+            "{}: {}",
+            play.probability,
+            play
+        );
+    }
+
+    #[test]
+    fn finesse_from_south() {
+        let play = SuitCombination::from_holdings("AQ", "32").unwrap().best_play(2);
+        assert_eq!(play.lead.map(|(seat, _)| seat), Some(Seat::South));
+        assert!(play.responses.contains(&(Some(Rank::King), Some(Rank::Ace))));
+        assert!(play
+            .successful_splits
+            .iter()
+            .all(|split| split.west.contains_rank(&Rank::King)));
+    }
+
+    #[test]
+    fn splits() {
+        let splits = SuitCombination::from_holdings("AKQ32", "654").unwrap().splits();
+        assert_eq!(splits.len(), 32);
+        assert!((splits.iter().map(|split| split.probability).sum::<f64>() - 1.0).abs() < 1e-9);
+        let three_two = splits
+            .iter()
+            .filter(|split| split.west.count_cards().abs_diff(split.east.count_cards()) == 1)
+            .map(|split| split.probability)
+            .sum::<f64>();
+        assert!((three_two - 0.6783).abs() < 1e-4);
+    }
+
+    #[test]
+    fn known_card() {
+        let combination = SuitCombination::from_holdings("AQ", "32")
+            .unwrap()
+            .with_known_card(Seat::West, Rank::King);
+        assert!(combination
+            .splits()
+            .iter()
+            .all(|split| split.west.contains_rank(&Rank::King)));
+        assert!((combination.best_play(2).probability - 1.0).abs() < 1e-9);
+    }
+
+    #[test_case("AQ", "Q3"; "Same card")]
+    #[test_case("AX", "32"; "Unknown rank")]
+    #[test_case("AA", "32"; "Card twice")]
+    fn invalid_holdings(north: &str, south: &str) {
+        assert!(SuitCombination::from_holdings(north, south).is_err());
+    }
+
+    #[test]
+    fn table() {
+        let table = SuitCombination::from_holdings("AQ", "32").unwrap().table();
+        assert_eq!(table.len(), 2);
+        assert!((table[0].probability - 1.0).abs() < 1e-9);
+        assert_eq!(
+            format!("{}", table[1]).lines().take(2).collect::<Vec<_>>(),
+            vec!["2 trick(s): 50.00%", "  Lead the 2 from S."]
+        );
+    }
+}
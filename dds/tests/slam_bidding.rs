@@ -0,0 +1,59 @@
+//! Slam auctions of the Forum D Plus 2015 bots, checked against the double dummy solver:
+//! when the bots bid a slam it makes, when they stop below it doesn't.
+
+use bridge_buddy_core::engine::bidding_engine::forum_d_plus_2015_bidding_engine::{
+    ForumDPlus2015BiddingEngine, KeycardResponses,
+};
+use bridge_buddy_core::game::bid_manager::BidManager;
+use bridge_buddy_core::primitives::contract::Contract;
+use bridge_buddy_core::primitives::Deal;
+use bridge_buddy_dds::DoubleDummySolver;
+use std::str::FromStr;
+use test_case::test_case;
+
+fn bid_deal(deal: &Deal<13>, keycard_responses: KeycardResponses) -> Contract {
+    let engine = ForumDPlus2015BiddingEngine::with_keycard_responses(keycard_responses);
+    let mut bid_manager = BidManager::new(deal.board.dealer());
+    while !bid_manager.bidding_has_ended() {
+        let hand = deal.hand_of(bid_manager.next_to_play());
+        let bid = engine.find_bid(hand, bid_manager.bids());
+        bid_manager.bid(bid).unwrap();
+    }
+    bid_manager.implied_contract().unwrap()
+}
+
+fn double_dummy_tricks(deal: Deal<13>, contract: &Contract) -> usize {
+    let dds = DoubleDummySolver::default();
+    let defenders_tricks = dds
+        .new_runner()
+        .solve_initial_position(deal, contract.strain, contract.declarer + 1);
+    13 - defenders_tricks
+}
+
+#[test_case(947, "N6NT"; "Gerber over 2NT")]
+#[test_case(1467, "S6NT"; "Gerber over 1NT")]
+#[test_case(2311, "E6S"; "Keycards after a game raise")]
+#[test_case(3576, "W6H"; "Four keycards")]
+#[test_case(4259, "W6H"; "Keycard Blackwood over the opening")]
+#[test_case(4912, "N6H"; "Three keycards")]
+#[test_case(4115, "W6H"; "Keycards after cue-bids")]
+fn slam_makes(seed: u64, expected: &str) {
+    let deal = Deal::from_u64_seed(seed);
+    let expected = Contract::from_str(expected).unwrap();
+    for keycard_responses in [KeycardResponses::Rkcb1430, KeycardResponses::Rkcb3014] {
+        assert_eq!(bid_deal(&deal, keycard_responses), expected);
+    }
+    assert!(double_dummy_tricks(deal, &expected) >= 12);
+}
+
+#[test_case(91, "W5H"; "Missing two keycards")]
+#[test_case(4182, "W4S"; "Minimum limit raise")]
+#[test_case(4889, "S4H"; "Signing off after a cue-bid")]
+fn slam_fails(seed: u64, expected: &str) {
+    let deal = Deal::from_u64_seed(seed);
+    let expected = Contract::from_str(expected).unwrap();
+    for keycard_responses in [KeycardResponses::Rkcb1430, KeycardResponses::Rkcb3014] {
+        assert_eq!(bid_deal(&deal, keycard_responses), expected);
+    }
+    assert!(double_dummy_tricks(deal, &expected) < 12);
+}